@@ -85,6 +85,18 @@ pub async fn create_workspace_directory(path: String) -> Result<(), String> {
             .map_err(|e| format!("创建子目录 {} 失败: {}", subdir, e))?;
     }
     
+    // 写入工作区配置：笔记直接放在根目录，附件放在 attachments
+    let config_path = workspace_path.join(zeno_core::WORKSPACE_CONFIG_FILE);
+    if !config_path.exists() {
+        let mut config = zeno_core::WorkspaceConfig::default();
+        config.directories.notes = PathBuf::from(".");
+        config.directories.assets = PathBuf::from("attachments");
+        config
+            .save(&workspace_path)
+            .await
+            .map_err(|e| format!("写入 zeno.yml 失败: {}", e))?;
+    }
+    
     // 创建 .zeno 目录（存放数据库等）
     let zeno_dir = workspace_path.join(".zeno");
     fs::create_dir_all(zeno_dir)
@@ -1,26 +1,33 @@
 use crate::models::link::{WikiLink, LinkParseResult, BacklinkInfo, SimilarNote, BrokenLink};
-use crate::services::{LinkParser, LinkIndex};
-use std::sync::Mutex;
+use crate::services::{workspace_relative_path, LinkParser, WorkspaceDatabases};
+use std::path::Path;
 use tauri::{command, State};
-
-/// 全局链接索引，使用Mutex保护
-pub type GlobalLinkIndex = Mutex<LinkIndex>;
+use zeno_core::{AmbiguousLink, NoteRepository, ReindexReport, UnlinkedMention};
 
 /// 解析文本中的链接
 #[command]
 pub async fn parse_links(content: String) -> Result<LinkParseResult, String> {
-    let parser = LinkParser::new().map_err(|e| e.to_string())?;
+    let parser = LinkParser::new();
     Ok(parser.parse_links(&content))
 }
 
 /// 提取链接的上下文
+///
+/// 传入 `target_content`（目标笔记的内容）时返回链接引用的内容：
+/// `[[笔记#标题]]` 为该章节，`[[笔记#^块ID]]` 为该块，没有锚点时为整篇笔记。
 #[command]
 pub async fn extract_link_context(
     content: String,
     link: WikiLink,
     context_size: Option<usize>,
+    target_content: Option<String>,
 ) -> Result<String, String> {
-    let parser = LinkParser::new().map_err(|e| e.to_string())?;
+    let parser = LinkParser::new();
+    if let Some(target_content) = target_content {
+        return parser
+            .extract_linked_section(&target_content, &link)
+            .ok_or_else(|| format!("目标笔记中找不到 {}", link.full_target()));
+    }
     let size = context_size.unwrap_or(2);
     Ok(parser.extract_link_context(&content, &link, size))
 }
@@ -32,7 +39,7 @@ pub async fn replace_link(
     old_link: WikiLink,
     new_target: String,
 ) -> Result<String, String> {
-    let parser = LinkParser::new().map_err(|e| e.to_string())?;
+    let parser = LinkParser::new();
     Ok(parser.replace_link(&content, &old_link, &new_target))
 }
 
@@ -42,119 +49,218 @@ pub async fn replace_multiple_links(
     content: String,
     replacements: Vec<(WikiLink, String)>,
 ) -> Result<String, String> {
-    let parser = LinkParser::new().map_err(|e| e.to_string())?;
+    let parser = LinkParser::new();
     Ok(parser.replace_multiple_links(&content, &replacements))
 }
 
-/// 注册笔记到链接索引
-#[command]
-pub async fn register_note_in_index(
-    note_id: String,
-    path: String,
-    title: String,
-    link_index: State<'_, GlobalLinkIndex>,
-) -> Result<(), String> {
-    let mut index = link_index.lock().map_err(|e| e.to_string())?;
-    index.register_note(note_id, path.into(), title);
-    Ok(())
-}
-
-/// 更新笔记的链接关系
+/// 重新索引一个笔记文件并增量更新受影响的链接
 #[command]
 pub async fn update_note_links(
-    note_id: String,
-    links: Vec<WikiLink>,
-    link_index: State<'_, GlobalLinkIndex>,
+    workspace_path: String,
+    file_path: String,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<(), String> {
-    let mut index = link_index.lock().map_err(|e| e.to_string())?;
-    index.update_note_links(&note_id, links)
+    let relative = workspace_relative_path(&workspace_path, Path::new(&file_path))
+        .ok_or_else(|| format!("笔记不在工作空间内: {}", file_path))?;
+    let indexer = databases.indexer(&workspace_path).await?;
+    indexer
+        .index_path(Path::new(&workspace_path), &relative)
+        .await
+        .map_err(|e| format!("索引笔记失败: {}", e))?;
+    Ok(())
 }
 
-/// 获取笔记的反向链接
+/// 获取笔记的反向链接，源笔记路径为绝对路径
 #[command]
 pub async fn get_backlinks(
-    note_id: String,
-    link_index: State<'_, GlobalLinkIndex>,
+    workspace_path: String,
+    file_path: String,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<Vec<BacklinkInfo>, String> {
-    let index = link_index.lock().map_err(|e| e.to_string())?;
-    Ok(index.get_backlinks(&note_id))
+    let Some(note_id) = find_note_id(&databases, &workspace_path, &file_path).await? else {
+        return Ok(Vec::new());
+    };
+    let store = databases.link_store(&workspace_path).await?;
+    let mut backlinks = store.get_backlinks(&note_id).await.map_err(|e| e.to_string())?;
+    for backlink in &mut backlinks {
+        backlink.source_note_path = absolute_path(&workspace_path, &backlink.source_note_path);
+    }
+    Ok(backlinks)
 }
 
-/// 获取笔记的正向链接
+/// 获取笔记的正向链接，返回目标笔记的绝对路径
 #[command]
 pub async fn get_outgoing_links(
-    note_id: String,
-    link_index: State<'_, GlobalLinkIndex>,
+    workspace_path: String,
+    file_path: String,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<Vec<String>, String> {
-    let index = link_index.lock().map_err(|e| e.to_string())?;
-    Ok(index.get_outgoing_links(&note_id))
+    let Some(note_id) = find_note_id(&databases, &workspace_path, &file_path).await? else {
+        return Ok(Vec::new());
+    };
+    let store = databases.link_store(&workspace_path).await?;
+    let repo = databases.repository(&workspace_path).await?;
+    let mut paths = Vec::new();
+    for target_id in store.get_outgoing_links(&note_id).await.map_err(|e| e.to_string())? {
+        if let Some(target) = repo.get_note_by_id(&target_id).await.map_err(|e| e.to_string())? {
+            paths.push(absolute_path(&workspace_path, &target.file_path));
+        }
+    }
+    Ok(paths)
 }
 
-/// 查找相似笔记
+/// 查找相似笔记，笔记路径为绝对路径
 #[command]
 pub async fn find_similar_notes(
-    note_id: String,
+    workspace_path: String,
+    file_path: String,
     limit: Option<usize>,
-    link_index: State<'_, GlobalLinkIndex>,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<Vec<SimilarNote>, String> {
-    let index = link_index.lock().map_err(|e| e.to_string())?;
+    let Some(note_id) = find_note_id(&databases, &workspace_path, &file_path).await? else {
+        return Ok(Vec::new());
+    };
+    let store = databases.link_store(&workspace_path).await?;
     let max_results = limit.unwrap_or(10);
-    Ok(index.find_similar_notes(&note_id, max_results))
+    let mut similar = store.find_similar_notes(&note_id, max_results).await.map_err(|e| e.to_string())?;
+    for note in &mut similar {
+        note.path = absolute_path(&workspace_path, &note.path);
+    }
+    Ok(similar)
 }
 
-/// 获取断链信息
+/// 获取断链信息，未指定笔记时检查整个工作空间
 #[command]
 pub async fn get_broken_links(
-    note_id: Option<String>,
-    link_index: State<'_, GlobalLinkIndex>,
+    workspace_path: String,
+    file_path: Option<String>,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<Vec<BrokenLink>, String> {
-    let index = link_index.lock().map_err(|e| e.to_string())?;
-    Ok(index.get_broken_links(note_id.as_deref()))
+    let note_id = match &file_path {
+        Some(file_path) => match find_note_id(&databases, &workspace_path, file_path).await? {
+            Some(note_id) => Some(note_id),
+            None => return Ok(Vec::new()),
+        },
+        None => None,
+    };
+    let store = databases.link_store(&workspace_path).await?;
+    let mut broken = store.get_broken_links(note_id.as_deref()).await.map_err(|e| e.to_string())?;
+    for link in &mut broken {
+        link.source_note_path = absolute_path(&workspace_path, &link.source_note_path);
+    }
+    Ok(broken)
+}
+
+/// 获取目标有歧义的链接（多篇笔记的标题、文件名或别名相同），路径为绝对路径
+#[command]
+pub async fn get_ambiguous_links(
+    workspace_path: String,
+    file_path: Option<String>,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<AmbiguousLink>, String> {
+    let note_id = match &file_path {
+        Some(file_path) => match find_note_id(&databases, &workspace_path, file_path).await? {
+            Some(note_id) => Some(note_id),
+            None => return Ok(Vec::new()),
+        },
+        None => None,
+    };
+    let store = databases.link_store(&workspace_path).await?;
+    let mut ambiguous = store.get_ambiguous_links(note_id.as_deref()).await.map_err(|e| e.to_string())?;
+    for link in &mut ambiguous {
+        link.source_note_path = absolute_path(&workspace_path, &link.source_note_path);
+        for path in &mut link.candidate_paths {
+            *path = absolute_path(&workspace_path, path);
+        }
+    }
+    Ok(ambiguous)
+}
+
+/// 获取提到笔记标题或别名但没有链接到它的地方，源笔记路径为绝对路径
+#[command]
+pub async fn get_unlinked_mentions(
+    workspace_path: String,
+    file_path: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<UnlinkedMention>, String> {
+    let Some(note_id) = find_note_id(&databases, &workspace_path, &file_path).await? else {
+        return Ok(Vec::new());
+    };
+    let store = databases.link_store(&workspace_path).await?;
+    let mut mentions = store
+        .get_unlinked_mentions(Path::new(&workspace_path), &note_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    for mention in &mut mentions {
+        mention.source_note_path = absolute_path(&workspace_path, &mention.source_note_path);
+    }
+    Ok(mentions)
 }
 
-/// 获取孤立笔记
+/// 把 `get_unlinked_mentions` 返回的提及改为链接，返回改写的文件数
+#[command]
+pub async fn link_unlinked_mentions(
+    workspace_path: String,
+    mentions: Vec<UnlinkedMention>,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<usize, String> {
+    let mut relative = Vec::with_capacity(mentions.len());
+    for mut mention in mentions {
+        let path = workspace_relative_path(&workspace_path, Path::new(&mention.source_note_path))
+            .ok_or_else(|| format!("只能修改工作空间内的笔记: {}", mention.source_note_path))?;
+        mention.source_note_path = path.to_string_lossy().to_string();
+        relative.push(mention);
+    }
+    let indexer = databases.indexer(&workspace_path).await?;
+    indexer
+        .link_mentions(Path::new(&workspace_path), &relative)
+        .await
+        .map_err(|e| format!("添加链接失败: {}", e))
+}
+
+/// 获取孤立笔记的绝对路径
 #[command]
 pub async fn get_orphaned_notes(
-    link_index: State<'_, GlobalLinkIndex>,
+    workspace_path: String,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<Vec<String>, String> {
-    let index = link_index.lock().map_err(|e| e.to_string())?;
-    Ok(index.get_orphaned_notes())
+    let store = databases.link_store(&workspace_path).await?;
+    let repo = databases.repository(&workspace_path).await?;
+    let mut paths = Vec::new();
+    for note_id in store.get_orphaned_notes().await.map_err(|e| e.to_string())? {
+        if let Some(note) = repo.get_note_by_id(&note_id).await.map_err(|e| e.to_string())? {
+            paths.push(absolute_path(&workspace_path, &note.file_path));
+        }
+    }
+    Ok(paths)
 }
 
 /// 获取链接统计信息
 #[command]
 pub async fn get_link_statistics(
-    link_index: State<'_, GlobalLinkIndex>,
+    workspace_path: String,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<serde_json::Value, String> {
-    let index = link_index.lock().map_err(|e| e.to_string())?;
-    let stats = index.get_statistics();
+    let store = databases.link_store(&workspace_path).await?;
+    let stats = store.get_statistics().await.map_err(|e| e.to_string())?;
     serde_json::to_value(stats).map_err(|e| e.to_string())
 }
 
-/// 重建链接索引（用于初始化或重新同步）
+/// 重新索引整个工作空间并重新解析所有链接（用于与磁盘上的修改重新同步）
 #[command]
 pub async fn rebuild_link_index(
-    notes_data: Vec<(String, String, String, String)>, // (id, path, title, content)
-    link_index: State<'_, GlobalLinkIndex>,
-) -> Result<(), String> {
-    let mut index = link_index.lock().map_err(|e| e.to_string())?;
-    let parser = LinkParser::new().map_err(|e| e.to_string())?;
-    
-    // 清空现有索引
-    *index = LinkIndex::new();
-    
-    // 注册所有笔记
-    for (note_id, path, title, _) in &notes_data {
-        index.register_note(note_id.clone(), path.clone().into(), title.clone());
-    }
-    
-    // 解析并更新所有链接
-    for (note_id, _, _, content) in notes_data {
-        let parse_result = parser.parse_links(&content);
-        index.update_note_links(&note_id, parse_result.links)?;
+    workspace_path: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<ReindexReport, String> {
+    let indexer = databases.indexer(&workspace_path).await?;
+    let report = indexer
+        .reindex_all(Path::new(&workspace_path), |_| {})
+        .await
+        .map_err(|e| format!("索引工作空间失败: {}", e))?;
+    if !report.has_changes() {
+        indexer.refresh_links().await.map_err(|e| e.to_string())?;
     }
-    
-    Ok(())
+    Ok(report)
 }
 
 /// 解析并预览链接（不保存到索引）
@@ -162,7 +268,7 @@ pub async fn rebuild_link_index(
 pub async fn preview_link_parsing(
     content: String,
 ) -> Result<serde_json::Value, String> {
-    let parser = LinkParser::new().map_err(|e| e.to_string())?;
+    let parser = LinkParser::new();
     let result = parser.parse_links(&content);
     
     let preview = serde_json::json!({
@@ -180,18 +286,35 @@ pub async fn preview_link_parsing(
 /// 验证链接目标是否存在
 #[command]
 pub async fn validate_link_targets(
+    workspace_path: String,
     links: Vec<WikiLink>,
-    link_index: State<'_, GlobalLinkIndex>,
+    databases: State<'_, WorkspaceDatabases>,
 ) -> Result<Vec<bool>, String> {
-    let _index = link_index.lock().map_err(|e| e.to_string())?;
-    let mut results = Vec::new();
-    
-    for link in links {
-        // 这里简化处理，实际应该调用 resolve_link_target
-        // 但该方法是私有的，所以我们检查是否有断链记录
-        let is_valid = !link.target.trim().is_empty();
-        results.push(is_valid);
+    let store = databases.link_store(&workspace_path).await?;
+    let targets: Vec<String> = links.into_iter().map(|link| link.target).collect();
+    let resolved = store.resolve_targets(&targets).await.map_err(|e| e.to_string())?;
+    Ok(resolved.iter().map(Option::is_some).collect())
+}
+
+/// 文件对应的笔记ID，尚未索引的笔记先索引
+pub(crate) async fn find_note_id(databases: &WorkspaceDatabases, workspace_path: &str, file_path: &str) -> Result<Option<String>, String> {
+    let Some(relative) = workspace_relative_path(workspace_path, Path::new(file_path)) else {
+        return Ok(None);
+    };
+    let path = relative.to_string_lossy();
+    let repo = databases.repository(workspace_path).await?;
+    if let Some(note) = repo.get_note_by_path(&path).await.map_err(|e| e.to_string())? {
+        return Ok(Some(note.id));
     }
-    
-    Ok(results)
-}
\ No newline at end of file
+
+    let indexer = databases.indexer(workspace_path).await?;
+    indexer
+        .index_path(Path::new(workspace_path), &relative)
+        .await
+        .map_err(|e| format!("索引笔记失败: {}", e))?;
+    Ok(repo.get_note_by_path(&path).await.map_err(|e| e.to_string())?.map(|note| note.id))
+}
+
+fn absolute_path(workspace_path: &str, relative: &str) -> String {
+    Path::new(workspace_path).join(relative).to_string_lossy().to_string()
+}
@@ -7,6 +7,8 @@ pub mod publisher;
 pub mod wechat;
 pub mod import_export;
 pub mod plugin_commands;
+pub mod search;
+pub mod trash;
 
 pub use notes::*;
 pub use config::*;
@@ -16,4 +18,6 @@ pub use tags::*;
 pub use publisher::*;
 pub use wechat::*;
 pub use import_export::*;
-pub use plugin_commands::*;
\ No newline at end of file
+pub use plugin_commands::*;
+pub use search::*;
+pub use trash::*;
\ No newline at end of file
@@ -34,8 +34,13 @@ pub async fn read_file_content(path: String) -> Result<String, String> {
         .map_err(|e| format!("读取文件失败: {}", e))
 }
 
+/// 写入文件，工作空间内的 Markdown 笔记随后重新索引，反向链接立即更新
 #[tauri::command]
-pub async fn write_file_content(path: String, content: String) -> Result<(), String> {
+pub async fn write_file_content(
+    path: String,
+    content: String,
+    databases: tauri::State<'_, crate::services::WorkspaceDatabases>,
+) -> Result<(), String> {
     let path = Path::new(&path);
     
     if let Some(parent) = path.parent() {
@@ -46,7 +51,29 @@ pub async fn write_file_content(path: String, content: String) -> Result<(), Str
     
     fs::write(path, content)
         .await
-        .map_err(|e| format!("写入文件失败: {}", e))
+        .map_err(|e| format!("写入文件失败: {}", e))?;
+
+    let is_markdown = path
+        .extension()
+        .is_some_and(|ext| ext == "md" || ext == "markdown");
+    if is_markdown {
+        if let Some(workspace_path) = crate::commands::get_config().await?.workspace_path {
+            if let Some(relative) = crate::services::workspace_relative_path(&workspace_path, path) {
+                // 索引失败不影响保存，下次重建索引时会再处理
+                let indexed = match databases.indexer(&workspace_path).await {
+                    Ok(indexer) => indexer
+                        .index_path(Path::new(&workspace_path), &relative)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = indexed {
+                    log::warn!("索引笔记失败 {}: {}", relative.display(), e);
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -129,7 +156,11 @@ async fn collect_notes_iterative(dir: &Path, notes: &mut Vec<NoteFile>) -> Resul
 }
 
 #[tauri::command]
-pub async fn create_note(title: Option<String>, parent_path: Option<String>) -> Result<String, String> {
+pub async fn create_note(
+    title: Option<String>,
+    parent_path: Option<String>,
+    template: Option<String>,
+) -> Result<String, String> {
     // 从配置获取工作空间路径
     let config = crate::commands::get_config().await?;
     let workspace_path = config.workspace_path
@@ -140,20 +171,33 @@ pub async fn create_note(title: Option<String>, parent_path: Option<String>) ->
         return Err(format!("工作空间不存在: {}", workspace.display()));
     }
     
-    // 确定目标目录
+    let workspace_config = zeno_core::WorkspaceConfig::load(workspace)
+        .await
+        .map_err(|e| format!("读取 zeno.yml 失败: {}", e))?;
+    
+    // 确定目标目录，未指定时使用配置中的笔记目录
     let target_dir = if let Some(parent) = parent_path {
         if parent.is_empty() {
             workspace.to_path_buf()
         } else {
+            // 绝对路径必须位于工作空间内，统一转为相对路径后校验
             let parent_path = Path::new(&parent);
-            if parent_path.is_absolute() {
-                parent_path.to_path_buf()
+            let relative = if parent_path.is_absolute() {
+                parent_path
+                    .strip_prefix(workspace)
+                    .map_err(|_| format!("目标目录不在工作空间内: {}", parent))?
             } else {
-                workspace.join(parent_path)
-            }
+                parent_path
+            };
+            zeno_core::validate_note_dir(relative).map_err(|e| e.to_string())?;
+            workspace.join(relative)
         }
     } else {
-        workspace.to_path_buf()
+        let notes_dir = workspace_config.notes_dir(workspace);
+        fs::create_dir_all(&notes_dir)
+            .await
+            .map_err(|e| format!("创建笔记目录失败: {}", e))?;
+        notes_dir
     };
     
     if !target_dir.exists() {
@@ -174,9 +218,16 @@ pub async fn create_note(title: Option<String>, parent_path: Option<String>) ->
         counter += 1;
     }
     
-    let content = format!("# {}\n\n", title);
+    // 使用工作空间模板目录中的模板渲染内容
+    let store = zeno_core::TemplateStore::new(workspace_config.templates_dir(workspace));
+    let vars = zeno_core::TemplateVars::for_note(&title);
+    let template = template.unwrap_or_else(|| workspace_config.defaults.note_template.clone());
+    let content = store
+        .render_note(&template, &vars)
+        .await
+        .map_err(|e| format!("渲染模板失败: {}", e))?;
     
-    fs::write(&final_path, content)
+    zeno_core::write_new_note(&final_path, &content)
         .await
         .map_err(|e| format!("创建文件失败: {}", e))?;
     
@@ -302,8 +353,12 @@ async fn build_file_tree_recursive(base_dir: &Path) -> Result<Vec<FileTreeNode>,
     Ok(root_children)
 }
 
+/// 将笔记移入工作空间回收站（`.zeno/trash`），可以用 `restore_note` 恢复
 #[tauri::command]
-pub async fn delete_note(file_path: String) -> Result<(), String> {
+pub async fn delete_note(
+    file_path: String,
+    databases: tauri::State<'_, crate::services::WorkspaceDatabases>,
+) -> Result<(), String> {
     let path = Path::new(&file_path);
     
     if !path.exists() {
@@ -323,9 +378,51 @@ pub async fn delete_note(file_path: String) -> Result<(), String> {
         return Err(format!("文件没有扩展名"));
     }
     
-    fs::remove_file(path)
+    let config = crate::commands::get_config().await?;
+    let workspace_path = config.workspace_path
+        .ok_or("未设置工作空间路径")?;
+    let relative = crate::services::workspace_relative_path(&workspace_path, path)
+        .ok_or_else(|| format!("只能删除工作空间内的笔记: {}", path.display()))?;
+    
+    let kb = databases.knowledge_base(&workspace_path).await?;
+    // 回收站依赖数据库记录，尚未索引的文件先导入
+    let note = match kb.get_note_by_path(&relative).await.map_err(|e| e.to_string())? {
+        Some(note) => note,
+        None => kb.import_note_from_file(&relative)
+            .await
+            .map_err(|e| format!("索引笔记失败: {}", e))?,
+    };
+    kb.delete_note(&note.id)
+        .await
+        .map_err(|e| format!("移入回收站失败: {}", e))?;
+    Ok(())
+}
+
+/// 重命名或移动笔记并改写其他笔记中指向它的链接，`dry_run` 时只返回改写计划
+#[tauri::command]
+pub async fn rename_note(
+    file_path: String,
+    new_path: String,
+    dry_run: bool,
+    databases: tauri::State<'_, crate::services::WorkspaceDatabases>,
+) -> Result<zeno_core::RenamePlan, String> {
+    let config = crate::commands::get_config().await?;
+    let workspace_path = config.workspace_path
+        .ok_or("未设置工作空间路径")?;
+    // 新路径还不存在，无法规范化，直接去掉工作空间前缀
+    let to = Path::new(&new_path)
+        .strip_prefix(&workspace_path)
+        .map_err(|_| format!("新路径不在工作空间内: {}", new_path))?
+        .to_path_buf();
+    let note_id = crate::commands::links::find_note_id(&databases, &workspace_path, &file_path)
+        .await?
+        .ok_or_else(|| format!("找不到笔记: {}", file_path))?;
+
+    let indexer = databases.indexer(&workspace_path).await?;
+    let root = Path::new(&workspace_path);
+    indexer.rename_note(root, &note_id, &to, dry_run)
         .await
-        .map_err(|e| format!("删除文件失败: {}", e))
+        .map_err(|e| format!("重命名笔记失败: {}", e))
 }
 
 #[tauri::command]
@@ -387,12 +484,17 @@ pub async fn create_folder(name: String, parent_path: Option<String>) -> Result<
         if parent.is_empty() {
             workspace.to_path_buf()
         } else {
+            // 绝对路径必须位于工作空间内，统一转为相对路径后校验
             let parent_path = Path::new(&parent);
-            if parent_path.is_absolute() {
-                parent_path.to_path_buf()
+            let relative = if parent_path.is_absolute() {
+                parent_path
+                    .strip_prefix(workspace)
+                    .map_err(|_| format!("目标目录不在工作空间内: {}", parent))?
             } else {
-                workspace.join(parent_path)
-            }
+                parent_path
+            };
+            zeno_core::validate_note_dir(relative).map_err(|e| e.to_string())?;
+            workspace.join(relative)
         }
     } else {
         workspace.to_path_buf()
@@ -448,8 +550,14 @@ pub async fn delete_folder(folder_path: String) -> Result<(), String> {
         .map_err(|e| format!("删除文件夹失败: {}", e))
 }
 
+/// 重命名文件夹并改写其他笔记中指向其中笔记的链接，`dry_run` 时只返回改写计划
 #[tauri::command]
-pub async fn rename_folder(old_path: String, new_name: String) -> Result<(), String> {
+pub async fn rename_folder(
+    old_path: String,
+    new_name: String,
+    dry_run: bool,
+    databases: tauri::State<'_, crate::services::WorkspaceDatabases>,
+) -> Result<zeno_core::RenamePlan, String> {
     let old = Path::new(&old_path);
     
     if !old.exists() {
@@ -466,19 +574,18 @@ pub async fn rename_folder(old_path: String, new_name: String) -> Result<(), Str
         .ok_or("未设置工作空间路径")?;
     let workspace = Path::new(&workspace_path);
     
-    if !old.starts_with(workspace) {
-        return Err(format!("只能重命名工作空间内的文件夹"));
-    }
-    
-    let parent = old.parent().ok_or("无法获取父目录")?;
+    let from = old
+        .strip_prefix(workspace)
+        .map_err(|_| "只能重命名工作空间内的文件夹".to_string())?
+        .to_path_buf();
     let new_folder_name = slugify(&new_name);
-    let new_path = parent.join(&new_folder_name);
-    
-    if new_path.exists() {
-        return Err(format!("目标文件夹已存在: {}", new_path.display()));
+    if new_folder_name.is_empty() {
+        return Err("文件夹名称不能为空".to_string());
     }
+    let to = from.with_file_name(&new_folder_name);
     
-    tokio::fs::rename(old, &new_path)
+    let indexer = databases.indexer(&workspace_path).await?;
+    indexer.rename_folder(workspace, &from, &to, dry_run)
         .await
         .map_err(|e| format!("重命名文件夹失败: {}", e))
 }
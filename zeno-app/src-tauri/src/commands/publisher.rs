@@ -1,6 +1,6 @@
 use crate::models::publisher::*;
 use crate::models::note::Note;
-use crate::services::zola_publisher::ZolaPublisher;
+use zeno_core::{PublishOptions, WorkspaceConfig, ZolaPublisher};
 use crate::services::note_service::NoteService;
 use std::path::PathBuf;
 use std::sync::Mutex;
@@ -17,8 +17,7 @@ pub async fn initialize_zola_site(
     config: ZolaConfig,
 ) -> Result<(), String> {
     let site_path = PathBuf::from(site_path);
-    let publisher = ZolaPublisher::new(config, site_path)
-        .map_err(|e| e.to_string())?;
+    let publisher = ZolaPublisher::new(config, site_path);
     
     publisher.initialize_site().await
         .map_err(|e| e.to_string())?;
@@ -37,8 +36,11 @@ pub async fn publish_notes_to_site(
     let workspace_path = PathBuf::from(workspace_path);
     
     // 创建 ZolaPublisher
-    let publisher = ZolaPublisher::new(config, site_path)
+    let workspace_config = WorkspaceConfig::load(&workspace_path)
+        .await
         .map_err(|e| e.to_string())?;
+    let publisher = ZolaPublisher::new(config, site_path)
+        .with_workspace(&workspace_path, &workspace_config);
     
     // 获取所有笔记
     let note_service = NoteService::new(workspace_path);
@@ -46,8 +48,9 @@ pub async fn publish_notes_to_site(
         .await
         .map_err(|e| e.to_string())?;
     
-    // 发布笔记
-    let result = publisher.publish_notes(notes).await
+    // 发布笔记并构建站点
+    let options = PublishOptions { drafts: false, build: true };
+    let result = publisher.publish_notes(notes, &options).await
         .map_err(|e| e.to_string())?;
     
     Ok(result)
@@ -228,8 +231,7 @@ pub async fn preview_publish_content(
     
     // 创建临时的 ZolaPublisher 来进行内容转换
     let temp_site_path = std::env::temp_dir().join("zeno_preview");
-    let publisher = ZolaPublisher::new(config, temp_site_path)
-        .map_err(|e| e.to_string())?;
+    let publisher = ZolaPublisher::new(config, temp_site_path);
     
     // 转换为 Zola 格式
     let zola_content = publisher.convert_to_zola_format(&note).await
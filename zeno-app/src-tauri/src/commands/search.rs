@@ -0,0 +1,113 @@
+use crate::services::WorkspaceDatabases;
+use serde::Serialize;
+use tauri::{command, State};
+use zeno_core::{
+    NoteRepository, SavedSearch, SearchFilters, SearchHistoryEntry, SearchQuery, SearchResult,
+};
+
+/// 搜索错误，语法错误带有出错位置（字符偏移），便于界面标出
+#[derive(Debug, Serialize)]
+pub struct SearchError {
+    pub message: String,
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+}
+
+impl From<zeno_core::Error> for SearchError {
+    fn from(err: zeno_core::Error) -> Self {
+        match err {
+            zeno_core::Error::Query(e) => Self {
+                message: e.message,
+                start: Some(e.start),
+                end: Some(e.end),
+            },
+            other => Self::from(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for SearchError {
+    fn from(message: String) -> Self {
+        Self { message, start: None, end: None }
+    }
+}
+
+/// 全文搜索笔记，并记录搜索历史
+#[command]
+pub async fn search_notes(
+    workspace_path: String,
+    query: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    let repo = databases.repository(&workspace_path).await?;
+    let query = SearchQuery { query, limit, offset, filters: SearchFilters::default() };
+    Ok(repo.search_notes(&query).await?)
+}
+
+/// 获取最近的搜索
+#[command]
+pub async fn get_search_history(
+    workspace_path: String,
+    limit: Option<usize>,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<SearchHistoryEntry>, String> {
+    let repo = databases.repository(&workspace_path).await?;
+    repo.get_search_history(limit.unwrap_or(20)).await.map_err(|e| e.to_string())
+}
+
+/// 清除搜索历史，返回删除的条数
+#[command]
+pub async fn clear_search_history(
+    workspace_path: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<u64, String> {
+    let repo = databases.repository(&workspace_path).await?;
+    repo.clear_search_history().await.map_err(|e| e.to_string())
+}
+
+/// 获取保存的搜索
+#[command]
+pub async fn get_saved_searches(
+    workspace_path: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<SavedSearch>, String> {
+    let repo = databases.repository(&workspace_path).await?;
+    repo.get_saved_searches().await.map_err(|e| e.to_string())
+}
+
+/// 保存搜索，同名时覆盖
+#[command]
+pub async fn save_search(
+    workspace_path: String,
+    name: String,
+    query: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<SavedSearch, SearchError> {
+    let repo = databases.repository(&workspace_path).await?;
+    Ok(repo.save_search(&name, &query).await?)
+}
+
+/// 删除保存的搜索
+#[command]
+pub async fn delete_saved_search(
+    workspace_path: String,
+    name: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<bool, String> {
+    let repo = databases.repository(&workspace_path).await?;
+    repo.delete_saved_search(&name).await.map_err(|e| e.to_string())
+}
+
+/// 运行保存的搜索，结果反映笔记的最新状态
+#[command]
+pub async fn run_saved_search(
+    workspace_path: String,
+    name: String,
+    limit: Option<usize>,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<SearchResult>, SearchError> {
+    let repo = databases.repository(&workspace_path).await?;
+    Ok(repo.run_saved_search(&name, limit).await?)
+}
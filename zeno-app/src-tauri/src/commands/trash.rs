@@ -0,0 +1,44 @@
+use crate::services::WorkspaceDatabases;
+use std::path::Path;
+use tauri::{command, State};
+use zeno_core::{TrashEntry, WorkspaceConfig};
+
+/// 列出回收站中的笔记，最近删除的在前
+///
+/// 列出前按 zeno.yml 的回收站设置彻底删除到期的笔记，应用长时间运行时保留期限同样生效。
+#[command]
+pub async fn list_trash(
+    workspace_path: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<TrashEntry>, String> {
+    let kb = databases.knowledge_base(&workspace_path).await?;
+    let config = WorkspaceConfig::load(Path::new(&workspace_path))
+        .await
+        .map_err(|e| format!("读取 zeno.yml 失败: {}", e))?;
+    if let Err(e) = kb.purge_expired_trash(&config.trash).await {
+        log::warn!("清理回收站失败: {}", e);
+    }
+    kb.list_trash().await.map_err(|e| e.to_string())
+}
+
+/// 将回收站中的笔记恢复到删除前的路径
+#[command]
+pub async fn restore_note(
+    workspace_path: String,
+    note_id: String,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<TrashEntry, String> {
+    let kb = databases.knowledge_base(&workspace_path).await?;
+    kb.restore_note(&note_id).await.map_err(|e| format!("恢复笔记失败: {}", e))
+}
+
+/// 彻底删除回收站中超过指定天数的笔记，未指定时清空回收站
+#[command]
+pub async fn purge_trash(
+    workspace_path: String,
+    older_than_days: Option<u32>,
+    databases: State<'_, WorkspaceDatabases>,
+) -> Result<Vec<TrashEntry>, String> {
+    let kb = databases.knowledge_base(&workspace_path).await?;
+    kb.purge_trash(older_than_days).await.map_err(|e| e.to_string())
+}
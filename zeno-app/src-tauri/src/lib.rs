@@ -3,7 +3,7 @@ pub mod models;
 pub mod services;
 
 use std::sync::{Mutex, Arc};
-use services::{LinkIndex, ImportExportManager, PluginManager, PluginAPIService, PluginRuntimeManager};
+use services::{ImportExportManager, PluginManager, PluginAPIService, PluginRuntimeManager, WorkspaceDatabases};
 use models::tag::TagHierarchy;
 use models::publisher::PublishConfig;
 use models::wechat::WeChatConfig;
@@ -14,7 +14,6 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .manage(Mutex::new(LinkIndex::new()))
         .manage(Mutex::new(TagHierarchy::new()))
         .manage(Mutex::new(PublishConfig::default()))
         .manage(Mutex::new(WeChatConfig::default()))
@@ -22,6 +21,7 @@ pub fn run() {
         .manage(Arc::new(Mutex::new(PluginManager::default())))
         .manage(Arc::new(Mutex::new(PluginAPIService::default())))
         .manage(Arc::new(Mutex::new(PluginRuntimeManager::default())))
+        .manage(WorkspaceDatabases::new())
         .invoke_handler(tauri::generate_handler![
             commands::get_app_version,
             commands::get_app_info,
@@ -32,6 +32,7 @@ pub fn run() {
             commands::create_note,
             commands::create_folder,
             commands::delete_note,
+            commands::rename_note,
             commands::delete_folder,
             commands::rename_folder,
             commands::show_in_folder,
@@ -46,12 +47,14 @@ pub fn run() {
             commands::extract_link_context,
             commands::replace_link,
             commands::replace_multiple_links,
-            commands::register_note_in_index,
             commands::update_note_links,
             commands::get_backlinks,
             commands::get_outgoing_links,
             commands::find_similar_notes,
             commands::get_broken_links,
+            commands::get_ambiguous_links,
+            commands::get_unlinked_mentions,
+            commands::link_unlinked_mentions,
             commands::get_orphaned_notes,
             commands::get_link_statistics,
             commands::rebuild_link_index,
@@ -136,6 +139,18 @@ pub fn run() {
             commands::search_plugin_marketplace,
             commands::get_plugin_details,
             commands::validate_plugin,
+            // 搜索相关命令
+            commands::search_notes,
+            commands::get_search_history,
+            commands::clear_search_history,
+            commands::get_saved_searches,
+            commands::save_search,
+            commands::delete_saved_search,
+            commands::run_saved_search,
+            // 回收站相关命令
+            commands::list_trash,
+            commands::restore_note,
+            commands::purge_trash,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -31,4 +31,14 @@ impl From<serde_json::Error> for AppError {
     fn from(err: serde_json::Error) -> Self {
         AppError::ParseError(err.to_string())
     }
-}
\ No newline at end of file
+}
+impl From<zeno_core::Error> for AppError {
+    fn from(err: zeno_core::Error) -> Self {
+        match err {
+            zeno_core::Error::Io(e) => AppError::FileError(e.to_string()),
+            zeno_core::Error::Parse(e) => AppError::ParseError(e.to_string()),
+            zeno_core::Error::Query(e) => AppError::ParseError(e.to_string()),
+            other => AppError::Unknown(other.to_string()),
+        }
+    }
+}
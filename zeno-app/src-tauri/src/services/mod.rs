@@ -1,27 +1,21 @@
 pub mod note_service;
 pub mod config_service;
-pub mod link_parser;
-pub mod link_index;
-pub mod zola_publisher;
 pub mod wechat_publisher;
-pub mod base_importer;
-pub mod base_exporter;
-pub mod obsidian_importer;
-pub mod import_export_manager;
 pub mod plugin_manager;
 pub mod plugin_api_service;
 pub mod plugin_runtime;
+pub mod workspace_db;
 
 pub use note_service::*;
 pub use config_service::*;
-pub use link_parser::*;
-pub use link_index::*;
-pub use zola_publisher::*;
 pub use wechat_publisher::*;
-pub use base_importer::*;
-pub use base_exporter::*;
-pub use obsidian_importer::*;
-pub use import_export_manager::*;
 pub use plugin_manager::*;
 pub use plugin_api_service::*;
-pub use plugin_runtime::*;
\ No newline at end of file
+pub use plugin_runtime::*;
+pub use workspace_db::*;
+
+// 链接解析和链接图谱由 zeno-core 提供
+pub use zeno_core::{LinkIndex, LinkIndexStats, LinkParser};
+
+// 导入导出框架由 zeno-core 提供，与命令行共用
+pub use zeno_core::ImportExportManager;
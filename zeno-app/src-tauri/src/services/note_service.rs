@@ -1,4 +1,7 @@
 use crate::models::{Note, AppError};
+use zeno_core::models::UNTITLED;
+use zeno_core::parser::MarkdownParser;
+use zeno_core::WorkspaceConfig;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use uuid::Uuid;
@@ -12,8 +15,14 @@ impl NoteService {
         Self { workspace_path }
     }
     
+    /// 按 zeno.yml 解析笔记目录
+    async fn notes_dir(&self) -> Result<PathBuf, AppError> {
+        let config = WorkspaceConfig::load(&self.workspace_path).await?;
+        Ok(config.notes_dir(&self.workspace_path))
+    }
+    
     pub async fn list_notes(&self) -> Result<Vec<Note>, AppError> {
-        let notes_dir = self.workspace_path.join("notes");
+        let notes_dir = self.notes_dir().await?;
         if !notes_dir.exists() {
             return Ok(Vec::new());
         }
@@ -35,30 +44,31 @@ impl NoteService {
     
     pub async fn load_note(&self, path: &Path) -> Result<Note, AppError> {
         let content = fs::read_to_string(path).await?;
-        let title = extract_title_from_content(&content)
-            .unwrap_or_else(|| {
-                path.file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("Untitled")
-                    .to_string()
-            });
+        let mut note = MarkdownParser::new().parse(&content, path.to_path_buf())?;
+        if note.title == UNTITLED {
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                note.title = stem.to_string();
+            }
+        }
+        note.file_size = content.len() as u64;
         
-        Ok(Note::new(path.to_path_buf(), title, content))
+        Ok(note)
     }
     
     pub async fn save_note(&self, note: &Note) -> Result<(), AppError> {
-        let notes_dir = self.workspace_path.join("notes");
-        fs::create_dir_all(&notes_dir).await?;
+        if let Some(parent) = note.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
         
-        fs::write(&note.path, &note.content).await?;
+        fs::write(&note.path, note.to_markdown()).await?;
         Ok(())
     }
     
     pub async fn create_note(&self, title: &str, content: &str) -> Result<Note, AppError> {
         let filename = slugify(title) + ".md";
-        let path = self.workspace_path.join("notes").join(filename);
+        let path = self.notes_dir().await?.join(filename);
         
-        let note = Note::new(path, title.to_string(), content.to_string());
+        let note = Note::with_title(path, title.to_string(), content.to_string());
         self.save_note(&note).await?;
         
         Ok(note)
@@ -80,15 +90,6 @@ fn is_markdown_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-fn extract_title_from_content(content: &str) -> Option<String> {
-    for line in content.lines() {
-        if line.starts_with("# ") {
-            return Some(line[2..].trim().to_string());
-        }
-    }
-    None
-}
-
 fn slugify(text: &str) -> String {
     text.to_lowercase()
         .chars()
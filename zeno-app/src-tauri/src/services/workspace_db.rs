@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use tokio::sync::Mutex;
+use zeno_core::{
+    Database, KnowledgeBase, LinkStore, LocalFileStorage, NoteRepository, SqliteIndexer, SqliteNoteRepository,
+    WorkspaceConfig,
+};
+
+/// 按工作区缓存的数据库连接
+///
+/// 每个工作区的数据库位于 `.zeno/zeno.db`，首次访问时创建并初始化，
+/// 同时按 zeno.yml 的回收站设置彻底删除到期的笔记；数据库中还没有笔记时
+/// 索引整个工作区，链接图谱等查询此后直接读取数据库。
+#[derive(Default)]
+pub struct WorkspaceDatabases {
+    databases: Mutex<HashMap<PathBuf, Database>>,
+}
+
+impl WorkspaceDatabases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取工作区数据库
+    pub async fn database(&self, workspace_path: &str) -> Result<Database, String> {
+        let root = PathBuf::from(workspace_path);
+        let mut databases = self.databases.lock().await;
+        if let Some(db) = databases.get(&root) {
+            return Ok(db.clone());
+        }
+
+        let db = Database::open_workspace(&root)
+            .await
+            .map_err(|e| format!("打开工作区数据库失败: {}", e))?;
+
+        let config = WorkspaceConfig::load(&root).await.unwrap_or_default();
+        if let Err(e) = Self::build_knowledge_base(&root, &config, &db).purge_expired_trash(&config.trash).await {
+            log::warn!("清理回收站失败: {}", e);
+        }
+        let repository = SqliteNoteRepository::new(db.pool().clone());
+        if repository.get_statistics().await.is_ok_and(|stats| stats.total_notes == 0) {
+            match Self::build_indexer(&config, &db).reindex_all(&root, |_| {}).await {
+                Ok(report) => log::info!("已索引工作区 {}: 新增 {} 篇笔记", root.display(), report.added),
+                Err(e) => log::warn!("索引工作区失败: {}", e),
+            }
+        }
+
+        databases.insert(root, db.clone());
+        Ok(db)
+    }
+
+    /// 获取工作区的笔记仓库，覆盖笔记时按 zeno.yml 保存版本历史
+    pub async fn repository(&self, workspace_path: &str) -> Result<SqliteNoteRepository, String> {
+        let db = self.database(workspace_path).await?;
+        let config = WorkspaceConfig::load(Path::new(workspace_path))
+            .await
+            .map_err(|e| format!("读取 zeno.yml 失败: {}", e))?;
+        Ok(SqliteNoteRepository::new(db.pool().clone()).with_history(config.history))
+    }
+
+    /// 获取工作区知识库，文件操作按 zeno.yml 的目录配置进行
+    pub async fn knowledge_base(&self, workspace_path: &str) -> Result<KnowledgeBase, String> {
+        let db = self.database(workspace_path).await?;
+        let root = PathBuf::from(workspace_path);
+        let config = WorkspaceConfig::load(&root)
+            .await
+            .map_err(|e| format!("读取 zeno.yml 失败: {}", e))?;
+        Ok(Self::build_knowledge_base(&root, &config, &db))
+    }
+
+    /// 获取工作区索引器，按 zeno.yml 跳过模板目录并保存版本历史
+    pub async fn indexer(&self, workspace_path: &str) -> Result<SqliteIndexer, String> {
+        let db = self.database(workspace_path).await?;
+        let config = WorkspaceConfig::load(Path::new(workspace_path))
+            .await
+            .map_err(|e| format!("读取 zeno.yml 失败: {}", e))?;
+        Ok(Self::build_indexer(&config, &db))
+    }
+
+    /// 获取工作区的链接图谱
+    pub async fn link_store(&self, workspace_path: &str) -> Result<LinkStore, String> {
+        let db = self.database(workspace_path).await?;
+        Ok(LinkStore::new(db.pool().clone()))
+    }
+
+    fn build_indexer(config: &WorkspaceConfig, db: &Database) -> SqliteIndexer {
+        SqliteIndexer::new(db.pool().clone())
+            .with_excluded_dirs(config.excluded_dirs())
+            .with_history(config.history.clone())
+    }
+
+    fn build_knowledge_base(root: &Path, config: &WorkspaceConfig, db: &Database) -> KnowledgeBase {
+        let repository = SqliteNoteRepository::new(db.pool().clone()).with_history(config.history.clone());
+        KnowledgeBase::new(
+            Box::new(LocalFileStorage::with_config(root.to_path_buf(), config)),
+            Box::new(repository),
+        )
+    }
+}
+
+/// 文件相对工作区的路径，文件不在工作区内或不存在时返回 `None`
+pub fn workspace_relative_path(workspace_path: &str, path: &Path) -> Option<PathBuf> {
+    let root = Path::new(workspace_path).canonicalize().ok()?;
+    path.canonicalize().ok()?.strip_prefix(&root).ok().map(Path::to_path_buf)
+}
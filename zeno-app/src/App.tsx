@@ -8,6 +8,7 @@ import EditorPage from './pages/EditorPage';
 import GraphPage from './pages/GraphPage';
 import PublisherPage from './pages/PublisherPage';
 import SettingsPage from './pages/SettingsPage';
+import TrashPage from './pages/TrashPage';
 import './index.css';
 
 function App() {
@@ -22,6 +23,7 @@ function App() {
               <Route path="/editor" element={<EditorPage />} />
               <Route path="/graph" element={<GraphPage />} />
               <Route path="/publisher" element={<PublisherPage />} />
+              <Route path="/trash" element={<TrashPage />} />
               <Route path="/settings" element={<SettingsPage />} />
             </Routes>
           </Layout>
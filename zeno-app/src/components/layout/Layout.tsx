@@ -303,7 +303,7 @@ const Layout: React.FC<LayoutProps> = ({ children }) => {
   };
 
   const handleDeleteFile = async (file: FileNode) => {
-    if (!window.confirm(`确定要删除文件 "${file.name}" 吗？此操作无法撤销。`)) {
+    if (!window.confirm(`确定要删除文件 "${file.name}" 吗？笔记会移入回收站，可以恢复。`)) {
       return;
     }
 
@@ -338,6 +338,43 @@ const Layout: React.FC<LayoutProps> = ({ children }) => {
     }
   };
 
+  const handleRenameFile = async (file: FileNode) => {
+    const currentName = file.name.replace(/\.(md|markdown)$/, '');
+    const newName = prompt('请输入新的笔记名称:', currentName);
+    if (!newName || newName === currentName) {
+      return;
+    }
+
+    const separatorIndex = Math.max(file.path.lastIndexOf('/'), file.path.lastIndexOf('\\'));
+    const fileName = /\.(md|markdown)$/.test(newName) ? newName : `${newName}.md`;
+    const newPath = file.path.slice(0, separatorIndex + 1) + fileName;
+
+    try {
+      // 先预览需要改写的链接，确认后再执行
+      const plan = await invoke<{ rewrites: { path: string; edits: unknown[] }[] }>('rename_note', {
+        filePath: file.path,
+        newPath,
+        dryRun: true,
+      });
+      if (plan.rewrites.length > 0) {
+        const files = plan.rewrites.map(rewrite => `  ${rewrite.path}（${rewrite.edits.length} 处）`).join('\n');
+        if (!window.confirm(`重命名后将改写以下文件中的链接:\n${files}\n\n确定要继续吗？`)) {
+          return;
+        }
+      }
+
+      await invoke('rename_note', { filePath: file.path, newPath, dryRun: false });
+
+      // 重新加载文件树
+      await loadFileTree();
+
+      console.log('笔记重命名成功');
+    } catch (err) {
+      console.error('重命名笔记失败:', err);
+      alert(`重命名笔记失败: ${err}`);
+    }
+  };
+
   const handleRenameFolder = async (folder: FileNode) => {
     const newName = prompt('请输入新的文件夹名称:', folder.name);
     if (!newName || newName === folder.name) {
@@ -345,7 +382,20 @@ const Layout: React.FC<LayoutProps> = ({ children }) => {
     }
 
     try {
-      await invoke('rename_folder', { oldPath: folder.path, newName });
+      // 先预览需要改写的链接，确认后再执行
+      const plan = await invoke<{ rewrites: { path: string; edits: unknown[] }[] }>('rename_folder', {
+        oldPath: folder.path,
+        newName,
+        dryRun: true,
+      });
+      if (plan.rewrites.length > 0) {
+        const files = plan.rewrites.map(rewrite => `  ${rewrite.path}（${rewrite.edits.length} 处）`).join('\n');
+        if (!window.confirm(`重命名后将改写以下文件中的链接:\n${files}\n\n确定要继续吗？`)) {
+          return;
+        }
+      }
+
+      await invoke('rename_folder', { oldPath: folder.path, newName, dryRun: false });
       
       // 重新加载文件树
       await loadFileTree();
@@ -387,6 +437,12 @@ const Layout: React.FC<LayoutProps> = ({ children }) => {
       icon: <Edit3 size={14} />,
       onClick: () => handleFileSelect(file)
     },
+    {
+      id: 'rename',
+      label: '重命名',
+      icon: <Edit size={14} />,
+      onClick: () => handleRenameFile(file)
+    },
     {
       id: 'separator1',
       label: '',
@@ -6,6 +6,7 @@ const navigation = [
   { name: '编辑器', href: '/editor', icon: '✏️' },
   { name: '知识图谱', href: '/graph', icon: '🕸️' },
   { name: '发布', href: '/publisher', icon: '🚀' },
+  { name: '回收站', href: '/trash', icon: '🗑️' },
   { name: '设置', href: '/settings', icon: '⚙️' },
 ]
 
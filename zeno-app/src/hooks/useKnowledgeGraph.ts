@@ -12,6 +12,17 @@ interface BacklinkInfo {
   occurrence_count: number;
 }
 
+export interface UnlinkedMention {
+  source_note_id: string;
+  source_note_path: string;
+  target_note_id: string;
+  text: string;
+  range: { start: number; end: number };
+  line_number: number;
+  context: string;
+  link: string;
+}
+
 interface SimilarNote {
   note_id: string;
   title: string;
@@ -37,6 +48,15 @@ export interface GraphStats {
   orphaned_notes: number;
 }
 
+// 链接图谱保存在工作空间数据库中，查询时需要工作空间路径
+const getWorkspacePath = async (): Promise<string> => {
+  const config = await invoke<any>('get_config');
+  if (!config.workspace_path) {
+    throw new Error('未设置工作空间路径，请先在设置中配置工作空间');
+  }
+  return config.workspace_path;
+};
+
 export const useKnowledgeGraph = () => {
   const [nodes, setNodes] = useState<GraphNode[]>([]);
   const [edges, setEdges] = useState<GraphEdge[]>([]);
@@ -50,14 +70,10 @@ export const useKnowledgeGraph = () => {
       setLoading(true);
       setError(null);
 
-      // 首先获取配置来获取工作空间路径
-      const config = await invoke<any>('get_config');
-      if (!config.workspace_path) {
-        throw new Error('未设置工作空间路径，请先在设置中配置工作空间');
-      }
+      const workspacePath = await getWorkspacePath();
 
       // 获取所有笔记文件
-      const noteFiles = await invoke<any[]>('list_notes', { dirPath: config.workspace_path });
+      const noteFiles = await invoke<any[]>('list_notes', { dirPath: workspacePath });
       const graphNodes: GraphNode[] = [];
       const edgeMap = new Map<string, GraphEdge>();
       const notes: Note[] = [];
@@ -111,14 +127,14 @@ export const useKnowledgeGraph = () => {
 
         // 获取笔记的反向链接
         try {
-          const backlinks = await invoke<BacklinkInfo[]>('get_backlinks', { noteId: note.id });
+          const backlinks = await invoke<BacklinkInfo[]>('get_backlinks', { workspacePath, filePath: note.path });
           
           for (const backlink of backlinks) {
-            const edgeId = `${backlink.source_note_id}-${note.id}`;
+            const edgeId = `${backlink.source_note_path}-${note.id}`;
             if (!edgeMap.has(edgeId)) {
               edgeMap.set(edgeId, {
                 id: edgeId,
-                source: backlink.source_note_id,
+                source: backlink.source_note_path,
                 target: note.id,
                 type: 'link',
                 weight: Math.min(5, backlink.occurrence_count),
@@ -136,18 +152,19 @@ export const useKnowledgeGraph = () => {
         // 获取相似笔记并创建相似性边
         try {
           const similarNotes = await invoke<SimilarNote[]>('find_similar_notes', { 
-            noteId: note.id, 
+            workspacePath,
+            filePath: note.path, 
             limit: 5 
           });
           
           for (const similar of similarNotes) {
             if (similar.similarity_score > 0.3) { // 只显示相似度较高的
-              const edgeId = `${note.id}-${similar.note_id}-similarity`;
+              const edgeId = `${note.id}-${similar.path}-similarity`;
               if (!edgeMap.has(edgeId)) {
                 edgeMap.set(edgeId, {
                   id: edgeId,
                   source: note.id,
-                  target: similar.note_id,
+                  target: similar.path,
                   type: 'similarity',
                   weight: Math.round(similar.similarity_score * 5),
                   metadata: {
@@ -247,7 +264,7 @@ export const useKnowledgeGraph = () => {
 
       // 获取统计信息
       try {
-        const linkStats = await invoke<GraphStats>('get_link_statistics');
+        const linkStats = await invoke<GraphStats>('get_link_statistics', { workspacePath });
         setStats(linkStats);
       } catch (err) {
         console.warn('Failed to get link statistics:', err);
@@ -266,35 +283,9 @@ export const useKnowledgeGraph = () => {
     try {
       setLoading(true);
       
-      // 首先获取配置来获取工作空间路径
-      const config = await invoke<any>('get_config');
-      if (!config.workspace_path) {
-        throw new Error('未设置工作空间路径，请先在设置中配置工作空间');
-      }
-
-      // 获取所有笔记文件
-      const noteFiles = await invoke<any[]>('list_notes', { dirPath: config.workspace_path });
-      const notesData: [string, string, string, string][] = [];
-
-      // 读取每个笔记文件的内容
-      for (const noteFile of noteFiles) {
-        try {
-          const content = await invoke<string>('read_file_content', { path: noteFile.path });
-          const title = noteFile.name.replace(/\.md$/, '');
-          
-          notesData.push([
-            noteFile.path, // 使用文件路径作为 ID
-            noteFile.path,
-            title,
-            content
-          ]);
-        } catch (err) {
-          console.warn(`Failed to read note file ${noteFile.path}:`, err);
-        }
-      }
-
-      // 重建链接索引
-      await invoke('rebuild_link_index', { notesData });
+      // 重新索引工作空间，链接保存在数据库中
+      const workspacePath = await getWorkspacePath();
+      await invoke('rebuild_link_index', { workspacePath });
       
       // 重新构建图谱
       await buildGraphFromNotes();
@@ -320,10 +311,11 @@ export const useKnowledgeGraph = () => {
         };
       } else {
         // 笔记节点 - 获取反向链接和相似笔记
+        const workspacePath = await getWorkspacePath();
         const [backlinks, similarNotes, outgoingLinks] = await Promise.all([
-          invoke<BacklinkInfo[]>('get_backlinks', { noteId: nodeId }),
-          invoke<SimilarNote[]>('find_similar_notes', { noteId: nodeId, limit: 10 }),
-          invoke<string[]>('get_outgoing_links', { noteId: nodeId }),
+          invoke<BacklinkInfo[]>('get_backlinks', { workspacePath, filePath: nodeId }),
+          invoke<SimilarNote[]>('find_similar_notes', { workspacePath, filePath: nodeId, limit: 10 }),
+          invoke<string[]>('get_outgoing_links', { workspacePath, filePath: nodeId }),
         ]);
 
         return {
@@ -353,17 +345,49 @@ export const useKnowledgeGraph = () => {
   // 获取断链信息
   const getBrokenLinks = useCallback(async () => {
     try {
-      return await invoke('get_broken_links');
+      return await invoke('get_broken_links', { workspacePath: await getWorkspacePath() });
     } catch (err) {
       console.error('Failed to get broken links:', err);
       return [];
     }
   }, []);
 
+  // 获取目标有歧义的链接（多篇笔记同名或别名相同）
+  const getAmbiguousLinks = useCallback(async () => {
+    try {
+      return await invoke('get_ambiguous_links', { workspacePath: await getWorkspacePath() });
+    } catch (err) {
+      console.error('Failed to get ambiguous links:', err);
+      return [];
+    }
+  }, []);
+
+  // 获取提到笔记标题或别名但没有链接到它的地方
+  const getUnlinkedMentions = useCallback(async (filePath: string) => {
+    try {
+      return await invoke<UnlinkedMention[]>('get_unlinked_mentions', {
+        workspacePath: await getWorkspacePath(),
+        filePath,
+      });
+    } catch (err) {
+      console.error('Failed to get unlinked mentions:', err);
+      return [];
+    }
+  }, []);
+
+  // 把提及改为链接，完成后重新加载图谱
+  const linkMentions = useCallback(async (mentions: UnlinkedMention[]) => {
+    await invoke<number>('link_unlinked_mentions', {
+      workspacePath: await getWorkspacePath(),
+      mentions,
+    });
+    await buildGraphFromNotes();
+  }, [buildGraphFromNotes]);
+
   // 获取孤立笔记
   const getOrphanedNotes = useCallback(async () => {
     try {
-      return await invoke<string[]>('get_orphaned_notes');
+      return await invoke<string[]>('get_orphaned_notes', { workspacePath: await getWorkspacePath() });
     } catch (err) {
       console.error('Failed to get orphaned notes:', err);
       return [];
@@ -381,6 +405,9 @@ export const useKnowledgeGraph = () => {
     getNodeDetails,
     searchNodes,
     getBrokenLinks,
+    getAmbiguousLinks,
+    getUnlinkedMentions,
+    linkMentions,
     getOrphanedNotes,
   };
 };
\ No newline at end of file
@@ -245,7 +245,7 @@ export default function NotesPage() {
   }
 
   const handleDeleteNote = async (note: NoteFile) => {
-    if (!window.confirm(`确定要删除笔记 "${note.name}" 吗？此操作无法撤销。`)) {
+    if (!window.confirm(`确定要删除笔记 "${note.name}" 吗？笔记会移入回收站，可以恢复。`)) {
       return
     }
 
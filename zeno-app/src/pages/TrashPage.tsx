@@ -0,0 +1,165 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { RotateCcw, Trash2 } from 'lucide-react'
+
+interface TrashEntry {
+  note_id: string
+  title: string
+  original_path: string
+  trash_path: string
+  deleted_at: string
+}
+
+// 回收站保存在工作空间数据库中，操作时需要工作空间路径
+const getWorkspacePath = async (): Promise<string> => {
+  const config = await invoke<{ workspace_path: string | null }>('get_config')
+  if (!config.workspace_path) {
+    throw new Error('未设置工作空间路径，请先在设置中配置工作空间')
+  }
+  return config.workspace_path
+}
+
+export default function TrashPage() {
+  const [entries, setEntries] = useState<TrashEntry[]>([])
+  const [loading, setLoading] = useState(true)
+  const [error, setError] = useState<string | null>(null)
+
+  // 列出时后端会先彻底删除超过保留期限的笔记
+  const refreshTrash = useCallback(async () => {
+    try {
+      setLoading(true)
+      setError(null)
+      const workspacePath = await getWorkspacePath()
+      setEntries(await invoke<TrashEntry[]>('list_trash', { workspacePath }))
+    } catch (err) {
+      console.error('加载回收站失败:', err)
+      setError(`加载回收站失败: ${err}`)
+    } finally {
+      setLoading(false)
+    }
+  }, [])
+
+  useEffect(() => {
+    refreshTrash()
+  }, [refreshTrash])
+
+  const handleRestore = async (entry: TrashEntry) => {
+    try {
+      const workspacePath = await getWorkspacePath()
+      await invoke('restore_note', { workspacePath, noteId: entry.note_id })
+      await refreshTrash()
+    } catch (err) {
+      console.error('恢复笔记失败:', err)
+      setError(`${err}`)
+    }
+  }
+
+  const handleEmpty = async () => {
+    if (!window.confirm(`确定要清空回收站吗？${entries.length} 篇笔记将被彻底删除，无法恢复。`)) {
+      return
+    }
+
+    try {
+      const workspacePath = await getWorkspacePath()
+      await invoke('purge_trash', { workspacePath, olderThanDays: null })
+      await refreshTrash()
+    } catch (err) {
+      console.error('清空回收站失败:', err)
+      setError(`清空回收站失败: ${err}`)
+    }
+  }
+
+  return (
+    <div style={{ padding: '2rem' }}>
+      <div style={{ maxWidth: '48rem', margin: '0 auto' }}>
+        <div style={{
+          display: 'flex',
+          alignItems: 'center',
+          justifyContent: 'space-between',
+          marginBottom: '1.5rem'
+        }}>
+          <h1 style={{ fontSize: '1.875rem', fontWeight: 'bold', margin: 0 }}>回收站</h1>
+          <button
+            onClick={handleEmpty}
+            disabled={loading || entries.length === 0}
+            style={{
+              display: 'flex',
+              alignItems: 'center',
+              gap: '0.5rem',
+              padding: '0.5rem 1rem',
+              backgroundColor: '#dc3545',
+              color: 'white',
+              border: 'none',
+              borderRadius: '0.375rem',
+              cursor: entries.length === 0 ? 'not-allowed' : 'pointer',
+              opacity: loading || entries.length === 0 ? 0.5 : 1
+            }}
+          >
+            <Trash2 size={14} />
+            清空回收站
+          </button>
+        </div>
+
+        {error && (
+          <div style={{
+            backgroundColor: '#f8d7da',
+            border: '1px solid #f5c6cb',
+            borderRadius: '0.375rem',
+            padding: '0.75rem 1rem',
+            marginBottom: '1rem',
+            color: '#721c24'
+          }}>
+            {error}
+          </div>
+        )}
+
+        {loading ? (
+          <div style={{ color: '#6c757d' }}>加载中...</div>
+        ) : entries.length === 0 ? (
+          <div style={{ color: '#6c757d', textAlign: 'center', padding: '3rem 0' }}>回收站是空的</div>
+        ) : (
+          <div style={{ display: 'flex', flexDirection: 'column', gap: '0.5rem' }}>
+            {entries.map(entry => (
+              <div
+                key={entry.note_id}
+                style={{
+                  display: 'flex',
+                  alignItems: 'center',
+                  justifyContent: 'space-between',
+                  border: '1px solid #e9ecef',
+                  borderRadius: '0.5rem',
+                  padding: '0.75rem 1rem'
+                }}
+              >
+                <div style={{ minWidth: 0 }}>
+                  <div style={{ fontWeight: 500 }}>{entry.title}</div>
+                  <div style={{ fontSize: '0.75rem', color: '#6c757d' }}>
+                    {entry.original_path} · 删除于 {new Date(entry.deleted_at).toLocaleString('zh-CN')}
+                  </div>
+                </div>
+                <button
+                  onClick={() => handleRestore(entry)}
+                  style={{
+                    display: 'flex',
+                    alignItems: 'center',
+                    gap: '0.375rem',
+                    padding: '0.375rem 0.75rem',
+                    backgroundColor: 'transparent',
+                    color: '#007bff',
+                    border: '1px solid #007bff',
+                    borderRadius: '0.375rem',
+                    cursor: 'pointer',
+                    flexShrink: 0
+                  }}
+                >
+                  <RotateCcw size={14} />
+                  恢复
+                </button>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  )
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use clap::Subcommand;
+use zeno_core::{format_bytes, BackupInfo, BackupManager};
+
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Subcommand)]
+pub enum BackupCommand {
+    /// 在线备份工作区数据库到 .zeno/backups，并按 zeno.yml 中的 backup 保留策略清理旧备份
+    Create,
+    /// 列出备份，最新的在前
+    List {
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 从备份恢复数据库，恢复前会先备份当前数据库
+    Restore {
+        /// 备份文件名（见 zeno backup list）或备份文件路径
+        backup: String,
+    },
+}
+
+pub async fn backup(workspace: &Workspace, command: BackupCommand) -> Result<()> {
+    let manager = BackupManager::new(&workspace.root, workspace.config.backup.clone());
+
+    match command {
+        BackupCommand::Create => {
+            let db = workspace.open_database().await?;
+            let backup = manager.create(&db).await?;
+            println!("✅ 已备份到 {}（{}，已通过完整性检查）", backup.path.display(), format_bytes(backup.size));
+            print_rotated(&manager.rotate().await?);
+        }
+        BackupCommand::List { format } => {
+            let backups = manager.list().await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&backups)?),
+                OutputFormat::Table if backups.is_empty() => println!("还没有备份，使用 zeno backup create 创建"),
+                OutputFormat::Table => {
+                    for backup in &backups {
+                        println!(
+                            "{}  {:>9}  {}",
+                            backup.created_at.format("%Y-%m-%d %H:%M:%S"),
+                            format_bytes(backup.size),
+                            backup.name
+                        );
+                    }
+                }
+            }
+        }
+        BackupCommand::Restore { backup } => {
+            let backup = manager.find(&backup).await?;
+            let db = workspace.open_database().await?;
+
+            // 恢复前先备份当前数据库，恢复可以撤销
+            let current = manager.create(&db).await?;
+            println!("已将当前数据库备份为 {}", current.name);
+
+            db.restore(&backup.path).await?;
+            println!("✅ 已从 {}（{}）恢复数据库", backup.name, backup.created_at.format("%Y-%m-%d %H:%M:%S"));
+            println!("运行 zeno index 使索引与笔记文件保持一致");
+        }
+    }
+
+    Ok(())
+}
+
+fn print_rotated(removed: &[BackupInfo]) {
+    for backup in removed {
+        println!("  ✗ {}", backup.name);
+    }
+    if !removed.is_empty() {
+        println!("🧹 已按保留策略删除 {} 个旧备份", removed.len());
+    }
+}
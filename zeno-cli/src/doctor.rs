@@ -0,0 +1,93 @@
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use zeno_core::{Doctor, DoctorReport, Severity};
+
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// 输出格式
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+    /// 应用安全修复（创建或迁移数据库、重建全文索引、重新索引文件）
+    #[arg(long)]
+    fix: bool,
+}
+
+/// JSON 输出
+#[derive(Serialize)]
+struct DoctorOutput<'a> {
+    healthy: bool,
+    errors: usize,
+    warnings: usize,
+    fixed: &'a [String],
+    #[serde(flatten)]
+    report: &'a DoctorReport,
+}
+
+/// 检查工作区健康状况，返回进程退出码：
+/// 0 表示没有问题，1 表示只有警告，2 表示存在错误
+pub async fn doctor(workspace: &Workspace, args: DoctorArgs) -> Result<i32> {
+    let doctor = Doctor::new(&workspace.root);
+    let mut report = doctor.check().await?;
+
+    let mut fixed = Vec::new();
+    if args.fix {
+        fixed = doctor.fix(&report).await?;
+        if !fixed.is_empty() {
+            report = doctor.check().await?;
+        }
+    }
+
+    match args.format {
+        OutputFormat::Json => {
+            let output = DoctorOutput {
+                healthy: report.is_healthy(),
+                errors: report.errors(),
+                warnings: report.warnings(),
+                fixed: &fixed,
+                report: &report,
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Table => print_report(&report, &fixed),
+    }
+
+    Ok(if report.errors() > 0 {
+        2
+    } else if report.warnings() > 0 {
+        1
+    } else {
+        0
+    })
+}
+
+fn print_report(report: &DoctorReport, fixed: &[String]) {
+    for fix in fixed {
+        println!("🔧 {}", fix);
+    }
+
+    for issue in &report.issues {
+        let icon = match issue.severity {
+            Severity::Error => "✗",
+            Severity::Warning => "!",
+        };
+        let path = issue.path.as_ref().map(|p| format!("{}: ", p.display())).unwrap_or_default();
+        let hint = if issue.fixable { "（可用 --fix 修复）" } else { "" };
+        println!("{} {}{}{}", icon, path, issue.message, hint);
+    }
+
+    if report.is_healthy() {
+        println!("✅ 工作区健康：{} 个文件，{} 篇已索引笔记", report.files, report.notes);
+    } else {
+        println!(
+            "共 {} 个错误，{} 个警告（{} 个文件，{} 篇已索引笔记）",
+            report.errors(),
+            report.warnings(),
+            report.files,
+            report.notes
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::Arc;
+use zeno_core::{
+    Database, FileEventProcessor, FileWatcher, IndexFailure, KnowledgeBase, LocalFileStorage, ProcessReport,
+    ReindexReport, SqliteIndexer, SqliteNoteRepository, WatcherConfig,
+};
+
+use crate::workspace::Workspace;
+
+#[derive(Args)]
+pub struct IndexArgs {
+    /// 索引完成后继续监控文件变化，按 Ctrl-C 退出
+    #[arg(long)]
+    watch: bool,
+}
+
+/// 建立或更新工作区索引
+pub async fn index(workspace: &Workspace, args: IndexArgs) -> Result<()> {
+    let root = workspace.root.canonicalize()?;
+    let db = workspace.open_database().await?;
+    let indexer = workspace.indexer(&db);
+
+    let report = reindex(&indexer, &root).await?;
+    print_report(&report);
+
+    // 彻底删除回收站中到期的笔记
+    let purged = workspace.knowledge_base(&db).purge_expired_trash(&workspace.config.trash).await?;
+    if !purged.is_empty() {
+        crate::trash::print_purged(&purged);
+    }
+
+    if args.watch {
+        return watch(workspace, &root, &db, indexer).await;
+    }
+    if !report.failed.is_empty() {
+        bail!("{} 个文件索引失败", report.failed.len());
+    }
+    Ok(())
+}
+
+/// 全量索引，终端上显示进度
+pub async fn reindex(indexer: &SqliteIndexer, root: &Path) -> Result<ReindexReport> {
+    let progress = std::io::stderr().is_terminal();
+    let report = indexer
+        .reindex_all(root, |p| {
+            if progress {
+                eprint!("\r\x1b[2K索引中 {}/{} {}", p.current, p.total, p.path.display());
+                let _ = std::io::stderr().flush();
+            }
+        })
+        .await?;
+    if progress {
+        eprint!("\r\x1b[2K");
+    }
+    Ok(report)
+}
+
+fn print_report(report: &ReindexReport) {
+    println!(
+        "新增 {}，更新 {}，未变 {}，删除 {}，失败 {}",
+        report.added,
+        report.updated,
+        report.unchanged,
+        report.removed,
+        report.failed.len()
+    );
+    print_failures(&report.failed);
+}
+
+pub fn print_failures(failed: &[IndexFailure]) {
+    for failure in failed {
+        eprintln!("  ✗ {}: {}", failure.path.display(), failure.error);
+    }
+}
+
+/// 监控文件变化并增量更新索引
+async fn watch(workspace: &Workspace, root: &Path, db: &Database, indexer: SqliteIndexer) -> Result<()> {
+    let pool = db.pool().clone();
+    let knowledge_base = Arc::new(KnowledgeBase::new(
+        Box::new(LocalFileStorage::with_config(root.to_path_buf(), &workspace.config)),
+        Box::new(SqliteNoteRepository::new(pool.clone()).with_history(workspace.config.history.clone())),
+    ));
+    let processor = FileEventProcessor::new(pool.clone(), knowledge_base);
+    let config = WatcherConfig {
+        excluded: workspace.config.excluded_dirs(),
+        ..Default::default()
+    };
+    let mut watcher = FileWatcher::start(root.to_path_buf(), pool, config)?;
+
+    println!("👀 正在监控 {}，按 Ctrl-C 退出", root.display());
+    loop {
+        tokio::select! {
+            flushed = watcher.flushed() => {
+                if !flushed {
+                    bail!("文件监控已停止");
+                }
+                // 单次处理失败（如数据库暂时被锁）时继续监控，未处理的事件留到下一轮
+                let report = match processor.process_pending().await {
+                    Ok(report) => report,
+                    Err(e) => {
+                        eprintln!("  ✗ 处理文件变化失败: {}", e);
+                        continue;
+                    }
+                };
+                if report.imported + report.renamed + report.removed > 0 {
+                    // 只重新解析变化的笔记和可能指向它们的笔记
+                    if let Err(e) = indexer.refresh_changed(&report.changed_paths, &report.previous_names).await {
+                        eprintln!("  ✗ 更新链接失败: {}", e);
+                    }
+                }
+                print_process_report(&report);
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    watcher.stop();
+    Ok(())
+}
+
+fn print_process_report(report: &ProcessReport) {
+    if report.imported + report.renamed + report.removed + report.failed.len() == 0 {
+        return;
+    }
+    println!(
+        "更新 {}，重命名 {}，删除 {}，失败 {}",
+        report.imported,
+        report.renamed,
+        report.removed,
+        report.failed.len()
+    );
+    print_failures(&report.failed);
+}
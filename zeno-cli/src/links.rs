@@ -0,0 +1,212 @@
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use serde::Serialize;
+use zeno_core::{BrokenLinkKind, LinkIndex, LinkStore};
+
+use crate::index;
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Subcommand)]
+pub enum LinksCommand {
+    /// 列出链接到指定笔记的笔记
+    Backlinks {
+        /// 笔记路径、文件名或标题
+        note: String,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 列出找不到目标笔记、标题或块的链接，存在断链时以退出码 1 结束；
+    /// 指向回收站中笔记的链接和目标有歧义（多篇笔记同名或别名相同）的链接单独列出，不算断链
+    Broken {
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 列出没有任何入链和出链的笔记
+    Orphans {
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 列出提到笔记标题或别名但没有链接到它的地方
+    Mentions {
+        /// 笔记路径、文件名或标题
+        note: String,
+        /// 把找到的提及全部改为链接
+        #[arg(long)]
+        link: bool,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 按共同链接查找相似笔记
+    Similar {
+        /// 笔记路径、文件名或标题
+        note: String,
+        /// 最多显示的结果数
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+}
+
+/// JSON 输出中的一篇笔记
+#[derive(Serialize)]
+struct NoteOutput<'a> {
+    id: &'a str,
+    path: String,
+    title: &'a str,
+}
+
+/// 分析链接图谱，返回进程退出码：`broken` 发现断链时为 1，其余为 0
+pub async fn links(workspace: &Workspace, command: LinksCommand) -> Result<i32> {
+    // 先增量更新索引，再从数据库构建链接图谱，保证结果与磁盘上的文件一致
+    let root = workspace.root.canonicalize()?;
+    let db = workspace.open_database().await?;
+    let indexer = workspace.indexer(&db);
+    let report = index::reindex(&indexer, &root).await?;
+    index::print_failures(&report.failed);
+    let index = LinkIndex::load(db.pool(), &root).await?;
+
+    match command {
+        LinksCommand::Backlinks { note, format } => {
+            let note_id = find_note(&index, &note)?;
+            let backlinks = index.get_backlinks(note_id);
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&backlinks)?),
+                OutputFormat::Table if backlinks.is_empty() => println!("没有笔记链接到 {}", note),
+                OutputFormat::Table => {
+                    for backlink in &backlinks {
+                        let count = if backlink.occurrence_count > 1 {
+                            format!("（{} 处）", backlink.occurrence_count)
+                        } else {
+                            String::new()
+                        };
+                        println!(
+                            "{}:{}  {}{}",
+                            backlink.source_note_path, backlink.line_number, backlink.source_note_title, count
+                        );
+                        println!("    {}", backlink.context);
+                    }
+                }
+            }
+        }
+        LinksCommand::Broken { format } => {
+            let broken = index.get_broken_links(None);
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&broken)?),
+                OutputFormat::Table => {
+                    for link in &broken {
+                        let missing = match link.kind {
+                            BrokenLinkKind::MissingNote => String::new(),
+                            BrokenLinkKind::MissingHeading | BrokenLinkKind::MissingBlock => {
+                                let target = link.target_note_id.as_deref().and_then(|id| index.note_path(id));
+                                let what = if link.kind == BrokenLinkKind::MissingBlock { "块" } else { "标题" };
+                                format!("（{} 中没有这个{}）", target.map(|p| p.display().to_string()).unwrap_or_default(), what)
+                            }
+                        };
+                        let hint = if link.suggestions.is_empty() {
+                            String::new()
+                        } else {
+                            format!("（是否指 {}？）", link.suggestions.join("、"))
+                        };
+                        println!("{}:{}: {}{}{}", link.source_note_path, link.link.line_number, link.link.raw, missing, hint);
+                    }
+                    // 目标在回收站中的链接单独列出，不算断链
+                    for link in index.get_trashed_links(None) {
+                        println!(
+                            "{}:{}: {}（{} 在回收站中）",
+                            link.source_note_path, link.link.line_number, link.link.raw, link.trashed_note_path
+                        );
+                    }
+                    // 多篇笔记同名的链接只作为警告
+                    for link in index.get_ambiguous_links(None) {
+                        println!(
+                            "{}:{}: {}（有歧义: {}）",
+                            link.source_note_path,
+                            link.link.line_number,
+                            link.link.raw,
+                            link.candidate_paths.join("、")
+                        );
+                    }
+                    if broken.is_empty() {
+                        println!("✅ 没有断链");
+                    } else {
+                        eprintln!("共 {} 个断链", broken.len());
+                    }
+                }
+            }
+            if !broken.is_empty() {
+                return Ok(1);
+            }
+        }
+        LinksCommand::Orphans { format } => {
+            let orphans = index.get_orphaned_notes();
+            let notes: Vec<NoteOutput> = orphans
+                .iter()
+                .map(|id| NoteOutput {
+                    id,
+                    path: index.note_path(id).map(|p| p.display().to_string()).unwrap_or_default(),
+                    title: index.note_title(id).unwrap_or_default(),
+                })
+                .collect();
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&notes)?),
+                OutputFormat::Table if notes.is_empty() => println!("没有孤立笔记"),
+                OutputFormat::Table => {
+                    for note in &notes {
+                        println!("{}  {}", note.path, note.title);
+                    }
+                }
+            }
+        }
+        LinksCommand::Mentions { note, link, format } => {
+            let note_id = find_note(&index, &note)?;
+            let mentions = LinkStore::new(db.pool().clone()).get_unlinked_mentions(&root, note_id).await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&mentions)?),
+                OutputFormat::Table if mentions.is_empty() => println!("没有未链接的提及"),
+                OutputFormat::Table => {
+                    for mention in &mentions {
+                        println!("{}:{}: {} → {}", mention.source_note_path, mention.line_number, mention.text, mention.link);
+                        println!("    {}", mention.context);
+                    }
+                }
+            }
+            if link && !mentions.is_empty() {
+                let files = indexer.link_mentions(&root, &mentions).await?;
+                // JSON 输出时标准输出只有结果
+                eprintln!("🔗 已在 {} 个文件中添加 {} 个链接", files, mentions.len());
+            }
+        }
+        LinksCommand::Similar { note, limit, format } => {
+            let note_id = find_note(&index, &note)?;
+            let similar = index.find_similar_notes(note_id, limit);
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&similar)?),
+                OutputFormat::Table if similar.is_empty() => println!("没有找到与 {} 相似的笔记", note),
+                OutputFormat::Table => {
+                    for note in &similar {
+                        println!(
+                            "{:.2}  {}  {}（共同链接 {}）",
+                            note.similarity_score,
+                            note.path,
+                            note.title,
+                            note.common_links.len()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(0)
+}
+
+fn find_note<'a>(index: &'a LinkIndex, query: &str) -> Result<&'a str> {
+    index.find_note(query).ok_or_else(|| anyhow!("找不到笔记: {}", query))
+}
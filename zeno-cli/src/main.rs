@@ -1,16 +1,59 @@
+mod backup;
+mod doctor;
+mod index;
+mod links;
+mod note;
+mod publish;
+mod rename;
+mod search;
+mod transfer;
+mod trash;
+mod versions;
+mod workspace;
+
 use clap::{Parser, Subcommand};
 use anyhow::Result;
 use std::path::PathBuf;
-use zeno_core::{MarkdownParser, LocalFileStorage, FileStorage};
+use zeno_core::{
+    DirectoryOverrides, FileStorage, LocalFileStorage, MarkdownParser, WorkspaceConfig, DEFAULT_CONFIG_YAML,
+    WORKSPACE_CONFIG_FILE,
+};
+use workspace::Workspace;
 
 #[derive(Parser)]
 #[command(name = "zeno")]
 #[command(about = "Zeno - 个人知识管理与发布平台", long_about = None)]
 struct Cli {
+    /// 知识库根目录
+    #[arg(short, long, global = true, default_value = ".")]
+    workspace: PathBuf,
+
+    /// 覆盖 zeno.yml 中的笔记目录
+    #[arg(long, global = true, value_name = "DIR")]
+    notes_dir: Option<PathBuf>,
+
+    /// 覆盖 zeno.yml 中的附件目录
+    #[arg(long, global = true, value_name = "DIR")]
+    assets_dir: Option<PathBuf>,
+
+    /// 覆盖 zeno.yml 中的模板目录
+    #[arg(long, global = true, value_name = "DIR")]
+    templates_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+impl Cli {
+    fn overrides(&self) -> DirectoryOverrides {
+        DirectoryOverrides {
+            notes: self.notes_dir.clone(),
+            assets: self.assets_dir.clone(),
+            templates: self.templates_dir.clone(),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// 解析 Markdown 文件
@@ -34,6 +77,83 @@ enum Commands {
         #[arg(short, long, default_value = ".")]
         path: PathBuf,
     },
+    /// 用模板创建新笔记
+    New(note::NewArgs),
+    /// 建立或更新工作区索引
+    #[command(visible_alias = "sync")]
+    Index(index::IndexArgs),
+    /// 检查工作区健康状况，退出码 0 表示正常、1 表示有警告、2 表示有错误
+    Doctor(doctor::DoctorArgs),
+    /// 分析笔记之间的链接：反向链接、断链、孤立笔记和相似笔记
+    Links {
+        #[command(subcommand)]
+        command: links::LinksCommand,
+    },
+    /// 发布笔记，任何笔记发布失败时以退出码 1 结束
+    Publish {
+        #[command(subcommand)]
+        command: publish::PublishCommand,
+    },
+    /// 从其他笔记应用导入笔记
+    Import {
+        #[command(subcommand)]
+        command: transfer::ImportCommand,
+    },
+    /// 导出笔记为其他格式
+    Export {
+        #[command(subcommand)]
+        command: transfer::ExportCommand,
+    },
+    /// 全文搜索笔记
+    Search(search::SearchArgs),
+    /// 查看、比较和恢复笔记的历史版本
+    Versions {
+        #[command(subcommand)]
+        command: versions::VersionsCommand,
+    },
+    /// 重命名或移动笔记，并改写其他笔记中指向它的链接
+    #[command(alias = "mv")]
+    Rename {
+        /// 笔记文件路径
+        note: PathBuf,
+        /// 新路径：只写文件名时保留在原目录，否则为相对工作区的路径
+        to: PathBuf,
+        /// 只显示将要改写的链接，不做任何修改
+        #[arg(long)]
+        dry_run: bool,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = search::OutputFormat::Table)]
+        format: search::OutputFormat,
+    },
+    /// 将笔记移入回收站
+    Delete {
+        /// 笔记文件路径
+        note: PathBuf,
+    },
+    /// 查看、恢复和清空回收站
+    Trash {
+        #[command(subcommand)]
+        command: trash::TrashCommand,
+    },
+    /// 备份和恢复工作区数据库
+    Backup {
+        #[command(subcommand)]
+        command: backup::BackupCommand,
+    },
+    /// 查看或清除搜索历史
+    History {
+        /// 显示条数
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+        /// 清除全部搜索历史
+        #[arg(long)]
+        clear: bool,
+    },
+    /// 管理保存的搜索
+    Saved {
+        #[command(subcommand)]
+        command: search::SavedCommand,
+    },
     /// 显示版本信息
     Version,
 }
@@ -45,6 +165,9 @@ async fn main() -> Result<()> {
     
     let cli = Cli::parse();
     
+    let overrides = cli.overrides();
+    let workspace = || Workspace::load(&cli.workspace, overrides.clone());
+    
     match cli.command {
         Commands::Parse { file, format } => {
             parse_file(file, format).await?;
@@ -53,7 +176,67 @@ async fn main() -> Result<()> {
             list_files(dir).await?;
         }
         Commands::Init { path } => {
-            init_workspace(path).await?;
+            init_workspace(path, overrides.clone()).await?;
+        }
+        Commands::New(args) => {
+            note::new_note(&workspace().await?, args).await?;
+        }
+        Commands::Index(args) => {
+            index::index(&workspace().await?, args).await?;
+        }
+        Commands::Doctor(args) => {
+            let code = doctor::doctor(&workspace().await?, args).await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
+        Commands::Links { command } => {
+            let code = links::links(&workspace().await?, command).await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
+        Commands::Publish { command } => {
+            let code = publish::publish(&workspace().await?, command).await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
+        Commands::Import { command } => {
+            let code = transfer::import(&cli.workspace, overrides.clone(), command).await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
+        Commands::Export { command } => {
+            let code = transfer::export(&workspace().await?, command).await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
+        Commands::Search(args) => {
+            search::search(&workspace().await?, args).await?;
+        }
+        Commands::Versions { command } => {
+            versions::versions(&workspace().await?, command).await?;
+        }
+        Commands::Rename { note, to, dry_run, format } => {
+            rename::rename(&workspace().await?, note, to, dry_run, format).await?;
+        }
+        Commands::Delete { note } => {
+            trash::delete(&workspace().await?, note).await?;
+        }
+        Commands::Trash { command } => {
+            trash::trash(&workspace().await?, command).await?;
+        }
+        Commands::Backup { command } => {
+            backup::backup(&workspace().await?, command).await?;
+        }
+        Commands::History { limit, clear } => {
+            search::history(&workspace().await?, limit, clear).await?;
+        }
+        Commands::Saved { command } => {
+            search::saved(&workspace().await?, command).await?;
         }
         Commands::Version => {
             println!("zeno-cli {}", env!("CARGO_PKG_VERSION"));
@@ -96,31 +279,38 @@ async fn list_files(dir: PathBuf) -> Result<()> {
     Ok(())
 }
 
-async fn init_workspace(path: PathBuf) -> Result<()> {
-    // 创建目录结构
+async fn init_workspace(path: PathBuf, overrides: DirectoryOverrides) -> Result<()> {
     tokio::fs::create_dir_all(&path).await?;
-    tokio::fs::create_dir_all(path.join("notes")).await?;
-    tokio::fs::create_dir_all(path.join("assets")).await?;
-    tokio::fs::create_dir_all(path.join("templates")).await?;
+
+    // 已有配置时沿用，不覆盖
+    let config_path = path.join(WORKSPACE_CONFIG_FILE);
+    let mut config = WorkspaceConfig::load(&path).await?;
+    config.apply_overrides(overrides)?;
+    if !config_path.exists() {
+        if config == WorkspaceConfig::default() {
+            tokio::fs::write(&config_path, DEFAULT_CONFIG_YAML).await?;
+        } else {
+            config.save(&path).await?;
+        }
+    }
+
+    // 创建目录结构
+    tokio::fs::create_dir_all(config.notes_dir(&path)).await?;
+    tokio::fs::create_dir_all(config.assets_dir(&path)).await?;
+    tokio::fs::create_dir_all(config.templates_dir(&path)).await?;
     
-    // 创建配置文件
-    let config = r#"# Zeno 知识库配置
-title: "我的知识库"
-description: "基于 Zeno 的个人知识管理系统"
-
-# 目录配置
-directories:
-  notes: "notes"
-  assets: "assets"
-  templates: "templates"
-
-# 默认设置
-defaults:
-  note_template: "default"
-  publish: false
+    // 创建默认笔记模板
+    let default_template = r#"---
+status: draft
+tags: []
+---
+
+# {{ title }}
+
 "#;
-    
-    tokio::fs::write(path.join("zeno.yml"), config).await?;
+
+    let template_path = config.templates_dir(&path).join(format!("{}.md", config.defaults.note_template));
+    write_if_missing(&template_path, default_template).await?;
     
     // 创建示例笔记
     let sample_note = r#"---
@@ -145,7 +335,7 @@ status: draft
 
 ## 开始使用
 
-1. 在 `notes/` 目录下创建新的 Markdown 文件
+1. 在笔记目录下创建新的 Markdown 文件，或运行 `zeno new "标题"`
 2. 使用前言（frontmatter）配置笔记元数据
 3. 享受写作的乐趣！
 
@@ -154,11 +344,27 @@ status: draft
 祝你使用愉快！ 🎉
 "#;
     
-    tokio::fs::write(path.join("notes/welcome.md"), sample_note).await?;
+    let note_path = config.notes_dir(&path).join("welcome.md");
+    write_if_missing(&note_path, sample_note).await?;
+
+    // 创建数据库并建立索引
+    let workspace = Workspace { root: path.clone(), config };
+    let db = workspace.open_database().await?;
+    let report = index::reindex(&workspace.indexer(&db), &path).await?;
     
+    let relative = |p: &std::path::Path| p.strip_prefix(&path).unwrap_or(p).display().to_string();
     println!("✅ 知识库已初始化到: {}", path.display());
-    println!("📝 已创建示例笔记: notes/welcome.md");
-    println!("⚙️  配置文件: zeno.yml");
+    println!("📝 示例笔记: {}", relative(&note_path));
+    println!("📄 默认模板: {}", relative(&template_path));
+    println!("⚙️  配置文件: {}", WORKSPACE_CONFIG_FILE);
+    println!("🗂️  数据库: {}（已索引 {} 篇笔记）", zeno_core::WORKSPACE_DB_PATH, report.added + report.updated + report.unchanged);
     
     Ok(())
-}
\ No newline at end of file
+}
+/// 文件不存在时才写入，避免覆盖已有内容
+async fn write_if_missing(path: &std::path::Path, content: &str) -> Result<()> {
+    if !tokio::fs::try_exists(path).await? {
+        tokio::fs::write(path, content).await?;
+    }
+    Ok(())
+}
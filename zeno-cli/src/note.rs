@@ -0,0 +1,58 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use std::path::PathBuf;
+use zeno_core::{new_note_path, validate_note_dir, write_new_note, TemplateStore, TemplateVars};
+
+use crate::workspace::Workspace;
+
+#[derive(Args)]
+pub struct NewArgs {
+    /// 笔记标题
+    title: String,
+    /// 模板名称，对应 templates/ 下的文件，默认读取 zeno.yml 中的 defaults.note_template
+    #[arg(short, long)]
+    template: Option<String>,
+    /// 笔记目录下的子目录
+    #[arg(short, long)]
+    dir: Option<PathBuf>,
+    /// 自定义模板变量，如 --var project=zeno，可重复
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    vars: Vec<(String, String)>,
+}
+
+fn parse_var(s: &str) -> Result<(String, String)> {
+    let (key, value) = s.split_once('=').ok_or_else(|| anyhow!("变量格式应为 KEY=VALUE: {}", s))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow!("变量名不能为空: {}", s));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// 用模板创建新笔记
+pub async fn new_note(workspace: &Workspace, args: NewArgs) -> Result<()> {
+    if let Some(sub) = &args.dir {
+        validate_note_dir(sub)?;
+    }
+    let template = args
+        .template
+        .unwrap_or_else(|| workspace.config.defaults.note_template.clone());
+
+    let mut vars = TemplateVars::for_note(&args.title);
+    for (key, value) in args.vars {
+        vars.set(key, value);
+    }
+
+    let store = TemplateStore::new(workspace.config.templates_dir(&workspace.root));
+    let content = store.render_note(&template, &vars).await?;
+
+    let mut dir = workspace.config.notes_dir(&workspace.root);
+    if let Some(sub) = args.dir {
+        dir.push(sub);
+    }
+    let path = new_note_path(&dir, &args.title)?;
+    write_new_note(&path, &content).await?;
+
+    println!("📝 已创建笔记: {}", path.strip_prefix(&workspace.root).unwrap_or(&path).display());
+    Ok(())
+}
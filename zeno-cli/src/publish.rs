@@ -0,0 +1,74 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use zeno_core::publisher::zola::PublishResult;
+use zeno_core::{PublishOptions, ZolaConfig, ZolaPublisher};
+
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Subcommand)]
+pub enum PublishCommand {
+    /// 发布到 Zola 站点，站点不存在时先按 zeno.yml 的发布设置初始化
+    Zola(ZolaArgs),
+}
+
+#[derive(Args)]
+pub struct ZolaArgs {
+    /// Zola 站点目录
+    #[arg(long, value_name = "DIR")]
+    site: PathBuf,
+    /// 同时发布草稿
+    #[arg(long)]
+    drafts: bool,
+    /// 发布后运行 zola build
+    #[arg(long)]
+    build: bool,
+    /// 输出格式
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+/// 发布笔记，返回进程退出码：任何笔记发布失败或构建失败时为 1
+pub async fn publish(workspace: &Workspace, command: PublishCommand) -> Result<i32> {
+    match command {
+        PublishCommand::Zola(args) => publish_zola(workspace, args).await,
+    }
+}
+
+async fn publish_zola(workspace: &Workspace, args: ZolaArgs) -> Result<i32> {
+    let publisher = ZolaPublisher::new(ZolaConfig::from_workspace(&workspace.config), args.site.clone())
+        .with_workspace(&workspace.root, &workspace.config);
+    if !publisher.is_initialized() {
+        publisher.initialize_site().await?;
+        eprintln!("已初始化 Zola 站点: {}", args.site.display());
+    }
+
+    let options = PublishOptions { drafts: args.drafts, build: args.build };
+    let result = publisher.publish_workspace(&options).await?;
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+        OutputFormat::Table => print_result(&result, &args),
+    }
+
+    Ok(if result.success { 0 } else { 1 })
+}
+
+fn print_result(result: &PublishResult, args: &ZolaArgs) {
+    for error in &result.errors {
+        eprintln!("  ✗ {}: {}", error.file_path.display(), error.message);
+    }
+    if args.build && !result.build_output.trim().is_empty() && result.success {
+        println!("{}", result.build_output.trim_end());
+    }
+
+    let content_dir = args.site.join("content");
+    println!(
+        "发布 {} 篇笔记到 {}，失败 {}，用时 {:.1}s",
+        result.total_pages,
+        content_dir.display(),
+        result.errors.len(),
+        result.build_time
+    );
+}
@@ -0,0 +1,81 @@
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+use crate::index;
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+/// 重命名或移动笔记，并改写其他笔记中指向它的链接
+pub async fn rename(workspace: &Workspace, note: PathBuf, to: PathBuf, dry_run: bool, format: OutputFormat) -> Result<()> {
+    let root = workspace.root.canonicalize()?;
+    let db = workspace.open_database().await?;
+    let indexer = workspace.indexer(&db);
+    let report = index::reindex(&indexer, &root).await?;
+    index::print_failures(&report.failed);
+
+    let kb = workspace.knowledge_base(&db);
+    let mut found = None;
+    for candidate in workspace.note_path_candidates(&root, &note) {
+        if let Some(record) = kb.get_note_by_path(&candidate).await? {
+            found = Some((record.id, candidate));
+            break;
+        }
+    }
+    let Some((note_id, from)) = found else {
+        bail!("找不到笔记: {}", note.display());
+    };
+
+    let to = destination(&root, &from, &to)?;
+    let plan = indexer.rename_note(&root, &note_id, &to, dry_run).await?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+        OutputFormat::Table => {
+            let verb = if dry_run { "将" } else { "已将" };
+            println!("📝 {} {} 移动到 {}", verb, plan.from.display(), plan.to.display());
+            for rewrite in &plan.rewrites {
+                println!("  {}", rewrite.path.display());
+                for edit in &rewrite.edits {
+                    println!("    {:>4}: {} → {}", edit.line, edit.before, edit.after);
+                }
+            }
+            let verb = if dry_run { "需要改写" } else { "已改写" };
+            println!("{} {} 个文件中的 {} 个链接", verb, plan.rewrites.len(), plan.link_count());
+        }
+    }
+    Ok(())
+}
+
+/// 新路径（相对工作区）：只有文件名时放在原笔记所在目录，否则为相对工作区的路径；
+/// 省略扩展名时使用 `.md`
+fn destination(root: &Path, from: &Path, to: &Path) -> Result<PathBuf> {
+    let mut to = if to.is_absolute() {
+        match to.strip_prefix(root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => bail!("新路径不在工作区内: {}", to.display()),
+        }
+    } else if to.parent().is_none_or(|parent| parent.as_os_str().is_empty()) {
+        from.with_file_name(to)
+    } else {
+        to.to_path_buf()
+    };
+    if to.extension().is_none() {
+        to.set_extension("md");
+    }
+    Ok(to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination() {
+        let root = Path::new("/ws");
+        let from = Path::new("notes/sub/old.md");
+        assert_eq!(destination(root, from, Path::new("new")).unwrap(), PathBuf::from("notes/sub/new.md"));
+        assert_eq!(destination(root, from, Path::new("notes/a.md")).unwrap(), PathBuf::from("notes/a.md"));
+        assert_eq!(destination(root, from, Path::new("/ws/x/b.md")).unwrap(), PathBuf::from("x/b.md"));
+        assert!(destination(root, from, Path::new("/other/b.md")).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::IsTerminal;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use zeno_core::{NoteRepository, NoteStatus, SearchFilters, SearchQuery, SearchResult};
+
+use crate::workspace::Workspace;
+
+/// 搜索结果输出格式
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// 查询语句，支持 tag:、status:、path:、modified: 等语法；以 - 开头的词需放在 -- 之后
+    #[arg(required = true)]
+    query: Vec<String>,
+    /// 输出格式
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+    /// 最多显示的结果数
+    #[arg(short, long, default_value_t = 20)]
+    limit: usize,
+    /// 只显示带有该标签的笔记，可重复
+    #[arg(short, long)]
+    tag: Vec<String>,
+    /// 只显示该状态的笔记 (draft|published|archived|deleted)
+    #[arg(short, long)]
+    status: Option<NoteStatus>,
+}
+
+/// JSON 输出中的一条结果
+#[derive(Serialize)]
+struct SearchHit<'a> {
+    rank: usize,
+    id: &'a str,
+    title: &'a str,
+    path: &'a str,
+    status: &'a str,
+    /// bm25 分数，越小越相关
+    score: f32,
+    /// 高亮片段，命中的词用 <mark> 包裹
+    snippet: Option<&'a str>,
+    matched_fields: &'a [String],
+}
+
+#[derive(Subcommand)]
+pub enum SavedCommand {
+    /// 列出保存的搜索
+    List,
+    /// 保存搜索，同名时覆盖
+    Save {
+        /// 名称
+        name: String,
+        /// 查询语句
+        query: String,
+    },
+    /// 删除保存的搜索
+    Delete {
+        /// 名称
+        name: String,
+    },
+    /// 运行保存的搜索
+    Run {
+        /// 名称
+        name: String,
+        /// 最多显示的结果数
+        #[arg(short, long)]
+        limit: Option<usize>,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+}
+
+pub async fn search(workspace: &Workspace, args: SearchArgs) -> Result<()> {
+    let kb = workspace.open_knowledge_base().await?;
+    let query = SearchQuery {
+        query: args.query.join(" "),
+        limit: Some(args.limit),
+        offset: None,
+        filters: SearchFilters {
+            tags: args.tag,
+            status: args.status,
+            ..Default::default()
+        },
+    };
+
+    let results = kb.search_notes(&query).await?;
+    print_results(&results, args.format)
+}
+
+pub async fn history(workspace: &Workspace, limit: usize, clear: bool) -> Result<()> {
+    let repo = workspace.open_repository().await?;
+
+    if clear {
+        let removed = repo.clear_search_history().await?;
+        println!("🧹 已清除 {} 条搜索历史", removed);
+        return Ok(());
+    }
+
+    let entries = repo.get_search_history(limit).await?;
+    if entries.is_empty() {
+        println!("暂无搜索历史");
+        return Ok(());
+    }
+    for entry in entries {
+        println!(
+            "{}  {:<30}  {} 条结果  {:.1} ms",
+            entry.created_at.format("%Y-%m-%d %H:%M"),
+            entry.query,
+            entry.result_count,
+            entry.search_time * 1000.0,
+        );
+    }
+    Ok(())
+}
+
+pub async fn saved(workspace: &Workspace, command: SavedCommand) -> Result<()> {
+    let repo = workspace.open_repository().await?;
+
+    match command {
+        SavedCommand::List => {
+            let searches = repo.get_saved_searches().await?;
+            if searches.is_empty() {
+                println!("暂无保存的搜索");
+            }
+            for search in searches {
+                println!("{:<20}  {}", search.name, search.query);
+            }
+        }
+        SavedCommand::Save { name, query } => {
+            let search = repo.save_search(&name, &query).await?;
+            println!("✅ 已保存搜索 {}: {}", search.name, search.query);
+        }
+        SavedCommand::Delete { name } => {
+            if !repo.delete_saved_search(&name).await? {
+                anyhow::bail!("未找到保存的搜索: {}", name);
+            }
+            println!("🗑️  已删除保存的搜索 {}", name);
+        }
+        SavedCommand::Run { name, limit, format } => {
+            let results = repo.run_saved_search(&name, limit).await?;
+            print_results(&results, format)?;
+        }
+    }
+    Ok(())
+}
+
+/// 按指定格式输出搜索结果
+pub fn print_results(results: &[SearchResult], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let hits: Vec<SearchHit> = results
+                .iter()
+                .enumerate()
+                .map(|(i, result)| SearchHit {
+                    rank: i + 1,
+                    id: &result.note.id,
+                    title: &result.note.title,
+                    path: &result.note.file_path,
+                    status: &result.note.status,
+                    score: result.score,
+                    snippet: result.highlights.first().map(String::as_str),
+                    matched_fields: &result.matched_fields,
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&hits)?);
+        }
+        OutputFormat::Table => print_table(results),
+    }
+    Ok(())
+}
+
+const TITLE_WIDTH: usize = 28;
+const PATH_WIDTH: usize = 32;
+const SNIPPET_WIDTH: usize = 60;
+
+fn print_table(results: &[SearchResult]) {
+    if results.is_empty() {
+        println!("没有匹配的笔记");
+        return;
+    }
+
+    let highlight = std::io::stdout().is_terminal();
+    println!("{:>3}  {}  {}  摘要", "#", pad("标题", TITLE_WIDTH), pad("路径", PATH_WIDTH));
+    for (i, result) in results.iter().enumerate() {
+        let snippet = result.highlights.first().map(String::as_str).unwrap_or_default();
+        println!(
+            "{:>3}  {}  {}  {}",
+            i + 1,
+            pad(&truncate(&result.note.title, TITLE_WIDTH), TITLE_WIDTH),
+            pad(&truncate(&result.note.file_path, PATH_WIDTH), PATH_WIDTH),
+            render_snippet(snippet, SNIPPET_WIDTH, highlight),
+        );
+    }
+    println!("共 {} 条结果", results.len());
+}
+
+/// 按显示宽度补齐空格（中日韩字符占两列）
+fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.width());
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// 按显示宽度截断，超出时以 … 结尾
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
+
+/// 把 `<mark>` 高亮转换为终端加粗，非终端时去掉标记
+fn render_snippet(snippet: &str, width: usize, highlight: bool) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    let mut rest = snippet;
+    let mut marked = false;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<mark>") {
+            if highlight {
+                out.push_str("\x1b[1;33m");
+            }
+            marked = true;
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("</mark>") {
+            if highlight {
+                out.push_str("\x1b[0m");
+            }
+            marked = false;
+            rest = after;
+            continue;
+        }
+
+        let Some(c) = rest.chars().next() else { break };
+        rest = &rest[c.len_utf8()..];
+        // 换行等空白折叠为一个空格，保持单行显示
+        if c.is_whitespace() {
+            if out.is_empty() || out.ends_with(' ') {
+                continue;
+            }
+            if used < width {
+                used += 1;
+                out.push(' ');
+            }
+            continue;
+        }
+        let w = c.width().unwrap_or(0);
+        if used + w > width {
+            out.push('…');
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+
+    if marked && highlight {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width_helpers() {
+        assert_eq!(pad("笔记", 6), "笔记  ");
+        assert_eq!(truncate("全文搜索引擎", 7), "全文搜…");
+        assert_eq!(truncate("short", 7), "short");
+        assert_eq!(render_snippet("支持<mark>搜索</mark>功能", 8, false), "支持搜索…");
+        assert_eq!(
+            render_snippet("<mark>rust</mark> notes", 20, true),
+            "\x1b[1;33mrust\x1b[0m notes"
+        );
+        assert_eq!(render_snippet("\n# 标题\n\n正文", 20, false), "# 标题 正文");
+    }
+}
@@ -0,0 +1,314 @@
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use zeno_core::import_export::{
+    ExportConfig, ExportPreview, ExportResult, ExporterType, ImportConfig, ImportPreview, ImportResult, ImporterType,
+    MergeMode,
+};
+use zeno_core::{format_bytes, DirectoryOverrides, ImportExportManager, TransferProgress};
+
+use crate::index;
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Subcommand)]
+pub enum ImportCommand {
+    /// 从 Obsidian 库导入笔记和附件，任何文件导入失败时以退出码 1 结束
+    Obsidian(ObsidianArgs),
+}
+
+#[derive(Args)]
+pub struct ObsidianArgs {
+    /// Obsidian 库目录（包含 .obsidian）
+    vault: PathBuf,
+    /// 导入到的工作区，默认为 --workspace
+    #[arg(long, value_name = "WORKSPACE")]
+    into: Option<PathBuf>,
+    /// 只预览和统计，不写入任何文件
+    #[arg(long)]
+    dry_run: bool,
+    /// 目标文件已存在时的处理方式
+    #[arg(long, value_enum, default_value_t = Merge::Skip)]
+    merge: Merge,
+    /// 结果报告路径，默认写入工作区的 .zeno/reports/（试运行时只在指定时写入）
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+    /// 输出格式
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Merge {
+    /// 覆盖，原文件备份为 *_backup_<时间>
+    Overwrite,
+    /// 跳过已存在的文件
+    Skip,
+    /// 以 name-1.md 等新名称导入
+    Rename,
+}
+
+impl From<Merge> for MergeMode {
+    fn from(merge: Merge) -> Self {
+        match merge {
+            Merge::Overwrite => MergeMode::Overwrite,
+            Merge::Skip => MergeMode::Skip,
+            Merge::Rename => MergeMode::Rename,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommand {
+    /// 导出为 HTML 文件，保留目录结构，任何笔记导出失败时以退出码 1 结束
+    Html(HtmlArgs),
+}
+
+#[derive(Args)]
+pub struct HtmlArgs {
+    /// 输出目录
+    out: PathBuf,
+    /// 只导出带有该标签（含子标签）的笔记，可重复
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// 结果报告路径，默认写入工作区的 .zeno/reports/
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+    /// 输出格式
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+/// 导入报告
+#[derive(Serialize)]
+struct ImportReport<'a> {
+    source: &'a Path,
+    target: &'a Path,
+    dry_run: bool,
+    preview: &'a ImportPreview,
+    result: &'a ImportResult,
+}
+
+/// 导出报告
+#[derive(Serialize)]
+struct ExportReport<'a> {
+    source: &'a Path,
+    target: &'a Path,
+    preview: &'a ExportPreview,
+    result: &'a ExportResult,
+}
+
+/// 导入笔记，返回进程退出码：任何文件导入失败时为 1
+pub async fn import(default_root: &Path, overrides: DirectoryOverrides, command: ImportCommand) -> Result<i32> {
+    match command {
+        ImportCommand::Obsidian(args) => import_obsidian(default_root, overrides, args).await,
+    }
+}
+
+async fn import_obsidian(default_root: &Path, overrides: DirectoryOverrides, args: ObsidianArgs) -> Result<i32> {
+    let workspace = Workspace::load(args.into.as_deref().unwrap_or(default_root), overrides).await?;
+    let target = workspace.config.notes_dir(&workspace.root);
+
+    let mut config = ImportConfig::new(
+        ImporterType::Obsidian,
+        args.vault.to_string_lossy().to_string(),
+        target.to_string_lossy().to_string(),
+    );
+    config.options.merge_mode = args.merge.into();
+    config.options.dry_run = args.dry_run;
+    // Zeno 直接解析 [[Wiki 链接]]，保留原样，避免改写成 Markdown 链接后断链
+    config.options.convert_links = false;
+
+    let manager = ImportExportManager::new();
+    let preview = manager.preview_import(&config).await?;
+    if let OutputFormat::Table = args.format {
+        print_import_preview(&args.vault, &target, &preview);
+    }
+
+    let bar = progress_bar(if args.dry_run { "检查中" } else { "导入中" });
+    let result = manager.import_with_progress(&config, &progress_reporter(&bar)).await;
+    bar.finish_and_clear();
+    let result = result?;
+
+    let report = ImportReport { source: &args.vault, target: &target, dry_run: args.dry_run, preview: &preview, result: &result };
+    let report_path = match args.report {
+        Some(path) => Some(path),
+        None if args.dry_run => None,
+        None => Some(default_report_path(&workspace.root, "import")),
+    };
+    if let Some(path) = &report_path {
+        write_report(path, &report).await?;
+    }
+
+    // 导入后更新索引，新笔记立即可以搜索和链接
+    if !args.dry_run && result.imported_count > 0 {
+        let db = workspace.open_database().await?;
+        let indexer = workspace.indexer(&db);
+        index::reindex(&indexer, &workspace.root.canonicalize()?).await?;
+    }
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Table => {
+            print_errors(&result.errors);
+            let verb = if args.dry_run { "试运行：将导入" } else { "导入" };
+            println!(
+                "{} {}，跳过 {}，失败 {}，用时 {:.1}s",
+                verb,
+                result.imported_count,
+                result.skipped_count,
+                result.failed_count,
+                result.processing_time_ms as f64 / 1000.0
+            );
+            if let Some(path) = &report_path {
+                println!("报告已写入 {}", path.display());
+            }
+        }
+    }
+
+    Ok(if result.failed_count > 0 { 1 } else { 0 })
+}
+
+/// 导出笔记，返回进程退出码：任何笔记导出失败时为 1
+pub async fn export(workspace: &Workspace, command: ExportCommand) -> Result<i32> {
+    match command {
+        ExportCommand::Html(args) => export_html(workspace, args).await,
+    }
+}
+
+async fn export_html(workspace: &Workspace, args: HtmlArgs) -> Result<i32> {
+    let source = workspace.config.notes_dir(&workspace.root);
+    if args.out.exists() && args.out.canonicalize()? == source.canonicalize()? {
+        bail!("输出目录不能是笔记目录本身: {}", args.out.display());
+    }
+
+    let mut config = ExportConfig::new(
+        ExporterType::Html,
+        source.to_string_lossy().to_string(),
+        args.out.to_string_lossy().to_string(),
+    );
+    config.options.filter_options.tag_filter = args.tags.clone();
+    config.options.excluded_dirs = export_excluded_dirs(workspace, &source, &args.out);
+
+    let manager = ImportExportManager::new();
+    let preview = manager.preview_export(&config).await?;
+    if let OutputFormat::Table = args.format {
+        println!("工作区: {}  →  {}", source.display(), args.out.display());
+        println!(
+            "笔记 {}，符合条件 {}，附件 {}，约 {}",
+            preview.total_notes,
+            preview.filtered_notes,
+            preview.total_attachments,
+            format_bytes(preview.estimated_size)
+        );
+        print_warnings(&preview.warnings);
+    }
+
+    let bar = progress_bar("导出中");
+    let result = manager.export_with_progress(&config, &progress_reporter(&bar)).await;
+    bar.finish_and_clear();
+    let result = result?;
+
+    let report = ExportReport { source: &source, target: &args.out, preview: &preview, result: &result };
+    let report_path = args.report.unwrap_or_else(|| default_report_path(&workspace.root, "export"));
+    write_report(&report_path, &report).await?;
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Table => {
+            print_errors(&result.errors);
+            println!(
+                "导出 {} 个文件（{}），失败 {}，用时 {:.1}s",
+                result.exported_count,
+                format_bytes(result.total_size),
+                result.failed_count,
+                result.processing_time_ms as f64 / 1000.0
+            );
+            println!("报告已写入 {}", report_path.display());
+        }
+    }
+
+    Ok(if result.failed_count > 0 { 1 } else { 0 })
+}
+
+/// 导出时跳过的目录：配置中排除的目录，以及位于笔记目录内的输出目录
+fn export_excluded_dirs(workspace: &Workspace, source: &Path, out: &Path) -> Vec<PathBuf> {
+    let mut excluded: Vec<PathBuf> = workspace
+        .config
+        .excluded_dirs()
+        .into_iter()
+        .filter_map(|dir| workspace.root.join(dir).strip_prefix(source).ok().map(Path::to_path_buf))
+        .collect();
+    if let (Ok(source), Ok(out)) = (source.canonicalize(), out.canonicalize()) {
+        if let Ok(relative) = out.strip_prefix(&source) {
+            excluded.push(relative.to_path_buf());
+        }
+    }
+    excluded
+}
+
+fn print_import_preview(vault: &Path, target: &Path, preview: &ImportPreview) {
+    println!("Obsidian 库: {}  →  {}", vault.display(), target.display());
+    println!(
+        "笔记 {}，附件 {}（图片 {}），约 {}",
+        preview.notes,
+        preview.attachments,
+        preview.media_files,
+        format_bytes(preview.estimated_size)
+    );
+    if !preview.conflicts.is_empty() {
+        println!("冲突 {} 个:", preview.conflicts.len());
+        for conflict in &preview.conflicts {
+            println!("  {} ({})", conflict.target_path, conflict.suggested_resolution);
+        }
+    }
+    print_warnings(&preview.warnings);
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("  ⚠ {}", warning);
+    }
+}
+
+fn print_errors(errors: &[String]) {
+    for error in errors {
+        eprintln!("  ✗ {}", error);
+    }
+}
+
+/// 终端上显示 `标签 当前/总数 文件` 形式的进度
+/// 进度条，输出到 stderr；stderr 不是终端时自动隐藏
+fn progress_bar(label: &'static str) -> ProgressBar {
+    let bar = ProgressBar::new(0);
+    bar.set_style(
+        ProgressStyle::with_template("{prefix} [{bar:30.cyan/blue}] {pos}/{len} {wide_msg}")
+            .expect("进度条模板有效")
+            .progress_chars("=> "),
+    );
+    bar.set_prefix(label);
+    bar
+}
+
+fn progress_reporter(bar: &ProgressBar) -> impl Fn(TransferProgress<'_>) + Send + Sync + '_ {
+    move |p| {
+        bar.set_length(p.total as u64);
+        bar.set_position(p.current as u64);
+        bar.set_message(p.path.display().to_string());
+    }
+}
+
+fn default_report_path(root: &Path, kind: &str) -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S%3f");
+    root.join(".zeno").join("reports").join(format!("{}-{}.json", kind, timestamp))
+}
+
+async fn write_report<T: Serialize>(path: &Path, report: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, serde_json::to_string_pretty(report)?).await?;
+    Ok(())
+}
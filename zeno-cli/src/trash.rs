@@ -0,0 +1,125 @@
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+use zeno_core::{KnowledgeBase, TrashEntry};
+
+use crate::index;
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Subcommand)]
+pub enum TrashCommand {
+    /// 列出回收站中的笔记
+    List {
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 将笔记恢复到删除前的路径
+    Restore {
+        /// 笔记 ID 或删除前的路径
+        note: String,
+    },
+    /// 彻底删除超过保留天数（zeno.yml 中的 trash.retention_days）的笔记
+    Purge {
+        /// 清空回收站，不论删除了多久
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+/// 将笔记移入回收站
+pub async fn delete(workspace: &Workspace, note: PathBuf) -> Result<()> {
+    let root = workspace.root.canonicalize()?;
+    let db = workspace.open_database().await?;
+    let indexer = workspace.indexer(&db);
+    let report = index::reindex(&indexer, &root).await?;
+    index::print_failures(&report.failed);
+
+    let kb = workspace.knowledge_base(&db);
+    let mut note_id = None;
+    for candidate in workspace.note_path_candidates(&root, &note) {
+        if let Some(found) = kb.get_note_by_path(&candidate).await? {
+            note_id = Some(found.id);
+            break;
+        }
+    }
+    let Some(note_id) = note_id else {
+        bail!("找不到笔记: {}", note.display());
+    };
+
+    let entry = kb.delete_note(&note_id).await?;
+    // 其他笔记指向它的链接随之失效，恢复后重新生效
+    indexer.refresh_links().await?;
+    println!("🗑️  已将 {} 移入回收站，可以用 zeno trash restore {} 恢复", entry.original_path, entry.note_id);
+    Ok(())
+}
+
+/// 管理回收站
+pub async fn trash(workspace: &Workspace, command: TrashCommand) -> Result<()> {
+    let root = workspace.root.canonicalize()?;
+    let db = workspace.open_database().await?;
+    let kb = workspace.knowledge_base(&db);
+
+    match command {
+        TrashCommand::List { format } => {
+            let entries = kb.list_trash().await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+                OutputFormat::Table if entries.is_empty() => println!("回收站是空的"),
+                OutputFormat::Table => {
+                    for entry in &entries {
+                        println!(
+                            "{}  {}  {}  {}",
+                            entry.deleted_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                            entry.note_id,
+                            entry.original_path,
+                            entry.title
+                        );
+                    }
+                }
+            }
+        }
+        TrashCommand::Restore { note } => {
+            let note_id = find_trashed(workspace, &root, &kb, &note).await?;
+            let entry = kb.restore_note(&note_id).await?;
+            workspace.indexer(&db).refresh_links().await?;
+            println!("✅ 已恢复 {}", entry.original_path);
+        }
+        TrashCommand::Purge { all } => {
+            let purged = if all {
+                kb.purge_trash(None).await?
+            } else if workspace.config.trash.retention_days == 0 {
+                println!("回收站设置为一直保留（trash.retention_days: 0），使用 --all 清空");
+                return Ok(());
+            } else {
+                kb.purge_expired_trash(&workspace.config.trash).await?
+            };
+            print_purged(&purged);
+        }
+    }
+
+    Ok(())
+}
+
+/// 显示彻底删除的笔记
+pub fn print_purged(purged: &[TrashEntry]) {
+    for entry in purged {
+        println!("  ✗ {}", entry.original_path);
+    }
+    println!("🧹 已从回收站彻底删除 {} 篇笔记", purged.len());
+}
+
+/// 按笔记 ID 或删除前的路径查找回收站中的笔记
+async fn find_trashed(workspace: &Workspace, root: &Path, kb: &KnowledgeBase, note: &str) -> Result<String> {
+    let entries = kb.list_trash().await?;
+    if let Some(entry) = entries.iter().find(|entry| entry.note_id == note) {
+        return Ok(entry.note_id.clone());
+    }
+    for candidate in workspace.note_path_candidates(root, Path::new(note)) {
+        if let Some(entry) = entries.iter().find(|entry| Path::new(&entry.original_path) == candidate) {
+            return Ok(entry.note_id.clone());
+        }
+    }
+    bail!("回收站中没有笔记: {}", note)
+}
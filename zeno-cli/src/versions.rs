@@ -0,0 +1,163 @@
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use zeno_core::{format_bytes, DiffKind, DiffLine, NoteRepository, SqliteNoteRepository};
+
+use crate::index;
+use crate::search::OutputFormat;
+use crate::workspace::Workspace;
+
+#[derive(Subcommand)]
+pub enum VersionsCommand {
+    /// 列出笔记的历史版本
+    List {
+        /// 笔记文件路径，笔记已删除时也可以使用原路径
+        note: PathBuf,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 逐行比较两个版本，未指定 TO 时与当前内容比较
+    Diff {
+        /// 笔记文件路径
+        note: PathBuf,
+        /// 旧版本号
+        from: i64,
+        /// 新版本号
+        to: Option<i64>,
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// 将笔记恢复到指定版本，当前内容会先保存为新版本
+    Restore {
+        /// 笔记文件路径
+        note: PathBuf,
+        /// 版本号
+        version: i64,
+    },
+    /// 按 zeno.yml 中的 history 保留策略清理旧版本
+    Prune,
+}
+
+/// JSON 输出中的一个版本，不含内容
+#[derive(Serialize)]
+struct VersionOutput<'a> {
+    version: i64,
+    title: &'a str,
+    path: &'a str,
+    size: usize,
+    modified_at: String,
+    saved_at: String,
+}
+
+/// 差异两侧各显示的上下文行数
+const DIFF_CONTEXT: usize = 2;
+
+pub async fn versions(workspace: &Workspace, command: VersionsCommand) -> Result<()> {
+    // 先更新索引，尚未索引的修改也会保存为版本
+    let root = workspace.root.canonicalize()?;
+    let db = workspace.open_database().await?;
+    let indexer = workspace.indexer(&db);
+    let report = index::reindex(&indexer, &root).await?;
+    index::print_failures(&report.failed);
+    let repo = indexer.repository();
+
+    match command {
+        VersionsCommand::List { note, format } => {
+            let note_id = find_note(workspace, &root, repo, &note).await?;
+            let versions = repo.get_note_versions(&note_id).await?;
+            match format {
+                OutputFormat::Json => {
+                    let output: Vec<VersionOutput> = versions
+                        .iter()
+                        .map(|v| VersionOutput {
+                            version: v.version,
+                            title: &v.title,
+                            path: &v.file_path,
+                            size: v.content.len(),
+                            modified_at: v.modified_at.to_rfc3339(),
+                            saved_at: v.created_at.to_rfc3339(),
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                }
+                OutputFormat::Table if versions.is_empty() => println!("{} 没有历史版本", note.display()),
+                OutputFormat::Table => {
+                    for v in &versions {
+                        println!(
+                            "{:>4}  保存于 {}  {:>9}  {}",
+                            v.version,
+                            v.created_at.format("%Y-%m-%d %H:%M"),
+                            format_bytes(v.content.len() as u64),
+                            v.title
+                        );
+                    }
+                }
+            }
+        }
+        VersionsCommand::Diff { note, from, to, format } => {
+            let note_id = find_note(workspace, &root, repo, &note).await?;
+            let diff = repo.diff_note_versions(&note_id, from, to).await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+                OutputFormat::Table => {
+                    let target = to.map_or("当前内容".to_string(), |to| format!("版本 {}", to));
+                    println!("--- 版本 {}\n+++ {}", from, target);
+                    print_diff(&diff);
+                }
+            }
+        }
+        VersionsCommand::Restore { note, version } => {
+            let note_id = find_note(workspace, &root, repo, &note).await?;
+            let (restored, path) = indexer.restore_version(&root, &note_id, version).await?;
+            println!("✅ 已将 {} 恢复到版本 {}（{}）", path.display(), restored.version, restored.created_at.format("%Y-%m-%d %H:%M"));
+        }
+        VersionsCommand::Prune => {
+            let removed = repo.prune_note_versions().await?;
+            println!("🧹 已清理 {} 个旧版本", removed);
+        }
+    }
+
+    Ok(())
+}
+
+/// 按路径查找笔记：可以是文件的实际路径，也可以是相对工作区或笔记目录的路径
+async fn find_note(workspace: &Workspace, root: &Path, repo: &SqliteNoteRepository, note: &Path) -> Result<String> {
+    for candidate in workspace.note_path_candidates(root, note) {
+        if let Some(id) = repo.find_versioned_note(&candidate.to_string_lossy()).await? {
+            return Ok(id);
+        }
+    }
+    bail!("找不到笔记: {}", note.display())
+}
+
+/// 只显示变化的行及其上下文，不相邻的片段之间用 `...` 分隔
+fn print_diff(diff: &[DiffLine]) {
+    let mut visible = vec![false; diff.len()];
+    for (i, line) in diff.iter().enumerate() {
+        if line.kind != DiffKind::Equal {
+            let end = (i + DIFF_CONTEXT).min(diff.len() - 1);
+            visible[i.saturating_sub(DIFF_CONTEXT)..=end].fill(true);
+        }
+    }
+    if !visible.contains(&true) {
+        println!("没有差异");
+        return;
+    }
+
+    let mut previous = None;
+    for (i, line) in diff.iter().enumerate().filter(|(i, _)| visible[*i]) {
+        if previous.is_some_and(|p| p + 1 != i) || (previous.is_none() && i > 0) {
+            println!("...");
+        }
+        let marker = match line.kind {
+            DiffKind::Equal => ' ',
+            DiffKind::Added => '+',
+            DiffKind::Removed => '-',
+        };
+        println!("{}{}", marker, line.text);
+        previous = Some(i);
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use zeno_core::{
+    Database, DirectoryOverrides, KnowledgeBase, LocalFileStorage, SqliteIndexer, SqliteNoteRepository,
+    WorkspaceConfig,
+};
+
+/// 命令行使用的工作区：根目录和 zeno.yml 配置
+pub struct Workspace {
+    pub root: PathBuf,
+    pub config: WorkspaceConfig,
+}
+
+impl Workspace {
+    /// 读取工作区配置并应用命令行中的目录覆盖
+    pub async fn load(root: &Path, overrides: DirectoryOverrides) -> Result<Self> {
+        let mut config = WorkspaceConfig::load(root).await?;
+        config.apply_overrides(overrides)?;
+        Ok(Self { root: root.to_path_buf(), config })
+    }
+
+    /// 打开工作区数据库，不存在时自动创建
+    pub async fn open_database(&self) -> Result<Database> {
+        Ok(Database::open_workspace(&self.root).await?)
+    }
+
+    /// 打开工作区的笔记仓库
+    pub async fn open_repository(&self) -> Result<SqliteNoteRepository> {
+        let db = self.open_database().await?;
+        Ok(SqliteNoteRepository::new(db.pool().clone()).with_history(self.config.history.clone()))
+    }
+
+    /// 打开工作区知识库
+    pub async fn open_knowledge_base(&self) -> Result<KnowledgeBase> {
+        let db = self.open_database().await?;
+        Ok(self.knowledge_base(&db))
+    }
+
+    /// 在已打开的数据库上创建知识库
+    pub fn knowledge_base(&self, db: &Database) -> KnowledgeBase {
+        let repository = SqliteNoteRepository::new(db.pool().clone()).with_history(self.config.history.clone());
+        KnowledgeBase::new(Box::new(self.file_storage()), Box::new(repository))
+    }
+
+    /// 按配置创建本地文件存储
+    pub fn file_storage(&self) -> LocalFileStorage {
+        LocalFileStorage::with_config(self.root.clone(), &self.config)
+    }
+
+    /// 命令行参数中笔记路径可能对应的索引路径（相对工作区），按优先级排列：
+    /// 文件的实际路径、参数本身、相对笔记目录的路径
+    pub fn note_path_candidates(&self, root: &Path, note: &Path) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Some(relative) = note.canonicalize().ok().and_then(|path| path.strip_prefix(root).ok().map(Path::to_path_buf)) {
+            candidates.push(relative);
+        }
+        candidates.push(note.to_path_buf());
+        candidates.push(self.config.directories.notes.join(note));
+        candidates
+    }
+
+    /// 创建跳过模板目录、按配置保留版本历史的索引器
+    pub fn indexer(&self, db: &Database) -> SqliteIndexer {
+        SqliteIndexer::new(db.pool().clone())
+            .with_excluded_dirs(self.config.excluded_dirs())
+            .with_history(self.config.history.clone())
+    }
+}
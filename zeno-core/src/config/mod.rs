@@ -0,0 +1,467 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::error::{Error, Result};
+use crate::template::DEFAULT_TEMPLATE;
+
+/// 工作区配置文件名
+pub const WORKSPACE_CONFIG_FILE: &str = "zeno.yml";
+
+/// `zeno init` 写入的默认配置
+pub const DEFAULT_CONFIG_YAML: &str = r#"# Zeno 知识库配置
+title: "我的知识库"
+description: "基于 Zeno 的个人知识管理系统"
+
+# 目录配置
+directories:
+  notes: "notes"
+  assets: "assets"
+  templates: "templates"
+
+# 默认设置
+defaults:
+  note_template: "default"
+  publish: false
+
+# 发布设置（zeno publish）
+publish:
+  base_url: "https://example.com"
+  author: ""
+  language: "zh"
+
+# 版本历史：每篇笔记保留的版本数（0 表示不保留）和天数（0 表示不限）
+history:
+  max_versions: 50
+  max_age_days: 0
+
+# 回收站：删除的笔记保留的天数，到期后彻底删除（0 表示一直保留）
+trash:
+  retention_days: 30
+
+# 数据库备份（zeno backup）：保留最近的 keep_last 个，以及最近若干天、若干周中各自最新的一个
+backup:
+  keep_last: 3
+  keep_daily: 7
+  keep_weekly: 4
+"#;
+
+/// 已知的配置项，用于报告未识别的键
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    ("title", &[]),
+    ("description", &[]),
+    ("directories", &["notes", "assets", "templates"]),
+    ("defaults", &["note_template", "publish"]),
+    ("publish", &["base_url", "author", "language", "build_search_index", "generate_feed"]),
+    ("history", &["max_versions", "max_age_days"]),
+    ("trash", &["retention_days"]),
+    ("backup", &["keep_last", "keep_daily", "keep_weekly"]),
+];
+
+/// 工作区配置（`zeno.yml`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// 知识库标题
+    pub title: String,
+    /// 知识库描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 目录配置
+    pub directories: DirectoryConfig,
+    /// 默认设置
+    pub defaults: DefaultsConfig,
+    /// 发布设置
+    pub publish: PublishSettings,
+    /// 版本历史设置
+    pub history: HistorySettings,
+    /// 回收站设置
+    pub trash: TrashSettings,
+    /// 备份保留策略
+    pub backup: BackupSettings,
+}
+
+/// 目录配置，路径相对于工作区根目录
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DirectoryConfig {
+    /// 笔记目录
+    pub notes: PathBuf,
+    /// 附件目录
+    pub assets: PathBuf,
+    /// 模板目录
+    pub templates: PathBuf,
+}
+
+/// 默认设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DefaultsConfig {
+    /// `zeno new` 默认使用的模板
+    pub note_template: String,
+    /// 新笔记是否默认发布
+    pub publish: bool,
+}
+
+/// 发布设置，站点标题和描述沿用知识库的 `title` 和 `description`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PublishSettings {
+    /// 站点地址
+    pub base_url: String,
+    /// 默认作者
+    pub author: String,
+    /// 站点语言
+    pub language: String,
+    /// 是否生成搜索索引
+    pub build_search_index: bool,
+    /// 是否生成订阅源
+    pub generate_feed: bool,
+}
+
+/// 版本历史保留策略，超出任一限制的旧版本会被清理
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HistorySettings {
+    /// 每篇笔记保留的版本数，0 表示不记录历史
+    pub max_versions: usize,
+    /// 版本保留的天数，0 表示不限
+    pub max_age_days: u32,
+}
+
+/// 回收站设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TrashSettings {
+    /// 删除的笔记保留的天数，到期后彻底删除，0 表示一直保留
+    pub retention_days: u32,
+}
+
+/// 备份保留策略，满足任一规则的备份会被保留，最新的备份总是保留
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BackupSettings {
+    /// 保留最近的备份数
+    pub keep_last: usize,
+    /// 保留最近若干天中每天最新的一个备份
+    pub keep_daily: usize,
+    /// 保留最近若干周中每周最新的一个备份
+    pub keep_weekly: usize,
+}
+
+/// 命令行等来源对目录配置的覆盖
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryOverrides {
+    pub notes: Option<PathBuf>,
+    pub assets: Option<PathBuf>,
+    pub templates: Option<PathBuf>,
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            title: "我的知识库".to_string(),
+            description: None,
+            directories: DirectoryConfig::default(),
+            defaults: DefaultsConfig::default(),
+            publish: PublishSettings::default(),
+            history: HistorySettings::default(),
+            trash: TrashSettings::default(),
+            backup: BackupSettings::default(),
+        }
+    }
+}
+
+impl Default for DirectoryConfig {
+    fn default() -> Self {
+        Self {
+            notes: PathBuf::from("notes"),
+            assets: PathBuf::from("assets"),
+            templates: PathBuf::from("templates"),
+        }
+    }
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            note_template: DEFAULT_TEMPLATE.to_string(),
+            publish: false,
+        }
+    }
+}
+
+impl Default for PublishSettings {
+    fn default() -> Self {
+        Self {
+            base_url: "https://example.com".to_string(),
+            author: String::new(),
+            language: "zh".to_string(),
+            build_search_index: true,
+            generate_feed: true,
+        }
+    }
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            max_versions: 50,
+            max_age_days: 0,
+        }
+    }
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self { retention_days: 30 }
+    }
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            keep_last: 3,
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+impl WorkspaceConfig {
+    /// 解析并校验配置，同时返回未识别的键（如 `directories.note`）
+    pub fn parse(yaml: &str) -> Result<(Self, Vec<String>)> {
+        let value: Value = serde_yaml::from_str(yaml)
+            .map_err(|e| Error::config(format!("{} 格式错误: {}", WORKSPACE_CONFIG_FILE, e)))?;
+        if value.is_null() {
+            return Ok((Self::default(), Vec::new()));
+        }
+        if !value.is_mapping() {
+            return Err(Error::config(format!("{} 必须是键值映射", WORKSPACE_CONFIG_FILE)));
+        }
+
+        let unknown = unknown_keys(&value);
+        let config: Self = serde_yaml::from_value(value)
+            .map_err(|e| Error::config(format!("{} 字段无效: {}", WORKSPACE_CONFIG_FILE, e)))?;
+        config.validate()?;
+        Ok((config, unknown))
+    }
+
+    /// 读取工作区配置，文件不存在时使用默认配置
+    ///
+    /// 未识别的键只记录警告，不影响加载。
+    pub async fn load(root: &Path) -> Result<Self> {
+        let path = root.join(WORKSPACE_CONFIG_FILE);
+        let yaml = match tokio::fs::read_to_string(&path).await {
+            Ok(yaml) => yaml,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let (config, unknown) = Self::parse(&yaml)?;
+        for key in unknown {
+            log::warn!("{} 中有未识别的配置项: {}", path.display(), key);
+        }
+        Ok(config)
+    }
+
+    /// 写入工作区配置
+    pub async fn save(&self, root: &Path) -> Result<()> {
+        self.validate()?;
+        tokio::fs::write(root.join(WORKSPACE_CONFIG_FILE), serde_yaml::to_string(self)?).await?;
+        Ok(())
+    }
+
+    /// 应用目录覆盖并重新校验
+    pub fn apply_overrides(&mut self, overrides: DirectoryOverrides) -> Result<()> {
+        if let Some(notes) = overrides.notes {
+            self.directories.notes = notes;
+        }
+        if let Some(assets) = overrides.assets {
+            self.directories.assets = assets;
+        }
+        if let Some(templates) = overrides.templates {
+            self.directories.templates = templates;
+        }
+        self.validate()
+    }
+
+    /// 校验配置
+    pub fn validate(&self) -> Result<()> {
+        let dirs = &self.directories;
+        for (key, path) in [("notes", &dirs.notes), ("assets", &dirs.assets), ("templates", &dirs.templates)] {
+            validate_dir(key, path)?;
+        }
+        for (key, path) in [("assets", &dirs.assets), ("templates", &dirs.templates)] {
+            if is_workspace_root(path) {
+                return Err(Error::config(format!("directories.{} 不能是工作区根目录", key)));
+            }
+            if normalize(path) == normalize(&dirs.notes) {
+                return Err(Error::config(format!("directories.{} 不能与 directories.notes 相同", key)));
+            }
+        }
+
+        let base_url = self.publish.base_url.trim();
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+            return Err(Error::config("publish.base_url 必须以 http:// 或 https:// 开头"));
+        }
+
+        if crate::template::validate_template_name(&self.defaults.note_template).is_err() {
+            return Err(Error::config("defaults.note_template 必须是 templates 目录下的模板名称"));
+        }
+        Ok(())
+    }
+
+    /// 笔记目录
+    pub fn notes_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.directories.notes)
+    }
+
+    /// 附件目录
+    pub fn assets_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.directories.assets)
+    }
+
+    /// 模板目录
+    pub fn templates_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.directories.templates)
+    }
+
+    /// 索引和监控时需要跳过的目录（相对工作区）
+    pub fn excluded_dirs(&self) -> Vec<PathBuf> {
+        vec![normalize(&self.directories.templates)]
+    }
+}
+
+/// 目录必须是工作区内的相对路径，且不能指向隐藏目录
+fn validate_dir(key: &str, path: &Path) -> Result<()> {
+    if path.as_os_str().is_empty() {
+        return Err(Error::config(format!("directories.{} 不能为空", key)));
+    }
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(name) if !name.to_string_lossy().starts_with('.') => {}
+            Component::Normal(_) => {
+                return Err(Error::config(format!("directories.{} 不能是隐藏目录: {}", key, path.display())));
+            }
+            _ => {
+                return Err(Error::config(format!(
+                    "directories.{} 必须是工作区内的相对路径: {}",
+                    key,
+                    path.display()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 去掉 `.` 组成部分，便于比较
+fn normalize(path: &Path) -> PathBuf {
+    path.components().filter(|c| *c != Component::CurDir).collect()
+}
+
+fn is_workspace_root(path: &Path) -> bool {
+    normalize(path).as_os_str().is_empty()
+}
+
+fn unknown_keys(value: &Value) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let Some(mapping) = value.as_mapping() else {
+        return unknown;
+    };
+
+    for (key, value) in mapping {
+        let key = key.as_str().map(str::to_string).unwrap_or_else(|| format!("{:?}", key));
+        match KNOWN_KEYS.iter().find(|(name, _)| *name == key) {
+            None => unknown.push(key),
+            Some((_, children)) if !children.is_empty() => {
+                if let Some(section) = value.as_mapping() {
+                    for child in section.keys() {
+                        let child = child.as_str().unwrap_or_default();
+                        if !children.contains(&child) {
+                            unknown.push(format!("{}.{}", key, child));
+                        }
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_default_config_yaml() {
+        let (config, unknown) = WorkspaceConfig::parse(DEFAULT_CONFIG_YAML).unwrap();
+        assert!(unknown.is_empty());
+        assert_eq!(config.directories, DirectoryConfig::default());
+        assert_eq!(config.defaults, DefaultsConfig::default());
+        assert_eq!(config.publish, PublishSettings::default());
+        assert_eq!(config.history, HistorySettings::default());
+        assert_eq!(config.trash, TrashSettings::default());
+        assert_eq!(config.backup, BackupSettings::default());
+        assert_eq!(config.description.as_deref(), Some("基于 Zeno 的个人知识管理系统"));
+    }
+
+    #[test]
+    fn test_parse_reports_unknown_keys_and_defaults() {
+        let yaml = "title: KB\ntheme: dark\ndirectories:\n  notes: docs\n  note: x\n";
+        let (config, unknown) = WorkspaceConfig::parse(yaml).unwrap();
+        assert_eq!(unknown, vec!["theme", "directories.note"]);
+        assert_eq!(config.title, "KB");
+        assert_eq!(config.directories.notes, PathBuf::from("docs"));
+        assert_eq!(config.directories.templates, PathBuf::from("templates"));
+        assert_eq!(config.defaults.note_template, "default");
+
+        assert_eq!(WorkspaceConfig::parse("").unwrap().0, WorkspaceConfig::default());
+    }
+
+    #[test]
+    fn test_validate() {
+        for yaml in [
+            "directories:\n  notes: /abs\n",
+            "directories:\n  notes: ../outside\n",
+            "directories:\n  notes: .zeno\n",
+            "directories:\n  templates: notes\n",
+            "directories:\n  assets: .\n",
+            "defaults:\n  note_template: a/b\n",
+            "defaults:\n  publish: maybe\n",
+            "publish:\n  base_url: example.com\n",
+            "history:\n  max_versions: -1\n",
+            "trash:\n  retention_days: -1\n",
+            "- a\n- b\n",
+        ] {
+            assert!(matches!(WorkspaceConfig::parse(yaml), Err(Error::Config(_))), "{}", yaml);
+        }
+
+        let (config, _) = WorkspaceConfig::parse("directories:\n  notes: .\n").unwrap();
+        assert_eq!(config.notes_dir(Path::new("/ws")), PathBuf::from("/ws/."));
+    }
+
+    #[tokio::test]
+    async fn test_load_and_overrides() {
+        let dir = tempdir().unwrap();
+        assert_eq!(WorkspaceConfig::load(dir.path()).await.unwrap(), WorkspaceConfig::default());
+
+        std::fs::write(dir.path().join(WORKSPACE_CONFIG_FILE), "directories:\n  notes: docs\n").unwrap();
+        let mut config = WorkspaceConfig::load(dir.path()).await.unwrap();
+        assert_eq!(config.notes_dir(dir.path()), dir.path().join("docs"));
+
+        config
+            .apply_overrides(DirectoryOverrides { templates: Some(PathBuf::from("tpl")), ..Default::default() })
+            .unwrap();
+        assert_eq!(config.templates_dir(dir.path()), dir.path().join("tpl"));
+        assert_eq!(config.excluded_dirs(), vec![PathBuf::from("tpl")]);
+        assert!(config
+            .apply_overrides(DirectoryOverrides { notes: Some(PathBuf::from("tpl")), ..Default::default() })
+            .is_err());
+    }
+}
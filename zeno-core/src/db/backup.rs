@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use super::Database;
+use crate::config::BackupSettings;
+use crate::error::{Error, Result};
+
+/// 备份目录（相对工作区）
+pub const BACKUP_DIR: &str = ".zeno/backups";
+
+/// 备份文件名中的时间格式（本地时间，精确到毫秒）
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+
+/// 一个数据库备份
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    /// 文件名，如 `zeno-20240630-120000000.db`
+    pub name: String,
+    pub path: PathBuf,
+    /// 备份时间，取自文件名，无法解析时取文件修改时间
+    pub created_at: DateTime<Local>,
+    /// 文件大小（字节）
+    pub size: u64,
+}
+
+impl BackupInfo {
+    /// 读取备份文件的信息
+    pub async fn from_path(path: &Path) -> Result<Self> {
+        let metadata = tokio::fs::metadata(path).await?;
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let created_at = match parse_backup_time(&name) {
+            Some(time) => time,
+            None => metadata.modified()?.into(),
+        };
+
+        Ok(Self { name, path: path.to_path_buf(), created_at, size: metadata.len() })
+    }
+}
+
+/// 工作区数据库的备份管理：创建、列出、轮换和查找备份
+pub struct BackupManager {
+    dir: PathBuf,
+    settings: BackupSettings,
+}
+
+impl BackupManager {
+    /// 管理工作区 `.zeno/backups` 下的备份
+    pub fn new(root: &Path, settings: BackupSettings) -> Self {
+        Self { dir: root.join(BACKUP_DIR), settings }
+    }
+
+    /// 备份目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 在线创建一个带时间戳的备份，备份经过完整性校验
+    pub async fn create(&self, db: &Database) -> Result<BackupInfo> {
+        let name = format!("zeno-{}.db", Local::now().format(BACKUP_TIME_FORMAT));
+        let path = self.dir.join(name);
+        db.backup(&path).await?;
+        BackupInfo::from_path(&path).await
+    }
+
+    /// 列出备份目录中的备份，从新到旧排列
+    pub async fn list(&self) -> Result<Vec<BackupInfo>> {
+        let mut backups = Vec::new();
+        if !self.dir.exists() {
+            return Ok(backups);
+        }
+
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if parse_backup_time(&name).is_some() {
+                backups.push(BackupInfo::from_path(&entry.path()).await?);
+            }
+        }
+
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.name.cmp(&a.name)));
+        Ok(backups)
+    }
+
+    /// 按保留策略删除旧备份，返回删除的备份
+    pub async fn rotate(&self) -> Result<Vec<BackupInfo>> {
+        let backups = self.list().await?;
+        let expired: Vec<BackupInfo> = expired_backups(&backups, &self.settings).into_iter().cloned().collect();
+        for backup in &expired {
+            tokio::fs::remove_file(&backup.path).await?;
+        }
+        Ok(expired)
+    }
+
+    /// 按文件名查找备份目录中的备份，也可以是任意备份文件的路径
+    pub async fn find(&self, name: &str) -> Result<BackupInfo> {
+        if let Some(backup) = self.list().await?.into_iter().find(|backup| backup.name == name) {
+            return Ok(backup);
+        }
+        let path = Path::new(name);
+        if path.is_file() {
+            return BackupInfo::from_path(path).await;
+        }
+        Err(Error::not_found(format!("备份 {}", name)))
+    }
+}
+
+/// 按保留策略挑出应删除的备份，`backups` 须按时间从新到旧排列
+///
+/// 保留最近的 `keep_last` 个（至少一个），以及最近 `keep_daily` 天、`keep_weekly` 周中各自最新的一个。
+pub fn expired_backups<'a>(backups: &'a [BackupInfo], settings: &BackupSettings) -> Vec<&'a BackupInfo> {
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    let mut expired = Vec::new();
+
+    for (i, backup) in backups.iter().enumerate() {
+        let date = backup.created_at.date_naive();
+        let week = (date.iso_week().year(), date.iso_week().week());
+
+        let mut keep = i < settings.keep_last.max(1);
+        if days.len() < settings.keep_daily && days.insert(date) {
+            keep = true;
+        }
+        if weeks.len() < settings.keep_weekly && weeks.insert(week) {
+            keep = true;
+        }
+        if !keep {
+            expired.push(backup);
+        }
+    }
+
+    expired
+}
+
+/// 以只读方式打开备份并检查完整性
+pub async fn verify_backup(path: &Path) -> Result<bool> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await?;
+    let db = Database::from_pool(pool);
+    let ok = db.check_integrity().await;
+    db.close().await;
+    ok
+}
+
+/// 从 `zeno-<时间>.db` 形式的文件名解析备份时间
+fn parse_backup_time(name: &str) -> Option<DateTime<Local>> {
+    let time = name.strip_prefix("zeno-")?.strip_suffix(".db")?;
+    let time = NaiveDateTime::parse_from_str(time, BACKUP_TIME_FORMAT).ok()?;
+    Local.from_local_datetime(&time).earliest()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup(time: &str) -> BackupInfo {
+        let name = format!("zeno-{}000.db", time);
+        BackupInfo {
+            created_at: parse_backup_time(&name).unwrap(),
+            path: PathBuf::from(&name),
+            name,
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_expired_backups() {
+        // 从新到旧：6 月 30 日（周日）三个，29 日两个，24 日（同一周的周一）一个，17 日（上一周）一个
+        let backups = vec![
+            backup("20240630-180000"),
+            backup("20240630-120000"),
+            backup("20240630-060000"),
+            backup("20240629-120000"),
+            backup("20240629-060000"),
+            backup("20240624-120000"),
+            backup("20240617-120000"),
+        ];
+        let names = |settings: BackupSettings| -> Vec<String> {
+            expired_backups(&backups, &settings).into_iter().map(|b| b.name[5..20].to_string()).collect()
+        };
+
+        assert_eq!(
+            names(BackupSettings { keep_last: 2, keep_daily: 2, keep_weekly: 0 }),
+            vec!["20240630-060000", "20240629-060000", "20240624-120000", "20240617-120000"]
+        );
+        assert_eq!(
+            names(BackupSettings { keep_last: 0, keep_daily: 0, keep_weekly: 2 }),
+            vec!["20240630-120000", "20240630-060000", "20240629-120000", "20240629-060000", "20240624-120000"]
+        );
+        assert!(names(BackupSettings { keep_last: 7, keep_daily: 0, keep_weekly: 0 }).is_empty());
+        assert_eq!(parse_backup_time("zeno-latest.db"), None);
+    }
+}
@@ -1,4 +1,4 @@
-use sqlx::{Pool, Sqlite, SqlitePool, sqlite::SqliteConnectOptions, ConnectOptions};
+use sqlx::{SqlitePool, sqlite::SqliteConnectOptions, ConnectOptions};
 use std::str::FromStr;
 use std::time::Duration;
 use crate::error::Result;
@@ -106,7 +106,7 @@ impl DatabaseInfo {
 }
 
 /// 格式化字节大小
-fn format_bytes(bytes: u64) -> String {
+pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
     let mut unit_index = 0;
@@ -1,4 +1,4 @@
-use sqlx::{SqlitePool, Row};
+use sqlx::SqlitePool;
 use crate::error::Result;
 
 /// 运行数据库迁移
@@ -63,8 +63,17 @@ async fn create_migration_table(pool: &SqlitePool) -> Result<()> {
     Ok(())
 }
 
-/// 获取当前数据库版本
+/// 获取当前数据库版本（迁移表不存在时为 0）
 async fn get_current_version(pool: &SqlitePool) -> Result<i32> {
+    let has_table: bool = sqlx::query_scalar(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='schema_migrations'"
+    )
+    .fetch_one(pool)
+    .await?;
+    if !has_table {
+        return Ok(0);
+    }
+
     let version = sqlx::query_scalar::<_, i32>(
         "SELECT COALESCE(MAX(version), 0) FROM schema_migrations"
     )
@@ -260,6 +269,382 @@ fn get_migrations() -> Vec<Migration> {
                 "DROP TABLE IF EXISTS plugins;".to_string(),
             ],
         },
+
+        // 版本 6: 全文索引同步
+        Migration {
+            version: 6,
+            description: "全文索引数据源与同步触发器".to_string(),
+            up_sql: vec![
+                // notes_fts 以 notes 为外部内容表，需要 notes 提供同名列
+                "ALTER TABLE notes ADD COLUMN tags TEXT NOT NULL DEFAULT '';".to_string(),
+                "ALTER TABLE notes ADD COLUMN categories TEXT NOT NULL DEFAULT '';".to_string(),
+
+                // 旧触发器会读取不存在的列，并且用 UPDATE 修改外部内容表索引
+                "DROP TRIGGER IF EXISTS update_fts_insert;".to_string(),
+                "DROP TRIGGER IF EXISTS update_fts_update;".to_string(),
+                "DROP TRIGGER IF EXISTS update_fts_delete;".to_string(),
+
+                // 回填已有笔记的标签和分类文本
+                r#"
+                UPDATE notes SET
+                    tags = COALESCE((
+                        SELECT GROUP_CONCAT(t.name, ' ')
+                        FROM note_tags nt JOIN tags t ON nt.tag_id = t.id
+                        WHERE nt.note_id = notes.id
+                    ), ''),
+                    categories = COALESCE((
+                        SELECT GROUP_CONCAT(c.name, ' ')
+                        FROM note_categories nc JOIN categories c ON nc.category_id = c.id
+                        WHERE nc.note_id = notes.id
+                    ), '');
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS notes_fts_insert
+                AFTER INSERT ON notes
+                FOR EACH ROW
+                BEGIN
+                    INSERT INTO notes_fts(rowid, title, content, tags, categories)
+                    VALUES (NEW.rowid, NEW.title, NEW.content, NEW.tags, NEW.categories);
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS notes_fts_delete
+                AFTER DELETE ON notes
+                FOR EACH ROW
+                BEGIN
+                    INSERT INTO notes_fts(notes_fts, rowid, title, content, tags, categories)
+                    VALUES ('delete', OLD.rowid, OLD.title, OLD.content, OLD.tags, OLD.categories);
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS notes_fts_update
+                AFTER UPDATE OF title, content, tags, categories ON notes
+                FOR EACH ROW
+                BEGIN
+                    INSERT INTO notes_fts(notes_fts, rowid, title, content, tags, categories)
+                    VALUES ('delete', OLD.rowid, OLD.title, OLD.content, OLD.tags, OLD.categories);
+                    INSERT INTO notes_fts(rowid, title, content, tags, categories)
+                    VALUES (NEW.rowid, NEW.title, NEW.content, NEW.tags, NEW.categories);
+                END;
+                "#.to_string(),
+
+                // 按新的数据源重建索引
+                "INSERT INTO notes_fts(notes_fts) VALUES ('rebuild');".to_string(),
+            ],
+            down_sql: vec![
+                "DROP TRIGGER IF EXISTS notes_fts_insert;".to_string(),
+                "DROP TRIGGER IF EXISTS notes_fts_delete;".to_string(),
+                "DROP TRIGGER IF EXISTS notes_fts_update;".to_string(),
+                "ALTER TABLE notes DROP COLUMN categories;".to_string(),
+                "ALTER TABLE notes DROP COLUMN tags;".to_string(),
+            ],
+        },
+
+        // 版本 7: 标签和分类文本随关联表自动更新
+        Migration {
+            version: 7,
+            description: "标签和分类全文索引同步".to_string(),
+            up_sql: vec![
+                r#"
+                CREATE TRIGGER IF NOT EXISTS note_tags_fts_insert
+                AFTER INSERT ON note_tags
+                FOR EACH ROW
+                BEGIN
+                    UPDATE notes SET tags = COALESCE((
+                        SELECT GROUP_CONCAT(t.name, ' ')
+                        FROM note_tags nt JOIN tags t ON nt.tag_id = t.id
+                        WHERE nt.note_id = NEW.note_id
+                    ), '')
+                    WHERE id = NEW.note_id;
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS note_tags_fts_delete
+                AFTER DELETE ON note_tags
+                FOR EACH ROW
+                BEGIN
+                    UPDATE notes SET tags = COALESCE((
+                        SELECT GROUP_CONCAT(t.name, ' ')
+                        FROM note_tags nt JOIN tags t ON nt.tag_id = t.id
+                        WHERE nt.note_id = OLD.note_id
+                    ), '')
+                    WHERE id = OLD.note_id;
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS note_categories_fts_insert
+                AFTER INSERT ON note_categories
+                FOR EACH ROW
+                BEGIN
+                    UPDATE notes SET categories = COALESCE((
+                        SELECT GROUP_CONCAT(c.name, ' ')
+                        FROM note_categories nc JOIN categories c ON nc.category_id = c.id
+                        WHERE nc.note_id = NEW.note_id
+                    ), '')
+                    WHERE id = NEW.note_id;
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS note_categories_fts_delete
+                AFTER DELETE ON note_categories
+                FOR EACH ROW
+                BEGIN
+                    UPDATE notes SET categories = COALESCE((
+                        SELECT GROUP_CONCAT(c.name, ' ')
+                        FROM note_categories nc JOIN categories c ON nc.category_id = c.id
+                        WHERE nc.note_id = OLD.note_id
+                    ), '')
+                    WHERE id = OLD.note_id;
+                END;
+                "#.to_string(),
+
+                // 标签或分类改名时更新所有引用它的笔记
+                r#"
+                CREATE TRIGGER IF NOT EXISTS tags_fts_rename
+                AFTER UPDATE OF name ON tags
+                FOR EACH ROW
+                BEGIN
+                    UPDATE notes SET tags = COALESCE((
+                        SELECT GROUP_CONCAT(t.name, ' ')
+                        FROM note_tags nt JOIN tags t ON nt.tag_id = t.id
+                        WHERE nt.note_id = notes.id
+                    ), '')
+                    WHERE id IN (SELECT note_id FROM note_tags WHERE tag_id = NEW.id);
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS categories_fts_rename
+                AFTER UPDATE OF name ON categories
+                FOR EACH ROW
+                BEGIN
+                    UPDATE notes SET categories = COALESCE((
+                        SELECT GROUP_CONCAT(c.name, ' ')
+                        FROM note_categories nc JOIN categories c ON nc.category_id = c.id
+                        WHERE nc.note_id = notes.id
+                    ), '')
+                    WHERE id IN (SELECT note_id FROM note_categories WHERE category_id = NEW.id);
+                END;
+                "#.to_string(),
+            ],
+            down_sql: vec![
+                "DROP TRIGGER IF EXISTS note_tags_fts_insert;".to_string(),
+                "DROP TRIGGER IF EXISTS note_tags_fts_delete;".to_string(),
+                "DROP TRIGGER IF EXISTS note_categories_fts_insert;".to_string(),
+                "DROP TRIGGER IF EXISTS note_categories_fts_delete;".to_string(),
+                "DROP TRIGGER IF EXISTS tags_fts_rename;".to_string(),
+                "DROP TRIGGER IF EXISTS categories_fts_rename;".to_string(),
+            ],
+        },
+
+        // 版本 8: 中日韩文本检索
+        Migration {
+            version: 8,
+            description: "trigram 全文索引".to_string(),
+            up_sql: vec![
+                // unicode61 把连续的汉字切成一个词，trigram 按字符子串匹配
+                r#"
+                CREATE VIRTUAL TABLE IF NOT EXISTS notes_trigram USING fts5(
+                    title,
+                    content,
+                    tags,
+                    categories,
+                    content='notes',
+                    content_rowid='rowid',
+                    tokenize='trigram'
+                );
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS notes_trigram_insert
+                AFTER INSERT ON notes
+                FOR EACH ROW
+                BEGIN
+                    INSERT INTO notes_trigram(rowid, title, content, tags, categories)
+                    VALUES (NEW.rowid, NEW.title, NEW.content, NEW.tags, NEW.categories);
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS notes_trigram_delete
+                AFTER DELETE ON notes
+                FOR EACH ROW
+                BEGIN
+                    INSERT INTO notes_trigram(notes_trigram, rowid, title, content, tags, categories)
+                    VALUES ('delete', OLD.rowid, OLD.title, OLD.content, OLD.tags, OLD.categories);
+                END;
+                "#.to_string(),
+
+                r#"
+                CREATE TRIGGER IF NOT EXISTS notes_trigram_update
+                AFTER UPDATE OF title, content, tags, categories ON notes
+                FOR EACH ROW
+                BEGIN
+                    INSERT INTO notes_trigram(notes_trigram, rowid, title, content, tags, categories)
+                    VALUES ('delete', OLD.rowid, OLD.title, OLD.content, OLD.tags, OLD.categories);
+                    INSERT INTO notes_trigram(rowid, title, content, tags, categories)
+                    VALUES (NEW.rowid, NEW.title, NEW.content, NEW.tags, NEW.categories);
+                END;
+                "#.to_string(),
+
+                "INSERT INTO notes_trigram(notes_trigram) VALUES ('rebuild');".to_string(),
+            ],
+            down_sql: vec![
+                "DROP TRIGGER IF EXISTS notes_trigram_insert;".to_string(),
+                "DROP TRIGGER IF EXISTS notes_trigram_delete;".to_string(),
+                "DROP TRIGGER IF EXISTS notes_trigram_update;".to_string(),
+                "DROP TABLE IF EXISTS notes_trigram;".to_string(),
+            ],
+        },
+
+        // 版本 9: 保存的搜索
+        Migration {
+            version: 9,
+            description: "保存的搜索".to_string(),
+            up_sql: vec![
+                r#"
+                CREATE TABLE IF NOT EXISTS saved_searches (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL UNIQUE,
+                    query TEXT NOT NULL,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                );
+                "#.to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_search_history_query ON search_history(query);".to_string(),
+            ],
+            down_sql: vec![
+                "DROP INDEX IF EXISTS idx_search_history_query;".to_string(),
+                "DROP TABLE IF EXISTS saved_searches;".to_string(),
+            ],
+        },
+
+        // 版本 10: 笔记版本历史
+        Migration {
+            version: 10,
+            description: "笔记版本历史".to_string(),
+            up_sql: vec![
+                // 不设外键，笔记删除后历史仍可用于找回
+                r#"
+                CREATE TABLE IF NOT EXISTS note_versions (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    note_id TEXT NOT NULL,
+                    version INTEGER NOT NULL,
+                    title TEXT NOT NULL,
+                    file_path TEXT NOT NULL,
+                    content TEXT NOT NULL,
+                    file_hash TEXT NOT NULL,
+                    modified_at TIMESTAMP NOT NULL,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE(note_id, version)
+                );
+                "#.to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_note_versions_path ON note_versions(file_path);".to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_note_versions_created ON note_versions(created_at);".to_string(),
+            ],
+            down_sql: vec![
+                "DROP INDEX IF EXISTS idx_note_versions_created;".to_string(),
+                "DROP INDEX IF EXISTS idx_note_versions_path;".to_string(),
+                "DROP TABLE IF EXISTS note_versions;".to_string(),
+            ],
+        },
+
+        // 版本 11: 回收站
+        Migration {
+            version: 11,
+            description: "回收站".to_string(),
+            up_sql: vec![
+                // 笔记行保留在 notes 中（状态为 deleted），标签和链接随之保留，彻底删除时级联清理
+                r#"
+                CREATE TABLE IF NOT EXISTS trash (
+                    note_id TEXT PRIMARY KEY,
+                    original_path TEXT NOT NULL,
+                    previous_status TEXT NOT NULL,
+                    deleted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+                );
+                "#.to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_trash_deleted ON trash(deleted_at);".to_string(),
+            ],
+            down_sql: vec![
+                "DROP INDEX IF EXISTS idx_trash_deleted;".to_string(),
+                "DROP TABLE IF EXISTS trash;".to_string(),
+            ],
+        },
+
+        // 版本 12: 笔记原文
+        Migration {
+            version: 12,
+            description: "笔记文件原文".to_string(),
+            up_sql: vec![
+                // content 不含前言，链接等位置需要按完整文件计算
+                "ALTER TABLE notes ADD COLUMN source TEXT;".to_string(),
+            ],
+            down_sql: vec![
+                "ALTER TABLE notes DROP COLUMN source;".to_string(),
+            ],
+        },
+
+        // 版本 13: 链接原文和笔记锚点
+        Migration {
+            version: 13,
+            description: "链接原文和笔记锚点".to_string(),
+            up_sql: vec![
+                // links 只保存能解析到笔记的链接；这里保存每个指向笔记的链接，
+                // 解析不到时 target_id 为空，用于查询断链、失效的锚点和有歧义的链接
+                r#"
+                CREATE TABLE IF NOT EXISTS link_refs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    source_id TEXT NOT NULL,
+                    target_id TEXT,
+                    raw TEXT NOT NULL,
+                    target TEXT NOT NULL,
+                    alias TEXT,
+                    anchor TEXT,
+                    is_embed BOOLEAN NOT NULL DEFAULT FALSE,
+                    range_start INTEGER NOT NULL,
+                    range_end INTEGER NOT NULL,
+                    line_number INTEGER NOT NULL,
+                    -- 需要在目标笔记中检查的锚点，Markdown 链接的片段不检查
+                    anchor_kind TEXT CHECK (anchor_kind IN ('heading', 'block')),
+                    anchor_key TEXT NOT NULL DEFAULT '',
+                    anchor_slug TEXT NOT NULL DEFAULT '',
+                    FOREIGN KEY (source_id) REFERENCES notes(id) ON DELETE CASCADE,
+                    FOREIGN KEY (target_id) REFERENCES notes(id) ON DELETE SET NULL
+                );
+                "#.to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_link_refs_source ON link_refs(source_id);".to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_link_refs_target ON link_refs(target_id);".to_string(),
+                r#"
+                CREATE TABLE IF NOT EXISTS note_anchors (
+                    note_id TEXT NOT NULL,
+                    kind TEXT NOT NULL CHECK (kind IN ('heading', 'block')),
+                    name TEXT NOT NULL,
+                    anchor_key TEXT NOT NULL,
+                    anchor_slug TEXT NOT NULL,
+                    line INTEGER NOT NULL,
+                    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+                );
+                "#.to_string(),
+                "CREATE INDEX IF NOT EXISTS idx_note_anchors_note ON note_anchors(note_id, kind);".to_string(),
+                // 已有笔记在首次查询断链时由保存的原文补全，空数据库无需补全
+                "INSERT OR IGNORE INTO system_config (key, value) SELECT 'link_refs_indexed', 'true' WHERE NOT EXISTS (SELECT 1 FROM notes);".to_string(),
+            ],
+            down_sql: vec![
+                "DELETE FROM system_config WHERE key = 'link_refs_indexed';".to_string(),
+                "DROP INDEX IF EXISTS idx_note_anchors_note;".to_string(),
+                "DROP TABLE IF EXISTS note_anchors;".to_string(),
+                "DROP INDEX IF EXISTS idx_link_refs_target;".to_string(),
+                "DROP INDEX IF EXISTS idx_link_refs_source;".to_string(),
+                "DROP TABLE IF EXISTS link_refs;".to_string(),
+            ],
+        },
     ]
 }
 
@@ -339,7 +724,8 @@ pub async fn validate_migrations(pool: &SqlitePool) -> Result<Vec<String>> {
     // 检查表是否存在
     let required_tables = vec![
         "notes", "tags", "note_tags", "categories", "note_categories",
-        "links", "notes_fts", "system_config", "file_events"
+        "links", "notes_fts", "notes_trigram", "system_config", "file_events", "note_versions", "trash",
+        "link_refs", "note_anchors"
     ];
     
     for table in required_tables {
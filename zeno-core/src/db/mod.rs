@@ -1,20 +1,37 @@
 pub mod models;
 pub mod connection;
 pub mod migrations;
+pub mod backup;
 
 pub use models::*;
 pub use connection::*;
+pub use backup::*;
 
-use sqlx::{Pool, Sqlite, SqlitePool};
-use std::path::Path;
-use crate::error::Result;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use crate::error::{Error, Result};
+
+/// 工作区数据库相对于工作区根目录的路径
+pub const WORKSPACE_DB_PATH: &str = ".zeno/zeno.db";
 
 /// 数据库管理器
+#[derive(Clone)]
 pub struct Database {
     pool: SqlitePool,
 }
 
 impl Database {
+    /// 打开工作区数据库，不存在时创建并初始化
+    pub async fn open_workspace<P: AsRef<Path>>(root: P) -> Result<Self> {
+        let db_path = root.as_ref().join(WORKSPACE_DB_PATH);
+        if let Some(parent) = db_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let db = Self::new(&db_path).await?;
+        db.initialize().await?;
+        Ok(db)
+    }
+
     /// 创建新的数据库连接
     pub async fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
         let db_url = format!("sqlite:{}", db_path.as_ref().display());
@@ -193,7 +210,7 @@ impl Database {
         .await?;
 
         // 最近笔记
-        let recent_notes: Vec<Note> = sqlx::query_as::<_, Note>(
+        let recent_notes: Vec<NoteRecord> = sqlx::query_as::<_, NoteRecord>(
             "SELECT * FROM notes WHERE status != 'deleted' ORDER BY modified_at DESC LIMIT 5"
         )
         .fetch_all(&self.pool)
@@ -262,37 +279,76 @@ impl Database {
         Ok(())
     }
 
-    /// 备份数据库
+    /// 在线备份数据库
+    ///
+    /// 使用 `VACUUM INTO` 写入临时文件，备份期间可以继续读写；
+    /// 通过完整性检查后才移动到 `backup_path`，目标文件已存在时返回错误。
     pub async fn backup<P: AsRef<Path>>(&self, backup_path: P) -> Result<()> {
-        // SQLite 数据库文件级备份
-        // 这里简化实现，实际应该使用 SQLite 的 VACUUM INTO 或文件复制
-        let backup_sql = format!(
-            "VACUUM INTO '{}'", 
-            backup_path.as_ref().display()
-        );
-        
-        sqlx::query(&backup_sql)
+        let backup_path = backup_path.as_ref();
+        if backup_path.exists() {
+            return Err(Error::validation(format!("备份文件已存在: {}", backup_path.display())));
+        }
+        if let Some(parent) = backup_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let staging = sidecar_path(backup_path, "tmp");
+        if staging.exists() {
+            tokio::fs::remove_file(&staging).await?;
+        }
+        sqlx::query("VACUUM INTO ?")
+            .bind(staging.to_string_lossy().to_string())
             .execute(&self.pool)
             .await?;
 
+        if !verify_backup(&staging).await? {
+            tokio::fs::remove_file(&staging).await?;
+            return Err(Error::validation(format!("备份未通过完整性检查: {}", backup_path.display())));
+        }
+        tokio::fs::rename(&staging, backup_path).await?;
         Ok(())
     }
 
-    /// 从备份恢复数据库
-    pub async fn restore<P: AsRef<Path>>(&self, backup_path: P) -> Result<()> {
-        // 检查备份文件是否存在
-        if !backup_path.as_ref().exists() {
-            return Err(crate::error::Error::NotFound(
-                format!("备份文件不存在: {}", backup_path.as_ref().display())
-            ));
+    /// 从备份恢复数据库，返回重新打开的数据库
+    ///
+    /// 先检查备份的完整性，再关闭连接池、替换数据库文件并重新打开，较旧的备份会被迁移到当前版本。
+    /// 连接池关闭后，此前克隆的 `Database` 都不能再使用。
+    pub async fn restore<P: AsRef<Path>>(self, backup_path: P) -> Result<Database> {
+        let backup_path = backup_path.as_ref();
+        if !backup_path.exists() {
+            return Err(Error::not_found(format!("备份文件不存在: {}", backup_path.display())));
+        }
+        if !verify_backup(backup_path).await? {
+            return Err(Error::validation(format!("备份未通过完整性检查: {}", backup_path.display())));
         }
 
-        // 这里应该实现完整的恢复逻辑
-        // 包括验证备份文件完整性、关闭当前连接、替换数据库文件等
-        // 为了简化，这里只是提示需要重启应用
-        log::warn!("数据库恢复需要重启应用程序");
-        
-        Ok(())
+        // 先复制到数据库旁的临时文件，关闭连接后只需重命名
+        let db_path = self.file_path().await?;
+        let staging = sidecar_path(&db_path, "restore");
+        tokio::fs::copy(backup_path, &staging).await?;
+
+        self.pool.close().await;
+        let replaced = replace_database_file(&staging, &db_path).await;
+        if replaced.is_err() {
+            let _ = tokio::fs::remove_file(&staging).await;
+        }
+
+        // 替换失败时重新打开原数据库
+        let db = Database::new(&db_path).await?;
+        replaced?;
+        db.initialize().await?;
+        Ok(db)
+    }
+
+    /// 数据库文件路径
+    async fn file_path(&self) -> Result<PathBuf> {
+        let file: String = sqlx::query_scalar("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_one(&self.pool)
+            .await?;
+        if file.is_empty() {
+            return Err(Error::validation("内存数据库无法恢复备份"));
+        }
+        Ok(PathBuf::from(file))
     }
 
     /// 检查数据库完整性
@@ -304,6 +360,42 @@ impl Database {
         Ok(result == "ok")
     }
 
+    /// 重建全文索引
+    ///
+    /// 从 `notes` 重建 `notes_fts` 和 `notes_trigram`，并根据关联表重新生成笔记的标签和分类文本，
+    /// 用于修复触发器之外的写入造成的索引不一致。
+    pub async fn rebuild_fts(&self) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // 先让索引与现有列一致，之后列的更新由触发器同步
+        for table in ["notes_fts", "notes_trigram"] {
+            sqlx::query(&format!("INSERT INTO {table}({table}) VALUES ('rebuild')"))
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE notes SET
+                tags = COALESCE((
+                    SELECT GROUP_CONCAT(t.name, ' ')
+                    FROM note_tags nt JOIN tags t ON nt.tag_id = t.id
+                    WHERE nt.note_id = notes.id
+                ), ''),
+                categories = COALESCE((
+                    SELECT GROUP_CONCAT(c.name, ' ')
+                    FROM note_categories nc JOIN categories c ON nc.category_id = c.id
+                    WHERE nc.note_id = notes.id
+                ), '')
+            "#
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// 优化数据库
     pub async fn optimize(&self) -> Result<()> {
         // 重建索引
@@ -324,6 +416,26 @@ impl Database {
     }
 }
 
+/// 在文件名后追加后缀，如 `zeno.db` -> `zeno.db-wal`
+fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push("-");
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// 用 `staging` 替换数据库文件，旧的 WAL 文件会被删除，避免重新打开时写回旧数据
+async fn replace_database_file(staging: &Path, db_path: &Path) -> Result<()> {
+    for suffix in ["wal", "shm"] {
+        let sidecar = sidecar_path(db_path, suffix);
+        if sidecar.exists() {
+            tokio::fs::remove_file(&sidecar).await?;
+        }
+    }
+    tokio::fs::rename(staging, db_path).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,9 +463,34 @@ mod tests {
         let db = Database::new(&db_path).await.unwrap();
         db.initialize().await.unwrap();
         
+        let note = NoteRecord::new("Backup".to_string(), "backup.md".to_string(), "before".to_string());
+        sqlx::query("INSERT INTO notes (id, title, file_path, content) VALUES (?, ?, ?, ?)")
+            .bind(&note.id)
+            .bind(&note.title)
+            .bind(&note.file_path)
+            .bind(&note.content)
+            .execute(db.pool())
+            .await
+            .unwrap();
+
         // 备份
         db.backup(&backup_path).await.unwrap();
         assert!(backup_path.exists());
+        assert!(verify_backup(&backup_path).await.unwrap());
+        assert!(matches!(db.backup(&backup_path).await, Err(Error::Validation(_))));
+
+        // 备份之后的修改在恢复后消失
+        sqlx::query("UPDATE notes SET content = 'after'").execute(db.pool()).await.unwrap();
+        let db = db.restore(&backup_path).await.unwrap();
+        let content: String = sqlx::query_scalar("SELECT content FROM notes").fetch_one(db.pool()).await.unwrap();
+        assert_eq!(content, "before");
+        assert!(db.check_integrity().await.unwrap());
+
+        // 损坏的备份不会被恢复
+        let corrupt = dir.path().join("corrupt.db");
+        std::fs::write(&corrupt, "not a database").unwrap();
+        assert!(db.clone().restore(&corrupt).await.is_err());
+        assert!(db.check_integrity().await.unwrap());
     }
 
     #[tokio::test]
@@ -367,4 +504,39 @@ mod tests {
         let is_ok = db.check_integrity().await.unwrap();
         assert!(is_ok);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_rebuild_fts() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+
+        let note = NoteRecord::new("Rebuild".to_string(), "rebuild.md".to_string(), "searchable text".to_string());
+        sqlx::query("INSERT INTO notes (id, title, file_path, content) VALUES (?, ?, ?, ?)")
+            .bind(&note.id)
+            .bind(&note.title)
+            .bind(&note.file_path)
+            .bind(&note.content)
+            .execute(db.pool())
+            .await
+            .unwrap();
+
+        let count = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM notes_fts WHERE notes_fts MATCH 'searchable'")
+                .fetch_one(db.pool())
+                .await
+                .unwrap()
+        };
+        assert_eq!(count().await, 1);
+
+        // 模拟索引丢失
+        sqlx::query("INSERT INTO notes_fts(notes_fts) VALUES ('delete-all')")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(count().await, 0);
+
+        db.rebuild_fts().await.unwrap();
+        assert_eq!(count().await, 1);
+    }
+}
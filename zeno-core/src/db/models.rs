@@ -1,65 +1,21 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-/// 笔记状态枚举
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum NoteStatus {
-    Draft,
-    Published,
-    Archived,
-    Deleted,
-}
-
-impl Default for NoteStatus {
-    fn default() -> Self {
-        Self::Draft
-    }
-}
-
-impl std::fmt::Display for NoteStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            NoteStatus::Draft => write!(f, "draft"),
-            NoteStatus::Published => write!(f, "published"),
-            NoteStatus::Archived => write!(f, "archived"),
-            NoteStatus::Deleted => write!(f, "deleted"),
-        }
-    }
-}
-
-impl std::str::FromStr for NoteStatus {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "draft" => Ok(NoteStatus::Draft),
-            "published" => Ok(NoteStatus::Published),
-            "archived" => Ok(NoteStatus::Archived),
-            "deleted" => Ok(NoteStatus::Deleted),
-            _ => Err(format!("无效的笔记状态: {}", s)),
-        }
-    }
-}
+pub use crate::models::{Frontmatter, NoteStatus, PublishConfig};
 
 /// 链接类型枚举
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LinkType {
+    #[default]
     Reference,
     Embed,
     Citation,
 }
 
-impl Default for LinkType {
-    fn default() -> Self {
-        Self::Reference
-    }
-}
-
 impl std::fmt::Display for LinkType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -83,9 +39,12 @@ impl std::str::FromStr for LinkType {
     }
 }
 
-/// 笔记模型
+/// 笔记数据库行
+///
+/// 与 `notes` 表一一对应；领域模型见 [`crate::models::Note`]，
+/// 两者之间通过 `From`/`TryFrom` 无损转换。
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
-pub struct Note {
+pub struct NoteRecord {
     pub id: String,
     pub title: String,
     pub file_path: String,
@@ -101,9 +60,12 @@ pub struct Note {
     pub file_size: i64,
     pub file_hash: String,
     pub search_vector: Option<String>,
+    /// 文件原文（含前言），为空时只有正文可用
+    #[sqlx(default)]
+    pub source: Option<String>,
 }
 
-impl Note {
+impl NoteRecord {
     /// 创建新笔记
     pub fn new(title: String, file_path: String, content: String) -> Self {
         let now = Utc::now();
@@ -123,6 +85,7 @@ impl Note {
             file_size: 0,
             file_hash: String::new(),
             search_vector: None,
+            source: None,
         }
     }
 
@@ -149,79 +112,73 @@ impl Note {
 
     /// 计算字数
     pub fn calculate_word_count(&mut self) {
-        // 简单的字数统计，不包括 frontmatter
-        let content_without_frontmatter = if self.content.starts_with("---") {
-            if let Some(end_pos) = self.content[3..].find("---") {
-                &self.content[end_pos + 6..]
-            } else {
-                &self.content
-            }
-        } else {
-            &self.content
-        };
-
-        // 统计中文字符和英文单词
-        let chinese_chars = content_without_frontmatter
-            .chars()
-            .filter(|c| c.is_alphabetic() && *c as u32 > 127)
-            .count();
-        
-        let english_words = content_without_frontmatter
-            .split_whitespace()
-            .filter(|word| word.chars().any(|c| c.is_alphabetic() && c as u32 <= 127))
-            .count();
-
-        self.word_count = (chinese_chars + english_words) as i32;
+        self.word_count = crate::models::count_words(&self.content) as i32;
     }
 
     /// 计算预估阅读时间（分钟）
     pub fn calculate_reading_time(&mut self) {
-        // 中文阅读速度：250-300字/分钟，英文：200-250词/分钟
-        // 这里使用 250 字/词 每分钟作为基准
-        self.reading_time = (self.word_count as f32 / 250.0).ceil() as i32;
-        if self.reading_time == 0 {
-            self.reading_time = 1; // 至少1分钟
-        }
+        self.reading_time = crate::models::calculate_reading_time(self.word_count.max(0) as usize) as i32;
     }
 }
 
-/// frontmatter 结构
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Frontmatter {
-    pub title: Option<String>,
-    pub date: Option<NaiveDate>,
-    pub tags: Vec<String>,
-    pub categories: Vec<String>,
-    pub status: Option<NoteStatus>,
-    pub description: Option<String>,
-    pub author: Option<String>,
-    pub publish: Option<PublishConfig>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, serde_json::Value>,
-}
-
-impl Default for Frontmatter {
-    fn default() -> Self {
+impl From<&crate::models::Note> for NoteRecord {
+    fn from(note: &crate::models::Note) -> Self {
         Self {
-            title: None,
-            date: None,
-            tags: Vec::new(),
-            categories: Vec::new(),
-            status: None,
-            description: None,
-            author: None,
-            publish: None,
-            extra: HashMap::new(),
+            id: note.id.to_string(),
+            title: note.title.clone(),
+            file_path: note.path.to_string_lossy().to_string(),
+            content: note.content.clone(),
+            html_content: note.html_content.clone(),
+            word_count: note.word_count as i32,
+            reading_time: note.reading_time as i32,
+            created_at: note.created_at,
+            modified_at: note.modified_at,
+            indexed_at: Utc::now(),
+            status: note.status.to_string(),
+            // Frontmatter 只包含可序列化的字段，这里不会失败
+            frontmatter: serde_json::to_string(&note.frontmatter).unwrap_or_else(|_| "{}".to_string()),
+            file_size: note.file_size as i64,
+            file_hash: note.checksum.clone(),
+            search_vector: None,
+            source: note.source.clone(),
         }
     }
 }
 
-/// 发布配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PublishConfig {
-    pub enabled: bool,
-    pub platforms: Vec<String>,
-    pub scheduled_at: Option<DateTime<Utc>>,
+impl From<crate::models::Note> for NoteRecord {
+    fn from(note: crate::models::Note) -> Self {
+        Self::from(&note)
+    }
+}
+
+impl TryFrom<NoteRecord> for crate::models::Note {
+    type Error = crate::error::Error;
+
+    fn try_from(record: NoteRecord) -> crate::error::Result<Self> {
+        let status = record.status.parse().map_err(crate::error::Error::validation)?;
+        let frontmatter = if record.frontmatter.trim().is_empty() {
+            Frontmatter::default()
+        } else {
+            serde_json::from_str(&record.frontmatter)?
+        };
+
+        Ok(Self {
+            id: Uuid::parse_str(&record.id)?,
+            path: std::path::PathBuf::from(record.file_path),
+            title: record.title,
+            content: record.content,
+            html_content: record.html_content,
+            frontmatter,
+            status,
+            created_at: record.created_at,
+            modified_at: record.modified_at,
+            checksum: record.file_hash,
+            file_size: record.file_size.max(0) as u64,
+            word_count: record.word_count.max(0) as usize,
+            reading_time: record.reading_time.max(0) as usize,
+            source: record.source,
+        })
+    }
 }
 
 /// 标签模型
@@ -318,7 +275,7 @@ impl Link {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoteDetail {
     #[serde(flatten)]
-    pub note: Note,
+    pub note: NoteRecord,
     pub tags: Vec<Tag>,
     pub categories: Vec<Category>,
     pub outbound_links: Vec<Link>,
@@ -328,7 +285,7 @@ pub struct NoteDetail {
 /// 搜索结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
-    pub note: Note,
+    pub note: NoteRecord,
     pub score: f32,
     pub highlights: Vec<String>,
     pub matched_fields: Vec<String>,
@@ -344,30 +301,115 @@ pub struct SearchQuery {
 }
 
 /// 搜索过滤器
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// 标签和分类条件之间是 AND 关系，路径前缀满足任一即可。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchFilters {
+    /// 必须包含的标签，同时匹配其子标签（`project` 匹配 `project/zeno`）
     pub tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
     pub categories: Vec<String>,
+    pub exclude_categories: Vec<String>,
+    /// 未指定时排除已删除的笔记
     pub status: Option<NoteStatus>,
-    pub date_range: Option<DateRange>,
+    /// 相对工作区的路径前缀
+    pub paths: Vec<String>,
+    pub exclude_paths: Vec<String>,
+    /// 修改时间范围
+    pub modified: Option<DateRange>,
+    /// 创建时间范围
+    pub created: Option<DateRange>,
 }
 
-impl Default for SearchFilters {
-    fn default() -> Self {
-        Self {
-            tags: Vec::new(),
-            categories: Vec::new(),
-            status: None,
-            date_range: None,
-        }
+impl SearchFilters {
+    /// 合并另一组过滤器，状态以自身为准，时间范围取交集
+    pub fn merge(&mut self, other: SearchFilters) {
+        self.tags.extend(other.tags);
+        self.exclude_tags.extend(other.exclude_tags);
+        self.categories.extend(other.categories);
+        self.exclude_categories.extend(other.exclude_categories);
+        self.status = self.status.take().or(other.status);
+        self.paths.extend(other.paths);
+        self.exclude_paths.extend(other.exclude_paths);
+        self.modified = DateRange::intersect(self.modified.take(), other.modified);
+        self.created = DateRange::intersect(self.created.take(), other.created);
+    }
+
+    /// 是否没有任何条件
+    pub fn is_empty(&self) -> bool {
+        self == &SearchFilters::default()
     }
 }
 
-/// 日期范围
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 搜索历史记录
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SearchHistoryEntry {
+    pub id: i64,
+    pub query: String,
+    pub result_count: i64,
+    /// 搜索耗时（秒）
+    pub search_time: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 保存的搜索，每次运行时重新执行查询
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavedSearch {
+    pub id: i64,
+    pub name: String,
+    pub query: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 笔记的历史版本，保存被覆盖前的完整 Markdown（含 frontmatter）
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NoteVersion {
+    pub id: i64,
+    pub note_id: String,
+    /// 同一笔记内从 1 开始递增的版本号
+    pub version: i64,
+    pub title: String,
+    pub file_path: String,
+    pub content: String,
+    pub file_hash: String,
+    /// 该版本内容的修改时间
+    pub modified_at: DateTime<Utc>,
+    /// 版本被保存的时间
+    pub created_at: DateTime<Utc>,
+}
+
+/// 回收站中的笔记
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TrashEntry {
+    pub note_id: String,
+    pub title: String,
+    /// 删除前的路径，恢复时移回这里
+    pub original_path: String,
+    /// 文件在回收站中的路径
+    pub trash_path: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// 日期范围，包含 `start`，不包含 `end`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct DateRange {
-    pub start: DateTime<Utc>,
-    pub end: DateTime<Utc>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    /// 两个可选范围的交集
+    pub fn intersect(a: Option<DateRange>, b: Option<DateRange>) -> Option<DateRange> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(DateRange {
+                start: a.start.into_iter().chain(b.start).max(),
+                end: a.end.into_iter().chain(b.end).min(),
+            }),
+            (a, b) => a.or(b),
+        }
+    }
 }
 
 /// 文件事件
@@ -402,6 +444,20 @@ impl std::fmt::Display for FileEventType {
     }
 }
 
+impl std::str::FromStr for FileEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(FileEventType::Created),
+            "modified" => Ok(FileEventType::Modified),
+            "deleted" => Ok(FileEventType::Deleted),
+            "renamed" => Ok(FileEventType::Renamed),
+            _ => Err(format!("无效的文件事件类型: {}", s)),
+        }
+    }
+}
+
 /// 系统配置
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct SystemConfig {
@@ -430,5 +486,37 @@ pub struct Statistics {
     pub total_links: i64,
     pub notes_by_status: HashMap<String, i64>,
     pub top_tags: Vec<(String, i32)>,
-    pub recent_notes: Vec<Note>,
-}
\ No newline at end of file
+    pub recent_notes: Vec<NoteRecord>,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Note;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_note_record_round_trip() {
+        let mut note = Note::new(PathBuf::from("notes/a.md"), "# 标题\n\n正文".to_string());
+        note.status = NoteStatus::Published;
+        note.html_content = Some("<h1>标题</h1>".to_string());
+        note.file_size = 42;
+        note.frontmatter.add_tag("rust".to_string());
+        note.frontmatter.status = Some(NoteStatus::Published);
+        note.frontmatter.aliases = vec!["A".to_string()];
+        note.frontmatter.set_custom_field("priority".to_string(), serde_json::json!("high"));
+
+        let record = NoteRecord::from(&note);
+        assert_eq!(record.status, "published");
+        assert_eq!(record.file_hash, note.checksum);
+
+        let restored = Note::try_from(record).unwrap();
+        assert_eq!(restored, note);
+    }
+
+    #[test]
+    fn test_note_record_invalid_status() {
+        let mut record = NoteRecord::new("t".to_string(), "t.md".to_string(), String::new());
+        record.status = "unknown".to_string();
+        assert!(Note::try_from(record).is_err());
+    }
+}
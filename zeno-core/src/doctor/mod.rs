@@ -0,0 +1,444 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::config::{WorkspaceConfig, WORKSPACE_CONFIG_FILE};
+use crate::db::{health_check, migrations, Database, WORKSPACE_DB_PATH};
+use crate::error::Result;
+use crate::indexer::{collect_markdown_files, SqliteIndexer};
+use crate::links::{BrokenLinkKind, LinkIndex};
+use crate::parser::MarkdownParser;
+
+/// 问题严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// zeno.yml 无法解析或校验失败
+    InvalidConfig,
+    /// zeno.yml 中有未识别的键
+    UnknownConfigKey,
+    /// 工作区数据库不存在
+    DatabaseMissing,
+    /// 数据库连接不健康
+    DatabaseUnhealthy,
+    /// `PRAGMA integrity_check` 失败
+    IntegrityFailed,
+    /// 有未执行的迁移
+    MigrationPending,
+    /// 迁移后的表结构不完整
+    MigrationInvalid,
+    /// 全文索引与笔记表不一致
+    SearchIndexCorrupt,
+    /// 文件未被索引
+    UnindexedFile,
+    /// 索引中的笔记文件已不存在
+    MissingFile,
+    /// 文件内容与索引不一致
+    ChecksumDrift,
+    /// 前言无法解析
+    InvalidFrontmatter,
+    /// 无法解析的笔记链接
+    BrokenLink,
+    /// 链接目标是多篇笔记的标题、文件名或别名
+    AmbiguousLink,
+    /// 多篇笔记标题相同
+    DuplicateTitle,
+}
+
+impl IssueKind {
+    /// 是否可以由 [`Doctor::fix`] 安全修复
+    pub fn is_fixable(self) -> bool {
+        matches!(
+            self,
+            IssueKind::DatabaseMissing
+                | IssueKind::MigrationPending
+                | IssueKind::SearchIndexCorrupt
+                | IssueKind::UnindexedFile
+                | IssueKind::MissingFile
+                | IssueKind::ChecksumDrift
+        )
+    }
+
+    fn severity(self) -> Severity {
+        match self {
+            IssueKind::InvalidConfig
+            | IssueKind::DatabaseUnhealthy
+            | IssueKind::IntegrityFailed
+            | IssueKind::MigrationInvalid
+            | IssueKind::SearchIndexCorrupt
+            | IssueKind::InvalidFrontmatter => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// 检查发现的问题
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub severity: Severity,
+    /// 相关文件（相对工作区）
+    pub path: Option<PathBuf>,
+    pub message: String,
+    pub fixable: bool,
+}
+
+impl Issue {
+    fn new(kind: IssueKind, path: Option<PathBuf>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            severity: kind.severity(),
+            path,
+            message: message.into(),
+            fixable: kind.is_fixable(),
+        }
+    }
+}
+
+/// 检查报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    /// 检查的 Markdown 文件数
+    pub files: usize,
+    /// 索引中的笔记数
+    pub notes: usize,
+    pub issues: Vec<Issue>,
+}
+
+impl DoctorReport {
+    pub fn errors(&self) -> usize {
+        self.issues.iter().filter(|i| i.severity == Severity::Error).count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.issues.iter().filter(|i| i.severity == Severity::Warning).count()
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn has(&self, kind: IssueKind) -> bool {
+        self.issues.iter().any(|i| i.kind == kind)
+    }
+
+    fn push(&mut self, kind: IssueKind, path: Option<PathBuf>, message: impl Into<String>) {
+        self.issues.push(Issue::new(kind, path, message));
+    }
+}
+
+/// 工作区健康检查
+///
+/// 合并数据库检查（连接、完整性、迁移、全文索引）和文件检查
+/// （未索引、已删除、内容漂移、前言、断链、重复标题）。
+pub struct Doctor {
+    root: PathBuf,
+    parser: MarkdownParser,
+}
+
+impl Doctor {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            parser: MarkdownParser::new(),
+        }
+    }
+
+    /// 执行全部检查，不修改工作区
+    pub async fn check(&self) -> Result<DoctorReport> {
+        let mut report = DoctorReport::default();
+        let config = self.check_config(&mut report).await;
+
+        let db = if self.root.join(WORKSPACE_DB_PATH).exists() {
+            let db = Database::new(self.root.join(WORKSPACE_DB_PATH)).await?;
+            self.check_database(&db, &mut report).await?;
+            Some(db)
+        } else {
+            report.push(IssueKind::DatabaseMissing, None, format!("数据库 {} 不存在", WORKSPACE_DB_PATH));
+            None
+        };
+
+        // 表结构不完整时跳过依赖索引的检查；回收站中的笔记单独查询
+        let (indexed, trashed) = match &db {
+            Some(db) if !report.has(IssueKind::MigrationPending) && !report.has(IssueKind::MigrationInvalid) => {
+                let rows: Vec<(String, String, String)> = sqlx::query_as(
+                    "SELECT id, file_path, file_hash FROM notes WHERE id NOT IN (SELECT note_id FROM trash)"
+                )
+                .fetch_all(db.pool())
+                .await?;
+                let trashed: Vec<(String, String, String)> = sqlx::query_as(
+                    "SELECT t.note_id, t.original_path, n.title FROM trash t JOIN notes n ON n.id = t.note_id"
+                )
+                .fetch_all(db.pool())
+                .await?;
+                (Some(rows), trashed)
+            }
+            _ => (None, Vec::new()),
+        };
+
+        self.check_files(&config, indexed, trashed, &mut report).await;
+        Ok(report)
+    }
+
+    /// 应用安全修复，返回执行的修复说明
+    ///
+    /// 只创建或迁移数据库、重建全文索引和重新索引文件，不会修改任何笔记文件。
+    pub async fn fix(&self, report: &DoctorReport) -> Result<Vec<String>> {
+        let mut fixed = Vec::new();
+        if !report.issues.iter().any(|i| i.fixable) {
+            return Ok(fixed);
+        }
+
+        let db = Database::open_workspace(&self.root).await?;
+        if report.has(IssueKind::DatabaseMissing) {
+            fixed.push(format!("创建数据库 {}", WORKSPACE_DB_PATH));
+        } else if report.has(IssueKind::MigrationPending) {
+            fixed.push("执行数据库迁移".to_string());
+        }
+
+        if report.has(IssueKind::SearchIndexCorrupt) {
+            db.rebuild_fts().await?;
+            fixed.push("重建全文索引".to_string());
+        }
+
+        let stale = [IssueKind::DatabaseMissing, IssueKind::UnindexedFile, IssueKind::MissingFile, IssueKind::ChecksumDrift];
+        if stale.iter().any(|kind| report.has(*kind)) {
+            let config = WorkspaceConfig::load(&self.root).await.unwrap_or_default();
+            let indexer = SqliteIndexer::new(db.pool().clone())
+                .with_excluded_dirs(config.excluded_dirs())
+                .with_history(config.history.clone());
+            let result = indexer.reindex_all(&self.root, |_| {}).await?;
+            fixed.push(format!(
+                "重新索引：新增 {}，更新 {}，删除 {}",
+                result.added, result.updated, result.removed
+            ));
+        }
+
+        Ok(fixed)
+    }
+
+    async fn check_config(&self, report: &mut DoctorReport) -> WorkspaceConfig {
+        let path = self.root.join(WORKSPACE_CONFIG_FILE);
+        let Ok(yaml) = tokio::fs::read_to_string(&path).await else {
+            return WorkspaceConfig::default();
+        };
+
+        match WorkspaceConfig::parse(&yaml) {
+            Ok((config, unknown)) => {
+                for key in unknown {
+                    report.push(
+                        IssueKind::UnknownConfigKey,
+                        Some(PathBuf::from(WORKSPACE_CONFIG_FILE)),
+                        format!("未识别的配置项: {}", key),
+                    );
+                }
+                config
+            }
+            Err(e) => {
+                report.push(IssueKind::InvalidConfig, Some(PathBuf::from(WORKSPACE_CONFIG_FILE)), e.to_string());
+                WorkspaceConfig::default()
+            }
+        }
+    }
+
+    async fn check_database(&self, db: &Database, report: &mut DoctorReport) -> Result<()> {
+        let health = health_check(db.pool()).await?;
+        if !health.is_healthy {
+            let reason = health.error_message.clone().unwrap_or_else(|| format!("响应时间 {}", health.formatted_response_time()));
+            report.push(IssueKind::DatabaseUnhealthy, None, format!("数据库连接异常: {}", reason));
+            return Ok(());
+        }
+
+        if !db.check_integrity().await? {
+            report.push(IssueKind::IntegrityFailed, None, "数据库完整性检查失败");
+        }
+
+        if migrations::needs_migration(db.pool()).await? {
+            report.push(IssueKind::MigrationPending, None, "数据库有未执行的迁移");
+            return Ok(());
+        }
+        for error in migrations::validate_migrations(db.pool()).await? {
+            report.push(IssueKind::MigrationInvalid, None, error);
+        }
+        if report.has(IssueKind::MigrationInvalid) {
+            return Ok(());
+        }
+
+        for table in ["notes_fts", "notes_trigram"] {
+            let check = format!("INSERT INTO {table}({table}) VALUES('integrity-check')");
+            if let Err(e) = sqlx::query(&check).execute(db.pool()).await {
+                report.push(IssueKind::SearchIndexCorrupt, None, format!("全文索引 {} 不一致: {}", table, e));
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_files(
+        &self,
+        config: &WorkspaceConfig,
+        indexed: Option<Vec<(String, String, String)>>,
+        trashed: Vec<(String, String, String)>,
+        report: &mut DoctorReport,
+    ) {
+        let files = collect_markdown_files(&self.root, &config.excluded_dirs());
+        report.files = files.len();
+
+        // 文件路径 -> (标题, 校验和, 内容)
+        let mut parsed = BTreeMap::new();
+        for path in files {
+            let result = match tokio::fs::read_to_string(self.root.join(&path)).await {
+                Ok(content) => self.parser.parse(&content, path.clone()).map(|note| (note, content)),
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Ok((note, content)) => {
+                    parsed.insert(path, (note.title, note.checksum, content));
+                }
+                Err(e) => report.push(IssueKind::InvalidFrontmatter, Some(path), e.to_string()),
+            }
+        }
+
+        if let Some(rows) = indexed {
+            report.notes = rows.len();
+            let mut hashes: HashMap<PathBuf, String> = HashMap::new();
+            for (_, file_path, file_hash) in rows {
+                hashes.insert(PathBuf::from(file_path), file_hash);
+            }
+            for (path, (_, checksum, _)) in &parsed {
+                match hashes.get(path) {
+                    None => report.push(IssueKind::UnindexedFile, Some(path.clone()), "文件未被索引"),
+                    Some(hash) if hash != checksum => {
+                        report.push(IssueKind::ChecksumDrift, Some(path.clone()), "文件内容与索引不一致")
+                    }
+                    Some(_) => {}
+                }
+            }
+            let mut missing: Vec<&PathBuf> = hashes
+                .keys()
+                .filter(|path| !parsed.contains_key(*path) && !self.root.join(path).exists())
+                .collect();
+            missing.sort();
+            for path in missing {
+                report.push(IssueKind::MissingFile, Some(path.clone()), "索引中的笔记文件已不存在");
+            }
+        }
+
+        // 链接按磁盘上的文件解析，不依赖索引是否最新；指向回收站中笔记的链接不算断链
+        let mut links = LinkIndex::build(parsed.iter().map(|(path, (title, _, content))| {
+            (path.to_string_lossy().to_string(), path.clone(), title.clone(), content.clone())
+        }));
+        for (id, original_path, title) in trashed {
+            if !parsed.contains_key(Path::new(&original_path)) {
+                links.register_trashed_note(id, PathBuf::from(original_path), title);
+            }
+        }
+        for broken in links.get_broken_links(None) {
+            let reason = match broken.kind {
+                BrokenLinkKind::MissingNote => "找不到目标笔记",
+                BrokenLinkKind::MissingHeading => "目标笔记中没有这个标题",
+                BrokenLinkKind::MissingBlock => "目标笔记中没有这个块",
+            };
+            report.push(
+                IssueKind::BrokenLink,
+                Some(PathBuf::from(broken.source_note_path)),
+                format!("第 {} 行: {} {}", broken.link.line_number, broken.link.raw, reason),
+            );
+        }
+        for ambiguous in links.get_ambiguous_links(None) {
+            report.push(
+                IssueKind::AmbiguousLink,
+                Some(PathBuf::from(ambiguous.source_note_path)),
+                format!(
+                    "第 {} 行: {} 可以指向多篇笔记: {}",
+                    ambiguous.link.line_number,
+                    ambiguous.link.raw,
+                    ambiguous.candidate_paths.join(", ")
+                ),
+            );
+        }
+
+        let mut titles: BTreeMap<String, Vec<&PathBuf>> = BTreeMap::new();
+        for (path, (title, _, _)) in &parsed {
+            titles.entry(title.to_lowercase()).or_default().push(path);
+        }
+        for paths in titles.values().filter(|paths| paths.len() > 1) {
+            let title = &parsed[paths[0]].0;
+            let list: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+            report.push(
+                IssueKind::DuplicateTitle,
+                Some(paths[0].clone()),
+                format!("标题 \"{}\" 重复: {}", title, list.join(", ")),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn kinds(report: &DoctorReport) -> Vec<IssueKind> {
+        report.issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[tokio::test]
+    async fn test_doctor_check_and_fix() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        std::fs::write(root.join("notes/a.md"), "# A\n\nsee [[B]] and [[Nowhere]]").unwrap();
+        std::fs::write(root.join("notes/b.md"), "# B\n\nbody").unwrap();
+
+        let doctor = Doctor::new(root);
+        let report = doctor.check().await.unwrap();
+        assert_eq!(kinds(&report), vec![IssueKind::DatabaseMissing, IssueKind::BrokenLink]);
+        assert_eq!(report.files, 2);
+
+        let fixed = doctor.fix(&report).await.unwrap();
+        assert_eq!(fixed.len(), 2);
+        let report = doctor.check().await.unwrap();
+        assert_eq!(kinds(&report), vec![IssueKind::BrokenLink]);
+        assert_eq!(report.notes, 2);
+        assert_eq!(report.errors(), 0);
+
+        // 文件漂移、新增、删除，以及前言错误和重复标题
+        std::fs::write(root.join("notes/a.md"), "# A\n\nchanged").unwrap();
+        std::fs::write(root.join("notes/c.md"), "# b\n").unwrap();
+        std::fs::remove_file(root.join("notes/b.md")).unwrap();
+        std::fs::write(root.join("notes/bad.md"), "---\ntitle: [oops\n---\nbody").unwrap();
+        let report = doctor.check().await.unwrap();
+        assert_eq!(
+            kinds(&report),
+            vec![
+                IssueKind::InvalidFrontmatter,
+                IssueKind::ChecksumDrift,
+                IssueKind::UnindexedFile,
+                IssueKind::MissingFile,
+            ]
+        );
+        assert_eq!(report.errors(), 1);
+
+        doctor.fix(&report).await.unwrap();
+        let report = doctor.check().await.unwrap();
+        assert_eq!(kinds(&report), vec![IssueKind::InvalidFrontmatter]);
+
+        std::fs::write(root.join("notes/d.md"), "# B\n").unwrap();
+        let report = doctor.check().await.unwrap();
+        assert!(kinds(&report).contains(&IssueKind::DuplicateTitle));
+
+        // 别名与其他笔记的标题相同
+        std::fs::write(root.join("notes/e.md"), "---\naliases: [A]\n---\n[[A]]\n").unwrap();
+        let report = doctor.check().await.unwrap();
+        assert!(kinds(&report).contains(&IssueKind::AmbiguousLink));
+    }
+}
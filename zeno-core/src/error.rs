@@ -25,7 +25,10 @@ pub enum Error {
     Uuid(#[from] uuid::Error),
 
     #[error("解析错误: {0}")]
-    Parse(String),
+    Parse(ParseError),
+
+    #[error("查询语法错误: {0}")]
+    Query(#[from] crate::storage::QuerySyntaxError),
 
     #[error("验证错误: {0}")]
     Validation(String),
@@ -70,10 +73,44 @@ pub enum Error {
     Multiple(Vec<Error>),
 }
 
+/// 解析错误详情，可携带出错位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// 错误信息
+    pub message: String,
+    /// 出错行号（从 1 开始）
+    pub line: Option<usize>,
+    /// 出错列号（从 1 开始）
+    pub column: Option<usize>,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{} (第 {} 行, 第 {} 列)", self.message, line, column),
+            (Some(line), None) => write!(f, "{} (第 {} 行)", self.message, line),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
 impl Error {
     /// 创建解析错误
     pub fn parse<T: std::fmt::Display>(msg: T) -> Self {
-        Self::Parse(msg.to_string())
+        Self::Parse(ParseError {
+            message: msg.to_string(),
+            line: None,
+            column: None,
+        })
+    }
+
+    /// 创建带位置信息的解析错误
+    pub fn parse_at<T: std::fmt::Display>(msg: T, line: usize, column: usize) -> Self {
+        Self::Parse(ParseError {
+            message: msg.to_string(),
+            line: Some(line),
+            column: Some(column),
+        })
     }
 
     /// 创建验证错误
@@ -143,7 +180,7 @@ impl Error {
     pub fn is_user_error(&self) -> bool {
         matches!(
             self,
-            Error::Validation(_) | Error::Config(_) | Error::Parse(_) | Error::NotFound(_)
+            Error::Validation(_) | Error::Config(_) | Error::Parse(_) | Error::Query(_) | Error::NotFound(_)
         )
     }
 
@@ -166,6 +203,7 @@ impl Error {
             Error::FileWatcher(_) => "FILE_WATCHER_ERROR",
             Error::Uuid(_) => "UUID_ERROR",
             Error::Parse(_) => "PARSE_ERROR",
+            Error::Query(_) => "QUERY_SYNTAX_ERROR",
             Error::Validation(_) => "VALIDATION_ERROR",
             Error::Config(_) => "CONFIG_ERROR",
             Error::NotFound(_) => "NOT_FOUND",
@@ -188,7 +226,7 @@ impl Error {
         match self {
             Error::NotFound(_) => 404,
             Error::Permission(_) => 403,
-            Error::Validation(_) | Error::Parse(_) => 400,
+            Error::Validation(_) | Error::Parse(_) | Error::Query(_) => 400,
             Error::Internal(_) | Error::Database(_) => 500,
             Error::Network(_) => 502,
             Error::Timeout => 504,
@@ -295,6 +333,10 @@ mod tests {
         assert_eq!(error.http_status(), 400);
         assert!(error.is_user_error());
         assert!(!error.is_fatal());
+
+        let error = Error::parse_at("前言格式错误", 3, 7);
+        assert_eq!(error.error_code(), "PARSE_ERROR");
+        assert!(error.to_string().contains("第 3 行, 第 7 列"));
     }
 
     #[test]
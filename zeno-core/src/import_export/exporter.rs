@@ -0,0 +1,891 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::model::*;
+use super::{ProgressFn, TransferProgress};
+use crate::indexer::collect_markdown_files;
+use crate::models::{Note, NoteStatus, UNTITLED};
+use crate::parser::MarkdownParser;
+
+/// 匹配图片 ![alt](path)
+static IMAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[.*?\]\(([^)]+)\)").unwrap());
+
+/// 匹配链接 [text](path)
+static LINK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[.*?\]\(([^)]+)\)").unwrap());
+
+#[async_trait]
+pub trait Exporter: Send + Sync {
+    /// 获取导出器的名称
+    fn name(&self) -> &str;
+
+    /// 获取导出器的版本
+    fn version(&self) -> &str;
+
+    /// 获取支持的输出格式
+    fn supported_formats(&self) -> Vec<&str>;
+
+    /// 验证目标路径是否有效
+    async fn validate_target(&self, target_path: &str) -> Result<bool>;
+
+    /// 预览导出内容
+    async fn preview_export(&self, config: &ExportConfig) -> Result<ExportPreview>;
+
+    /// 执行导出，每处理一个笔记调用一次 `on_progress`
+    async fn export(&self, config: &ExportConfig, on_progress: ProgressFn<'_>) -> Result<ExportResult>;
+
+    /// 处理单个笔记
+    async fn process_note(&self, note: &Note, config: &ExportConfig) -> Result<ExportedFile>;
+
+    /// 转换内容格式
+    fn convert_content(&self, content: &str, from_format: &str, to_format: &str) -> Result<String>;
+
+    /// 处理链接重写
+    fn rewrite_links(&self, content: &str, link_mappings: &HashMap<String, String>) -> String;
+
+    /// 嵌入资源文件
+    async fn embed_assets(&self, content: &str, base_path: &Path) -> Result<String>;
+
+    /// 过滤笔记
+    fn filter_notes(&self, notes: &[Note], filter_options: &FilterOptions) -> Vec<Note>;
+}
+
+pub struct BaseExporter {
+    pub name: String,
+    pub version: String,
+}
+
+impl BaseExporter {
+    pub fn new(name: String, version: String) -> Self {
+        Self { name, version }
+    }
+
+    /// 递归扫描工作空间获取所有笔记，跳过隐藏目录和 `excluded_dirs`
+    pub async fn scan_workspace(&self, workspace_path: &str, excluded_dirs: &[PathBuf]) -> Result<Vec<Note>> {
+        let workspace = Path::new(workspace_path);
+        if !workspace.exists() {
+            return Err(anyhow!("Workspace path does not exist: {}", workspace_path));
+        }
+
+        let mut notes = Vec::new();
+        for relative in collect_markdown_files(workspace, excluded_dirs) {
+            let path = workspace.join(&relative);
+            match self.load_note(&path).await {
+                Ok(note) => notes.push(note),
+                Err(e) => log::warn!("跳过无法解析的笔记 {}: {}", path.display(), e),
+            }
+        }
+        Ok(notes)
+    }
+
+    fn is_markdown_file(&self, path: &Path) -> bool {
+        if let Some(ext) = path.extension() {
+            if let Some(ext_str) = ext.to_str() {
+                return matches!(ext_str.to_lowercase().as_str(), "md" | "markdown" | "txt");
+            }
+        }
+        false
+    }
+
+    async fn load_note(&self, path: &Path) -> Result<Note> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let metadata = tokio::fs::metadata(path).await?;
+
+        let mut note = MarkdownParser::new().parse(&content, path.to_path_buf())?;
+        if note.title == UNTITLED {
+            note.title = path.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+        }
+
+        // 更新时间戳
+        note.created_at = metadata.created()
+            .map(chrono::DateTime::from)
+            .unwrap_or_else(|_| Utc::now());
+        note.modified_at = metadata.modified()
+            .map(chrono::DateTime::from)
+            .unwrap_or_else(|_| Utc::now());
+
+        Ok(note)
+    }
+
+    /// 创建输出目录结构
+    pub async fn create_output_structure(&self, target_path: &str, preserve_structure: bool, notes: &[Note], workspace_root: &Path) -> Result<()> {
+        let target_root = Path::new(target_path);
+
+        if !target_root.exists() {
+            tokio::fs::create_dir_all(target_root).await?;
+        }
+
+        if !preserve_structure {
+            return Ok(());
+        }
+
+        for note in notes {
+            let note_path = Path::new(&note.path);
+            if let Ok(relative_path) = note_path.strip_prefix(workspace_root) {
+                if let Some(parent) = relative_path.parent() {
+                    let target_dir = target_root.join(parent);
+                    if !target_dir.exists() {
+                        tokio::fs::create_dir_all(&target_dir).await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 生成输出文件路径
+    pub fn generate_output_path(&self, note: &Note, workspace_root: &Path, target_path: &str, preserve_structure: bool, format: &str) -> Result<PathBuf> {
+        let target_root = Path::new(target_path);
+        let note_path = Path::new(&note.path);
+
+        let output_filename = self.generate_output_filename(note, format);
+
+        if preserve_structure {
+            if let Ok(relative_path) = note_path.strip_prefix(workspace_root) {
+                if let Some(parent) = relative_path.parent() {
+                    return Ok(target_root.join(parent).join(output_filename));
+                }
+            }
+        }
+
+        Ok(target_root.join(output_filename))
+    }
+
+    fn generate_output_filename(&self, note: &Note, format: &str) -> String {
+        let base_name = Path::new(&note.path)
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+
+        format!("{}.{}", base_name, format)
+    }
+
+    /// 应用过滤器
+    pub fn apply_filters(&self, notes: &[Note], filter_options: &FilterOptions) -> Vec<Note> {
+        notes.iter()
+            .filter(|note| self.passes_filter(note, filter_options))
+            .cloned()
+            .collect()
+    }
+
+    fn passes_filter(&self, note: &Note, filter_options: &FilterOptions) -> bool {
+        // 状态过滤：已删除的笔记从不导出
+        match note.status {
+            NoteStatus::Deleted => return false,
+            NoteStatus::Draft if filter_options.exclude_drafts => return false,
+            NoteStatus::Archived if !filter_options.include_archived => return false,
+            _ => {}
+        }
+
+        // 日期范围过滤
+        if let Some(date_range) = &filter_options.date_range {
+            if note.modified_at < date_range.start || note.modified_at > date_range.end {
+                return false;
+            }
+        }
+
+        // 标签过滤：匹配标签本身或其子标签，`rust` 匹配 `rust` 和 `rust/async`
+        if !filter_options.tag_filter.is_empty() {
+            let has_matching_tag = filter_options.tag_filter.iter().any(|filter_tag| {
+                note.frontmatter.tags.iter().any(|note_tag| tag_matches(note_tag, filter_tag))
+            });
+            if !has_matching_tag {
+                return false;
+            }
+        }
+
+        // 路径过滤
+        if !filter_options.path_filter.is_empty() {
+            let matches_path = filter_options.path_filter.iter()
+                .any(|filter_path| note.path.to_string_lossy().contains(filter_path));
+            if !matches_path {
+                return false;
+            }
+        }
+
+        // 内容过滤
+        if let Some(content_filter) = &filter_options.content_filter {
+            if !note.content.contains(content_filter) {
+                return false;
+            }
+        }
+
+        // 最小字数过滤
+        if let Some(min_words) = filter_options.minimum_word_count {
+            let word_count = note.content.split_whitespace().count() as u32;
+            if word_count < min_words {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// 处理附件文件
+    pub async fn process_attachments(&self, notes: &[Note], target_path: &str, include_attachments: bool) -> Result<Vec<ExportedFile>> {
+        if !include_attachments {
+            return Ok(Vec::new());
+        }
+
+        let mut attachment_files = Vec::new();
+        let attachment_dir = Path::new(target_path).join("attachments");
+
+        for note in notes {
+            let attachments = self.extract_attachments_from_note(note)?;
+
+            for attachment_path in attachments {
+                let source_path = Path::new(&attachment_path);
+                if source_path.is_file() {
+                    let filename = source_path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy();
+                    let target_attachment_path = attachment_dir.join(&*filename);
+
+                    tokio::fs::create_dir_all(&attachment_dir).await?;
+                    tokio::fs::copy(source_path, &target_attachment_path).await?;
+
+                    let metadata = tokio::fs::metadata(source_path).await?;
+
+                    attachment_files.push(ExportedFile {
+                        source_path: attachment_path,
+                        output_path: target_attachment_path.to_string_lossy().to_string(),
+                        file_type: ExportFileType::Attachment,
+                        original_size: metadata.len(),
+                        exported_size: metadata.len(),
+                        status: ExportStatus::Success,
+                        transformations: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        Ok(attachment_files)
+    }
+
+    fn extract_attachments_from_note(&self, note: &Note) -> Result<Vec<String>> {
+        let mut attachments = Vec::new();
+        let note_dir = Path::new(&note.path).parent().unwrap_or(Path::new("."));
+
+        for caps in IMAGE_REGEX.captures_iter(&note.content) {
+            if let Some(path) = caps.get(1) {
+                let attachment_path = path.as_str();
+                if !attachment_path.starts_with("http") {
+                    // 相对路径，需要解析为绝对路径
+                    let full_path = note_dir.join(attachment_path);
+                    attachments.push(full_path.to_string_lossy().to_string());
+                }
+            }
+        }
+
+        for caps in LINK_REGEX.captures_iter(&note.content) {
+            if let Some(path) = caps.get(1) {
+                let link_path = path.as_str();
+                if !link_path.starts_with("http") && !link_path.ends_with(".md") {
+                    // 可能是本地文件链接
+                    let full_path = note_dir.join(link_path);
+                    let full_path_str = full_path.to_string_lossy().to_string();
+                    if full_path.is_file() && !self.is_markdown_file(&full_path) && !attachments.contains(&full_path_str) {
+                        attachments.push(full_path_str);
+                    }
+                }
+            }
+        }
+
+        Ok(attachments)
+    }
+
+    /// 生成导出统计
+    pub fn generate_export_stats(&self, files: &[ExportedFile], start_time: Instant) -> ExportResult {
+        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+
+        let mut result = ExportResult::new();
+        result.processing_time_ms = processing_time_ms;
+
+        let mut total_original_size = 0u64;
+        let mut total_exported_size = 0u64;
+
+        for file in files {
+            total_original_size += file.original_size;
+            total_exported_size += file.exported_size;
+
+            match file.status {
+                ExportStatus::Success => result.exported_count += 1,
+                ExportStatus::Skipped => result.skipped_count += 1,
+                ExportStatus::Failed => result.failed_count += 1,
+                ExportStatus::Warning => {
+                    result.exported_count += 1;
+                    result.warnings.push(format!("Warning processing {}", file.source_path));
+                }
+            }
+        }
+
+        result.output_files = files.to_vec();
+        result.total_size = total_exported_size;
+        result.success = result.failed_count == 0;
+
+        if total_original_size > 0 {
+            result.compression_ratio = Some(
+                (total_exported_size as f64) / (total_original_size as f64)
+            );
+        }
+
+        result
+    }
+
+    /// 创建导出目录树
+    pub fn create_export_directory_tree(&self, notes: &[Note], workspace_root: &Path) -> Vec<ExportDirectoryNode> {
+        let mut root_nodes = Vec::new();
+        let mut path_counts: HashMap<PathBuf, (u32, u32)> = HashMap::new(); // (note_count, attachment_count)
+
+        // 计算每个目录的文件数量
+        for note in notes {
+            let note_path = Path::new(&note.path);
+            if let Ok(relative_path) = note_path.strip_prefix(workspace_root) {
+                let mut current_path = workspace_root.to_path_buf();
+                let attachments = self.extract_attachments_from_note(note).map(|a| a.len() as u32).unwrap_or(0);
+
+                for component in relative_path.components() {
+                    current_path.push(component);
+                    let (note_count, attachment_count) = path_counts.entry(current_path.clone()).or_insert((0, 0));
+                    *note_count += 1;
+                    *attachment_count += attachments;
+                }
+            }
+        }
+
+        // 构建目录树（简化版本）
+        for (path, (note_count, attachment_count)) in path_counts {
+            if path.parent() == Some(workspace_root) {
+                root_nodes.push(ExportDirectoryNode {
+                    name: path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    note_count,
+                    attachment_count,
+                    children: Vec::new(), // 简化版本，不递归构建子节点
+                });
+            }
+        }
+        root_nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        root_nodes
+    }
+
+    /// 预览按过滤器导出的笔记
+    pub async fn default_preview_export(&self, config: &ExportConfig, filtered_notes: &[Note], total_notes: usize) -> ExportPreview {
+        let workspace_root = Path::new(&config.source_workspace);
+        let total_attachments = filtered_notes
+            .iter()
+            .filter_map(|note| self.extract_attachments_from_note(note).ok())
+            .map(|attachments| attachments.len() as u32)
+            .sum();
+
+        ExportPreview {
+            total_notes: total_notes as u32,
+            total_attachments,
+            estimated_size: filtered_notes.iter().map(|n| n.content.len() as u64).sum(),
+            filtered_notes: filtered_notes.len() as u32,
+            warnings: Vec::new(),
+            structure: self.create_export_directory_tree(filtered_notes, workspace_root),
+        }
+    }
+
+    /// 逐个导出笔记，失败的笔记记录为 `Failed` 并收集错误信息
+    pub async fn export_notes<E: Exporter + ?Sized>(
+        &self,
+        exporter: &E,
+        notes: &[Note],
+        config: &ExportConfig,
+        on_progress: ProgressFn<'_>,
+    ) -> (Vec<ExportedFile>, Vec<String>) {
+        let mut exported_files = Vec::new();
+        let mut errors = Vec::new();
+        let total = notes.len();
+
+        for (i, note) in notes.iter().enumerate() {
+            match exporter.process_note(note, config).await {
+                Ok(exported_file) => exported_files.push(exported_file),
+                Err(e) => {
+                    errors.push(format!("{}: {}", note.path.display(), e));
+                    exported_files.push(ExportedFile {
+                        source_path: note.path.to_string_lossy().to_string(),
+                        output_path: String::new(),
+                        file_type: ExportFileType::Note,
+                        original_size: note.content.len() as u64,
+                        exported_size: 0,
+                        status: ExportStatus::Failed,
+                        transformations: Vec::new(),
+                    });
+                }
+            }
+            on_progress(TransferProgress { current: i + 1, total, path: &note.path });
+        }
+
+        (exported_files, errors)
+    }
+
+    /// 默认的内容转换实现
+    pub fn default_convert_content(&self, content: &str, _from_format: &str, _to_format: &str) -> Result<String> {
+        // 基础实现：直接返回原内容
+        Ok(content.to_string())
+    }
+
+    /// 默认的链接重写实现
+    pub fn default_rewrite_links(&self, content: &str, link_mappings: &HashMap<String, String>) -> String {
+        let mut result = content.to_string();
+
+        for (from_link, to_link) in link_mappings {
+            result = result.replace(from_link, to_link);
+        }
+
+        result
+    }
+
+    /// 默认的资源嵌入实现
+    pub async fn default_embed_assets(&self, content: &str, _base_path: &Path) -> Result<String> {
+        // 基础实现：不进行资源嵌入
+        Ok(content.to_string())
+    }
+}
+
+/// 标签相同或为其子标签，忽略大小写
+fn tag_matches(note_tag: &str, filter_tag: &str) -> bool {
+    let note_tag = note_tag.trim_start_matches('#').to_lowercase();
+    let filter_tag = filter_tag.trim_start_matches('#').to_lowercase();
+    note_tag == filter_tag || note_tag.starts_with(&format!("{}/", filter_tag))
+}
+
+/// 校验输出路径的父目录存在或可以创建
+async fn validate_output_path(target_path: &str) -> Result<bool> {
+    let path = Path::new(target_path);
+    if let Some(parent) = path.parent() {
+        Ok(parent.exists() || tokio::fs::create_dir_all(parent).await.is_ok())
+    } else {
+        Ok(true)
+    }
+}
+
+// 基础导出器实现
+pub struct MarkdownExporter {
+    base: BaseExporter,
+}
+
+impl MarkdownExporter {
+    pub fn new() -> Self {
+        Self {
+            base: BaseExporter::new("Markdown Exporter".to_string(), "1.0.0".to_string()),
+        }
+    }
+}
+
+impl Default for MarkdownExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Exporter for MarkdownExporter {
+    fn name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn version(&self) -> &str {
+        &self.base.version
+    }
+
+    fn supported_formats(&self) -> Vec<&str> {
+        vec!["md", "markdown"]
+    }
+
+    async fn validate_target(&self, target_path: &str) -> Result<bool> {
+        validate_output_path(target_path).await
+    }
+
+    async fn preview_export(&self, config: &ExportConfig) -> Result<ExportPreview> {
+        let notes = self.base.scan_workspace(&config.source_workspace, &config.options.excluded_dirs).await?;
+        let filtered_notes = self.filter_notes(&notes, &config.options.filter_options);
+        Ok(self.base.default_preview_export(config, &filtered_notes, notes.len()).await)
+    }
+
+    async fn export(&self, config: &ExportConfig, on_progress: ProgressFn<'_>) -> Result<ExportResult> {
+        let start_time = Instant::now();
+        let notes = self.base.scan_workspace(&config.source_workspace, &config.options.excluded_dirs).await?;
+        let filtered_notes = self.filter_notes(&notes, &config.options.filter_options);
+
+        // 创建输出目录结构
+        let workspace_root = Path::new(&config.source_workspace);
+        self.base.create_output_structure(&config.target_path, config.options.preserve_structure, &filtered_notes, workspace_root).await?;
+
+        let (mut exported_files, errors) = self.base.export_notes(self, &filtered_notes, config, on_progress).await;
+
+        // 处理附件
+        if config.options.include_attachments {
+            let attachment_files = self.base.process_attachments(&filtered_notes, &config.target_path, true).await?;
+            exported_files.extend(attachment_files);
+        }
+
+        let mut result = self.base.generate_export_stats(&exported_files, start_time);
+        result.errors = errors;
+        Ok(result)
+    }
+
+    async fn process_note(&self, note: &Note, config: &ExportConfig) -> Result<ExportedFile> {
+        let workspace_root = Path::new(&config.source_workspace);
+        let output_path = self.base.generate_output_path(note, workspace_root, &config.target_path, config.options.preserve_structure, "md")?;
+
+        let mut content = note.content.clone();
+        let mut transformations = Vec::new();
+
+        if config.options.convert_links {
+            let link_mappings = HashMap::new(); // 简化实现
+            let new_content = self.rewrite_links(&content, &link_mappings);
+            if new_content != content {
+                transformations.push(ExportTransformation {
+                    transformation_type: ExportTransformationType::LinkRewriting,
+                    description: "Rewritten internal links".to_string(),
+                    from_format: "original".to_string(),
+                    to_format: "markdown".to_string(),
+                });
+                content = new_content;
+            }
+        }
+
+        // 写入文件
+        tokio::fs::write(&output_path, &content).await?;
+
+        Ok(ExportedFile {
+            source_path: note.path.to_string_lossy().to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            file_type: ExportFileType::Note,
+            original_size: note.content.len() as u64,
+            exported_size: content.len() as u64,
+            status: ExportStatus::Success,
+            transformations,
+        })
+    }
+
+    fn convert_content(&self, content: &str, from_format: &str, to_format: &str) -> Result<String> {
+        self.base.default_convert_content(content, from_format, to_format)
+    }
+
+    fn rewrite_links(&self, content: &str, link_mappings: &HashMap<String, String>) -> String {
+        self.base.default_rewrite_links(content, link_mappings)
+    }
+
+    async fn embed_assets(&self, content: &str, base_path: &Path) -> Result<String> {
+        self.base.default_embed_assets(content, base_path).await
+    }
+
+    fn filter_notes(&self, notes: &[Note], filter_options: &FilterOptions) -> Vec<Note> {
+        self.base.apply_filters(notes, filter_options)
+    }
+}
+
+// HTML 导出器实现
+pub struct HtmlExporter {
+    base: BaseExporter,
+}
+
+impl HtmlExporter {
+    pub fn new() -> Self {
+        Self {
+            base: BaseExporter::new("HTML Exporter".to_string(), "1.0.0".to_string()),
+        }
+    }
+}
+
+impl Default for HtmlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Exporter for HtmlExporter {
+    fn name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn version(&self) -> &str {
+        &self.base.version
+    }
+
+    fn supported_formats(&self) -> Vec<&str> {
+        vec!["html", "htm"]
+    }
+
+    async fn validate_target(&self, target_path: &str) -> Result<bool> {
+        validate_output_path(target_path).await
+    }
+
+    async fn preview_export(&self, config: &ExportConfig) -> Result<ExportPreview> {
+        let notes = self.base.scan_workspace(&config.source_workspace, &config.options.excluded_dirs).await?;
+        let filtered_notes = self.filter_notes(&notes, &config.options.filter_options);
+        Ok(self.base.default_preview_export(config, &filtered_notes, notes.len()).await)
+    }
+
+    async fn export(&self, config: &ExportConfig, on_progress: ProgressFn<'_>) -> Result<ExportResult> {
+        let start_time = Instant::now();
+        let notes = self.base.scan_workspace(&config.source_workspace, &config.options.excluded_dirs).await?;
+        let filtered_notes = self.filter_notes(&notes, &config.options.filter_options);
+
+        // 创建输出目录结构
+        let workspace_root = Path::new(&config.source_workspace);
+        self.base.create_output_structure(&config.target_path, config.options.preserve_structure, &filtered_notes, workspace_root).await?;
+
+        let (mut exported_files, errors) = self.base.export_notes(self, &filtered_notes, config, on_progress).await;
+
+        // 处理附件
+        if config.options.include_attachments {
+            let attachment_files = self.base.process_attachments(&filtered_notes, &config.target_path, true).await?;
+            exported_files.extend(attachment_files);
+        }
+
+        let mut result = self.base.generate_export_stats(&exported_files, start_time);
+        result.errors = errors;
+        Ok(result)
+    }
+
+    async fn process_note(&self, note: &Note, config: &ExportConfig) -> Result<ExportedFile> {
+        let workspace_root = Path::new(&config.source_workspace);
+        let output_path = self.base.generate_output_path(note, workspace_root, &config.target_path, config.options.preserve_structure, "html")?;
+
+        // 将 Markdown 转换为 HTML
+        let html_content = self.markdown_to_html(&note.title, &note.content)?;
+
+        // 写入文件
+        tokio::fs::write(&output_path, &html_content).await?;
+
+        Ok(ExportedFile {
+            source_path: note.path.to_string_lossy().to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            file_type: ExportFileType::Note,
+            original_size: note.content.len() as u64,
+            exported_size: html_content.len() as u64,
+            status: ExportStatus::Success,
+            transformations: vec![ExportTransformation {
+                transformation_type: ExportTransformationType::FormatConversion,
+                description: "Converted Markdown to HTML".to_string(),
+                from_format: "markdown".to_string(),
+                to_format: "html".to_string(),
+            }],
+        })
+    }
+
+    fn convert_content(&self, content: &str, from_format: &str, to_format: &str) -> Result<String> {
+        if from_format == "markdown" && to_format == "html" {
+            self.markdown_to_html(UNTITLED, content)
+        } else {
+            self.base.default_convert_content(content, from_format, to_format)
+        }
+    }
+
+    fn rewrite_links(&self, content: &str, link_mappings: &HashMap<String, String>) -> String {
+        self.base.default_rewrite_links(content, link_mappings)
+    }
+
+    async fn embed_assets(&self, content: &str, base_path: &Path) -> Result<String> {
+        self.base.default_embed_assets(content, base_path).await
+    }
+
+    fn filter_notes(&self, notes: &[Note], filter_options: &FilterOptions) -> Vec<Note> {
+        self.base.apply_filters(notes, filter_options)
+    }
+}
+
+impl HtmlExporter {
+    fn markdown_to_html(&self, title: &str, markdown: &str) -> Result<String> {
+        use pulldown_cmark::{html, Options, Parser};
+
+        let parser = Parser::new_ext(markdown, Options::all());
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, parser);
+
+        // 包装在完整的 HTML 文档中
+        let full_html = format!(
+            r#"<!DOCTYPE html>
+<html lang="zh">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{}</title>
+    <style>
+        body {{ font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif; line-height: 1.6; max-width: 800px; margin: 0 auto; padding: 20px; }}
+        h1, h2, h3, h4, h5, h6 {{ color: #333; }}
+        code {{ background: #f4f4f4; padding: 2px 4px; border-radius: 3px; }}
+        pre {{ background: #f4f4f4; padding: 10px; border-radius: 5px; overflow-x: auto; }}
+        blockquote {{ border-left: 4px solid #ddd; margin: 0; padding-left: 20px; color: #666; }}
+    </style>
+</head>
+<body>
+{}
+</body>
+</html>"#,
+            escape_html(title),
+            html_output
+        );
+
+        Ok(full_html)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// JSON 导出器实现
+pub struct JsonExporter {
+    base: BaseExporter,
+}
+
+impl JsonExporter {
+    pub fn new() -> Self {
+        Self {
+            base: BaseExporter::new("JSON Exporter".to_string(), "1.0.0".to_string()),
+        }
+    }
+}
+
+impl Default for JsonExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Exporter for JsonExporter {
+    fn name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn version(&self) -> &str {
+        &self.base.version
+    }
+
+    fn supported_formats(&self) -> Vec<&str> {
+        vec!["json"]
+    }
+
+    async fn validate_target(&self, target_path: &str) -> Result<bool> {
+        validate_output_path(target_path).await
+    }
+
+    async fn preview_export(&self, config: &ExportConfig) -> Result<ExportPreview> {
+        let notes = self.base.scan_workspace(&config.source_workspace, &config.options.excluded_dirs).await?;
+        let filtered_notes = self.filter_notes(&notes, &config.options.filter_options);
+
+        Ok(ExportPreview {
+            structure: Vec::new(),
+            ..self.base.default_preview_export(config, &filtered_notes, notes.len()).await
+        })
+    }
+
+    async fn export(&self, config: &ExportConfig, on_progress: ProgressFn<'_>) -> Result<ExportResult> {
+        let start_time = Instant::now();
+        let notes = self.base.scan_workspace(&config.source_workspace, &config.options.excluded_dirs).await?;
+        let filtered_notes = self.filter_notes(&notes, &config.options.filter_options);
+
+        // 将所有笔记序列化为 JSON
+        let json_content = serde_json::to_string_pretty(&filtered_notes)?;
+
+        // 写入单个 JSON 文件
+        let output_path = Path::new(&config.target_path);
+        tokio::fs::write(output_path, &json_content).await?;
+        on_progress(TransferProgress { current: 1, total: 1, path: output_path });
+
+        let exported_file = ExportedFile {
+            source_path: config.source_workspace.clone(),
+            output_path: config.target_path.clone(),
+            file_type: ExportFileType::Archive,
+            original_size: filtered_notes.iter().map(|n| n.content.len() as u64).sum(),
+            exported_size: json_content.len() as u64,
+            status: ExportStatus::Success,
+            transformations: vec![ExportTransformation {
+                transformation_type: ExportTransformationType::FormatConversion,
+                description: "Exported all notes as JSON".to_string(),
+                from_format: "markdown".to_string(),
+                to_format: "json".to_string(),
+            }],
+        };
+
+        Ok(self.base.generate_export_stats(&[exported_file], start_time))
+    }
+
+    async fn process_note(&self, _note: &Note, _config: &ExportConfig) -> Result<ExportedFile> {
+        // JSON 导出器一次性处理所有笔记，不单独处理
+        Err(anyhow!("JSON exporter processes all notes at once"))
+    }
+
+    fn convert_content(&self, content: &str, from_format: &str, to_format: &str) -> Result<String> {
+        self.base.default_convert_content(content, from_format, to_format)
+    }
+
+    fn rewrite_links(&self, content: &str, link_mappings: &HashMap<String, String>) -> String {
+        self.base.default_rewrite_links(content, link_mappings)
+    }
+
+    async fn embed_assets(&self, content: &str, base_path: &Path) -> Result<String> {
+        self.base.default_embed_assets(content, base_path).await
+    }
+
+    fn filter_notes(&self, notes: &[Note], filter_options: &FilterOptions) -> Vec<Note> {
+        self.base.apply_filters(notes, filter_options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tag_filter_matches_nested_tags_only() {
+        assert!(tag_matches("rust", "rust"));
+        assert!(tag_matches("Rust/async", "rust"));
+        assert!(tag_matches("rust", "#rust"));
+        assert!(!tag_matches("trust", "rust"));
+        assert!(!tag_matches("rustacean", "rust"));
+    }
+
+    #[tokio::test]
+    async fn test_html_export_filters_by_tag_and_keeps_structure() {
+        let workspace = tempdir().unwrap();
+        let out = tempdir().unwrap();
+        std::fs::create_dir_all(workspace.path().join("dev")).unwrap();
+        std::fs::create_dir_all(workspace.path().join("templates")).unwrap();
+        std::fs::write(workspace.path().join("dev/rust.md"), "---\ntitle: <Rust>\ntags: [rust]\n---\n# Rust\n").unwrap();
+        std::fs::write(workspace.path().join("other.md"), "---\ntags: [misc]\n---\nbody\n").unwrap();
+        std::fs::write(workspace.path().join("templates/t.md"), "---\ntags: [rust]\n---\n").unwrap();
+
+        let mut config = ExportConfig::new(
+            ExporterType::Html,
+            workspace.path().to_string_lossy().to_string(),
+            out.path().to_string_lossy().to_string(),
+        );
+        config.options.filter_options.tag_filter = vec!["rust".to_string()];
+        config.options.excluded_dirs = vec![PathBuf::from("templates")];
+
+        let result = HtmlExporter::new().export(&config, &|_| {}).await.unwrap();
+
+        assert_eq!(result.exported_count, 1);
+        let html = std::fs::read_to_string(out.path().join("dev/rust.html")).unwrap();
+        assert!(html.contains("<title>&lt;Rust&gt;</title>"));
+        assert!(!out.path().join("other.html").exists());
+        assert!(!out.path().join("templates").exists());
+    }
+}
@@ -0,0 +1,667 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use walkdir::WalkDir;
+
+use super::model::*;
+use super::{ProgressFn, TransferProgress};
+
+#[async_trait]
+pub trait Importer: Send + Sync {
+    /// 获取导入器的名称
+    fn name(&self) -> &str;
+
+    /// 获取导入器的版本
+    fn version(&self) -> &str;
+
+    /// 获取支持的文件扩展名
+    fn supported_extensions(&self) -> Vec<&str>;
+
+    /// 验证源路径是否有效
+    async fn validate_source(&self, source_path: &str) -> Result<bool>;
+
+    /// 预览导入内容
+    async fn preview_import(&self, config: &ImportConfig) -> Result<ImportPreview>;
+
+    /// 执行导入，每处理一个文件调用一次 `on_progress`
+    async fn import(&self, config: &ImportConfig, on_progress: ProgressFn<'_>) -> Result<ImportResult>;
+
+    /// 处理单个文件
+    async fn process_file(&self, file_path: &Path, config: &ImportConfig) -> Result<ImportedFile>;
+
+    /// 转换链接格式
+    fn convert_links(&self, content: &str, mappings: &HashMap<String, String>) -> String;
+
+    /// 转换标签格式
+    fn convert_tags(&self, content: &str) -> String;
+
+    /// 提取元数据
+    fn extract_metadata(&self, content: &str) -> Result<HashMap<String, String>>;
+
+    /// 检查文件冲突
+    async fn check_conflicts(&self, config: &ImportConfig) -> Result<Vec<FileConflict>>;
+}
+
+pub struct BaseImporter {
+    pub name: String,
+    pub version: String,
+}
+
+impl BaseImporter {
+    pub fn new(name: String, version: String) -> Self {
+        Self { name, version }
+    }
+
+    /// 递归扫描源目录获取所有支持的文件，跳过隐藏文件和目录（如 `.obsidian`）
+    pub async fn scan_source_directory(&self, source_path: &str, extensions: &[&str]) -> Result<Vec<PathBuf>> {
+        let source = Path::new(source_path);
+        if !source.exists() {
+            return Err(anyhow!("Source path does not exist: {}", source_path));
+        }
+
+        let mut files: Vec<PathBuf> = walk_visible(source)
+            .filter(|path| self.is_supported_file(path, extensions))
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
+    pub fn is_supported_file(&self, path: &Path, extensions: &[&str]) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.iter().any(|supported| supported.eq_ignore_ascii_case(ext)))
+    }
+
+    /// 创建目标目录结构
+    pub async fn create_target_structure(&self, target_workspace: &str, preserve_structure: bool, source_files: &[PathBuf], source_root: &Path) -> Result<()> {
+        if !preserve_structure {
+            return Ok(());
+        }
+
+        for file_path in source_files {
+            if let Ok(relative_path) = file_path.strip_prefix(source_root) {
+                if let Some(parent) = relative_path.parent() {
+                    let target_dir = Path::new(target_workspace).join(parent);
+                    if !target_dir.exists() {
+                        tokio::fs::create_dir_all(&target_dir).await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 生成目标文件路径
+    pub fn generate_target_path(&self, source_file: &Path, source_root: &Path, target_workspace: &str, preserve_structure: bool) -> Result<PathBuf> {
+        let target_root = Path::new(target_workspace);
+
+        if preserve_structure {
+            let relative_path = source_file.strip_prefix(source_root)?;
+            Ok(target_root.join(relative_path))
+        } else {
+            let filename = source_file.file_name()
+                .ok_or_else(|| anyhow!("Invalid file name"))?;
+            Ok(target_root.join(filename))
+        }
+    }
+
+    /// 检查文件是否已存在
+    pub async fn file_exists(&self, target_path: &Path) -> bool {
+        tokio::fs::metadata(target_path).await.is_ok()
+    }
+
+    /// 按合并模式处理目标文件已存在的情况，返回实际写入的路径，`None` 表示跳过
+    pub async fn resolve_conflict(&self, target_path: &Path, options: &ImportOptions) -> Result<Option<PathBuf>> {
+        if !self.file_exists(target_path).await {
+            return Ok(Some(target_path.to_path_buf()));
+        }
+
+        match options.merge_mode {
+            MergeMode::Skip => Ok(None),
+            MergeMode::Overwrite => {
+                if options.backup_existing && !options.dry_run {
+                    self.backup_existing_file(target_path).await?;
+                }
+                Ok(Some(target_path.to_path_buf()))
+            }
+            MergeMode::Rename => Ok(Some(self.generate_unique_path(target_path).await)),
+            MergeMode::Merge => Err(anyhow!("Merge mode is not supported yet: {}", target_path.display())),
+        }
+    }
+
+    /// 在文件名后追加序号，直到找到不存在的路径：`note.md` -> `note-1.md`
+    pub async fn generate_unique_path(&self, path: &Path) -> PathBuf {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let extension = path.extension().map(|ext| ext.to_string_lossy().to_string());
+
+        let mut index = 1;
+        loop {
+            let file_name = match &extension {
+                Some(ext) => format!("{}-{}.{}", stem, index, ext),
+                None => format!("{}-{}", stem, index),
+            };
+            let candidate = path.with_file_name(file_name);
+            if !self.file_exists(&candidate).await {
+                return candidate;
+            }
+            index += 1;
+        }
+    }
+
+    /// 写入导入的文件，试运行时不写入
+    pub async fn write_file(&self, target_path: &Path, content: &str, dry_run: bool) -> Result<()> {
+        if dry_run {
+            return Ok(());
+        }
+        if let Some(parent) = target_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(target_path, content).await?;
+        Ok(())
+    }
+
+    /// 备份现有文件
+    pub async fn backup_existing_file(&self, target_path: &Path) -> Result<()> {
+        if !self.file_exists(target_path).await {
+            return Ok(());
+        }
+
+        let backup_path = self.generate_backup_path(target_path);
+        tokio::fs::rename(target_path, backup_path).await?;
+        Ok(())
+    }
+
+    fn generate_backup_path(&self, original_path: &Path) -> PathBuf {
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let mut backup_path = original_path.to_path_buf();
+
+        if let Some(stem) = original_path.file_stem() {
+            if let Some(extension) = original_path.extension() {
+                backup_path.set_file_name(format!("{}_backup_{}.{}",
+                    stem.to_string_lossy(),
+                    timestamp,
+                    extension.to_string_lossy()
+                ));
+            } else {
+                backup_path.set_file_name(format!("{}_backup_{}",
+                    stem.to_string_lossy(),
+                    timestamp
+                ));
+            }
+        }
+
+        backup_path
+    }
+
+    /// 复制附件文件
+    pub async fn copy_attachment(&self, source_path: &Path, target_path: &Path) -> Result<()> {
+        if let Some(parent) = target_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::copy(source_path, target_path).await?;
+        Ok(())
+    }
+
+    /// 生成导入统计
+    pub fn generate_import_stats(&self, files: &[ImportedFile], start_time: Instant) -> ImportResult {
+        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+
+        let mut result = ImportResult::new();
+        result.processing_time_ms = processing_time_ms;
+
+        for file in files {
+            match file.status {
+                ImportStatus::Success => result.imported_count += 1,
+                ImportStatus::Skipped => {
+                    result.skipped_count += 1;
+                    result.duplicate_files.push(file.target_path.clone());
+                }
+                ImportStatus::Failed => result.failed_count += 1,
+                ImportStatus::Warning => {
+                    result.imported_count += 1;
+                    result.warnings.push(format!("Warning processing {}", file.source_path));
+                }
+            }
+        }
+
+        result.imported_files = files.to_vec();
+        result.success = result.failed_count == 0;
+
+        result
+    }
+
+    /// 创建目录节点树
+    pub fn create_directory_tree(&self, files: &[PathBuf], root_path: &Path) -> Vec<DirectoryNode> {
+        let mut root_nodes = Vec::new();
+        let mut path_counts: HashMap<PathBuf, u32> = HashMap::new();
+
+        // 计算每个目录的文件数量
+        for file in files {
+            if let Ok(relative_path) = file.strip_prefix(root_path) {
+                let mut current_path = root_path.to_path_buf();
+
+                for component in relative_path.components() {
+                    current_path.push(component);
+                    *path_counts.entry(current_path.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+
+        // 构建目录树（简化版本，实际实现会更复杂）
+        for (path, count) in path_counts {
+            if path.parent() == Some(root_path) {
+                root_nodes.push(DirectoryNode {
+                    name: path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    file_count: count,
+                    children: Vec::new(), // 简化版本，不递归构建子节点
+                });
+            }
+        }
+        root_nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        root_nodes
+    }
+
+    /// 默认的链接转换实现
+    pub fn default_convert_links(&self, content: &str, _mappings: &HashMap<String, String>) -> String {
+        // 基础实现：将 [[link]] 格式转换为 [link](link.md)
+        let link_regex = regex::Regex::new(r"\[\[([^\]]+)\]\]").unwrap();
+        link_regex.replace_all(content, |caps: &regex::Captures| {
+            let link_text = &caps[1];
+            format!("[{}]({}.md)", link_text, link_text.replace(' ', "_").to_lowercase())
+        }).to_string()
+    }
+
+    /// 默认的标签转换实现
+    pub fn default_convert_tags(&self, content: &str) -> String {
+        // 基础实现：将 #tag 格式保持不变
+        content.to_string()
+    }
+
+    /// 默认的元数据提取实现
+    pub fn default_extract_metadata(&self, content: &str) -> Result<HashMap<String, String>> {
+        let mut metadata = HashMap::new();
+
+        // 尝试解析 YAML frontmatter
+        if let Some(rest) = content.strip_prefix("---") {
+            if let Some(end_index) = rest.find("---") {
+                let yaml_content = &rest[..end_index];
+                if let Ok(yaml_value) = serde_yaml::from_str::<serde_yaml::Value>(yaml_content) {
+                    if let Some(mapping) = yaml_value.as_mapping() {
+                        for (key, value) in mapping {
+                            if let (Some(key_str), Some(value_str)) = (key.as_str(), value.as_str()) {
+                                metadata.insert(key_str.to_string(), value_str.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// 递归列出目录下的文件，跳过隐藏文件和目录
+pub(crate) fn walk_visible(root: &Path) -> impl Iterator<Item = PathBuf> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+}
+
+// 基础 Markdown 导入器实现
+pub struct MarkdownImporter {
+    base: BaseImporter,
+}
+
+impl MarkdownImporter {
+    pub fn new() -> Self {
+        Self {
+            base: BaseImporter::new("Markdown Importer".to_string(), "1.0.0".to_string()),
+        }
+    }
+}
+
+impl Default for MarkdownImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Importer for MarkdownImporter {
+    fn name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn version(&self) -> &str {
+        &self.base.version
+    }
+
+    fn supported_extensions(&self) -> Vec<&str> {
+        vec!["md", "markdown", "txt"]
+    }
+
+    async fn validate_source(&self, source_path: &str) -> Result<bool> {
+        let path = Path::new(source_path);
+        Ok(path.exists() && (path.is_dir() || self.base.is_supported_file(path, &self.supported_extensions())))
+    }
+
+    async fn preview_import(&self, config: &ImportConfig) -> Result<ImportPreview> {
+        let files = self.base.scan_source_directory(&config.source_path, &self.supported_extensions()).await?;
+
+        let mut preview = ImportPreview {
+            total_files: files.len() as u32,
+            notes: files.len() as u32,
+            attachments: 0,
+            media_files: 0,
+            estimated_size: 0,
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            structure: Vec::new(),
+        };
+
+        // 计算估计大小
+        for file in &files {
+            if let Ok(metadata) = tokio::fs::metadata(file).await {
+                preview.estimated_size += metadata.len();
+            }
+        }
+
+        // 检查冲突
+        preview.conflicts = self.check_conflicts(config).await?;
+
+        // 创建目录结构
+        let source_root = Path::new(&config.source_path);
+        preview.structure = self.base.create_directory_tree(&files, source_root);
+
+        Ok(preview)
+    }
+
+    async fn import(&self, config: &ImportConfig, on_progress: ProgressFn<'_>) -> Result<ImportResult> {
+        let start_time = Instant::now();
+        let files = self.base.scan_source_directory(&config.source_path, &self.supported_extensions()).await?;
+
+        // 创建目标目录结构
+        let source_root = Path::new(&config.source_path);
+        if !config.options.dry_run {
+            self.base.create_target_structure(&config.target_workspace, config.options.preserve_structure, &files, source_root).await?;
+        }
+
+        let mut imported_files = Vec::new();
+        let mut errors = Vec::new();
+        let total = files.len();
+
+        for (i, file_path) in files.iter().enumerate() {
+            match self.process_file(file_path, config).await {
+                Ok(imported_file) => imported_files.push(imported_file),
+                Err(e) => {
+                    errors.push(format!("{}: {}", file_path.display(), e));
+                    imported_files.push(ImportedFile {
+                        source_path: file_path.to_string_lossy().to_string(),
+                        target_path: String::new(),
+                        file_type: FileType::Note,
+                        size: 0,
+                        modified_time: Utc::now(),
+                        status: ImportStatus::Failed,
+                        transformations: Vec::new(),
+                    });
+                }
+            }
+            on_progress(TransferProgress { current: i + 1, total, path: file_path });
+        }
+
+        let mut result = self.base.generate_import_stats(&imported_files, start_time);
+        result.errors = errors;
+        Ok(result)
+    }
+
+    async fn process_file(&self, file_path: &Path, config: &ImportConfig) -> Result<ImportedFile> {
+        let content = tokio::fs::read_to_string(file_path).await?;
+        let metadata = tokio::fs::metadata(file_path).await?;
+        let modified_time = metadata.modified().map(chrono::DateTime::from).unwrap_or_else(|_| Utc::now());
+
+        // 转换内容
+        let mut transformed_content = content;
+        let mut transformations = Vec::new();
+
+        if config.options.convert_links {
+            let new_content = self.convert_links(&transformed_content, &config.options.custom_mappings);
+            if new_content != transformed_content {
+                transformations.push(Transformation {
+                    transformation_type: TransformationType::LinkConversion,
+                    description: "Converted wiki-style links to markdown links".to_string(),
+                    from_value: "[[link]]".to_string(),
+                    to_value: "[link](link.md)".to_string(),
+                });
+                transformed_content = new_content;
+            }
+        }
+
+        if config.options.convert_tags {
+            let new_content = self.convert_tags(&transformed_content);
+            if new_content != transformed_content {
+                transformations.push(Transformation {
+                    transformation_type: TransformationType::TagConversion,
+                    description: "Converted tag formats".to_string(),
+                    from_value: "#tag".to_string(),
+                    to_value: "#tag".to_string(),
+                });
+                transformed_content = new_content;
+            }
+        }
+
+        // 生成目标路径并处理文件冲突
+        let source_root = Path::new(&config.source_path);
+        let target_path = self.base.generate_target_path(file_path, source_root, &config.target_workspace, config.options.preserve_structure)?;
+        let (target_path, status) = match self.base.resolve_conflict(&target_path, &config.options).await? {
+            Some(path) => (path, ImportStatus::Success),
+            None => (target_path, ImportStatus::Skipped),
+        };
+
+        if matches!(status, ImportStatus::Success) {
+            self.base.write_file(&target_path, &transformed_content, config.options.dry_run).await?;
+        }
+
+        Ok(ImportedFile {
+            source_path: file_path.to_string_lossy().to_string(),
+            target_path: target_path.to_string_lossy().to_string(),
+            file_type: FileType::Note,
+            size: metadata.len(),
+            modified_time,
+            status,
+            transformations,
+        })
+    }
+
+    fn convert_links(&self, content: &str, mappings: &HashMap<String, String>) -> String {
+        self.base.default_convert_links(content, mappings)
+    }
+
+    fn convert_tags(&self, content: &str) -> String {
+        self.base.default_convert_tags(content)
+    }
+
+    fn extract_metadata(&self, content: &str) -> Result<HashMap<String, String>> {
+        self.base.default_extract_metadata(content)
+    }
+
+    async fn check_conflicts(&self, config: &ImportConfig) -> Result<Vec<FileConflict>> {
+        let files = self.base.scan_source_directory(&config.source_path, &self.supported_extensions()).await?;
+        check_name_collisions(&self.base, &files, config).await
+    }
+}
+
+// 通用导入器实现
+pub struct GenericImporter {
+    base: BaseImporter,
+}
+
+impl GenericImporter {
+    pub fn new() -> Self {
+        Self {
+            base: BaseImporter::new("Generic Importer".to_string(), "1.0.0".to_string()),
+        }
+    }
+}
+
+impl Default for GenericImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Importer for GenericImporter {
+    fn name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn version(&self) -> &str {
+        &self.base.version
+    }
+
+    fn supported_extensions(&self) -> Vec<&str> {
+        vec!["txt", "md", "markdown", "html", "htm", "json", "xml"]
+    }
+
+    async fn validate_source(&self, source_path: &str) -> Result<bool> {
+        let path = Path::new(source_path);
+        Ok(path.exists())
+    }
+
+    async fn preview_import(&self, _config: &ImportConfig) -> Result<ImportPreview> {
+        // 简化的预览实现
+        Ok(ImportPreview {
+            total_files: 0,
+            notes: 0,
+            attachments: 0,
+            media_files: 0,
+            estimated_size: 0,
+            warnings: vec!["Generic importer: limited functionality".to_string()],
+            conflicts: Vec::new(),
+            structure: Vec::new(),
+        })
+    }
+
+    async fn import(&self, _config: &ImportConfig, _on_progress: ProgressFn<'_>) -> Result<ImportResult> {
+        // 简化的导入实现
+        Ok(ImportResult::new())
+    }
+
+    async fn process_file(&self, _file_path: &Path, _config: &ImportConfig) -> Result<ImportedFile> {
+        Err(anyhow!("Generic importer: process_file not implemented"))
+    }
+
+    fn convert_links(&self, content: &str, mappings: &HashMap<String, String>) -> String {
+        self.base.default_convert_links(content, mappings)
+    }
+
+    fn convert_tags(&self, content: &str) -> String {
+        self.base.default_convert_tags(content)
+    }
+
+    fn extract_metadata(&self, content: &str) -> Result<HashMap<String, String>> {
+        self.base.default_extract_metadata(content)
+    }
+
+    async fn check_conflicts(&self, _config: &ImportConfig) -> Result<Vec<FileConflict>> {
+        Ok(Vec::new())
+    }
+}
+
+/// 列出目标位置已存在同名文件的源文件
+pub(crate) async fn check_name_collisions(base: &BaseImporter, files: &[PathBuf], config: &ImportConfig) -> Result<Vec<FileConflict>> {
+    let mut conflicts = Vec::new();
+    let source_root = Path::new(&config.source_path);
+
+    for file_path in files {
+        let target_path = base.generate_target_path(file_path, source_root, &config.target_workspace, config.options.preserve_structure)?;
+
+        if base.file_exists(&target_path).await {
+            conflicts.push(FileConflict {
+                source_path: file_path.to_string_lossy().to_string(),
+                target_path: target_path.to_string_lossy().to_string(),
+                conflict_type: ConflictType::NameCollision,
+                suggested_resolution: match config.options.merge_mode {
+                    MergeMode::Skip => "文件将被跳过".to_string(),
+                    MergeMode::Overwrite => "文件将被覆盖".to_string(),
+                    MergeMode::Merge => "文件将被合并".to_string(),
+                    MergeMode::Rename => "文件将被重命名".to_string(),
+                },
+            });
+        }
+    }
+
+    Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn config(source: &Path, target: &Path, merge_mode: MergeMode) -> ImportConfig {
+        let mut config = ImportConfig::new(
+            ImporterType::Markdown,
+            source.to_string_lossy().to_string(),
+            target.to_string_lossy().to_string(),
+        );
+        config.options.merge_mode = merge_mode;
+        config.options.convert_links = false;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_markdown_import_is_recursive_and_honours_merge_mode() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        std::fs::create_dir_all(source.path().join("a/b")).unwrap();
+        std::fs::write(source.path().join("top.md"), "top").unwrap();
+        std::fs::write(source.path().join("a/b/deep.md"), "deep").unwrap();
+        std::fs::write(target.path().join("top.md"), "existing").unwrap();
+
+        let importer = MarkdownImporter::new();
+        let result = importer
+            .import(&config(source.path(), target.path(), MergeMode::Skip), &|_| {})
+            .await
+            .unwrap();
+        assert_eq!((result.imported_count, result.skipped_count), (1, 1));
+        assert_eq!(std::fs::read_to_string(target.path().join("top.md")).unwrap(), "existing");
+        assert!(target.path().join("a/b/deep.md").exists());
+
+        let result = importer
+            .import(&config(source.path(), target.path(), MergeMode::Rename), &|_| {})
+            .await
+            .unwrap();
+        assert_eq!(result.imported_count, 2);
+        assert_eq!(std::fs::read_to_string(target.path().join("top-1.md")).unwrap(), "top");
+        assert!(target.path().join("a/b/deep-1.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_writes_nothing() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        std::fs::write(source.path().join("note.md"), "body").unwrap();
+
+        let mut config = config(source.path(), &target.path().join("notes"), MergeMode::Skip);
+        config.options.dry_run = true;
+        let result = MarkdownImporter::new().import(&config, &|_| {}).await.unwrap();
+
+        assert_eq!(result.imported_count, 1);
+        assert!(!target.path().join("notes").exists());
+    }
+}
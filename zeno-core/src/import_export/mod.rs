@@ -0,0 +1,181 @@
+//! 导入导出：从 Obsidian 等来源导入笔记，导出为 Markdown、HTML 或 JSON
+
+pub mod exporter;
+pub mod importer;
+pub mod model;
+pub mod obsidian;
+
+pub use exporter::*;
+pub use importer::*;
+pub use model::*;
+pub use obsidian::*;
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 导入导出进度
+#[derive(Debug, Clone)]
+pub struct TransferProgress<'a> {
+    /// 已处理数量（从 1 开始）
+    pub current: usize,
+    /// 总数量
+    pub total: usize,
+    /// 当前文件
+    pub path: &'a Path,
+}
+
+/// 进度回调，每处理一个文件调用一次
+pub type ProgressFn<'a> = &'a (dyn Fn(TransferProgress<'_>) + Send + Sync);
+
+pub struct ImportExportManager {
+    importers: HashMap<ImporterType, Arc<dyn Importer>>,
+    exporters: HashMap<ExporterType, Arc<dyn Exporter>>,
+}
+
+impl ImportExportManager {
+    pub fn new() -> Self {
+        let mut manager = Self {
+            importers: HashMap::new(),
+            exporters: HashMap::new(),
+        };
+
+        // 注册默认导入器
+        manager.register_default_importers();
+
+        // 注册默认导出器
+        manager.register_default_exporters();
+
+        manager
+    }
+
+    /// 注册默认导入器
+    fn register_default_importers(&mut self) {
+        // Obsidian 导入器
+        self.importers.insert(
+            ImporterType::Obsidian,
+            Arc::new(ObsidianImporter::new())
+        );
+
+        // 基础 Markdown 导入器
+        self.importers.insert(
+            ImporterType::Markdown,
+            Arc::new(MarkdownImporter::new())
+        );
+
+        // 通用导入器
+        self.importers.insert(
+            ImporterType::Generic,
+            Arc::new(GenericImporter::new())
+        );
+    }
+
+    /// 注册默认导出器
+    fn register_default_exporters(&mut self) {
+        // Markdown 导出器
+        self.exporters.insert(
+            ExporterType::Markdown,
+            Arc::new(MarkdownExporter::new())
+        );
+
+        // HTML 导出器
+        self.exporters.insert(
+            ExporterType::Html,
+            Arc::new(HtmlExporter::new())
+        );
+
+        // JSON 导出器
+        self.exporters.insert(
+            ExporterType::Json,
+            Arc::new(JsonExporter::new())
+        );
+    }
+
+    /// 注册自定义导入器
+    pub fn register_importer(&mut self, importer_type: ImporterType, importer: Arc<dyn Importer>) {
+        self.importers.insert(importer_type, importer);
+    }
+
+    /// 注册自定义导出器
+    pub fn register_exporter(&mut self, exporter_type: ExporterType, exporter: Arc<dyn Exporter>) {
+        self.exporters.insert(exporter_type, exporter);
+    }
+
+    /// 获取所有可用的导入器类型
+    pub fn get_available_importers(&self) -> Vec<ImporterType> {
+        self.importers.keys().cloned().collect()
+    }
+
+    /// 获取所有可用的导出器类型
+    pub fn get_available_exporters(&self) -> Vec<ExporterType> {
+        self.exporters.keys().cloned().collect()
+    }
+
+    /// 预览导入
+    pub async fn preview_import(&self, config: &ImportConfig) -> Result<ImportPreview> {
+        let importer = self.importer(config)?;
+
+        // 验证源路径
+        if !importer.validate_source(&config.source_path).await? {
+            return Err(anyhow!("Invalid source path: {}", config.source_path));
+        }
+
+        importer.preview_import(config).await
+    }
+
+    /// 执行导入
+    pub async fn import(&self, config: &ImportConfig) -> Result<ImportResult> {
+        self.import_with_progress(config, &|_| {}).await
+    }
+
+    /// 执行导入，每处理一个文件调用一次 `on_progress`
+    pub async fn import_with_progress(&self, config: &ImportConfig, on_progress: ProgressFn<'_>) -> Result<ImportResult> {
+        let importer = self.importer(config)?;
+
+        // 验证源路径
+        if !importer.validate_source(&config.source_path).await? {
+            return Err(anyhow!("Invalid source path: {}", config.source_path));
+        }
+
+        importer.import(config, on_progress).await
+    }
+
+    /// 预览导出
+    pub async fn preview_export(&self, config: &ExportConfig) -> Result<ExportPreview> {
+        self.exporter(config)?.preview_export(config).await
+    }
+
+    /// 执行导出
+    pub async fn export(&self, config: &ExportConfig) -> Result<ExportResult> {
+        self.export_with_progress(config, &|_| {}).await
+    }
+
+    /// 执行导出，每处理一个笔记调用一次 `on_progress`
+    pub async fn export_with_progress(&self, config: &ExportConfig, on_progress: ProgressFn<'_>) -> Result<ExportResult> {
+        let exporter = self.exporter(config)?;
+
+        // 验证目标路径
+        if !exporter.validate_target(&config.target_path).await? {
+            return Err(anyhow!("Invalid target path: {}", config.target_path));
+        }
+
+        exporter.export(config, on_progress).await
+    }
+
+    fn importer(&self, config: &ImportConfig) -> Result<&Arc<dyn Importer>> {
+        self.importers.get(&config.importer_type)
+            .ok_or_else(|| anyhow!("Unsupported importer type: {:?}", config.importer_type))
+    }
+
+    fn exporter(&self, config: &ExportConfig) -> Result<&Arc<dyn Exporter>> {
+        self.exporters.get(&config.exporter_type)
+            .ok_or_else(|| anyhow!("Unsupported exporter type: {:?}", config.exporter_type))
+    }
+}
+
+impl Default for ImportExportManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
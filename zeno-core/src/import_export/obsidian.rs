@@ -1,13 +1,20 @@
-use crate::models::importer::*;
-use crate::models::note::Note;
-use crate::services::base_importer::{Importer, BaseImporter};
-use anyhow::{Result, anyhow};
-use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use anyhow::Result;
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::importer::{check_name_collisions, walk_visible, BaseImporter, Importer};
+use super::model::*;
+use super::{ProgressFn, TransferProgress};
+use crate::models::{extract_title, Frontmatter, Note, NoteStatus};
+
+/// 匹配行首或空白后的 #标签，排除标题和链接中的锚点
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\s)#([\w/\-]+)").unwrap());
 
 /// Obsidian 专用导入器
 pub struct ObsidianImporter {
@@ -52,6 +59,8 @@ struct VaultMetadata {
     plugin_data: HashMap<String, serde_json::Value>,
     note_count: u32,
     attachment_count: u32,
+    media_count: u32,
+    canvas_count: u32,
     template_count: u32,
 }
 
@@ -148,69 +157,46 @@ impl ObsidianImporter {
 
         let config = self.read_vault_config(vault_path).await?;
         
-        // 扫描文件
-        let mut note_count = 0u32;
-        let mut attachment_count = 0u32;
-        let mut template_count = 0u32;
-
-        // 简化为直接计数，避免递归调用问题
-        // self.count_files_recursive(vault_path, &mut note_count, &mut attachment_count, &mut template_count).await?;
-
-        Ok(VaultMetadata {
+        let mut metadata = VaultMetadata {
             name: vault_name,
             path: vault_path.to_string_lossy().to_string(),
             config,
             plugin_data: HashMap::new(),
-            note_count,
-            attachment_count,
-            template_count,
-        })
+            note_count: 0,
+            attachment_count: 0,
+            media_count: 0,
+            canvas_count: 0,
+            template_count: 0,
+        };
+
+        // 统计文件，.obsidian 等隐藏目录不计入
+        for path in walk_visible(vault_path) {
+            self.classify_file(&path, &mut metadata);
+        }
+
+        Ok(metadata)
     }
 
-    fn count_files_recursive<'a>(
-        &'a self,
-        dir: &'a Path,
-        note_count: &'a mut u32,
-        attachment_count: &'a mut u32,
-        template_count: &'a mut u32,
-    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
-        Box::pin(async move {
-            let mut entries = tokio::fs::read_dir(dir).await?;
-            
-            while let Some(entry) = entries.next_entry().await? {
-                let path = entry.path();
-                
-                if path.is_dir() {
-                    // 跳过 .obsidian 目录
-                    if path.file_name().unwrap_or_default() == ".obsidian" {
-                        continue;
-                    }
-                    // 改为迭代式计数，避免递归问题
-                    // self.count_files_recursive(&path, note_count, attachment_count, template_count).await?;
+    fn classify_file(&self, path: &Path, metadata: &mut VaultMetadata) {
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default().to_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => {
+                // 检查是否是模板
+                if path.to_string_lossy().to_lowercase().contains("template") {
+                    metadata.template_count += 1;
                 } else {
-                    self.classify_file(&path, note_count, attachment_count, template_count);
+                    metadata.note_count += 1;
                 }
             }
-            
-            Ok(())
-        })
-    }
-
-    fn classify_file(&self, path: &Path, note_count: &mut u32, attachment_count: &mut u32, template_count: &mut u32) {
-        if let Some(ext) = path.extension() {
-            if let Some(ext_str) = ext.to_str() {
-                match ext_str.to_lowercase().as_str() {
-                    "md" | "markdown" => {
-                        // 检查是否是模板
-                        if path.to_string_lossy().contains("template") || 
-                           path.to_string_lossy().contains("Template") {
-                            *template_count += 1;
-                        } else {
-                            *note_count += 1;
-                        }
-                    }
-                    "canvas" => *note_count += 1, // Canvas 文件也算作笔记
-                    _ => *attachment_count += 1,
+            "canvas" => {
+                // Canvas 文件也算作笔记
+                metadata.note_count += 1;
+                metadata.canvas_count += 1;
+            }
+            _ => {
+                metadata.attachment_count += 1;
+                if self.is_image_file(&path.to_string_lossy()) {
+                    metadata.media_count += 1;
                 }
             }
         }
@@ -229,14 +215,14 @@ impl ObsidianImporter {
         // 解析 frontmatter
         let (frontmatter, main_content) = self.parse_frontmatter(&content)?;
         
-        // 提取标签
-        let tags = self.extract_tags(&content);
+        // 提取正文中的标签
+        let tags = self.extract_tags(&main_content);
         
         // 提取别名
         let aliases = self.extract_aliases(&frontmatter);
         
         // 解析链接
-        let (links, backlinks) = self.parse_obsidian_links(&main_content)?;
+        let (links, _) = self.parse_obsidian_links(&main_content)?;
 
         // 检查是否是 Canvas 文件
         let canvas_data = if path.extension().unwrap_or_default() == "canvas" {
@@ -255,10 +241,10 @@ impl ObsidianImporter {
             links,
             backlinks: Vec::new(), // 反向链接需要在所有笔记解析完成后计算
             created_at: metadata.created()
-                .map(|t| chrono::DateTime::from(t))
+                .map(DateTime::from)
                 .unwrap_or_else(|_| Utc::now()),
             modified_at: metadata.modified()
-                .map(|t| chrono::DateTime::from(t))
+                .map(DateTime::from)
                 .unwrap_or_else(|_| Utc::now()),
             canvas_data,
         })
@@ -313,11 +299,13 @@ impl ObsidianImporter {
     fn extract_tags(&self, content: &str) -> Vec<String> {
         let mut tags = Vec::new();
         
-        // 匹配 #tag 格式的标签
-        let tag_regex = Regex::new(r"#([a-zA-Z0-9_/\-]+)").unwrap();
-        for caps in tag_regex.captures_iter(content) {
+        // 匹配 #tag 格式的标签，纯数字（如 #1）不是标签
+        for caps in TAG_REGEX.captures_iter(content) {
             if let Some(tag) = caps.get(1) {
-                tags.push(tag.as_str().to_string());
+                let tag = tag.as_str().trim_end_matches('/');
+                if !tag.chars().all(|c| c.is_ascii_digit()) {
+                    tags.push(tag.to_string());
+                }
             }
         }
         
@@ -462,35 +450,37 @@ impl ObsidianImporter {
     }
 
     /// 转换 Obsidian 笔记为 Zeno 格式
-    fn convert_obsidian_note_to_zeno(&self, obsidian_note: &ObsidianNote, config: &ImportConfig) -> Result<Note> {
+    fn convert_obsidian_note_to_zeno(&self, obsidian_note: &ObsidianNote, config: &ImportConfig) -> Result<(Note, Vec<Transformation>)> {
         let mut content = obsidian_note.content.clone();
         let mut transformations = Vec::new();
 
         // 转换链接格式
         if config.options.convert_links {
-            content = self.convert_obsidian_links(&content, &config.options.custom_mappings);
-            transformations.push(Transformation {
-                transformation_type: TransformationType::LinkConversion,
-                description: "Converted Obsidian wiki links to markdown format".to_string(),
-                from_value: "[[Link]]".to_string(),
-                to_value: "[Link](Link.md)".to_string(),
-            });
+            let converted = self.convert_obsidian_links(&content, &config.options.custom_mappings);
+            if converted != content {
+                content = converted;
+                transformations.push(Transformation {
+                    transformation_type: TransformationType::LinkConversion,
+                    description: "Converted Obsidian wiki links to markdown format".to_string(),
+                    from_value: "[[Link]]".to_string(),
+                    to_value: "[Link](Link.md)".to_string(),
+                });
+            }
         }
 
-        // 处理 frontmatter
-        let zeno_frontmatter = if config.options.convert_tags {
-            self.convert_frontmatter(&obsidian_note.frontmatter, &obsidian_note.tags, &obsidian_note.aliases)?
-        } else {
-            None
-        };
-
-        // 如果有 frontmatter，添加到内容开头
-        if let Some(ref fm) = zeno_frontmatter {
-            let yaml_content = serde_yaml::to_string(fm)?;
-            content = format!("---\n{}---\n\n{}", yaml_content, content);
+        // 处理 frontmatter，正文中的 #标签 只在 convert_tags 时并入前言
+        let inline_tags: &[String] = if config.options.convert_tags { &obsidian_note.tags } else { &[] };
+        let zeno_frontmatter = self.convert_frontmatter(&obsidian_note.frontmatter, inline_tags, &obsidian_note.aliases)?;
+        if zeno_frontmatter.is_some() {
+            transformations.push(Transformation {
+                transformation_type: TransformationType::FrontmatterConversion,
+                description: "Converted Obsidian frontmatter to Zeno format".to_string(),
+                from_value: "YAML frontmatter".to_string(),
+                to_value: "Zeno frontmatter".to_string(),
+            });
         }
 
-        let mut note = Note::new(
+        let mut note = Note::with_title(
             PathBuf::from(obsidian_note.path.clone()),
             obsidian_note.title.clone(),
             content
@@ -498,9 +488,18 @@ impl ObsidianImporter {
         
         note.created_at = obsidian_note.created_at;
         note.modified_at = obsidian_note.modified_at;
-        note.frontmatter = zeno_frontmatter;
-        
-        Ok(note)
+        if let Some(frontmatter) = zeno_frontmatter {
+            if let Some(status) = &frontmatter.status {
+                note.status = status.clone();
+            }
+            note.frontmatter = frontmatter;
+        }
+        // Obsidian 以文件名作为标题，没有标题和一级标题时写入 frontmatter 保留
+        if note.frontmatter.title.is_none() && extract_title(&note.content).is_none() {
+            note.frontmatter.title = Some(obsidian_note.title.clone());
+        }
+
+        Ok((note, transformations))
     }
 
     /// 转换 Obsidian 链接格式
@@ -564,9 +563,9 @@ impl ObsidianImporter {
         obsidian_frontmatter: &Option<HashMap<String, serde_json::Value>>,
         tags: &[String],
         aliases: &[String],
-    ) -> Result<Option<crate::models::note::Frontmatter>> {
-        let mut zeno_frontmatter = crate::models::note::Frontmatter::default();
-        
+    ) -> Result<Option<Frontmatter>> {
+        let mut zeno_frontmatter = Frontmatter::default();
+
         // 复制已有的 frontmatter
         if let Some(fm) = obsidian_frontmatter {
             for (key, value) in fm {
@@ -581,67 +580,67 @@ impl ObsidianImporter {
                             zeno_frontmatter.description = Some(desc.to_string());
                         }
                     }
+                    "author" => {
+                        if let Some(author) = value.as_str() {
+                            zeno_frontmatter.author = Some(author.to_string());
+                        }
+                    }
                     "date" | "created" => {
-                        if let Some(date_str) = value.as_str() {
-                            if let Ok(date) = chrono::DateTime::parse_from_rfc3339(date_str) {
-                                zeno_frontmatter.date = Some(date.date_naive());
-                            }
+                        if let Some(date) = value.as_str().and_then(parse_date) {
+                            zeno_frontmatter.date = Some(date);
                         }
                     }
                     "modified" | "updated" => {
-                        if let Some(date_str) = value.as_str() {
-                            if let Ok(date) = chrono::DateTime::parse_from_rfc3339(date_str) {
-                                zeno_frontmatter.extra.insert("updated".to_string(), serde_json::Value::String(date.with_timezone(&Utc).format("%Y-%m-%d").to_string()));
-                            }
+                        if let Some(date) = value.as_str().and_then(parse_date) {
+                            zeno_frontmatter.custom.insert("updated".to_string(), serde_json::Value::String(date.format("%Y-%m-%d").to_string()));
+                        }
+                    }
+                    "tags" | "tag" => zeno_frontmatter.tags.extend(string_list(value)),
+                    "categories" | "category" => zeno_frontmatter.categories.extend(string_list(value)),
+                    "status" => {
+                        if let Some(status) = value.as_str().and_then(|s| s.parse::<NoteStatus>().ok()) {
+                            zeno_frontmatter.status = Some(status);
                         }
                     }
                     "draft" => {
                         if let Some(draft) = value.as_bool() {
                             if draft {
-                                zeno_frontmatter.status = crate::models::note::NoteStatus::Draft;
+                                zeno_frontmatter.status = Some(NoteStatus::Draft);
                             } else {
-                                zeno_frontmatter.status = crate::models::note::NoteStatus::Published;
+                                zeno_frontmatter.status = Some(NoteStatus::Published);
                             }
                         }
                     }
+                    // 别名在下面统一写入
+                    "aliases" | "alias" => {}
                     _ => {
                         // 其他字段保存到 extra 中
-                        zeno_frontmatter.extra.insert(key.clone(), value.clone());
+                        zeno_frontmatter.custom.insert(key.clone(), value.clone());
                     }
                 }
             }
         }
 
-        // 添加标签
-        if !tags.is_empty() {
-            zeno_frontmatter.tags = tags.to_vec();
+        // 合并正文中的标签
+        for tag in tags {
+            if !zeno_frontmatter.tags.contains(tag) {
+                zeno_frontmatter.tags.push(tag.clone());
+            }
         }
 
         // 添加别名
-        if !aliases.is_empty() {
-            zeno_frontmatter.extra.insert(
-                "aliases".to_string(),
-                serde_json::Value::Array(
-                    aliases.iter()
-                        .map(|alias| serde_json::Value::String(alias.clone()))
-                        .collect()
-                )
-            );
-        }
+        zeno_frontmatter.aliases = aliases.to_vec();
 
         // 只有在有内容时才返回 frontmatter
-        if zeno_frontmatter.title.is_some() || 
-           zeno_frontmatter.description.is_some() ||
-           !zeno_frontmatter.tags.is_empty() ||
-           !zeno_frontmatter.extra.is_empty() {
-            Ok(Some(zeno_frontmatter))
-        } else {
+        if zeno_frontmatter == Frontmatter::default() {
             Ok(None)
+        } else {
+            Ok(Some(zeno_frontmatter))
         }
     }
 
     /// 计算反向链接
-    fn calculate_backlinks(&self, notes: &mut Vec<ObsidianNote>) {
+    fn calculate_backlinks(&self, notes: &mut [ObsidianNote]) {
         let mut backlink_map: HashMap<String, Vec<ObsidianLink>> = HashMap::new();
         
         // 收集所有链接
@@ -657,7 +656,7 @@ impl ObsidianImporter {
                 };
                 
                 backlink_map.entry(target_key)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(backlink);
             }
         }
@@ -703,9 +702,9 @@ impl Importer for ObsidianImporter {
     async fn preview_import(&self, config: &ImportConfig) -> Result<ImportPreview> {
         let vault_path = Path::new(&config.source_path);
         let vault_metadata = self.scan_obsidian_vault(vault_path).await?;
-        
+
         let mut warnings = Vec::new();
-        
+
         // 检查插件兼容性
         if let Some(vault_config) = &vault_metadata.config {
             for (plugin_name, plugin_config) in &vault_config.plugins {
@@ -716,14 +715,14 @@ impl Importer for ObsidianImporter {
         }
 
         // 检查 Canvas 文件
-        if vault_metadata.note_count > 0 {
-            warnings.push("Canvas 文件将被转换为普通笔记，可能丢失视觉布局信息".to_string());
+        if vault_metadata.canvas_count > 0 {
+            warnings.push(format!("{} 个 Canvas 文件将按原样导入，Zeno 不会渲染其视觉布局", vault_metadata.canvas_count));
         }
 
         // 预估大小
         let files = self.base.scan_source_directory(&config.source_path, &self.supported_extensions()).await?;
         let mut estimated_size = 0u64;
-        for file in &files {
+        for file in walk_visible(vault_path) {
             if let Ok(metadata) = tokio::fs::metadata(file).await {
                 estimated_size += metadata.len();
             }
@@ -733,14 +732,14 @@ impl Importer for ObsidianImporter {
         let conflicts = self.check_conflicts(config).await?;
 
         // 创建目录结构
-        let source_root = Path::new(&config.source_path);
-        let structure = self.base.create_directory_tree(&files, source_root);
+        let structure = self.base.create_directory_tree(&files, vault_path);
 
+        let attachments = if config.options.include_attachments { vault_metadata.attachment_count } else { 0 };
         Ok(ImportPreview {
-            total_files: vault_metadata.note_count + vault_metadata.attachment_count,
-            notes: vault_metadata.note_count,
-            attachments: vault_metadata.attachment_count,
-            media_files: vault_metadata.attachment_count, // 简化处理
+            total_files: vault_metadata.note_count + vault_metadata.template_count + attachments,
+            notes: vault_metadata.note_count + vault_metadata.template_count,
+            attachments,
+            media_files: if config.options.include_attachments { vault_metadata.media_count } else { 0 },
             estimated_size,
             warnings,
             conflicts,
@@ -748,29 +747,35 @@ impl Importer for ObsidianImporter {
         })
     }
 
-    async fn import(&self, config: &ImportConfig) -> Result<ImportResult> {
-        self.import_internal(config).await
-    }
-
-    async fn import_internal(&self, config: &ImportConfig) -> Result<ImportResult> {
-        let start_time = std::time::Instant::now();
+    async fn import(&self, config: &ImportConfig, on_progress: ProgressFn<'_>) -> Result<ImportResult> {
+        let start_time = Instant::now();
         let vault_path = Path::new(&config.source_path);
-        
+
         // 扫描所有 Markdown 文件
         let files = self.base.scan_source_directory(&config.source_path, &self.supported_extensions()).await?;
-        
+        let attachments = if config.options.include_attachments {
+            walk_visible(vault_path).filter(|path| !self.is_markdown_or_canvas_file(path)).collect()
+        } else {
+            Vec::new()
+        };
+        let total = files.len() + attachments.len();
+
         // 创建目标目录结构
-        let source_root = vault_path;
-        self.base.create_target_structure(&config.target_workspace, config.options.preserve_structure, &files, source_root).await?;
-        
+        if !config.options.dry_run {
+            self.base.create_target_structure(&config.target_workspace, config.options.preserve_structure, &files, vault_path).await?;
+        }
+
         let mut imported_files = Vec::new();
+        let mut errors = Vec::new();
         let mut obsidian_notes = Vec::new();
-        
+
         // 第一遍：解析所有 Obsidian 笔记
         for file_path in &files {
             match self.parse_obsidian_note(file_path).await {
                 Ok(obsidian_note) => obsidian_notes.push(obsidian_note),
                 Err(e) => {
+                    log::warn!("Failed to parse Obsidian note {}: {}", file_path.display(), e);
+                    errors.push(format!("{}: {}", file_path.display(), e));
                     imported_files.push(ImportedFile {
                         source_path: file_path.to_string_lossy().to_string(),
                         target_path: String::new(),
@@ -780,19 +785,21 @@ impl Importer for ObsidianImporter {
                         status: ImportStatus::Failed,
                         transformations: Vec::new(),
                     });
-                    eprintln!("Failed to parse Obsidian note {}: {}", file_path.display(), e);
                 }
             }
         }
-        
+
         // 计算反向链接
         self.calculate_backlinks(&mut obsidian_notes);
-        
+
         // 第二遍：转换并保存笔记
-        for obsidian_note in obsidian_notes {
-            match self.process_obsidian_note(&obsidian_note, config).await {
+        let mut current = imported_files.len();
+        for obsidian_note in &obsidian_notes {
+            match self.process_obsidian_note(obsidian_note, config).await {
                 Ok(imported_file) => imported_files.push(imported_file),
                 Err(e) => {
+                    log::warn!("Failed to process Obsidian note {}: {}", obsidian_note.path, e);
+                    errors.push(format!("{}: {}", obsidian_note.path, e));
                     imported_files.push(ImportedFile {
                         source_path: obsidian_note.path.clone(),
                         target_path: String::new(),
@@ -802,22 +809,39 @@ impl Importer for ObsidianImporter {
                         status: ImportStatus::Failed,
                         transformations: Vec::new(),
                     });
-                    eprintln!("Failed to process Obsidian note {}: {}", obsidian_note.path, e);
                 }
             }
+            current += 1;
+            on_progress(TransferProgress { current, total, path: Path::new(&obsidian_note.path) });
         }
-        
+
         // 处理附件
-        if config.options.include_attachments {
-            let attachment_files = self.process_attachments(&config.source_path, &config.target_workspace, config.options.preserve_structure).await?;
-            imported_files.extend(attachment_files);
+        for attachment in &attachments {
+            match self.process_attachment(attachment, config).await {
+                Ok(imported_file) => imported_files.push(imported_file),
+                Err(e) => {
+                    errors.push(format!("{}: {}", attachment.display(), e));
+                    imported_files.push(ImportedFile {
+                        source_path: attachment.to_string_lossy().to_string(),
+                        target_path: String::new(),
+                        file_type: FileType::Attachment,
+                        size: 0,
+                        modified_time: Utc::now(),
+                        status: ImportStatus::Failed,
+                        transformations: Vec::new(),
+                    });
+                }
+            }
+            current += 1;
+            on_progress(TransferProgress { current, total, path: attachment });
         }
-        
-        Ok(self.base.generate_import_stats(&imported_files, start_time))
+
+        let mut result = self.base.generate_import_stats(&imported_files, start_time);
+        result.errors = errors;
+        Ok(result)
     }
 
     async fn process_file(&self, file_path: &Path, config: &ImportConfig) -> Result<ImportedFile> {
-        // 这个方法被 import_internal 覆盖，但仍需要提供实现
         let obsidian_note = self.parse_obsidian_note(file_path).await?;
         self.process_obsidian_note(&obsidian_note, config).await
     }
@@ -834,7 +858,7 @@ impl Importer for ObsidianImporter {
     fn extract_metadata(&self, content: &str) -> Result<HashMap<String, String>> {
         let (frontmatter, _) = self.parse_frontmatter(content)?;
         let mut metadata = HashMap::new();
-        
+
         if let Some(fm) = frontmatter {
             for (key, value) in fm {
                 if let Some(string_value) = value.as_str() {
@@ -844,79 +868,40 @@ impl Importer for ObsidianImporter {
                 }
             }
         }
-        
+
         Ok(metadata)
     }
 
     async fn check_conflicts(&self, config: &ImportConfig) -> Result<Vec<FileConflict>> {
         let files = self.base.scan_source_directory(&config.source_path, &self.supported_extensions()).await?;
-        let mut conflicts = Vec::new();
-        
-        let source_root = Path::new(&config.source_path);
-        
-        for file_path in files {
-            let target_path = self.base.generate_target_path(&file_path, source_root, &config.target_workspace, config.options.preserve_structure)?;
-            
-            if self.base.file_exists(&target_path).await {
-                conflicts.push(FileConflict {
-                    source_path: file_path.to_string_lossy().to_string(),
-                    target_path: target_path.to_string_lossy().to_string(),
-                    conflict_type: ConflictType::NameCollision,
-                    suggested_resolution: match config.options.merge_mode {
-                        MergeMode::Skip => "文件将被跳过".to_string(),
-                        MergeMode::Overwrite => "文件将被覆盖".to_string(),
-                        MergeMode::Merge => "文件将被合并".to_string(),
-                        MergeMode::Rename => "文件将被重命名".to_string(),
-                    },
-                });
-            }
-        }
-        
-        Ok(conflicts)
+        check_name_collisions(&self.base, &files, config).await
     }
 }
 
 impl ObsidianImporter {
     /// 处理单个 Obsidian 笔记
     async fn process_obsidian_note(&self, obsidian_note: &ObsidianNote, config: &ImportConfig) -> Result<ImportedFile> {
-        let note = self.convert_obsidian_note_to_zeno(obsidian_note, config)?;
-        
-        // 生成目标路径
+        let (note, transformations) = self.convert_obsidian_note_to_zeno(obsidian_note, config)?;
+
+        // 生成目标路径并处理冲突
         let source_path = Path::new(&obsidian_note.path);
         let source_root = Path::new(&config.source_path);
         let target_path = self.base.generate_target_path(source_path, source_root, &config.target_workspace, config.options.preserve_structure)?;
-        
-        // 处理冲突
-        if self.base.file_exists(&target_path).await {
-            match config.options.merge_mode {
-                MergeMode::Skip => {
-                    return Ok(ImportedFile {
-                        source_path: obsidian_note.path.clone(),
-                        target_path: target_path.to_string_lossy().to_string(),
-                        file_type: FileType::Note,
-                        size: obsidian_note.content.len() as u64,
-                        modified_time: obsidian_note.modified_at,
-                        status: ImportStatus::Skipped,
-                        transformations: Vec::new(),
-                    });
-                }
-                MergeMode::Overwrite => {
-                    if config.options.backup_existing {
-                        self.base.backup_existing_file(&target_path).await?;
-                    }
-                }
-                _ => {
-                    // 其他合并模式的实现
-                }
-            }
-        }
-        
+        let Some(target_path) = self.base.resolve_conflict(&target_path, &config.options).await? else {
+            return Ok(ImportedFile {
+                source_path: obsidian_note.path.clone(),
+                target_path: target_path.to_string_lossy().to_string(),
+                file_type: FileType::Note,
+                size: obsidian_note.content.len() as u64,
+                modified_time: obsidian_note.modified_at,
+                status: ImportStatus::Skipped,
+                transformations: Vec::new(),
+            });
+        };
+
         // 写入文件
-        if let Some(parent) = target_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-        tokio::fs::write(&target_path, &note.content).await?;
-        
+        self.base.write_file(&target_path, &note.to_markdown(), config.options.dry_run).await?;
+
         Ok(ImportedFile {
             source_path: obsidian_note.path.clone(),
             target_path: target_path.to_string_lossy().to_string(),
@@ -924,105 +909,42 @@ impl ObsidianImporter {
             size: obsidian_note.content.len() as u64,
             modified_time: obsidian_note.modified_at,
             status: ImportStatus::Success,
-            transformations: vec![
-                Transformation {
-                    transformation_type: TransformationType::LinkConversion,
-                    description: "Converted Obsidian links to Zeno format".to_string(),
-                    from_value: "[[link]]".to_string(),
-                    to_value: "[link](link.md)".to_string(),
-                },
-                Transformation {
-                    transformation_type: TransformationType::FrontmatterConversion,
-                    description: "Converted Obsidian frontmatter to Zeno format".to_string(),
-                    from_value: "YAML frontmatter".to_string(),
-                    to_value: "Zeno frontmatter".to_string(),
-                },
-            ],
+            transformations,
         })
     }
 
-    /// 处理附件文件
-    async fn process_attachments(&self, source_path: &str, target_workspace: &str, preserve_structure: bool) -> Result<Vec<ImportedFile>> {
-        let mut attachment_files = Vec::new();
-        let source_root = Path::new(source_path);
-        let target_root = Path::new(target_workspace);
-        
-        // 创建附件目录
-        let attachments_dir = if preserve_structure {
-            target_root.to_path_buf()
+    /// 复制单个附件，不保留目录结构时放入 `attachments/`
+    async fn process_attachment(&self, path: &Path, config: &ImportConfig) -> Result<ImportedFile> {
+        let source_root = Path::new(&config.source_path);
+        let target_root = Path::new(&config.target_workspace);
+        let target_path = if config.options.preserve_structure {
+            target_root.join(path.strip_prefix(source_root).unwrap_or(path))
         } else {
-            target_root.join("attachments")
+            target_root.join("attachments").join(path.file_name().unwrap_or_default())
         };
-        
-        if !attachments_dir.exists() {
-            tokio::fs::create_dir_all(&attachments_dir).await?;
-        }
-        
-        self.copy_attachments_iterative(source_root, &attachments_dir, source_root, preserve_structure, &mut attachment_files).await?;
-        
-        Ok(attachment_files)
-    }
 
-    /// 迭代式复制附件文件（避免递归调用的复杂性）
-    async fn copy_attachments_iterative(
-        &self,
-        source_dir: &Path,
-        target_base: &Path,
-        source_root: &Path,
-        preserve_structure: bool,
-        attachment_files: &mut Vec<ImportedFile>,
-    ) -> Result<()> {
-        use std::collections::VecDeque;
-        
-        let mut dirs_to_process = VecDeque::new();
-        dirs_to_process.push_back(source_dir.to_path_buf());
-        
-        while let Some(current_dir) = dirs_to_process.pop_front() {
-            let mut entries = tokio::fs::read_dir(&current_dir).await?;
-            
-            while let Some(entry) = entries.next_entry().await? {
-                let path = entry.path();
-                
-                if path.is_dir() {
-                    // 跳过 .obsidian 目录
-                    if path.file_name().unwrap_or_default() != ".obsidian" {
-                        dirs_to_process.push_back(path);
-                    }
-                } else if !self.is_markdown_or_canvas_file(&path) {
-                    // 复制非 Markdown/Canvas 文件
-                    let target_path = if preserve_structure {
-                        if let Ok(relative_path) = path.strip_prefix(source_root) {
-                            target_base.join(relative_path)
-                        } else {
-                            target_base.join(path.file_name().unwrap_or_default())
-                        }
-                    } else {
-                        target_base.join(path.file_name().unwrap_or_default())
-                    };
-                    
-                    if let Some(parent) = target_path.parent() {
-                        tokio::fs::create_dir_all(parent).await?;
-                    }
-                    
-                    tokio::fs::copy(&path, &target_path).await?;
-                    
-                    let metadata = tokio::fs::metadata(&path).await?;
-                    attachment_files.push(ImportedFile {
-                        source_path: path.to_string_lossy().to_string(),
-                        target_path: target_path.to_string_lossy().to_string(),
-                        file_type: FileType::Attachment,
-                        size: metadata.len(),
-                        modified_time: metadata.modified().map(chrono::DateTime::from).unwrap_or_else(|_| Utc::now()),
-                        status: ImportStatus::Success,
-                        transformations: Vec::new(),
-                    });
+        let metadata = tokio::fs::metadata(path).await?;
+        let file_type = if self.is_image_file(&path.to_string_lossy()) { FileType::Media } else { FileType::Attachment };
+        let (target_path, status) = match self.base.resolve_conflict(&target_path, &config.options).await? {
+            Some(resolved) => {
+                if !config.options.dry_run {
+                    self.base.copy_attachment(path, &resolved).await?;
                 }
+                (resolved, ImportStatus::Success)
             }
-        }
-        
-        Ok(())
-    }
+            None => (target_path, ImportStatus::Skipped),
+        };
 
+        Ok(ImportedFile {
+            source_path: path.to_string_lossy().to_string(),
+            target_path: target_path.to_string_lossy().to_string(),
+            file_type,
+            size: metadata.len(),
+            modified_time: metadata.modified().map(DateTime::from).unwrap_or_else(|_| Utc::now()),
+            status,
+            transformations: Vec::new(),
+        })
+    }
 
     fn is_markdown_or_canvas_file(&self, path: &Path) -> bool {
         if let Some(ext) = path.extension() {
@@ -1032,4 +954,106 @@ impl ObsidianImporter {
         }
         false
     }
-}
\ No newline at end of file
+}
+
+impl Default for ObsidianImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 解析 RFC 3339 时间或 `YYYY-MM-DD` 日期
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.date_naive())
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .ok()
+}
+
+/// 同时接受列表和逗号分隔的字符串
+fn string_list(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(|item| item.trim().trim_start_matches('#').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        serde_json::Value::String(text) => text
+            .split(',')
+            .map(|item| item.trim().trim_start_matches('#').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn vault() -> tempfile::TempDir {
+        let vault = tempdir().unwrap();
+        std::fs::create_dir_all(vault.path().join(".obsidian")).unwrap();
+        std::fs::write(vault.path().join(".obsidian/app.json"), "{}").unwrap();
+        std::fs::create_dir_all(vault.path().join("daily/assets")).unwrap();
+        std::fs::write(
+            vault.path().join("daily/2024-07-01.md"),
+            "---\ntags: [journal]\naliases: Today\ncreated: 2024-07-01\n---\nMet [[Alice]] #people and saw ![[photo.png]]\n",
+        )
+        .unwrap();
+        std::fs::write(vault.path().join("daily/assets/photo.png"), [0u8; 4]).unwrap();
+        std::fs::write(vault.path().join("Alice.md"), "# Alice\n").unwrap();
+        vault
+    }
+
+    fn config(vault: &Path, target: &Path) -> ImportConfig {
+        let mut config = ImportConfig::new(
+            ImporterType::Obsidian,
+            vault.to_string_lossy().to_string(),
+            target.to_string_lossy().to_string(),
+        );
+        config.options.convert_links = false;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_preview_counts_nested_files() {
+        let vault = vault();
+        let target = tempdir().unwrap();
+        std::fs::write(target.path().join("Alice.md"), "existing").unwrap();
+
+        let preview = ObsidianImporter::new().preview_import(&config(vault.path(), target.path())).await.unwrap();
+
+        assert_eq!(preview.notes, 2);
+        assert_eq!(preview.attachments, 1);
+        assert_eq!(preview.media_files, 1);
+        assert_eq!(preview.conflicts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_converts_frontmatter_and_copies_attachments() {
+        let vault = vault();
+        let target = tempdir().unwrap();
+        let progress = std::sync::Mutex::new(Vec::new());
+
+        let result = ObsidianImporter::new()
+            .import(&config(vault.path(), target.path()), &|p| progress.lock().unwrap().push((p.current, p.total)))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.errors);
+        assert_eq!(result.imported_count, 3);
+        assert_eq!(progress.into_inner().unwrap().last(), Some(&(3, 3)));
+        assert!(target.path().join("daily/assets/photo.png").exists());
+        assert!(!target.path().join(".obsidian").exists());
+
+        let imported = std::fs::read_to_string(target.path().join("daily/2024-07-01.md")).unwrap();
+        let note = crate::parser::MarkdownParser::new().parse(&imported, PathBuf::from("daily/2024-07-01.md")).unwrap();
+        assert_eq!(note.frontmatter.tags, vec!["journal", "people"]);
+        assert_eq!(note.frontmatter.date, NaiveDate::from_ymd_opt(2024, 7, 1));
+        assert_eq!(note.frontmatter.aliases, vec!["Today"]);
+        assert!(note.content.contains("[[Alice]]"));
+    }
+}
@@ -1,5 +1,9 @@
+pub mod sqlite;
+
+pub use sqlite::*;
+
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::Path;
 
 use crate::models::Note;
 
@@ -16,7 +20,7 @@ pub trait Indexer: Send + Sync {
     async fn search(&self, query: &str) -> Result<Vec<Note>>;
     
     /// 根据路径查找笔记
-    async fn find_by_path(&self, path: &PathBuf) -> Result<Option<Note>>;
+    async fn find_by_path(&self, path: &Path) -> Result<Option<Note>>;
     
     /// 获取所有笔记
     async fn get_all_notes(&self) -> Result<Vec<Note>>;
@@ -0,0 +1,1146 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use walkdir::WalkDir;
+
+use crate::db::models::{Link, LinkType, NoteRecord, SearchFilters, SearchQuery};
+use crate::config::HistorySettings;
+use crate::db::models::NoteVersion;
+use crate::error::{Error, Result};
+use crate::links::{is_note_target, LinkIndex, LinkParser, LinkResolver, NoteAnchors, RenamePlan, UnlinkedMention, WikiLink};
+use crate::models::{Frontmatter, Note};
+use crate::parser::{self, MarkdownParser};
+use crate::storage::history::record_version;
+use crate::storage::{collect_tags, NoteRepository, SqliteNoteRepository};
+
+use super::Indexer;
+
+/// 单篇笔记的索引结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexOutcome {
+    /// 新增
+    Added,
+    /// 内容变化后重新索引
+    Updated,
+    /// 哈希未变化，跳过
+    Unchanged,
+}
+
+/// 批量索引进度
+#[derive(Debug, Clone)]
+pub struct IndexProgress<'a> {
+    /// 已处理数量（从 1 开始）
+    pub current: usize,
+    /// 总数量
+    pub total: usize,
+    /// 当前文件（相对工作区）
+    pub path: &'a Path,
+    /// 处理结果，失败时为 None
+    pub outcome: Option<IndexOutcome>,
+}
+
+/// 索引失败的文件
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// 批量索引报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReindexReport {
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    pub failed: Vec<IndexFailure>,
+}
+
+impl ReindexReport {
+    /// 是否有任何笔记发生变化
+    pub fn has_changes(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+/// 基于 SQLite 的增量索引器
+///
+/// 只重新索引 `file_hash` 发生变化的笔记，笔记、标签、分类、出链和
+/// 全文索引（由触发器维护）在同一个事务中更新，被覆盖的旧内容保存为历史版本。
+pub struct SqliteIndexer {
+    repository: SqliteNoteRepository,
+    parser: MarkdownParser,
+    excluded: Vec<PathBuf>,
+}
+
+impl SqliteIndexer {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: SqliteNoteRepository::new(pool),
+            parser: MarkdownParser::new(),
+            excluded: Vec::new(),
+        }
+    }
+
+    /// 全量索引时跳过的目录（相对工作区），如模板目录
+    pub fn with_excluded_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.excluded = dirs;
+        self
+    }
+
+    /// 设置版本历史的保留策略
+    pub fn with_history(mut self, history: HistorySettings) -> Self {
+        self.repository = self.repository.with_history(history);
+        self
+    }
+
+    /// 获取底层仓库
+    pub fn repository(&self) -> &SqliteNoteRepository {
+        &self.repository
+    }
+
+    /// 索引单篇笔记
+    ///
+    /// 已存在同路径的笔记时沿用其 ID，保证反向链接不丢失。
+    pub async fn index(&self, note: &Note) -> Result<IndexOutcome> {
+        self.index_with(note, &mut None).await
+    }
+
+    /// 在单独的事务中索引笔记，批量索引时共用同一个链接解析器
+    async fn index_with(&self, note: &Note, resolver: &mut Option<LinkResolver>) -> Result<IndexOutcome> {
+        let mut tx = self.repository.pool().begin().await?;
+        let outcome = self.index_in(&mut tx, note, resolver).await?;
+        if outcome != IndexOutcome::Unchanged {
+            tx.commit().await?;
+        }
+        Ok(outcome)
+    }
+
+    /// 删除笔记索引，返回是否存在该笔记
+    pub async fn remove(&self, note_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM notes WHERE id = ?")
+            .bind(note_id)
+            .execute(self.repository.pool())
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 重新索引工作区内的全部 Markdown 文件
+    ///
+    /// 未变化的文件会被跳过，已不存在的文件会从索引中删除；
+    /// 每处理一个文件调用一次 `on_progress`。
+    pub async fn reindex_all<F>(&self, root: &Path, mut on_progress: F) -> Result<ReindexReport>
+    where
+        F: FnMut(IndexProgress<'_>),
+    {
+        let files = collect_markdown_files(root, &self.excluded);
+        let total = files.len();
+        let mut report = ReindexReport::default();
+        let mut seen = HashSet::new();
+        let mut resolver = None;
+
+        for (i, path) in files.iter().enumerate() {
+            seen.insert(path.to_string_lossy().to_string());
+
+            let indexed = match self.load_file(root, path).await {
+                Ok(note) => self.index_with(&note, &mut resolver).await,
+                Err(e) => Err(e),
+            };
+            let outcome = match indexed {
+                Ok(outcome) => {
+                    match outcome {
+                        IndexOutcome::Added => report.added += 1,
+                        IndexOutcome::Updated => report.updated += 1,
+                        IndexOutcome::Unchanged => report.unchanged += 1,
+                    }
+                    Some(outcome)
+                }
+                Err(e) => {
+                    log::warn!("索引失败 {}: {}", path.display(), e);
+                    report.failed.push(IndexFailure { path: path.clone(), error: e.to_string() });
+                    None
+                }
+            };
+
+            on_progress(IndexProgress { current: i + 1, total, path, outcome });
+        }
+
+        // 删除文件已不存在的笔记，回收站中的笔记由回收站管理
+        let indexed: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, file_path FROM notes WHERE id NOT IN (SELECT note_id FROM trash)"
+        )
+        .fetch_all(self.repository.pool())
+        .await?;
+        for (id, file_path) in indexed {
+            if !seen.contains(&file_path) && self.remove(&id).await? {
+                report.removed += 1;
+            }
+        }
+
+        // 标题或路径变化会影响其他笔记的链接解析
+        if report.has_changes() {
+            self.refresh_links().await?;
+        }
+
+        Ok(report)
+    }
+
+    /// 重新解析所有笔记的出链
+    pub async fn refresh_links(&self) -> Result<()> {
+        let mut tx = self.repository.pool().begin().await?;
+        let resolver = LinkResolver::load(&mut tx).await?;
+
+        let notes: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT id, file_path, COALESCE(source, content) FROM notes WHERE status != 'deleted'"
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (id, file_path, content) in notes {
+            self.write_links(&mut tx, &resolver, &id, Path::new(&file_path), &content).await?;
+            SqliteNoteRepository::update_note_anchors(&mut tx, &id, &NoteAnchors::parse(&content)).await?;
+        }
+        // 升级前索引的笔记没有链接原文和锚点，由此判断是否需要补全
+        sqlx::query("INSERT OR REPLACE INTO system_config (key, value) VALUES ('link_refs_indexed', 'true')")
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// 索引单个文件并增量更新受影响的链接，`path` 为相对工作区的路径
+    ///
+    /// 新笔记可能修复其他笔记中的断链，标题或别名变化会改变指向它的链接；
+    /// 只重新解析原本链接到它或正文提到其文件名、新旧标题和别名的笔记，而不是全部笔记。
+    pub async fn index_path(&self, root: &Path, path: &Path) -> Result<IndexOutcome> {
+        let file_path = path.to_string_lossy().to_string();
+        let old = self.repository.get_note_by_path(&file_path).await?;
+        let old_title = old.as_ref().map(|note| note.title.clone());
+        let old_aliases = old.as_ref().map(|note| record_aliases(&note.frontmatter)).unwrap_or_default();
+
+        let outcome = self.index_file(root, path).await?;
+        if outcome == IndexOutcome::Unchanged {
+            return Ok(outcome);
+        }
+        let Some(note) = self.repository.get_note_by_path(&file_path).await? else {
+            return Ok(outcome);
+        };
+        let aliases = record_aliases(&note.frontmatter);
+        if old_title.as_deref() == Some(note.title.as_str()) && old_aliases == aliases {
+            return Ok(outcome);
+        }
+
+        let mut names = vec![note.title.clone()];
+        names.extend(old_title);
+        names.extend(path.file_stem().map(|stem| stem.to_string_lossy().to_string()));
+        names.extend(aliases);
+        names.extend(old_aliases);
+        self.refresh_links_to(&[note.id.as_str()], &names).await?;
+        Ok(outcome)
+    }
+
+    /// 更新文件监控导入的笔记所影响的链接
+    ///
+    /// `paths` 为新增、更新或重命名后的笔记路径（相对工作区），这些笔记由知识库导入，
+    /// 只写入了笔记本身；这里重新解析它们的出链和锚点，再重新解析原本链接到它们的笔记，
+    /// 以及正文提到新增或失去的名称（`previous_names` 为变化前的标题、文件名和别名）的笔记。
+    pub async fn refresh_changed(&self, paths: &[PathBuf], previous_names: &[String]) -> Result<()> {
+        let mut tx = self.repository.pool().begin().await?;
+        let resolver = LinkResolver::load(&mut tx).await?;
+
+        let mut note_ids = Vec::new();
+        let mut current_names = Vec::new();
+        for path in paths {
+            let note: Option<(String, String, String, String)> = sqlx::query_as(
+                "SELECT id, title, frontmatter, COALESCE(source, content) FROM notes WHERE file_path = ? AND status != 'deleted'"
+            )
+            .bind(path.to_string_lossy().to_string())
+            .fetch_optional(&mut *tx)
+            .await?;
+            let Some((id, title, frontmatter, content)) = note else {
+                continue;
+            };
+            self.write_links(&mut tx, &resolver, &id, path, &content).await?;
+            SqliteNoteRepository::update_note_anchors(&mut tx, &id, &NoteAnchors::parse(&content)).await?;
+
+            current_names.push(title);
+            current_names.extend(path.file_stem().map(|stem| stem.to_string_lossy().to_string()));
+            current_names.extend(record_aliases(&frontmatter));
+            note_ids.push(id);
+        }
+
+        // 前后都有的名称解析结果不变
+        let key = |name: &String| name.trim().to_lowercase();
+        let previous: HashSet<String> = previous_names.iter().map(key).collect();
+        let current: HashSet<String> = current_names.iter().map(key).collect();
+        let names: Vec<String> = previous_names
+            .iter()
+            .chain(&current_names)
+            .filter(|name| !(previous.contains(&key(name)) && current.contains(&key(name))))
+            .cloned()
+            .collect();
+
+        let note_ids: Vec<&str> = note_ids.iter().map(String::as_str).collect();
+        self.refresh_links_to_in(&mut tx, &note_ids, &names).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// 重新解析可能指向 `note_ids` 的笔记的出链：已经链接到它们的，以及正文包含 `names` 之一的
+    async fn refresh_links_to(&self, note_ids: &[&str], names: &[String]) -> Result<()> {
+        let mut tx = self.repository.pool().begin().await?;
+        self.refresh_links_to_in(&mut tx, note_ids, names).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// 在调用方的事务中执行 [`refresh_links_to`](Self::refresh_links_to)
+    async fn refresh_links_to_in(&self, conn: &mut SqliteConnection, note_ids: &[&str], names: &[String]) -> Result<()> {
+        let resolver = LinkResolver::load(&mut *conn).await?;
+
+        let mut notes: Vec<(String, String, String)> = Vec::new();
+        for note_id in note_ids {
+            notes.extend(
+                sqlx::query_as::<_, (String, String, String)>(
+                    r#"
+                    SELECT id, file_path, COALESCE(source, content) FROM notes
+                    WHERE status != 'deleted' AND id IN (SELECT source_id FROM links WHERE target_id = ?)
+                    "#
+                )
+                .bind(note_id)
+                .fetch_all(&mut *conn)
+                .await?,
+            );
+        }
+        for name in names.iter().filter(|name| !name.trim().is_empty()) {
+            // LIKE 只对 ASCII 忽略大小写，名称中的 % 和 _ 最多多匹配几篇笔记
+            notes.extend(
+                sqlx::query_as::<_, (String, String, String)>(
+                    r#"
+                    SELECT id, file_path, COALESCE(source, content) FROM notes
+                    WHERE status != 'deleted' AND content LIKE '%' || ? || '%'
+                    "#
+                )
+                .bind(name.trim())
+                .fetch_all(&mut *conn)
+                .await?,
+            );
+        }
+
+        let mut seen = HashSet::new();
+        for (id, file_path, content) in notes {
+            if note_ids.contains(&id.as_str()) || !seen.insert(id.clone()) {
+                continue;
+            }
+            self.write_links(&mut *conn, &resolver, &id, Path::new(&file_path), &content).await?;
+        }
+        Ok(())
+    }
+
+    /// 将笔记恢复到历史版本，返回该版本和写入的文件路径（相对工作区）
+    ///
+    /// 版本内容写回笔记当前的文件，笔记已删除时写回版本记录的路径。
+    /// 写入前先索引当前文件，当前内容因此也会保存为新版本，恢复可以撤销。
+    pub async fn restore_version(&self, root: &Path, note_id: &str, version: i64) -> Result<(NoteVersion, PathBuf)> {
+        let snapshot = self.repository.get_note_version(note_id, version).await?
+            .ok_or_else(|| Error::not_found(format!("笔记 {} 的版本 {}", note_id, version)))?;
+        if self.repository.list_trash().await?.iter().any(|entry| entry.note_id == note_id) {
+            return Err(Error::validation(format!("笔记 {} 在回收站中，请先恢复", note_id)));
+        }
+
+        let path = match self.repository.get_note_by_id(note_id).await? {
+            Some(note) => {
+                let path = PathBuf::from(note.file_path);
+                if root.join(&path).exists() {
+                    self.index_file(root, &path).await?;
+                }
+                path
+            }
+            None => {
+                let path = PathBuf::from(&snapshot.file_path);
+                if root.join(&path).exists() {
+                    return Err(Error::validation(format!("{} 已被其他文件占用", path.display())));
+                }
+                path
+            }
+        };
+
+        let full_path = root.join(&path);
+        if let Some(parent) = full_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&full_path, &snapshot.content).await?;
+
+        // 沿用原 ID，已删除的笔记恢复后反向链接仍然有效
+        let mut note = self.load_file(root, &path).await?;
+        note.id = uuid::Uuid::parse_str(note_id)?;
+        if self.index(&note).await? != IndexOutcome::Unchanged {
+            self.refresh_links().await?;
+        }
+
+        Ok((snapshot, path))
+    }
+
+    /// 重命名或移动笔记，并改写其他笔记中指向它的链接，`to` 为相对工作区的新路径
+    ///
+    /// `dry_run` 时只返回改写计划。否则先移动文件、写入改写后的内容，再在一个事务中
+    /// 更新索引；任何一步失败都会恢复已改写的文件并把笔记移回原处。
+    pub async fn rename_note(&self, root: &Path, note_id: &str, to: &Path, dry_run: bool) -> Result<RenamePlan> {
+        if self.repository.list_trash().await?.iter().any(|entry| entry.note_id == note_id) {
+            return Err(Error::validation(format!("笔记 {} 在回收站中，请先恢复", note_id)));
+        }
+        let is_markdown = matches!(to.extension().and_then(|ext| ext.to_str()), Some("md" | "markdown"));
+        if !is_markdown || !is_relative_path(to) {
+            return Err(Error::validation(format!("新路径必须是工作区内的 Markdown 文件: {}", to.display())));
+        }
+        if root.join(to).exists() {
+            return Err(Error::validation(format!("{} 已存在", to.display())));
+        }
+
+        let index = LinkIndex::load(self.repository.pool(), root).await?;
+        let sources = self.rename_sources(root, &index, &[note_id]).await?;
+        let plan = RenamePlan::new(&index, note_id, to, &sources)?;
+        self.execute_rename(root, &index, plan, sources, dry_run).await
+    }
+
+    /// 重命名或移动文件夹，并改写其他笔记中指向其中笔记的链接，路径都相对工作区
+    ///
+    /// 文件夹整体移动，其中的附件等其他文件随之移动；失败时的恢复方式与 [`Self::rename_note`] 相同。
+    pub async fn rename_folder(&self, root: &Path, from: &Path, to: &Path, dry_run: bool) -> Result<RenamePlan> {
+        if !is_relative_path(from) || !is_relative_path(to) || to.starts_with(from) {
+            return Err(Error::validation(format!("无法将 {} 移动到 {}", from.display(), to.display())));
+        }
+        if !root.join(from).is_dir() {
+            return Err(Error::not_found(format!("文件夹 {}", from.display())));
+        }
+        if root.join(to).exists() {
+            return Err(Error::validation(format!("{} 已存在", to.display())));
+        }
+
+        let index = LinkIndex::load(self.repository.pool(), root).await?;
+        let note_ids = index.notes_under(from);
+        let sources = self.rename_sources(root, &index, &note_ids).await?;
+        let plan = RenamePlan::for_folder(&index, from, to, &sources)?;
+        self.execute_rename(root, &index, plan, sources, dry_run).await
+    }
+
+    /// 重命名时可能需要改写的笔记的 `(ID, 文件内容)`：移动的笔记和链接到它们的笔记
+    async fn rename_sources(&self, root: &Path, index: &LinkIndex, note_ids: &[&str]) -> Result<Vec<(String, String)>> {
+        let mut source_ids: Vec<String> = note_ids.iter().map(|id| id.to_string()).collect();
+        for note_id in note_ids {
+            source_ids.extend(index.get_backlinks(note_id).into_iter().map(|b| b.source_note_id));
+        }
+        let mut seen = HashSet::new();
+        let mut sources = Vec::new();
+        for id in source_ids {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            if let Some(path) = index.note_path(&id) {
+                sources.push((id, tokio::fs::read_to_string(root.join(path)).await?));
+            }
+        }
+        Ok(sources)
+    }
+
+    /// 执行重命名计划，失败时恢复已改写的文件并把文件或文件夹移回原处
+    async fn execute_rename(
+        &self,
+        root: &Path,
+        index: &LinkIndex,
+        plan: RenamePlan,
+        sources: Vec<(String, String)>,
+        dry_run: bool,
+    ) -> Result<RenamePlan> {
+        if dry_run {
+            return Ok(plan);
+        }
+
+        // 新路径和文件名可能让其他笔记中原本的断链解析到移动的笔记
+        let mut names = Vec::new();
+        for note_move in &plan.moves {
+            names.extend(note_move.to.file_stem().map(|s| s.to_string_lossy().to_string()));
+            names.extend(index.note_title(&note_move.note_id).map(str::to_string));
+        }
+        names.sort();
+        names.dedup();
+
+        let originals: HashMap<String, String> = sources.into_iter().collect();
+        let mut written = Vec::new();
+        let mut moved = false;
+        if let Err(e) = self.apply_rename(root, &plan, &originals, &names, &mut written, &mut moved).await {
+            for (path, original) in &written {
+                if let Err(e) = tokio::fs::write(root.join(path), original).await {
+                    log::warn!("无法恢复 {}: {}", path.display(), e);
+                }
+            }
+            if moved {
+                if let Err(e) = tokio::fs::rename(root.join(&plan.to), root.join(&plan.from)).await {
+                    log::warn!("无法将 {} 移回 {}: {}", plan.to.display(), plan.from.display(), e);
+                }
+            }
+            return Err(e);
+        }
+        Ok(plan)
+    }
+
+    /// 执行重命名计划，记录已写入的文件及其原文和是否已移动，供失败时回滚
+    ///
+    /// 数据库中的路径、改写的笔记和指向移动笔记的链接在同一个事务中更新。
+    async fn apply_rename<'a>(
+        &self,
+        root: &Path,
+        plan: &'a RenamePlan,
+        originals: &'a HashMap<String, String>,
+        names: &[String],
+        written: &mut Vec<(&'a Path, &'a str)>,
+        moved: &mut bool,
+    ) -> Result<()> {
+        let to = root.join(&plan.to);
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::rename(root.join(&plan.from), &to).await?;
+        *moved = true;
+
+        for rewrite in &plan.rewrites {
+            // 改写期间文件被修改过时放弃，避免覆盖新内容
+            let current = tokio::fs::read_to_string(root.join(&rewrite.path)).await?;
+            let original = match originals.get(&rewrite.note_id) {
+                Some(original) if *original == current => original,
+                _ => return Err(Error::validation(format!("{} 在重命名期间被修改", rewrite.path.display()))),
+            };
+            written.push((&rewrite.path, original));
+            tokio::fs::write(root.join(&rewrite.path), &rewrite.content).await?;
+        }
+
+        let mut tx = self.repository.pool().begin().await?;
+        for note_move in &plan.moves {
+            sqlx::query("UPDATE notes SET file_path = ? WHERE id = ?")
+                .bind(note_move.to.to_string_lossy().to_string())
+                .bind(&note_move.note_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        let mut paths: Vec<&Path> = plan.rewrites.iter().map(|r| r.path.as_path()).collect();
+        for note_move in &plan.moves {
+            if !paths.contains(&note_move.to.as_path()) {
+                paths.push(&note_move.to);
+            }
+        }
+        let mut resolver = None;
+        for path in paths {
+            let note = self.load_file(root, path).await?;
+            self.index_in(&mut tx, &note, &mut resolver).await?;
+        }
+        let note_ids: Vec<&str> = plan.moves.iter().map(|m| m.note_id.as_str()).collect();
+        self.refresh_links_to_in(&mut tx, &note_ids, names).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// 把未链接的提及改为链接并重新索引改动的笔记，返回改写的文件数
+    ///
+    /// 写入前核对每个提及的位置上仍是查找时的原文，任一文件不符时不做任何改动；
+    /// 写入或重新索引失败时恢复已改写的文件。
+    pub async fn link_mentions(&self, root: &Path, mentions: &[UnlinkedMention]) -> Result<usize> {
+        let mut by_path: BTreeMap<&str, Vec<UnlinkedMention>> = BTreeMap::new();
+        for mention in mentions {
+            by_path.entry(&mention.source_note_path).or_default().push(mention.clone());
+        }
+
+        let parser = LinkParser::new();
+        let mut rewrites = Vec::new();
+        for (path, mentions) in &by_path {
+            let content = tokio::fs::read_to_string(root.join(path)).await?;
+            let linked = parser
+                .link_mentions(&content, mentions)
+                .ok_or_else(|| Error::validation(format!("{} 已修改，请重新查找未链接的提及", path)))?;
+            rewrites.push((Path::new(*path), content, linked));
+        }
+
+        let mut written = Vec::new();
+        if let Err(e) = self.apply_link_mentions(root, &rewrites, &mut written).await {
+            for (path, original, _) in &rewrites[..written.len()] {
+                if let Err(e) = tokio::fs::write(root.join(path), original).await {
+                    log::warn!("无法恢复 {}: {}", path.display(), e);
+                    continue;
+                }
+                if let Err(e) = self.index_path(root, path).await {
+                    log::warn!("无法重新索引 {}: {}", path.display(), e);
+                }
+            }
+            return Err(e);
+        }
+        Ok(rewrites.len())
+    }
+
+    /// 写入改写后的内容并重新索引，记录已写入的文件，供失败时回滚
+    async fn apply_link_mentions(
+        &self,
+        root: &Path,
+        rewrites: &[(&Path, String, String)],
+        written: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        for (path, original, linked) in rewrites {
+            // 核对之后文件被修改过时放弃，避免覆盖新内容
+            let current = tokio::fs::read_to_string(root.join(path)).await?;
+            if &current != original {
+                return Err(Error::validation(format!("{} 在改写期间被修改", path.display())));
+            }
+            written.push(path.to_path_buf());
+            tokio::fs::write(root.join(path), linked).await?;
+        }
+        for (path, _, _) in rewrites {
+            self.index_path(root, path).await?;
+        }
+        Ok(())
+    }
+
+    /// 读取并索引单个文件，`path` 为相对工作区的路径
+    async fn index_file(&self, root: &Path, path: &Path) -> Result<IndexOutcome> {
+        let note = self.load_file(root, path).await?;
+        self.index(&note).await
+    }
+
+    /// 读取并解析单个文件，`path` 为相对工作区的路径
+    async fn load_file(&self, root: &Path, path: &Path) -> Result<Note> {
+        let full_path = root.join(path);
+        let content = tokio::fs::read_to_string(&full_path).await?;
+        let metadata = tokio::fs::metadata(&full_path).await?;
+
+        let mut note = self.parser.parse(&content, path.to_path_buf())?;
+        note.file_size = metadata.len();
+        if let Ok(modified) = metadata.modified() {
+            note.modified_at = modified.into();
+        }
+
+        Ok(note)
+    }
+
+    /// 在调用方的事务中索引笔记
+    ///
+    /// `resolver` 为空时在需要解析链接时从数据库加载，之后随索引的笔记增量更新，
+    /// 批量索引时只需加载一次。
+    async fn index_in(
+        &self,
+        conn: &mut SqliteConnection,
+        note: &Note,
+        resolver: &mut Option<LinkResolver>,
+    ) -> Result<IndexOutcome> {
+        let file_path = note.path.to_string_lossy().to_string();
+
+        // 优先按路径匹配，其次按 ID 匹配，不匹配回收站中的笔记
+        let existing: Option<(String, String, String, bool)> = sqlx::query_as(
+            r#"
+            SELECT id, file_hash, status, source IS NOT NULL FROM notes
+            WHERE (file_path = ? OR id = ?) AND id NOT IN (SELECT note_id FROM trash)
+            ORDER BY file_path = ? DESC
+            LIMIT 1
+            "#
+        )
+        .bind(&file_path)
+        .bind(note.id.to_string())
+        .bind(&file_path)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let mut record = NoteRecord::from(note);
+        let outcome = match existing {
+            // 没有保存原文的旧记录需要重新索引一次
+            Some((_, hash, status, has_source))
+                if hash == note.checksum && status != "deleted" && (has_source || note.source.is_none()) =>
+            {
+                return Ok(IndexOutcome::Unchanged);
+            }
+            Some((id, _, _, _)) => {
+                record.id = id;
+                // 覆盖前保存旧内容
+                record_version(&mut *conn, &record, self.repository.history()).await?;
+                IndexOutcome::Updated
+            }
+            None => IndexOutcome::Added,
+        };
+
+        let tags = collect_tags(&note.frontmatter, &note.content);
+
+        sqlx::query(
+            r#"
+            INSERT INTO notes (
+                id, title, file_path, content, html_content, word_count, reading_time,
+                created_at, modified_at, indexed_at, status, frontmatter, file_size, file_hash, source
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                file_path = excluded.file_path,
+                content = excluded.content,
+                html_content = excluded.html_content,
+                word_count = excluded.word_count,
+                reading_time = excluded.reading_time,
+                modified_at = excluded.modified_at,
+                indexed_at = excluded.indexed_at,
+                status = excluded.status,
+                frontmatter = excluded.frontmatter,
+                file_size = excluded.file_size,
+                file_hash = excluded.file_hash,
+                source = excluded.source
+            "#
+        )
+        .bind(&record.id)
+        .bind(&record.title)
+        .bind(&record.file_path)
+        .bind(&record.content)
+        .bind(&record.html_content)
+        .bind(record.word_count)
+        .bind(record.reading_time)
+        .bind(record.created_at)
+        .bind(record.modified_at)
+        .bind(&record.status)
+        .bind(&record.frontmatter)
+        .bind(record.file_size)
+        .bind(&record.file_hash)
+        .bind(&record.source)
+        .execute(&mut *conn)
+        .await?;
+
+        SqliteNoteRepository::update_note_tags(&mut *conn, &record.id, &tags).await?;
+        SqliteNoteRepository::update_note_categories(&mut *conn, &record.id, &note.frontmatter.categories).await?;
+
+        if resolver.is_none() {
+            *resolver = Some(LinkResolver::load(&mut *conn).await?);
+        }
+        let resolver = resolver.as_mut().expect("链接解析器已加载");
+        resolver.insert(&record.id, &record.title, &record.file_path, &note.frontmatter.aliases);
+        let text = note.source.as_deref().unwrap_or(&note.content);
+        self.write_links(&mut *conn, resolver, &record.id, &note.path, text).await?;
+        SqliteNoteRepository::update_note_anchors(&mut *conn, &record.id, &NoteAnchors::parse(text)).await?;
+
+        Ok(outcome)
+    }
+
+    /// 写入笔记的出链，以及全部指向笔记的链接原文和解析结果（解析不到的也保存，用于检查断链）
+    async fn write_links(
+        &self,
+        conn: &mut SqliteConnection,
+        resolver: &LinkResolver,
+        note_id: &str,
+        path: &Path,
+        content: &str,
+    ) -> Result<()> {
+        let links = self.resolve_links(resolver, note_id, path, content)?;
+        SqliteNoteRepository::update_note_links(&mut *conn, note_id, &links).await?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let refs: Vec<(WikiLink, Option<String>)> = LinkParser::new()
+            .parse_links(content)
+            .links
+            .into_iter()
+            .filter(is_note_target)
+            .map(|link| {
+                let target_id = resolver.resolve_link(base, &link).map(str::to_string);
+                (link, target_id)
+            })
+            .collect();
+        SqliteNoteRepository::update_link_refs(&mut *conn, note_id, &refs).await
+    }
+
+    /// 解析笔记中指向其他笔记的链接，无法解析的链接会被忽略
+    ///
+    /// `content` 为文件原文时行号相对于完整文件，旧记录只有正文时相对于正文。
+    fn resolve_links(&self, resolver: &LinkResolver, note_id: &str, path: &Path, content: &str) -> Result<Vec<Link>> {
+        let parsed = self.parser.parse_content(content)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut links = Vec::new();
+
+        for wiki in &parsed.wiki_links {
+            if let Some(target_id) = resolver.resolve_wiki(&wiki.target) {
+                let anchor = wiki.display_text.clone().unwrap_or_else(|| wiki.target.clone());
+                let mut link = Link::new(note_id.to_string(), target_id.to_string(), anchor);
+                link.source_line = wiki.line as i32;
+                link.source_column = wiki.column as i32;
+                links.push(link);
+            }
+        }
+
+        for md in &parsed.links {
+            let link_type = match md.link_type {
+                parser::LinkType::Internal => LinkType::Reference,
+                parser::LinkType::Image => LinkType::Embed,
+                _ => continue,
+            };
+            if let Some(target_id) = resolver.resolve_path(base, &md.url) {
+                let mut link = Link::new(note_id.to_string(), target_id.to_string(), md.text.clone());
+                link.link_type = link_type.to_string();
+                link.source_line = md.line as i32;
+                link.source_column = md.column as i32;
+                links.push(link);
+            }
+        }
+
+        Ok(links)
+    }
+}
+
+#[async_trait::async_trait]
+impl Indexer for SqliteIndexer {
+    async fn index_note(&mut self, note: &Note) -> anyhow::Result<()> {
+        self.index(note).await?;
+        Ok(())
+    }
+
+    async fn remove_note(&mut self, note_id: &str) -> anyhow::Result<()> {
+        self.remove(note_id).await?;
+        Ok(())
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<Note>> {
+        let query = SearchQuery {
+            query: query.to_string(),
+            limit: None,
+            offset: None,
+            filters: SearchFilters::default(),
+        };
+        let results = self.repository.search_notes(&query).await?;
+        Ok(results
+            .into_iter()
+            .map(|result| Note::try_from(result.note))
+            .collect::<Result<_>>()?)
+    }
+
+    async fn find_by_path(&self, path: &Path) -> anyhow::Result<Option<Note>> {
+        let record = self.repository.get_note_by_path(&path.to_string_lossy()).await?;
+        Ok(record.map(Note::try_from).transpose()?)
+    }
+
+    async fn get_all_notes(&self) -> anyhow::Result<Vec<Note>> {
+        let records = self.repository.list_notes(None).await?;
+        Ok(records
+            .into_iter()
+            .map(Note::try_from)
+            .collect::<Result<_>>()?)
+    }
+}
+
+/// 笔记记录中以 JSON 保存的前言里的别名
+pub(crate) fn record_aliases(frontmatter: &str) -> Vec<String> {
+    serde_json::from_str::<Frontmatter>(frontmatter)
+        .map(|frontmatter| frontmatter.aliases)
+        .unwrap_or_default()
+}
+
+/// 非空且只由普通组成部分构成的相对路径，不会指向工作区之外
+fn is_relative_path(path: &Path) -> bool {
+    path.components().next().is_some() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// 统一路径分隔符、去掉 `.md` 扩展名并转为小写
+pub(crate) fn normalize_key(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    let path = path
+        .strip_suffix(".md")
+        .or_else(|| path.strip_suffix(".markdown"))
+        .unwrap_or(path);
+    path.to_lowercase()
+}
+
+/// 收集工作区内的 Markdown 文件（相对路径，跳过隐藏目录和排除的目录）
+pub(crate) fn collect_markdown_files(root: &Path, excluded: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            !hidden && !excluded.iter().any(|dir| relative == dir)
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            matches!(
+                entry.path().extension().and_then(|ext| ext.to_str()),
+                Some("md") | Some("markdown")
+            )
+        })
+        .filter_map(|entry| entry.path().strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::calculate_checksum;
+    use crate::storage::DiffKind;
+    use tempfile::{tempdir, TempDir};
+
+    async fn setup() -> (TempDir, SqliteIndexer) {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        (dir, SqliteIndexer::new(db.pool().clone()))
+    }
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join("notes").join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_index_is_incremental() {
+        let (_dir, mut indexer) = setup().await;
+        let parser = MarkdownParser::new();
+
+        let note = parser.parse("---\ntags: [rust]\n---\n# Alpha\n\nfirst draft #idea", PathBuf::from("alpha.md")).unwrap();
+        assert_eq!(indexer.index(&note).await.unwrap(), IndexOutcome::Added);
+        assert_eq!(indexer.index(&note).await.unwrap(), IndexOutcome::Unchanged);
+
+        let found = indexer.search("draft").await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "Alpha");
+
+        let edited = parser.parse("# Alpha\n\nsecond revision", PathBuf::from("alpha.md")).unwrap();
+        assert_eq!(indexer.index(&edited).await.unwrap(), IndexOutcome::Updated);
+        assert!(indexer.search("draft").await.unwrap().is_empty());
+        assert_eq!(indexer.search("revision").await.unwrap().len(), 1);
+
+        // 重新索引沿用原 ID，标签随内容更新
+        let stored = indexer.find_by_path(Path::new("alpha.md")).await.unwrap().unwrap();
+        assert_eq!(stored.id, note.id);
+        let detail = indexer.repository().get_note_detail(&note.id.to_string()).await.unwrap().unwrap();
+        assert!(detail.tags.is_empty());
+
+        indexer.remove_note(&note.id.to_string()).await.unwrap();
+        assert!(indexer.get_all_notes().await.unwrap().is_empty());
+        assert!(indexer.search("revision").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tags_and_links_are_indexed() {
+        let (_dir, indexer) = setup().await;
+        let parser = MarkdownParser::new();
+
+        let target = parser.parse("# Target Note\n\ncontent", PathBuf::from("notes/target.md")).unwrap();
+        indexer.index(&target).await.unwrap();
+
+        let source = parser.parse(
+            "---\ntags: [rust]\ncategories: [dev]\n---\n# Source\n\nsee [[Target Note]] and [t](target.md) #idea",
+            PathBuf::from("notes/source.md"),
+        ).unwrap();
+        indexer.index(&source).await.unwrap();
+
+        let detail = indexer.repository().get_note_detail(&source.id.to_string()).await.unwrap().unwrap();
+        let tags: Vec<_> = detail.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tags, vec!["idea", "rust"]);
+        assert_eq!(detail.categories[0].name, "dev");
+        assert_eq!(detail.outbound_links.len(), 2);
+        assert!(detail.outbound_links.iter().all(|l| l.target_id == target.id.to_string()));
+        // 行号相对于完整文件，重新解析全部链接后不变
+        assert!(detail.outbound_links.iter().all(|l| l.source_line == 7));
+        indexer.refresh_links().await.unwrap();
+        let links = indexer.repository().get_outbound_links(&source.id.to_string()).await.unwrap();
+        assert!(links.iter().all(|l| l.source_line == 7));
+
+        // 标签进入全文索引
+        assert_eq!(indexer.search("tags:rust").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reindex_all_reports_progress() {
+        let (dir, indexer) = setup().await;
+        let indexer = indexer.with_excluded_dirs(vec![PathBuf::from("templates")]);
+        std::fs::create_dir_all(dir.path().join("templates")).unwrap();
+        std::fs::write(dir.path().join("templates/default.md"), "# {{ title }}").unwrap();
+        write(dir.path(), "a.md", "# A\n\nlinks to [[B]]");
+        write(dir.path(), "sub/b.md", "# B\n\nbody");
+        write(dir.path(), "broken.md", "---\ntitle: [oops\n---\nbody");
+
+        let mut progress = Vec::new();
+        let report = indexer
+            .reindex_all(dir.path(), |p| progress.push((p.current, p.total, p.outcome)))
+            .await
+            .unwrap();
+        assert_eq!(report.added, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].path, PathBuf::from("notes/broken.md"));
+        assert_eq!(progress.len(), 3);
+        assert!(progress.iter().all(|(_, total, _)| *total == 3));
+
+        // 先索引的 a.md 在批量结束后也能链接到 b.md
+        let a = indexer.repository().get_note_by_path("notes/a.md").await.unwrap().unwrap();
+        assert_eq!(indexer.repository().get_outbound_links(&a.id).await.unwrap().len(), 1);
+
+        let report = indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        assert_eq!(report.unchanged, 2);
+        assert!(!report.has_changes());
+
+        std::fs::remove_file(dir.path().join("notes/sub/b.md")).unwrap();
+        let report = indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(indexer.repository().get_outbound_links(&a.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_path_updates_affected_links() {
+        let (dir, indexer) = setup().await;
+        let repo = indexer.repository();
+        write(dir.path(), "a.md", "# A\n\nsee [[Beta]]");
+        write(dir.path(), "c.md", "# C\n\nsee [[Gamma]]");
+        indexer.index_path(dir.path(), Path::new("notes/a.md")).await.unwrap();
+        indexer.index_path(dir.path(), Path::new("notes/c.md")).await.unwrap();
+        let a = repo.get_note_by_path("notes/a.md").await.unwrap().unwrap();
+        let c = repo.get_note_by_path("notes/c.md").await.unwrap().unwrap();
+        assert!(repo.get_outbound_links(&a.id).await.unwrap().is_empty());
+
+        // 新笔记修复已有的断链
+        write(dir.path(), "b.md", "# Beta");
+        let outcome = indexer.index_path(dir.path(), Path::new("notes/b.md")).await.unwrap();
+        assert_eq!(outcome, IndexOutcome::Added);
+        let b = repo.get_note_by_path("notes/b.md").await.unwrap().unwrap();
+        assert_eq!(repo.get_inbound_links(&b.id).await.unwrap()[0].source_id, a.id);
+
+        // 改标题后旧链接失效，提到新标题的笔记链接过来
+        write(dir.path(), "b.md", "# Gamma");
+        indexer.index_path(dir.path(), Path::new("notes/b.md")).await.unwrap();
+        let inbound = repo.get_inbound_links(&b.id).await.unwrap();
+        assert_eq!(inbound.len(), 1);
+        assert_eq!(inbound[0].source_id, c.id);
+
+        // 只增加别名，按别名写的链接也解析过来
+        write(dir.path(), "b.md", "---\naliases: [beta]\n---\n# Gamma");
+        indexer.index_path(dir.path(), Path::new("notes/b.md")).await.unwrap();
+        assert_eq!(repo.get_inbound_links(&b.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rename_note_rewrites_links_and_rolls_back() {
+        let (dir, indexer) = setup().await;
+        let repo = indexer.repository();
+        write(dir.path(), "a.md", "see [[old]] and ![[old#图|300]]");
+        write(dir.path(), "old.md", "no title");
+        write(dir.path(), "stale.md", "# Stale");
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let old = repo.get_note_by_path("notes/old.md").await.unwrap().unwrap();
+
+        // 试运行不改动任何文件
+        let plan = indexer.rename_note(dir.path(), &old.id, Path::new("notes/new.md"), true).await.unwrap();
+        assert_eq!(plan.link_count(), 2);
+        assert!(dir.path().join("notes/old.md").exists());
+
+        // 新路径被索引中的旧记录占用，更新索引失败后全部恢复
+        std::fs::remove_file(dir.path().join("notes/stale.md")).unwrap();
+        let err = indexer.rename_note(dir.path(), &old.id, Path::new("notes/stale.md"), false).await;
+        assert!(err.is_err());
+        assert!(dir.path().join("notes/old.md").exists());
+        assert!(!dir.path().join("notes/stale.md").exists());
+        let a = std::fs::read_to_string(dir.path().join("notes/a.md")).unwrap();
+        assert_eq!(a, "see [[old]] and ![[old#图|300]]");
+
+        indexer.rename_note(dir.path(), &old.id, Path::new("notes/sub/new.md"), false).await.unwrap();
+        let a = std::fs::read_to_string(dir.path().join("notes/a.md")).unwrap();
+        assert_eq!(a, "see [[new]] and ![[new#图|300]]");
+        assert!(!dir.path().join("notes/old.md").exists());
+        let moved = repo.get_note_by_path("notes/sub/new.md").await.unwrap().unwrap();
+        assert_eq!(moved.id, old.id);
+        assert_eq!(repo.get_inbound_links(&old.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rename_folder_moves_notes_and_rewrites_links() {
+        let (dir, indexer) = setup().await;
+        let repo = indexer.repository();
+        write(dir.path(), "a.md", "[[proj/x]] [link](proj/y.md) [[X]]");
+        write(dir.path(), "proj/x.md", "# X\n\n[y](y.md) [[proj/y]]");
+        write(dir.path(), "proj/y.md", "# Y");
+        write(dir.path(), "proj/img.png", "png");
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let x = repo.get_note_by_path("notes/proj/x.md").await.unwrap().unwrap();
+
+        let plan = indexer.rename_folder(dir.path(), Path::new("notes/proj"), Path::new("notes/work"), true).await.unwrap();
+        assert_eq!(plan.moves.len(), 2);
+        assert_eq!(plan.link_count(), 3);
+        assert!(dir.path().join("notes/proj/x.md").exists());
+        assert!(indexer.rename_folder(dir.path(), Path::new("notes/proj"), Path::new("notes/proj/sub"), false).await.is_err());
+        assert!(indexer.rename_folder(dir.path(), Path::new("notes/proj"), Path::new("../out"), false).await.is_err());
+
+        indexer.rename_folder(dir.path(), Path::new("notes/proj"), Path::new("notes/work"), false).await.unwrap();
+        assert!(!dir.path().join("notes/proj").exists());
+        assert!(dir.path().join("notes/work/img.png").exists());
+        let a = std::fs::read_to_string(dir.path().join("notes/a.md")).unwrap();
+        assert_eq!(a, "[[x]] [link](work/y.md) [[X]]");
+        let moved = std::fs::read_to_string(dir.path().join("notes/work/x.md")).unwrap();
+        assert_eq!(moved, "# X\n\n[y](y.md) [[y]]");
+        assert_eq!(repo.get_note_by_path("notes/work/x.md").await.unwrap().unwrap().id, x.id);
+        assert_eq!(repo.get_inbound_links(&x.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_versions_and_restore() {
+        let (dir, indexer) = setup().await;
+        let repo = indexer.repository();
+        write(dir.path(), "a.md", "# A\n\nfirst");
+        write(dir.path(), "b.md", "# B\n\nsee [[A]]");
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let a = repo.get_note_by_path("notes/a.md").await.unwrap().unwrap();
+        let b = repo.get_note_by_path("notes/b.md").await.unwrap().unwrap();
+
+        // 导入等操作覆盖文件后，旧内容保存为版本 1
+        write(dir.path(), "a.md", "# A\n\nsecond");
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let versions = repo.get_note_versions(&a.id).await.unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!((versions[0].version, versions[0].content.as_str()), (1, "# A\n\nfirst"));
+        let diff = repo.diff_note_versions(&a.id, 1, None).await.unwrap();
+        assert!(diff.iter().any(|line| line.kind == DiffKind::Removed && line.text == "first"));
+        assert!(diff.iter().any(|line| line.kind == DiffKind::Added && line.text == "second"));
+
+        // 恢复前当前内容也被保存，恢复可以撤销
+        let (restored, path) = indexer.restore_version(dir.path(), &a.id, 1).await.unwrap();
+        assert_eq!((restored.version, path), (1, PathBuf::from("notes/a.md")));
+        assert_eq!(std::fs::read_to_string(dir.path().join("notes/a.md")).unwrap(), "# A\n\nfirst");
+        let versions = repo.get_note_versions(&a.id).await.unwrap();
+        assert_eq!(versions.iter().map(|v| v.version).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(versions[0].content, "# A\n\nsecond");
+
+        // 删除的笔记按原 ID 恢复，反向链接随之恢复
+        std::fs::remove_file(dir.path().join("notes/a.md")).unwrap();
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        assert_eq!(repo.find_versioned_note("notes/a.md").await.unwrap(), Some(a.id.clone()));
+        indexer.restore_version(dir.path(), &a.id, 2).await.unwrap();
+        let restored = repo.get_note_by_path("notes/a.md").await.unwrap().unwrap();
+        assert_eq!(restored.id, a.id);
+        assert_eq!(repo.get_inbound_links(&a.id).await.unwrap()[0].source_id, b.id);
+
+        assert!(matches!(indexer.restore_version(dir.path(), &a.id, 9).await, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_restore_keeps_toml_frontmatter() {
+        let (dir, indexer) = setup().await;
+        let repo = indexer.repository();
+        let original = "+++\ntitle = \"TOML\"\ntags = [\"b\", \"a\"]\n[extra]\nweight = 3\n+++\n\nfirst\n";
+        write(dir.path(), "t.md", original);
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let note = repo.get_note_by_path("notes/t.md").await.unwrap().unwrap();
+
+        write(dir.path(), "t.md", "+++\ntitle = \"TOML\"\n+++\n\nsecond\n");
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let versions = repo.get_note_versions(&note.id).await.unwrap();
+        assert_eq!(versions[0].content, original);
+        assert_eq!(versions[0].file_hash, calculate_checksum(original));
+
+        indexer.restore_version(dir.path(), &note.id, 1).await.unwrap();
+        assert_eq!(std::fs::read_to_string(dir.path().join("notes/t.md")).unwrap(), original);
+        let restored = repo.get_note_by_id(&note.id).await.unwrap().unwrap();
+        assert_eq!(restored.file_hash, calculate_checksum(original));
+    }
+}
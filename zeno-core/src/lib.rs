@@ -1,13 +1,31 @@
+pub mod models;
 pub mod parser;
 pub mod indexer;
 pub mod storage;
 pub mod publisher;
 pub mod db;
+pub mod watcher;
+pub mod template;
+pub mod config;
+pub mod doctor;
+pub mod links;
+pub mod import_export;
 pub mod error;
 
+pub use models::{Note, NoteStatus, Frontmatter, PublishConfig};
 pub use parser::*;
 pub use storage::*;
 pub use indexer::*;
 pub use publisher::*;
 pub use db::*;
-pub use error::*;
\ No newline at end of file
+pub use watcher::*;
+pub use template::*;
+pub use config::*;
+pub use doctor::*;
+// 链接图谱中的 WikiLink/LinkType 与解析器重名，通过 `links::` 访问
+pub use links::{AmbiguousLink, Anchor, AnchorKind, BacklinkInfo, BrokenLink, BrokenLinkKind, FileRewrite, LinkEdit, LinkIndex, LinkIndexStats, LinkParser, LinkStore, NoteAnchors, NoteMove, RenamePlan, SimilarNote, TrashedLink, UnlinkedMention};
+// 解析器与数据库都定义了 Link/LinkType，根级别导出数据库中的类型
+pub use db::{Link, LinkType};
+// 导入导出模型名称较通用（FileType、DateRange 等），通过 `import_export::` 访问
+pub use import_export::{ImportExportManager, TransferProgress};
+pub use error::*;
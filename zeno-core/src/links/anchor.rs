@@ -0,0 +1,262 @@
+use std::ops::RangeInclusive;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::model::{LinkType, WikiLink};
+use crate::parser::{slugify, split_frontmatter, MarkdownParser};
+
+/// 行尾的块ID，如 `一段文字 ^block-1`
+static BLOCK_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|[ \t])\^([A-Za-z0-9-]+)[ \t]*$").unwrap());
+
+/// 列表项
+static LIST_ITEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*([-*+]|\d+[.)])\s").unwrap());
+
+/// 锚点类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnchorKind {
+    /// 标题，`[[笔记#标题]]`
+    Heading,
+    /// 块，`[[笔记#^块ID]]`
+    Block,
+}
+
+impl AnchorKind {
+    /// 保存到数据库时的名称
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            AnchorKind::Heading => "heading",
+            AnchorKind::Block => "block",
+        }
+    }
+}
+
+/// 笔记中可以被链接定位的标题或块
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anchor {
+    pub kind: AnchorKind,
+    /// 标题文本或块ID（不含 `^`）
+    pub name: String,
+    /// 标题级别 (1-6)，块为 0
+    pub level: u8,
+    /// 所在行号（从 1 开始，相对于完整文档）
+    pub line: usize,
+}
+
+impl Anchor {
+    /// 比较键和 slug，链接中的锚点与其中之一相同即匹配
+    pub(crate) fn keys(&self) -> (String, String) {
+        anchor_keys(self.kind, &self.name)
+    }
+}
+
+/// 笔记中的全部标题和块，按出现顺序排列
+#[derive(Debug, Clone, Default)]
+pub struct NoteAnchors {
+    anchors: Vec<Anchor>,
+    /// 标题占据的行，Setext 标题包括下划线
+    heading_lines: Vec<RangeInclusive<usize>>,
+}
+
+impl NoteAnchors {
+    /// 提取笔记中的标题和块ID，跳过前言和代码块
+    ///
+    /// 标题由 Markdown 解析器识别（ATX 和 Setext 标题），块ID按行查找。
+    pub fn parse(content: &str) -> Self {
+        let outline = MarkdownParser::new().outline(content);
+        let body_offset = split_frontmatter(content).map_or(0, |block| block.body_offset);
+        let first_line = content[..body_offset].matches('\n').count() + 1;
+
+        let mut anchors: Vec<Anchor> = outline
+            .headings
+            .iter()
+            .map(|(heading, _)| Anchor {
+                kind: AnchorKind::Heading,
+                name: heading.text.clone(),
+                level: heading.level,
+                line: heading.line,
+            })
+            .collect();
+        let heading_lines: Vec<RangeInclusive<usize>> = outline.headings.into_iter().map(|(_, lines)| lines).collect();
+
+        for (i, line) in content[body_offset..].lines().enumerate() {
+            let line_number = first_line + i;
+            let skipped = |ranges: &[RangeInclusive<usize>]| ranges.iter().any(|range| range.contains(&line_number));
+            if skipped(&outline.code_blocks) || skipped(&heading_lines) {
+                continue;
+            }
+            if let Some(cap) = BLOCK_ID_REGEX.captures(line) {
+                anchors.push(Anchor { kind: AnchorKind::Block, name: cap[1].to_string(), level: 0, line: line_number });
+            }
+        }
+        anchors.sort_by_key(|anchor| anchor.line);
+
+        Self { anchors, heading_lines }
+    }
+
+    /// 全部锚点
+    pub fn iter(&self) -> impl Iterator<Item = &Anchor> {
+        self.anchors.iter()
+    }
+
+    /// 查找链接中的锚点：`^` 开头的按块ID匹配，其余按标题文本或其 slug 匹配（忽略大小写）。
+    /// `标题#子标题` 形式的嵌套标题按最后一级匹配
+    pub fn find(&self, anchor: &str) -> Option<&Anchor> {
+        let (kind, key, slug) = lookup_keys(anchor)?;
+        self.anchors.iter().filter(|a| a.kind == kind).find(|a| {
+            let (anchor_key, anchor_slug) = a.keys();
+            anchor_key == key || anchor_slug == slug
+        })
+    }
+
+    /// 锚点引用的文本：标题为到下一个同级或更高级标题之前的整节，块为所在段落或列表项（不含块ID）
+    pub fn section(&self, content: &str, anchor: &Anchor) -> String {
+        let lines: Vec<&str> = content.lines().collect();
+        let index = anchor.line.saturating_sub(1).min(lines.len());
+
+        let (start, end) = match anchor.kind {
+            AnchorKind::Heading => {
+                let end = self
+                    .anchors
+                    .iter()
+                    .find(|a| a.kind == AnchorKind::Heading && a.line > anchor.line && a.level <= anchor.level)
+                    .map_or(lines.len(), |next| next.line - 1);
+                (index, end)
+            }
+            AnchorKind::Block => {
+                let line = lines.get(index).copied().unwrap_or_default();
+                let marker_only = BLOCK_ID_REGEX.replace(line, "").trim().is_empty();
+                // 单独一行的块ID指向上方的段落（引用、表格等），列表项只取本行
+                let mut end = index + 1;
+                if marker_only {
+                    end = index;
+                    while end > 0 && lines[end - 1].trim().is_empty() {
+                        end -= 1;
+                    }
+                }
+                let start = if !marker_only && LIST_ITEM_REGEX.is_match(line) {
+                    index
+                } else {
+                    let mut start = end;
+                    while start > 0 && !lines[start - 1].trim().is_empty() && !self.is_heading_line(start) {
+                        start -= 1;
+                    }
+                    start
+                };
+                (start, end)
+            }
+        };
+
+        let mut section: Vec<String> = lines[start.min(end)..end].iter().map(|line| line.to_string()).collect();
+        if anchor.kind == AnchorKind::Block {
+            if let Some(last) = section.last_mut() {
+                *last = BLOCK_ID_REGEX.replace(last, "").trim_end().to_string();
+            }
+        }
+        while section.last().is_some_and(|line| line.trim().is_empty()) {
+            section.pop();
+        }
+        section.join("\n")
+    }
+
+    /// 第 `line` 行（从 1 开始）是否属于标题
+    fn is_heading_line(&self, line: usize) -> bool {
+        self.heading_lines.iter().any(|range| range.contains(&line))
+    }
+}
+
+/// 链接中的锚点要查找的类型、比较键和 slug，规则与 [`NoteAnchors::find`] 相同；
+/// 标题为空时不匹配任何锚点，返回 `None`
+pub(crate) fn lookup_keys(anchor: &str) -> Option<(AnchorKind, String, String)> {
+    let anchor = anchor.replace("%20", " ");
+    let anchor = anchor.trim();
+    if let Some(id) = anchor.strip_prefix('^') {
+        let (key, slug) = anchor_keys(AnchorKind::Block, id);
+        return Some((AnchorKind::Block, key, slug));
+    }
+
+    let heading = anchor.rsplit('#').next().unwrap_or_default();
+    let (key, slug) = anchor_keys(AnchorKind::Heading, heading);
+    (!key.is_empty()).then_some((AnchorKind::Heading, key, slug))
+}
+
+/// 链接需要在目标笔记中检查的锚点类型、比较键和 slug
+///
+/// `[text](url#片段)` 的片段由各渲染器生成的 slug 决定，不做检查；
+/// 标题为空的锚点比较键为空，不匹配任何锚点。
+pub(crate) fn checked_anchor(link: &WikiLink) -> Option<(AnchorKind, String, String)> {
+    if link.link_type() == LinkType::Markdown {
+        return None;
+    }
+    let anchor = link.target_anchor()?;
+    Some(lookup_keys(&anchor).unwrap_or((AnchorKind::Heading, String::new(), String::new())))
+}
+
+/// 标题按文本和 slug 比较，块ID只忽略 ASCII 大小写
+fn anchor_keys(kind: AnchorKind, name: &str) -> (String, String) {
+    match kind {
+        AnchorKind::Heading => (heading_key(name), slugify(name)),
+        AnchorKind::Block => {
+            let key = name.to_ascii_lowercase();
+            (key.clone(), key)
+        }
+    }
+}
+
+/// 标题的比较键：忽略大小写、Wiki 链接中不能出现的字符和多余空白
+fn heading_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '#' | '^' | '|' | '[' | ']' | ':'))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "---\ntitle: 笔记\n# 不是标题\n---\n# 概述\n\n第一段\n第二行 ^intro\n\n## 细节: 实现\n\n- 条目一\n- 条目二 ^item\n\n```\n# 代码\n```\n\n> 引用\n\n^quote\n\n# 结尾\n";
+
+    #[test]
+    fn test_parse_and_find_anchors() {
+        let anchors = NoteAnchors::parse(NOTE);
+        let names: Vec<&str> = anchors.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["概述", "intro", "细节: 实现", "item", "quote", "结尾"]);
+        assert_eq!(anchors.iter().next().unwrap().line, 5);
+
+        assert_eq!(anchors.find("概述").unwrap().line, 5);
+        assert_eq!(anchors.find("细节 实现").unwrap().level, 2);
+        assert_eq!(anchors.find("概述#细节-实现").unwrap().name, "细节: 实现");
+        assert_eq!(anchors.find("^ITEM").unwrap().kind, AnchorKind::Block);
+        assert!(anchors.find("代码").is_none());
+        assert!(anchors.find("^missing").is_none());
+    }
+
+    #[test]
+    fn test_anchor_section() {
+        let anchors = NoteAnchors::parse(NOTE);
+        let section = |name: &str| anchors.section(NOTE, anchors.find(name).unwrap());
+        assert!(section("概述").starts_with("# 概述\n\n第一段"));
+        assert!(section("概述").ends_with("^quote"));
+        assert_eq!(section("细节: 实现").lines().last(), Some("^quote"));
+        assert_eq!(section("结尾"), "# 结尾");
+        assert_eq!(section("^intro"), "第一段\n第二行");
+        assert_eq!(section("^item"), "- 条目二");
+        assert_eq!(section("^quote"), "> 引用");
+    }
+
+    #[test]
+    fn test_setext_headings() {
+        let note = "总览\n===\n开头 ^top\n\n    # 缩进代码\n\n小节\n---\n\n内容\n";
+        let anchors = NoteAnchors::parse(note);
+        let names: Vec<(&str, u8)> = anchors.iter().map(|a| (a.name.as_str(), a.level)).collect();
+        assert_eq!(names, vec![("总览", 1), ("top", 0), ("小节", 2)]);
+        assert_eq!(anchors.find("小节").unwrap().line, 7);
+        assert_eq!(anchors.section(note, anchors.find("^top").unwrap()), "开头");
+        assert_eq!(anchors.section(note, anchors.find("小节").unwrap()), "小节\n---\n\n内容");
+    }
+}
@@ -0,0 +1,990 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use super::anchor::{AnchorKind, NoteAnchors};
+use super::model::{AmbiguousLink, BacklinkInfo, BrokenLink, BrokenLinkKind, LinkType, SimilarNote, TrashedLink, WikiLink};
+use super::parser::LinkParser;
+use super::resolver::LinkResolver;
+use crate::error::Result;
+use crate::parser::parse_frontmatter;
+
+/// 笔记中的一条链接及其解析结果
+#[derive(Debug, Clone)]
+struct NoteLink {
+    link: WikiLink,
+    /// 链接所在行的文本
+    context: String,
+    /// 解析到的目标笔记，`None` 表示断链
+    target_id: Option<String>,
+    /// 无法解析但指向回收站中笔记时的笔记ID
+    trashed_id: Option<String>,
+}
+
+/// 链接索引和管理系统
+///
+/// 链接按以下顺序解析：规范化路径、文件名或标题（忽略大小写）、前言中的别名、路径后缀。
+/// 笔记注册或移除后会重新解析受影响的链接。带锚点的链接（`[[笔记#标题]]`、
+/// `[[笔记#^块ID]]`）在查询断链时对照目标笔记中的标题和块ID检查。
+#[derive(Debug, Clone, Default)]
+pub struct LinkIndex {
+    /// 正向链接映射: 笔记ID -> 链接目标集合
+    outgoing_links: HashMap<String, HashSet<String>>,
+    /// 反向链接映射: 笔记ID -> 引用源集合
+    incoming_links: HashMap<String, HashSet<String>>,
+    /// 笔记ID -> 笔记中的链接（按出现顺序）
+    note_links: HashMap<String, Vec<NoteLink>>,
+    /// 笔记ID到路径的映射
+    id_to_path: HashMap<String, PathBuf>,
+    /// 笔记ID到标题的映射
+    id_to_title: HashMap<String, String>,
+    /// 笔记ID -> 前言中的别名
+    id_to_aliases: HashMap<String, Vec<String>>,
+    /// 按路径、名称和别名解析链接目标，与索引器写入链接时的规则相同
+    resolver: LinkResolver,
+    /// 笔记ID -> 标题和块ID，只记录通过内容更新过链接的笔记
+    anchors: HashMap<String, NoteAnchors>,
+    /// 回收站中的笔记（按原路径注册），指向它们的链接不算断链
+    trash: Option<Box<LinkIndex>>,
+}
+
+impl LinkIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 由 `(ID, 路径, 标题, 内容)` 构建索引，别名取自内容的前言
+    ///
+    /// 先登记全部笔记及其别名，再逐篇解析链接，每篇笔记只解析一次。
+    pub fn build(notes: impl IntoIterator<Item = (String, PathBuf, String, String)>) -> Self {
+        let notes: Vec<_> = notes.into_iter().collect();
+        let mut index = Self::new();
+        for (note_id, path, title, content) in &notes {
+            index.id_to_path.insert(note_id.clone(), path.clone());
+            index.id_to_title.insert(note_id.clone(), title.clone());
+            index.id_to_aliases.insert(note_id.clone(), clean_aliases(frontmatter_aliases(content)));
+            index.insert_keys(note_id);
+            index.outgoing_links.entry(note_id.clone()).or_default();
+            index.incoming_links.entry(note_id.clone()).or_default();
+        }
+        for (note_id, _, _, content) in &notes {
+            index.update_note_content(note_id, content);
+        }
+        index
+    }
+
+    /// 从工作区数据库加载笔记并构建索引
+    ///
+    /// 内容优先读取 `root` 下的文件，使行号与磁盘上的文件一致；
+    /// 回收站中的笔记按原路径注册为已删除。
+    pub async fn load(pool: &SqlitePool, root: &Path) -> Result<Self> {
+        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT id, file_path, title, COALESCE(source, content) FROM notes WHERE status != 'deleted' ORDER BY file_path"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut notes = Vec::with_capacity(rows.len());
+        for (id, file_path, title, content) in rows {
+            let content = tokio::fs::read_to_string(root.join(&file_path)).await.unwrap_or(content);
+            notes.push((id, PathBuf::from(file_path), title, content));
+        }
+        let mut index = Self::build(notes);
+
+        let trashed: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT t.note_id, t.original_path, n.title FROM trash t JOIN notes n ON n.id = t.note_id"
+        )
+        .fetch_all(pool)
+        .await?;
+        for (id, original_path, title) in trashed {
+            index.register_trashed_note(id, PathBuf::from(original_path), title);
+        }
+        Ok(index)
+    }
+
+    /// 注册一个笔记，重新注册（如移动后）时保留已有的别名
+    pub fn register_note(&mut self, note_id: String, path: PathBuf, title: String) {
+        let aliases = self.id_to_aliases.get(&note_id).cloned();
+        if self.id_to_path.contains_key(&note_id) {
+            self.unregister_note(&note_id);
+        }
+        if let Some(trash) = &mut self.trash {
+            trash.unregister_note(&note_id);
+        }
+
+        self.id_to_path.insert(note_id.clone(), path);
+        self.id_to_title.insert(note_id.clone(), title);
+        if let Some(aliases) = aliases {
+            self.id_to_aliases.insert(note_id.clone(), aliases);
+        }
+        self.insert_keys(&note_id);
+
+        // 初始化链接集合
+        self.outgoing_links.entry(note_id.clone()).or_default();
+        self.incoming_links.entry(note_id).or_default();
+
+        // 新笔记可能修复已有的断链
+        self.resolve_broken_links();
+    }
+
+    /// 注册回收站中的笔记，`path` 为删除前的路径
+    ///
+    /// 该笔记会从索引中移除，指向它的链接标记为"在回收站中"而不是断链。
+    pub fn register_trashed_note(&mut self, note_id: String, path: PathBuf, title: String) {
+        if self.id_to_path.contains_key(&note_id) {
+            self.unregister_note(&note_id);
+        }
+        self.trash.get_or_insert_with(Default::default).register_note(note_id, path, title);
+        self.resolve_broken_links();
+    }
+
+    /// 重新解析包含断链的笔记
+    fn resolve_broken_links(&mut self) {
+        let broken: Vec<String> = self
+            .note_links
+            .iter()
+            .filter(|(_, links)| links.iter().any(|l| l.target_id.is_none()))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for source_id in broken {
+            self.resolve_note_links(&source_id);
+        }
+    }
+
+    /// 移除笔记注册，笔记在回收站中时一并移除
+    pub fn unregister_note(&mut self, note_id: &str) {
+        if let Some(trash) = self.trash.as_mut().filter(|trash| trash.id_to_path.contains_key(note_id)) {
+            trash.unregister_note(note_id);
+            // 彻底删除后，指向它的链接变为断链
+            let sources: Vec<String> = self
+                .note_links
+                .iter()
+                .filter(|(_, links)| links.iter().any(|l| l.trashed_id.as_deref() == Some(note_id)))
+                .map(|(id, _)| id.clone())
+                .collect();
+            for source_id in sources {
+                self.resolve_note_links(&source_id);
+            }
+        }
+
+        self.clear_note_links(note_id);
+        self.note_links.remove(note_id);
+        self.anchors.remove(note_id);
+        self.outgoing_links.remove(note_id);
+        self.id_to_path.remove(note_id);
+        self.id_to_title.remove(note_id);
+        self.id_to_aliases.remove(note_id);
+        self.resolver.remove(note_id);
+
+        // 指向该笔记的链接变为断链
+        if let Some(incoming) = self.incoming_links.remove(note_id) {
+            for source_id in incoming {
+                self.resolve_note_links(&source_id);
+            }
+        }
+    }
+
+    /// 更新笔记的链接关系
+    pub fn update_note_links(&mut self, note_id: &str, links: Vec<WikiLink>) -> std::result::Result<(), String> {
+        let links = links
+            .into_iter()
+            .map(|link| NoteLink { link, context: String::new(), target_id: None, trashed_id: None })
+            .collect();
+        self.set_note_links(note_id, links);
+        Ok(())
+    }
+
+    /// 解析笔记内容并更新链接关系，同时记录链接所在行作为上下文、笔记中的标题和块ID以及前言中的别名
+    pub fn update_note_content(&mut self, note_id: &str, content: &str) {
+        self.set_note_aliases(note_id, frontmatter_aliases(content));
+        self.anchors.insert(note_id.to_string(), NoteAnchors::parse(content));
+        let parser = LinkParser::new();
+        let links = parser
+            .parse_links(content)
+            .links
+            .into_iter()
+            .map(|link| NoteLink {
+                context: parser.extract_link_context(content, &link, 0).trim().to_string(),
+                link,
+                target_id: None,
+                trashed_id: None,
+            })
+            .collect();
+        self.set_note_links(note_id, links);
+    }
+
+    /// 设置笔记的别名，别名变化后重新解析所有链接
+    pub fn set_note_aliases(&mut self, note_id: &str, aliases: Vec<String>) {
+        if !self.id_to_path.contains_key(note_id) {
+            return;
+        }
+        let aliases = clean_aliases(aliases);
+        let current = self.id_to_aliases.get(note_id).map(Vec::as_slice).unwrap_or_default();
+        if current == aliases.as_slice() {
+            return;
+        }
+
+        self.id_to_aliases.insert(note_id.to_string(), aliases);
+        self.insert_keys(note_id);
+        let sources: Vec<String> = self.note_links.keys().cloned().collect();
+        for source_id in sources {
+            self.resolve_note_links(&source_id);
+        }
+    }
+
+    fn set_note_links(&mut self, note_id: &str, links: Vec<NoteLink>) {
+        // 附件等非笔记链接不参与链接图谱
+        let links = links.into_iter().filter(|l| is_note_target(&l.link)).collect();
+        self.note_links.insert(note_id.to_string(), links);
+        self.resolve_note_links(note_id);
+    }
+
+    /// 重新解析笔记中的所有链接
+    fn resolve_note_links(&mut self, note_id: &str) {
+        self.clear_note_links(note_id);
+
+        let Some(mut links) = self.note_links.remove(note_id) else {
+            return;
+        };
+        let base = self.source_dir(note_id);
+        for entry in &mut links {
+            entry.target_id = self.resolve_link(&base, &entry.link).map(str::to_string);
+            entry.trashed_id = match (&entry.target_id, &self.trash) {
+                (None, Some(trash)) => trash.resolve_link(&base, &entry.link).map(str::to_string),
+                _ => None,
+            };
+            if let Some(target_id) = &entry.target_id {
+                // 链接到自身不计入链接关系
+                if target_id != note_id {
+                    self.add_link_relationship(note_id, target_id);
+                }
+            }
+        }
+        self.note_links.insert(note_id.to_string(), links);
+    }
+
+    /// 清理笔记的所有出链关系
+    fn clear_note_links(&mut self, note_id: &str) {
+        if let Some(outgoing) = self.outgoing_links.get_mut(note_id) {
+            for target_id in std::mem::take(outgoing) {
+                if let Some(incoming) = self.incoming_links.get_mut(&target_id) {
+                    incoming.remove(note_id);
+                }
+            }
+        }
+    }
+
+    /// 添加链接关系
+    fn add_link_relationship(&mut self, source_id: &str, target_id: &str) {
+        self.outgoing_links
+            .entry(source_id.to_string())
+            .or_default()
+            .insert(target_id.to_string());
+
+        self.incoming_links
+            .entry(target_id.to_string())
+            .or_default()
+            .insert(source_id.to_string());
+    }
+
+    /// 在解析器中登记笔记的路径、文件名、标题和别名
+    fn insert_keys(&mut self, note_id: &str) {
+        let path = self.id_to_path[note_id].to_string_lossy();
+        let title = self.id_to_title.get(note_id).map(String::as_str).unwrap_or_default();
+        let aliases = self.id_to_aliases.get(note_id).map(Vec::as_slice).unwrap_or_default();
+        self.resolver.insert(note_id, title, &path, aliases);
+    }
+
+    /// 源笔记所在目录，Markdown 链接相对于它解析
+    fn source_dir(&self, source_id: &str) -> PathBuf {
+        self.id_to_path
+            .get(source_id)
+            .and_then(|path| path.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    /// 解析 `base` 目录下的笔记中的链接
+    fn resolve_link(&self, base: &Path, link: &WikiLink) -> Option<&str> {
+        self.resolver.resolve_link(base, link)
+    }
+
+    /// 解析位于 `source`（笔记路径）中的链接，返回目标笔记ID
+    pub fn resolve_link_at(&self, source: &Path, link: &WikiLink) -> Option<&str> {
+        self.resolve_link(source.parent().unwrap_or_else(|| Path::new("")), link)
+    }
+
+    /// 把链接目标（路径、文件名、标题、别名或路径后缀）解析为笔记ID
+    pub fn resolve_target(&self, target: &str) -> Option<&str> {
+        self.resolver.resolve_wiki(target)
+    }
+
+    /// 按ID、路径、文件名或标题查找笔记
+    pub fn find_note(&self, query: &str) -> Option<&str> {
+        match self.id_to_path.get_key_value(query) {
+            Some((note_id, _)) => Some(note_id),
+            None => self.resolve_target(query),
+        }
+    }
+
+    /// 获取笔记的反向链接，按源笔记路径排序
+    pub fn get_backlinks(&self, note_id: &str) -> Vec<BacklinkInfo> {
+        let mut backlinks = Vec::new();
+
+        if let Some(incoming) = self.incoming_links.get(note_id) {
+            for source_id in incoming {
+                let Some(path) = self.id_to_path.get(source_id) else {
+                    continue;
+                };
+                let links: Vec<&NoteLink> = self.note_links[source_id]
+                    .iter()
+                    .filter(|l| l.target_id.as_deref() == Some(note_id))
+                    .collect();
+                let Some(first) = links.first() else {
+                    continue;
+                };
+
+                backlinks.push(BacklinkInfo {
+                    source_note_id: source_id.clone(),
+                    source_note_title: self.note_title(source_id).unwrap_or_default().to_string(),
+                    source_note_path: path.to_string_lossy().to_string(),
+                    context: first.context.clone(),
+                    line_number: first.link.line_number,
+                    link_type: first.link.link_type(),
+                    occurrence_count: links.len(),
+                });
+            }
+        }
+
+        backlinks.sort_by(|a, b| a.source_note_path.cmp(&b.source_note_path));
+        backlinks
+    }
+
+    /// 获取笔记的正向链接
+    pub fn get_outgoing_links(&self, note_id: &str) -> Vec<String> {
+        let mut links: Vec<String> = self
+            .outgoing_links
+            .get(note_id)
+            .map(|links| links.iter().cloned().collect())
+            .unwrap_or_default();
+        links.sort_by(|a, b| self.id_to_path.get(a).cmp(&self.id_to_path.get(b)));
+        links
+    }
+
+    /// 查找相似笔记
+    pub fn find_similar_notes(&self, note_id: &str, limit: usize) -> Vec<SimilarNote> {
+        rank_similar_notes(note_id, &self.outgoing_links, &self.incoming_links, limit, |id| {
+            (
+                self.note_title(id).unwrap_or_default().to_string(),
+                self.id_to_path.get(id).map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+            )
+        })
+    }
+
+    /// 获取断链信息，按源笔记路径和行号排序
+    pub fn get_broken_links(&self, note_id: Option<&str>) -> Vec<BrokenLink> {
+        let mut broken_links = Vec::new();
+
+        for (source_id, links) in &self.note_links {
+            if note_id.is_some_and(|id| id != source_id) {
+                continue;
+            }
+            for entry in links {
+                let Some(kind) = self.broken_kind(entry) else {
+                    continue;
+                };
+                let suggestions = match &entry.target_id {
+                    Some(target_id) => self.suggest_anchor_fixes(target_id, kind, &entry.link.target_anchor().unwrap_or_default()),
+                    None => self.suggest_link_fixes(&entry.link),
+                };
+                broken_links.push(BrokenLink {
+                    source_note_id: source_id.clone(),
+                    source_note_path: self
+                        .id_to_path
+                        .get(source_id)
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    link: entry.link.clone(),
+                    kind,
+                    target_note_id: entry.target_id.clone(),
+                    suggestions,
+                });
+            }
+        }
+
+        broken_links.sort_by(|a, b| {
+            a.source_note_path
+                .cmp(&b.source_note_path)
+                .then(a.link.range.start.cmp(&b.link.range.start))
+        });
+        broken_links
+    }
+
+    /// 获取指向回收站中笔记的链接，按源笔记路径和行号排序
+    pub fn get_trashed_links(&self, note_id: Option<&str>) -> Vec<TrashedLink> {
+        let Some(trash) = &self.trash else {
+            return Vec::new();
+        };
+        let mut trashed_links = Vec::new();
+
+        for (source_id, links) in &self.note_links {
+            if note_id.is_some_and(|id| id != source_id) {
+                continue;
+            }
+            for entry in links {
+                let Some(trashed_id) = &entry.trashed_id else {
+                    continue;
+                };
+                trashed_links.push(TrashedLink {
+                    source_note_id: source_id.clone(),
+                    source_note_path: self
+                        .id_to_path
+                        .get(source_id)
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    link: entry.link.clone(),
+                    trashed_note_id: trashed_id.clone(),
+                    trashed_note_path: trash
+                        .note_path(trashed_id)
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        trashed_links.sort_by(|a, b| {
+            a.source_note_path
+                .cmp(&b.source_note_path)
+                .then(a.link.range.start.cmp(&b.link.range.start))
+        });
+        trashed_links
+    }
+
+    /// 获取目标有歧义的链接（多篇笔记的标题、文件名或别名相同），按源笔记路径和行号排序
+    pub fn get_ambiguous_links(&self, note_id: Option<&str>) -> Vec<AmbiguousLink> {
+        let mut ambiguous_links = Vec::new();
+
+        for (source_id, links) in &self.note_links {
+            if note_id.is_some_and(|id| id != source_id) {
+                continue;
+            }
+            for entry in links {
+                let Some(target_id) = &entry.target_id else {
+                    continue;
+                };
+                let candidates = self.name_claimants(&entry.link);
+                if candidates.len() < 2 {
+                    continue;
+                }
+                ambiguous_links.push(AmbiguousLink {
+                    source_note_id: source_id.clone(),
+                    source_note_path: self
+                        .id_to_path
+                        .get(source_id)
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    link: entry.link.clone(),
+                    target_note_id: target_id.clone(),
+                    candidate_paths: candidates
+                        .iter()
+                        .map(|id| self.id_to_path[*id].to_string_lossy().to_string())
+                        .collect(),
+                    candidates: candidates.into_iter().map(str::to_string).collect(),
+                });
+            }
+        }
+
+        ambiguous_links.sort_by(|a, b| {
+            a.source_note_path
+                .cmp(&b.source_note_path)
+                .then(a.link.range.start.cmp(&b.link.range.start))
+        });
+        ambiguous_links
+    }
+
+    /// 以链接目标为标题、文件名或别名的笔记（按路径排序）；按路径链接的不算
+    fn name_claimants(&self, link: &WikiLink) -> Vec<&str> {
+        if link.link_type() == LinkType::Markdown {
+            return Vec::new();
+        }
+        self.resolver.claimants(&link.target)
+    }
+
+    /// 链接是否失效：找不到目标笔记（且不在回收站中），或目标笔记中没有链接的标题、块ID
+    fn broken_kind(&self, entry: &NoteLink) -> Option<BrokenLinkKind> {
+        let Some(target_id) = &entry.target_id else {
+            return entry.trashed_id.is_none().then_some(BrokenLinkKind::MissingNote);
+        };
+        // `[text](url#片段)` 的片段由各渲染器生成的 slug 决定，不做检查
+        if entry.link.link_type() == LinkType::Markdown {
+            return None;
+        }
+        let anchor = entry.link.target_anchor()?;
+        // 只通过链接更新的笔记不知道其中的标题，不做检查
+        let anchors = self.anchors.get(target_id)?;
+        if anchors.find(&anchor).is_some() {
+            return None;
+        }
+        Some(if anchor.starts_with('^') { BrokenLinkKind::MissingBlock } else { BrokenLinkKind::MissingHeading })
+    }
+
+    /// 目标笔记中与失效锚点相近的标题或块ID
+    fn suggest_anchor_fixes(&self, target_id: &str, kind: BrokenLinkKind, anchor: &str) -> Vec<String> {
+        let Some(anchors) = self.anchors.get(target_id) else {
+            return Vec::new();
+        };
+        let anchor_kind = if kind == BrokenLinkKind::MissingBlock { AnchorKind::Block } else { AnchorKind::Heading };
+        let names = anchors.iter().filter(|a| a.kind == anchor_kind).map(|a| a.name.clone());
+        suggest_anchor_fixes(names, kind, anchor)
+    }
+
+    /// 建议断链修复方案
+    fn suggest_link_fixes(&self, link: &WikiLink) -> Vec<String> {
+        let candidates = self.id_to_title.values().cloned().chain(
+            self.id_to_path
+                .values()
+                .filter_map(|path| path.file_stem())
+                .map(|stem| stem.to_string_lossy().to_string()),
+        );
+        suggest_link_fixes(candidates, &link.target)
+    }
+
+    /// 获取孤立笔记（没有任何出链和入链），按路径排序
+    pub fn get_orphaned_notes(&self) -> Vec<String> {
+        let is_empty = |links: &HashMap<String, HashSet<String>>, id: &str| {
+            links.get(id).is_none_or(|links| links.is_empty())
+        };
+
+        let mut orphans: Vec<String> = self
+            .id_to_path
+            .keys()
+            .filter(|id| is_empty(&self.outgoing_links, id) && is_empty(&self.incoming_links, id))
+            .cloned()
+            .collect();
+        orphans.sort_by(|a, b| self.id_to_path[a].cmp(&self.id_to_path[b]));
+        orphans
+    }
+
+    /// 获取链接统计信息
+    pub fn get_statistics(&self) -> LinkIndexStats {
+        LinkIndexStats {
+            total_notes: self.id_to_path.len(),
+            total_links: self.outgoing_links.values().map(|links| links.len()).sum(),
+            total_broken_links: self.count_links(|l| self.broken_kind(l).is_some()),
+            total_trashed_links: self.count_links(|l| l.trashed_id.is_some()),
+            total_ambiguous_links: self.count_links(|l| l.target_id.is_some() && self.name_claimants(&l.link).len() > 1),
+            orphaned_notes: self.get_orphaned_notes().len(),
+        }
+    }
+
+    fn count_links(&self, predicate: impl Fn(&NoteLink) -> bool) -> usize {
+        self.note_links
+            .values()
+            .map(|links| links.iter().filter(|l| predicate(l)).count())
+            .sum()
+    }
+
+    /// 根据笔记ID获取路径
+    pub fn note_path(&self, note_id: &str) -> Option<&Path> {
+        self.id_to_path.get(note_id).map(PathBuf::as_path)
+    }
+
+    /// 位于目录 `dir`（相对工作区）下的笔记，按路径排序
+    pub fn notes_under(&self, dir: &Path) -> Vec<&str> {
+        let mut notes: Vec<(&PathBuf, &String)> = self
+            .id_to_path
+            .iter()
+            .filter(|(_, path)| path.starts_with(dir))
+            .map(|(id, path)| (path, id))
+            .collect();
+        notes.sort();
+        notes.into_iter().map(|(_, id)| id.as_str()).collect()
+    }
+
+    /// 根据笔记ID获取标题
+    pub fn note_title(&self, note_id: &str) -> Option<&str> {
+        self.id_to_title.get(note_id).map(String::as_str)
+    }
+
+    /// 笔记前言中的别名
+    pub fn note_aliases(&self, note_id: &str) -> &[String] {
+        self.id_to_aliases.get(note_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// 笔记中的标题和块ID
+    pub fn note_anchors(&self, note_id: &str) -> Option<&NoteAnchors> {
+        self.anchors.get(note_id)
+    }
+}
+
+/// 链接索引统计信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkIndexStats {
+    pub total_notes: usize,
+    pub total_links: usize,
+    pub total_broken_links: usize,
+    /// 指向回收站中笔记的链接数，不计入断链
+    pub total_trashed_links: usize,
+    /// 目标有歧义的链接数
+    pub total_ambiguous_links: usize,
+    pub orphaned_notes: usize,
+}
+
+/// 内容前言中的别名，没有前言或前言无法解析时为空
+fn frontmatter_aliases(content: &str) -> Vec<String> {
+    parse_frontmatter(content).map(|(frontmatter, _)| frontmatter.aliases).unwrap_or_default()
+}
+
+/// 去掉别名两端的空白和空别名
+fn clean_aliases(aliases: Vec<String>) -> Vec<String> {
+    aliases
+        .into_iter()
+        .map(|alias| alias.trim().to_string())
+        .filter(|alias| !alias.is_empty())
+        .collect()
+}
+
+/// 链接是否指向笔记；`![[图片.png]]` 等附件链接不算
+pub(crate) fn is_note_target(link: &WikiLink) -> bool {
+    let target = match link.link_type() {
+        LinkType::Markdown => link.target.split(['#', '?']).next().unwrap_or_default(),
+        _ => link.target.as_str(),
+    };
+    let target = target.trim();
+    if target.is_empty() {
+        return false;
+    }
+
+    match Path::new(target).extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown") => true,
+        // `v1.2`、`第 3.5 节` 之类的标题不当作扩展名
+        Some(ext) => !(ext.len() <= 5
+            && ext.chars().all(|c| c.is_ascii_alphanumeric())
+            && ext.chars().any(|c| c.is_ascii_alphabetic())),
+        None => true,
+    }
+}
+
+/// `names` 中与失效锚点相近的标题或块ID，`names` 为目标笔记中与 `kind` 同类的锚点
+pub(super) fn suggest_anchor_fixes(names: impl IntoIterator<Item = String>, kind: BrokenLinkKind, anchor: &str) -> Vec<String> {
+    let prefix = if kind == BrokenLinkKind::MissingBlock { "^" } else { "" };
+    let wanted = anchor.trim_start_matches('^').rsplit('#').next().unwrap_or_default().to_lowercase();
+
+    let mut suggestions: Vec<(f64, String)> = names
+        .into_iter()
+        .map(|name| (string_similarity(&wanted, &name.to_lowercase()), format!("{}{}", prefix, name)))
+        .filter(|(similarity, _)| *similarity > 0.6)
+        .collect();
+    suggestions.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    suggestions.dedup_by(|a, b| a.1 == b.1);
+    suggestions.into_iter().map(|(_, name)| name).take(5).collect()
+}
+
+/// `candidates`（笔记标题和文件名）中与找不到的链接目标相近的名称
+pub(super) fn suggest_link_fixes(candidates: impl IntoIterator<Item = String>, target: &str) -> Vec<String> {
+    let target_lower = target.to_lowercase();
+    let mut suggestions: Vec<(f64, String)> = candidates
+        .into_iter()
+        .map(|name| (string_similarity(&target_lower, &name.to_lowercase()), name))
+        .filter(|(similarity, _)| *similarity > 0.6)
+        .collect();
+    suggestions.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut seen = HashSet::new();
+    suggestions
+        .into_iter()
+        .map(|(_, name)| name)
+        .filter(|name| seen.insert(name.clone()))
+        .take(5)
+        .collect()
+}
+
+/// 综合共同出链和共同入链计算相似笔记，`note_info` 返回笔记的标题和路径
+pub(super) fn rank_similar_notes(
+    note_id: &str,
+    outgoing_links: &HashMap<String, HashSet<String>>,
+    incoming_links: &HashMap<String, HashSet<String>>,
+    limit: usize,
+    note_info: impl Fn(&str) -> (String, String),
+) -> Vec<SimilarNote> {
+    let mut similarities = Vec::new();
+
+    let empty = HashSet::new();
+    let outgoing = outgoing_links.get(note_id).unwrap_or(&empty);
+    let incoming = incoming_links.get(note_id).unwrap_or(&empty);
+    let others: HashSet<&String> = outgoing_links.keys().chain(incoming_links.keys()).collect();
+
+    for other_id in others {
+        if other_id == note_id {
+            continue;
+        }
+
+        let other_outgoing = outgoing_links.get(other_id).unwrap_or(&empty);
+        let other_incoming = incoming_links.get(other_id).unwrap_or(&empty);
+
+        // 综合共同出链和共同入链的相似度
+        let similarity = (jaccard(outgoing, other_outgoing) + jaccard(incoming, other_incoming)) / 2.0;
+
+        if similarity > 0.1 {
+            let mut common_links: Vec<String> = outgoing
+                .intersection(other_outgoing)
+                .chain(incoming.intersection(other_incoming))
+                .cloned()
+                .collect();
+            common_links.sort();
+            common_links.dedup();
+
+            let common_links_count = common_links.len();
+            let (title, path) = note_info(other_id);
+
+            similarities.push(SimilarNote {
+                note_id: other_id.clone(),
+                title,
+                path,
+                similarity_score: similarity,
+                common_links,
+                similarity_reason: format!("共同链接: {}", common_links_count),
+            });
+        }
+    }
+
+    // 按相似度排序，相同时按路径
+    similarities.sort_by(|a, b| {
+        b.similarity_score
+            .total_cmp(&a.similarity_score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    similarities.truncate(limit);
+
+    similarities
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// 计算字符串相似度（基于编辑距离）
+fn string_similarity(s1: &str, s2: &str) -> f64 {
+    let len1 = s1.chars().count();
+    let len2 = s2.chars().count();
+
+    if len1 == 0 && len2 == 0 {
+        return 1.0;
+    }
+
+    if len1 == 0 || len2 == 0 {
+        return 0.0;
+    }
+
+    let max_len = std::cmp::max(len1, len2);
+    1.0 - (levenshtein_distance(s1, s2) as f64 / max_len as f64)
+}
+
+/// 计算编辑距离
+fn levenshtein_distance(s1: &str, s2: &str) -> usize {
+    let chars2: Vec<char> = s2.chars().collect();
+    let mut previous: Vec<usize> = (0..=chars2.len()).collect();
+
+    for (i, c1) in s1.chars().enumerate() {
+        let mut current = vec![i + 1; chars2.len() + 1];
+        for (j, c2) in chars2.iter().enumerate() {
+            let cost = if c1 == *c2 { 0 } else { 1 };
+            current[j + 1] = (previous[j + 1] + 1).min(current[j] + 1).min(previous[j] + cost);
+        }
+        previous = current;
+    }
+
+    previous[chars2.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, path: &str, title: &str, content: &str) -> (String, PathBuf, String, String) {
+        (id.to_string(), PathBuf::from(path), title.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_link_index_basic_operations() {
+        let mut index = LinkIndex::new();
+
+        // 注册笔记
+        index.register_note(
+            "note1".to_string(),
+            PathBuf::from("/path/note1.md"),
+            "笔记1".to_string(),
+        );
+        index.register_note(
+            "note2".to_string(),
+            PathBuf::from("/path/note2.md"),
+            "笔记2".to_string(),
+        );
+
+        // 创建链接
+        let links = vec![WikiLink {
+            raw: "[[笔记2]]".to_string(),
+            target: "笔记2".to_string(),
+            alias: None,
+            anchor: None,
+            is_embed: false,
+            range: 0..8,
+            line_number: 1,
+        }];
+
+        index.update_note_links("note1", links).unwrap();
+
+        // 测试反向链接
+        let backlinks = index.get_backlinks("note2");
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].source_note_id, "note1");
+
+        // 测试正向链接
+        let outgoing = index.get_outgoing_links("note1");
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(outgoing[0], "note2");
+    }
+
+    #[test]
+    fn test_build_resolves_paths_names_and_broken_links() {
+        let mut index = LinkIndex::build(vec![
+            note("a", "notes/a.md", "Alpha", "# Alpha\n\n[[beta]] [[Gamma Note|g]] [[sub/delta]]\n[[Missing]] ![[img.png]]\n[d](sub/delta.md) [[Bata]] [[beta]]"),
+            note("b", "notes/beta.md", "Beta", "# Beta\n\n[a](./a.md#top)"),
+            note("c", "notes/gamma.md", "Gamma Note", "# Gamma Note"),
+            note("d", "notes/sub/delta.md", "Delta", "# Delta"),
+            note("e", "notes/lonely.md", "Lonely", "# Lonely\n\n`[[Missing]]`"),
+        ]);
+
+        assert_eq!(index.get_outgoing_links("a"), vec!["b", "c", "d"]);
+        let backlinks = index.get_backlinks("b");
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!((backlinks[0].line_number, backlinks[0].occurrence_count), (3, 2));
+        assert_eq!(backlinks[0].context, "[[beta]] [[Gamma Note|g]] [[sub/delta]]");
+        assert_eq!(index.get_backlinks("a")[0].link_type, LinkType::Markdown);
+
+        let broken = index.get_broken_links(None);
+        let targets: Vec<&str> = broken.iter().map(|b| b.link.target.as_str()).collect();
+        assert_eq!(targets, vec!["Missing", "Bata"]);
+        assert_eq!(broken[0].link.line_number, 4);
+        assert_eq!(broken[1].suggestions, vec!["Beta", "beta"]);
+
+        assert_eq!(index.get_orphaned_notes(), vec!["e"]);
+        assert_eq!(index.find_note("GAMMA NOTE"), Some("c"));
+        assert_eq!(index.find_note("notes/sub/delta.md"), Some("d"));
+
+        // 新笔记修复断链，删除笔记产生断链
+        index.register_note("m".to_string(), PathBuf::from("notes/missing.md"), "Missing".to_string());
+        assert_eq!(index.get_broken_links(None).len(), 1);
+        index.unregister_note("c");
+        let broken = index.get_broken_links(Some("a"));
+        assert_eq!(broken.len(), 2);
+        assert_eq!(broken[0].link.target, "Gamma Note");
+        assert_eq!(index.get_statistics().total_broken_links, 2);
+    }
+
+    #[test]
+    fn test_broken_heading_and_block_links() {
+        let index = LinkIndex::build(vec![
+            note("a", "notes/a.md", "A", "[[B#实现方法]] [[B#结果#细节]] ![[B#^step]]\n[[B#实现方发]] [[B#^stepp]] [b](b.md#nowhere)"),
+            note("b", "notes/b.md", "B", "# B\n\n## 实现方法\n步骤 ^step\n\n## 结果\n### 细节"),
+        ]);
+
+        let broken = index.get_broken_links(None);
+        let kinds: Vec<(&str, BrokenLinkKind)> = broken.iter().map(|b| (b.link.raw.as_str(), b.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![("[[B#实现方发]]", BrokenLinkKind::MissingHeading), ("[[B#^stepp]]", BrokenLinkKind::MissingBlock)]
+        );
+        assert_eq!(broken[0].target_note_id.as_deref(), Some("b"));
+        assert_eq!(broken[0].suggestions, vec!["实现方法"]);
+        assert_eq!(broken[1].suggestions, vec!["^step"]);
+        assert_eq!(index.get_statistics().total_broken_links, 2);
+        // 锚点失效不影响链接关系
+        assert_eq!(index.get_backlinks("b")[0].occurrence_count, 6);
+        assert_eq!(index.note_anchors("b").unwrap().iter().count(), 5);
+    }
+
+    #[test]
+    fn test_aliases_resolve_and_report_ambiguity() {
+        let mut index = LinkIndex::build(vec![
+            note("a", "notes/a.md", "A", "[[ZK]] [[卡片盒]] [[Box]] [[notes/b]]"),
+            note("b", "notes/b.md", "卡片盒笔记法", "---\naliases: [ZK, 卡片盒]\n---\n正文"),
+            note("c", "notes/c.md", "Box", "---\nalias: zk\n---\n"),
+        ]);
+
+        assert_eq!(index.note_aliases("b"), ["ZK", "卡片盒"]);
+        assert_eq!(index.resolve_target("卡片盒"), Some("b"));
+        assert_eq!(index.get_outgoing_links("a"), vec!["b", "c"]);
+        assert!(index.get_broken_links(None).is_empty());
+
+        // 两篇笔记都声明了 ZK，按路径靠前的解析并给出警告；按路径链接的不算
+        let ambiguous = index.get_ambiguous_links(None);
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].link.target, "ZK");
+        assert_eq!(ambiguous[0].target_note_id, "b");
+        assert_eq!(ambiguous[0].candidate_paths, vec!["notes/b.md", "notes/c.md"]);
+        assert_eq!(index.get_statistics().total_ambiguous_links, 1);
+
+        // 移除别名后链接变为断链，标题优先于其他笔记的别名
+        index.update_note_content("b", "正文");
+        assert_eq!(index.resolve_target("zk"), Some("c"));
+        let broken: Vec<String> = index.get_broken_links(None).into_iter().map(|b| b.link.target).collect();
+        assert_eq!(broken, vec!["卡片盒"]);
+        index.update_note_content("c", "---\naliases: [A]\n---\n");
+        assert_eq!(index.resolve_target("a"), Some("a"));
+        assert_eq!(index.get_ambiguous_links(None).len(), 0);
+    }
+
+    #[test]
+    fn test_links_to_trashed_notes() {
+        let mut index = LinkIndex::build(vec![
+            note("a", "notes/a.md", "Alpha", "[[Beta]]\n[b](beta.md)\n[[Missing]]"),
+            note("b", "notes/beta.md", "Beta", "# Beta"),
+        ]);
+
+        // 移入回收站：指向它的链接不算断链
+        index.register_trashed_note("b".to_string(), PathBuf::from("notes/beta.md"), "Beta".to_string());
+        let broken: Vec<String> = index.get_broken_links(None).into_iter().map(|b| b.link.target).collect();
+        assert_eq!(broken, vec!["Missing"]);
+        let trashed = index.get_trashed_links(Some("a"));
+        assert_eq!(trashed.len(), 2);
+        assert_eq!((trashed[0].trashed_note_id.as_str(), trashed[0].trashed_note_path.as_str()), ("b", "notes/beta.md"));
+        assert_eq!(trashed[1].link.line_number, 2);
+        let stats = index.get_statistics();
+        assert_eq!((stats.total_notes, stats.total_broken_links, stats.total_trashed_links), (1, 1, 2));
+
+        // 恢复后链接重新生效
+        index.register_note("b".to_string(), PathBuf::from("notes/beta.md"), "Beta".to_string());
+        assert!(index.get_trashed_links(None).is_empty());
+        assert_eq!(index.get_backlinks("b").len(), 1);
+
+        // 彻底删除后变为断链
+        index.register_trashed_note("b".to_string(), PathBuf::from("notes/beta.md"), "Beta".to_string());
+        index.unregister_note("b");
+        assert!(index.get_trashed_links(None).is_empty());
+        assert_eq!(index.get_broken_links(None).len(), 3);
+    }
+
+    #[test]
+    fn test_find_similar_notes() {
+        let index = LinkIndex::build(vec![
+            note("a", "a.md", "A", "[[x]] [[y]]"),
+            note("b", "b.md", "B", "[[x]] [[y]]"),
+            note("c", "c.md", "C", "[[x]]"),
+            note("x", "x.md", "X", ""),
+            note("y", "y.md", "Y", ""),
+        ]);
+
+        let similar = index.find_similar_notes("a", 10);
+        let ids: Vec<&str> = similar.iter().map(|s| s.note_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+        assert_eq!(similar[0].similarity_score, 0.5);
+        assert_eq!(similar[0].common_links, vec!["x", "y"]);
+    }
+}
@@ -0,0 +1,17 @@
+//! 链接图谱：解析笔记中的链接，维护正向/反向链接、断链和孤立笔记
+
+pub mod anchor;
+pub mod index;
+pub mod model;
+pub mod parser;
+pub mod rename;
+mod resolver;
+pub mod store;
+
+pub use anchor::*;
+pub use index::*;
+pub use model::*;
+pub use parser::*;
+pub use rename::*;
+pub(crate) use resolver::LinkResolver;
+pub use store::*;
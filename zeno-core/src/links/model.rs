@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Wiki链接结构，支持多种链接格式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WikiLink {
+    /// 原始链接文本，如 "[[target|alias]]"
+    pub raw: String,
+    /// 目标文件路径或标题
+    pub target: String,
+    /// 显示别名，如果有的话
+    pub alias: Option<String>,
+    /// 锚点，如 "#section"
+    pub anchor: Option<String>,
+    /// 是否为嵌入链接（以 ! 开头）
+    pub is_embed: bool,
+    /// 在文档中的位置范围
+    pub range: Range<usize>,
+    /// 链接所在的行号
+    pub line_number: usize,
+}
+
+impl WikiLink {
+    /// 获取显示文本（别名或目标）
+    pub fn display_text(&self) -> &str {
+        self.alias.as_ref().unwrap_or(&self.target)
+    }
+    
+    /// 获取完整目标（包含锚点）
+    pub fn full_target(&self) -> String {
+        if let Some(anchor) = &self.anchor {
+            format!("{}#{}", self.target, anchor)
+        } else {
+            self.target.clone()
+        }
+    }
+    
+    /// 链接中的锚点（标题或 `^块ID`），`[text](url)` 形式的链接取 URL 中的片段
+    pub fn target_anchor(&self) -> Option<String> {
+        let anchor = match self.link_type() {
+            LinkType::Markdown => self.target.split_once('#').map(|(_, fragment)| fragment.to_string()),
+            _ => self.anchor.clone(),
+        };
+        anchor.filter(|anchor| !anchor.trim().is_empty())
+    }
+
+    /// 检查是否为有效链接
+    pub fn is_valid(&self) -> bool {
+        !self.target.trim().is_empty()
+    }
+
+    /// 链接类型，`[text](url)` 形式的链接也用本结构表示
+    pub fn link_type(&self) -> LinkType {
+        if self.is_embed {
+            LinkType::Embed
+        } else if self.raw.starts_with("[[") {
+            LinkType::Wiki
+        } else {
+            LinkType::Markdown
+        }
+    }
+}
+
+/// 反向链接信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacklinkInfo {
+    /// 源笔记ID
+    pub source_note_id: String,
+    /// 源笔记标题
+    pub source_note_title: String,
+    /// 源笔记路径
+    pub source_note_path: String,
+    /// 链接上下文（链接前后的文本）
+    pub context: String,
+    /// 链接在源笔记中的行号
+    pub line_number: usize,
+    /// 链接类型
+    pub link_type: LinkType,
+    /// 链接出现次数
+    pub occurrence_count: usize,
+}
+
+/// 链接类型
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LinkType {
+    /// 普通Wiki链接
+    Wiki,
+    /// 嵌入链接
+    Embed,
+    /// Markdown链接
+    Markdown,
+    /// 标签引用
+    Tag,
+}
+
+/// 链接解析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkParseResult {
+    /// 解析到的所有链接
+    pub links: Vec<WikiLink>,
+    /// 解析错误（如果有）
+    pub errors: Vec<LinkParseError>,
+    /// 解析统计信息
+    pub stats: LinkStats,
+}
+
+/// 链接解析错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkParseError {
+    /// 错误位置
+    pub position: Range<usize>,
+    /// 错误信息
+    pub message: String,
+    /// 原始文本
+    pub raw_text: String,
+}
+
+/// 链接统计信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkStats {
+    /// Wiki链接数量
+    pub wiki_links: usize,
+    /// 嵌入链接数量
+    pub embed_links: usize,
+    /// Markdown链接数量
+    pub markdown_links: usize,
+    /// 断链数量
+    pub broken_links: usize,
+}
+
+/// 相似笔记信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarNote {
+    /// 笔记ID
+    pub note_id: String,
+    /// 笔记标题
+    pub title: String,
+    /// 笔记路径
+    pub path: String,
+    /// 相似度评分 (0.0 - 1.0)
+    pub similarity_score: f64,
+    /// 共同链接
+    pub common_links: Vec<String>,
+    /// 相似性原因
+    pub similarity_reason: String,
+}
+
+/// 断链类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrokenLinkKind {
+    /// 找不到目标笔记
+    #[default]
+    MissingNote,
+    /// 目标笔记中没有链接的标题
+    MissingHeading,
+    /// 目标笔记中没有链接的块ID
+    MissingBlock,
+}
+
+/// 断链信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLink {
+    /// 源笔记ID
+    pub source_note_id: String,
+    /// 源笔记路径
+    pub source_note_path: String,
+    /// 断链信息
+    pub link: WikiLink,
+    /// 断链类型
+    #[serde(default)]
+    pub kind: BrokenLinkKind,
+    /// 锚点失效时为目标笔记ID
+    #[serde(default)]
+    pub target_note_id: Option<String>,
+    /// 建议的修复方案：找不到笔记时为相近的笔记名，锚点失效时为目标笔记中相近的标题或块ID
+    pub suggestions: Vec<String>,
+}
+
+/// 指向回收站中笔记的链接，笔记恢复后重新生效
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedLink {
+    /// 源笔记ID
+    pub source_note_id: String,
+    /// 源笔记路径
+    pub source_note_path: String,
+    /// 链接信息
+    pub link: WikiLink,
+    /// 回收站中的目标笔记ID
+    pub trashed_note_id: String,
+    /// 目标笔记删除前的路径
+    pub trashed_note_path: String,
+}
+
+/// 目标同时是多篇笔记的标题、文件名或别名的链接，解析结果可能不是作者想要的笔记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmbiguousLink {
+    /// 源笔记ID
+    pub source_note_id: String,
+    /// 源笔记路径
+    pub source_note_path: String,
+    /// 链接信息
+    pub link: WikiLink,
+    /// 实际解析到的笔记ID
+    pub target_note_id: String,
+    /// 声明了该名称的笔记ID，按路径排序
+    pub candidates: Vec<String>,
+    /// 候选笔记的路径，与 `candidates` 一一对应
+    pub candidate_paths: Vec<String>,
+}
+
+/// 未链接的提及：笔记中出现了另一篇笔记的标题或别名，但没有写成链接
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlinkedMention {
+    /// 源笔记ID
+    pub source_note_id: String,
+    /// 源笔记路径
+    pub source_note_path: String,
+    /// 被提及的笔记ID
+    pub target_note_id: String,
+    /// 匹配到的原文
+    pub text: String,
+    /// 原文在文件内容中的字节范围
+    pub range: Range<usize>,
+    /// 所在行号（从 1 开始，相对于完整文件）
+    pub line_number: usize,
+    /// 所在行的文本
+    pub context: String,
+    /// 改为链接时写入的文本，如 `[[标题]]` 或 `[[路径|原文]]`
+    pub link: String,
+}
@@ -0,0 +1,442 @@
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+
+use super::anchor::NoteAnchors;
+use super::model::{LinkParseError, LinkParseResult, LinkStats, LinkType, UnlinkedMention, WikiLink};
+use crate::models::is_cjk;
+use crate::parser::split_frontmatter;
+
+/// 匹配 [[target]] 或 [[target|alias]] 或 ![[target]]
+static WIKI_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(!?)\[\[([^\]]+?)(?:\|([^\]]+?))?\]\]").unwrap());
+
+/// 匹配 [text](url)
+static MARKDOWN_LINK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+?)\]\(([^)]+?)\)").unwrap());
+
+/// 代码块和行内代码，其中的链接语法不算链接
+static CODE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)```.*?```|~~~.*?~~~|`[^`\n]+`").unwrap());
+
+/// 裸 URL，查找提及时跳过
+static URL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[a-zA-Z][a-zA-Z0-9+.-]*://[^\s<>()]+").unwrap());
+
+/// Wiki链接解析器
+#[derive(Debug, Clone, Default)]
+pub struct LinkParser;
+
+impl LinkParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析文本中的所有链接
+    pub fn parse_links(&self, content: &str) -> LinkParseResult {
+        let mut links = Vec::new();
+        let mut errors = Vec::new();
+        let mut stats = LinkStats::default();
+        let code_ranges: Vec<Range<usize>> = CODE_REGEX.find_iter(content).map(|m| m.range()).collect();
+
+        // 解析Wiki链接
+        self.parse_wiki_links(content, &code_ranges, &mut links, &mut errors, &mut stats);
+
+        // 解析Markdown链接
+        self.parse_markdown_links(content, &code_ranges, &mut links, &mut stats);
+
+        // 按位置排序
+        links.sort_by_key(|link| link.range.start);
+
+        LinkParseResult {
+            links,
+            errors,
+            stats,
+        }
+    }
+
+    /// 解析Wiki风格的链接
+    fn parse_wiki_links(
+        &self,
+        content: &str,
+        code_ranges: &[Range<usize>],
+        links: &mut Vec<WikiLink>,
+        errors: &mut Vec<LinkParseError>,
+        stats: &mut LinkStats,
+    ) {
+        for cap in WIKI_LINK_REGEX.captures_iter(content) {
+            if in_code(cap.get(0).unwrap().start(), code_ranges) {
+                continue;
+            }
+            match self.parse_single_wiki_link(content, &cap) {
+                Ok(link) => {
+                    if link.is_embed {
+                        stats.embed_links += 1;
+                    } else {
+                        stats.wiki_links += 1;
+                    }
+                    links.push(link);
+                }
+                Err(error) => {
+                    errors.push(error);
+                }
+            }
+        }
+    }
+
+    /// 解析单个Wiki链接
+    fn parse_single_wiki_link(
+        &self,
+        content: &str,
+        cap: &regex::Captures,
+    ) -> Result<WikiLink, LinkParseError> {
+        let full_match = cap.get(0).unwrap();
+        let is_embed = cap.get(1).is_some_and(|m| m.as_str() == "!");
+        let target_with_anchor = cap.get(2).unwrap().as_str();
+        let alias = cap.get(3).map(|m| m.as_str().to_string());
+
+        // 解析目标和锚点
+        let (target, anchor) = if let Some(pos) = target_with_anchor.find('#') {
+            (
+                target_with_anchor[..pos].to_string(),
+                Some(target_with_anchor[pos + 1..].to_string()),
+            )
+        } else {
+            (target_with_anchor.to_string(), None)
+        };
+
+        // 验证目标
+        if target.trim().is_empty() {
+            return Err(LinkParseError {
+                position: full_match.range(),
+                message: "链接目标不能为空".to_string(),
+                raw_text: full_match.as_str().to_string(),
+            });
+        }
+
+        Ok(WikiLink {
+            raw: full_match.as_str().to_string(),
+            target: target.trim().to_string(),
+            alias: alias.map(|a| a.trim().to_string()),
+            anchor: anchor.map(|a| a.trim().to_string()),
+            is_embed,
+            range: full_match.range(),
+            line_number: line_number(content, full_match.start()),
+        })
+    }
+
+    /// 解析Markdown链接
+    fn parse_markdown_links(
+        &self,
+        content: &str,
+        code_ranges: &[Range<usize>],
+        links: &mut Vec<WikiLink>,
+        stats: &mut LinkStats,
+    ) {
+        for cap in MARKDOWN_LINK_REGEX.captures_iter(content) {
+            let full_match = cap.get(0).unwrap();
+            if in_code(full_match.start(), code_ranges) {
+                continue;
+            }
+            let text = cap.get(1).unwrap().as_str();
+            // 去掉可选的标题：[text](url "title")
+            let url = cap.get(2).unwrap().as_str().split_whitespace().next().unwrap_or_default();
+
+            // 只处理本地文件链接，忽略外部链接、邮箱和页内锚点
+            if url.is_empty() || url.contains("://") || url.starts_with("mailto:") || url.starts_with('#') {
+                continue;
+            }
+
+            let link = WikiLink {
+                raw: full_match.as_str().to_string(),
+                target: url.to_string(),
+                alias: Some(text.to_string()),
+                anchor: None,
+                is_embed: false,
+                range: full_match.range(),
+                line_number: line_number(content, full_match.start()),
+            };
+
+            stats.markdown_links += 1;
+            links.push(link);
+        }
+    }
+
+    /// 提取链接周围的上下文
+    pub fn extract_link_context(&self, content: &str, link: &WikiLink, context_size: usize) -> String {
+        let lines: Vec<&str> = content.lines().collect();
+        let line_idx = link.line_number.saturating_sub(1);
+
+        if line_idx >= lines.len() {
+            return String::new();
+        }
+
+        let start_line = line_idx.saturating_sub(context_size);
+        let end_line = std::cmp::min(line_idx + context_size + 1, lines.len());
+
+        lines[start_line..end_line].join("\n")
+    }
+
+    /// 提取链接引用的内容，`target_content` 为目标笔记的内容
+    ///
+    /// 链接带锚点时返回对应的章节（`[[笔记#标题]]`）或块（`[[笔记#^块ID]]`），
+    /// 否则返回整篇内容；锚点在目标笔记中不存在时返回 `None`。
+    pub fn extract_linked_section(&self, target_content: &str, link: &WikiLink) -> Option<String> {
+        let Some(anchor) = link.target_anchor() else {
+            return Some(target_content.to_string());
+        };
+        let anchors = NoteAnchors::parse(target_content);
+        anchors.find(&anchor).map(|found| anchors.section(target_content, found))
+    }
+
+    /// 查找文本中出现的名称（笔记标题、别名），返回在 `content` 中的字节范围
+    ///
+    /// 忽略大小写；名称首尾是字母或数字时按词边界匹配，中日韩文字没有词边界，直接匹配。
+    /// 跳过前言、代码、已有的链接、URL 和 `#标签`，重叠的匹配取较长的。
+    pub fn find_mentions(&self, content: &str, names: &[String]) -> Vec<Range<usize>> {
+        let body_offset = split_frontmatter(content).map_or(0, |block| block.body_offset);
+        let mut excluded: Vec<Range<usize>> = CODE_REGEX.find_iter(content).map(|m| m.range()).collect();
+        excluded.push(0..body_offset);
+        excluded.extend(URL_REGEX.find_iter(content).map(|m| m.range()));
+        excluded.extend(self.parse_links(content).links.into_iter().map(|link| link.range));
+
+        let mut candidates = Vec::new();
+        for name in names.iter().map(|name| name.trim()).filter(|name| !name.is_empty()) {
+            let Ok(regex) = RegexBuilder::new(&regex::escape(name)).case_insensitive(true).build() else {
+                continue;
+            };
+            for m in regex.find_iter(content) {
+                let range = m.range();
+                let overlaps = excluded.iter().any(|e| e.start < range.end && range.start < e.end);
+                if !overlaps && is_standalone(content, &range) {
+                    candidates.push(range);
+                }
+            }
+        }
+
+        candidates.sort_by_key(|range| (range.start, std::cmp::Reverse(range.end)));
+        let mut mentions: Vec<Range<usize>> = Vec::new();
+        for range in candidates {
+            if mentions.last().is_none_or(|last| last.end <= range.start) {
+                mentions.push(range);
+            }
+        }
+        mentions
+    }
+
+    /// 把未链接的提及替换为 `mention.link`，从后往前替换
+    ///
+    /// 提及的原文与内容不符（文件在查找后被修改）或提及互相重叠时返回 `None`。
+    pub fn link_mentions(&self, content: &str, mentions: &[UnlinkedMention]) -> Option<String> {
+        let mut sorted: Vec<&UnlinkedMention> = mentions.iter().collect();
+        sorted.sort_by_key(|mention| std::cmp::Reverse(mention.range.start));
+
+        let mut result = content.to_string();
+        let mut next_start = content.len();
+        for mention in sorted {
+            if mention.range.end > next_start || content.get(mention.range.clone()) != Some(mention.text.as_str()) {
+                return None;
+            }
+            result.replace_range(mention.range.clone(), &mention.link);
+            next_start = mention.range.start;
+        }
+        Some(result)
+    }
+
+    /// 替换文档中的链接
+    pub fn replace_link(&self, content: &str, old_link: &WikiLink, new_target: &str) -> String {
+        let mut result = content.to_string();
+        result.replace_range(old_link.range.clone(), &format_link(old_link, new_target));
+        result
+    }
+
+    /// 批量替换链接
+    pub fn replace_multiple_links(
+        &self,
+        content: &str,
+        replacements: &[(WikiLink, String)],
+    ) -> String {
+        let mut result = content.to_string();
+
+        // 按位置逆序排序，从后往前替换以避免位置偏移
+        let mut sorted_replacements = replacements.to_vec();
+        sorted_replacements.sort_by_key(|(link, _)| std::cmp::Reverse(link.range.start));
+
+        for (old_link, new_target) in sorted_replacements {
+            result.replace_range(old_link.range.clone(), &format_link(&old_link, &new_target));
+        }
+
+        result
+    }
+}
+
+/// 按新目标重新生成链接文本
+///
+/// Wiki 链接保留嵌入标记、锚点和别名；`[text](url)` 形式的链接只替换 URL，
+/// 其中的锚点属于 URL，需要包含在 `new_target` 中。
+fn format_link(old_link: &WikiLink, new_target: &str) -> String {
+    if old_link.link_type() == LinkType::Markdown {
+        return old_link.raw.replacen(&format!("]({}", old_link.target), &format!("]({}", new_target), 1);
+    }
+
+    let embed = if old_link.is_embed { "!" } else { "" };
+    let anchor = old_link.anchor.as_ref().map(|anchor| format!("#{}", anchor)).unwrap_or_default();
+    let alias = old_link.alias.as_ref().map(|alias| format!("|{}", alias)).unwrap_or_default();
+    format!("{}[[{}{}{}]]", embed, new_target, anchor, alias)
+}
+
+/// 计算给定位置的行号
+fn line_number(content: &str, position: usize) -> usize {
+    content[..position].matches('\n').count() + 1
+}
+
+fn in_code(position: usize, code_ranges: &[Range<usize>]) -> bool {
+    code_ranges.iter().any(|range| range.contains(&position))
+}
+
+/// 匹配是否独立成词：首尾是字母或数字（中日韩文字除外）时，相邻的字符不能也是；
+/// 紧跟在 `#` 后的是标签
+fn is_standalone(content: &str, range: &Range<usize>) -> bool {
+    let is_word = |c: char| (c.is_alphanumeric() || c == '_') && !is_cjk(c);
+    let text = &content[range.clone()];
+    let before = content[..range.start].chars().next_back();
+    let after = content[range.end..].chars().next();
+
+    let joined_before = text.chars().next().is_some_and(is_word) && before.is_some_and(is_word);
+    let joined_after = text.chars().next_back().is_some_and(is_word) && after.is_some_and(is_word);
+    !joined_before && !joined_after && before != Some('#')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wiki_links() {
+        let parser = LinkParser::new();
+        let content = r#"
+这是一个 [[测试笔记]] 的链接。
+还有一个带别名的 [[another-note|另一个笔记]] 链接。
+这是嵌入链接 ![[图片.png]]。
+带锚点的链接 [[笔记#章节]]。
+代码中的 `[[不是链接]]` 会被忽略。
+"#;
+
+        let result = parser.parse_links(content);
+
+        assert_eq!(result.links.len(), 4);
+        assert_eq!(result.stats.wiki_links, 3);
+        assert_eq!(result.stats.embed_links, 1);
+
+        // 测试第一个链接
+        let first_link = &result.links[0];
+        assert_eq!(first_link.target, "测试笔记");
+        assert_eq!(first_link.alias, None);
+        assert_eq!(first_link.line_number, 2);
+        assert!(!first_link.is_embed);
+
+        // 测试带别名的链接
+        let alias_link = &result.links[1];
+        assert_eq!(alias_link.target, "another-note");
+        assert_eq!(alias_link.alias, Some("另一个笔记".to_string()));
+
+        // 测试嵌入链接
+        let embed_link = &result.links[2];
+        assert_eq!(embed_link.target, "图片.png");
+        assert!(embed_link.is_embed);
+
+        // 测试锚点链接
+        let anchor_link = &result.links[3];
+        assert_eq!(anchor_link.target, "笔记");
+        assert_eq!(anchor_link.anchor, Some("章节".to_string()));
+    }
+
+    #[test]
+    fn test_parse_markdown_links() {
+        let parser = LinkParser::new();
+        let content = "这是 [Markdown链接](./path/to/file.md) 和 [外部链接](https://example.com)。";
+
+        let result = parser.parse_links(content);
+
+        // 应该只解析本地文件链接，忽略HTTP链接
+        assert_eq!(result.links.len(), 1);
+        assert_eq!(result.stats.markdown_links, 1);
+
+        let link = &result.links[0];
+        assert_eq!(link.target, "./path/to/file.md");
+        assert_eq!(link.alias, Some("Markdown链接".to_string()));
+    }
+
+    #[test]
+    fn test_replace_links_keeps_anchor_alias_and_embed() {
+        let parser = LinkParser::new();
+        let content = "[[Old#章节|别名]] ![[Old|300]] [文字](../old.md#top \"标题\")";
+        let links = parser.parse_links(content).links;
+        let replacements = vec![
+            (links[0].clone(), "New".to_string()),
+            (links[1].clone(), "sub/New".to_string()),
+            (links[2].clone(), "../sub/new.md#top".to_string()),
+        ];
+
+        assert_eq!(
+            parser.replace_multiple_links(content, &replacements),
+            "[[New#章节|别名]] ![[sub/New|300]] [文字](../sub/new.md#top \"标题\")"
+        );
+    }
+
+    #[test]
+    fn test_link_context_extraction() {
+        let parser = LinkParser::new();
+        let content = r#"第一行
+第二行有一个 [[测试链接]]
+第三行
+第四行"#;
+
+        let result = parser.parse_links(content);
+        let link = &result.links[0];
+
+        let context = parser.extract_link_context(content, link, 1);
+        assert!(context.contains("第一行"));
+        assert!(context.contains("第二行有一个 [[测试链接]]"));
+        assert!(context.contains("第三行"));
+    }
+
+    #[test]
+    fn test_extract_linked_section() {
+        let parser = LinkParser::new();
+        let target = "# 笔记\n\n## 方法\n步骤 ^step\n\n## 结果\n完成";
+        let links = parser.parse_links("[[笔记#方法]] ![[笔记#^step]] [[笔记]] [[笔记#没有]]").links;
+
+        assert_eq!(parser.extract_linked_section(target, &links[0]).unwrap(), "## 方法\n步骤 ^step");
+        assert_eq!(parser.extract_linked_section(target, &links[1]).unwrap(), "步骤");
+        assert_eq!(parser.extract_linked_section(target, &links[2]).unwrap(), target);
+        assert!(parser.extract_linked_section(target, &links[3]).is_none());
+    }
+
+    #[test]
+    fn test_find_mentions() {
+        let parser = LinkParser::new();
+        let names = vec!["Rust".to_string(), "卡片盒".to_string(), "Rust Lang".to_string()];
+        let content = "---\ntitle: Rust\n---\nrust 和 RUST LANG，学习Rust笔记；Rusty trust #rust\n\
+            卡片盒方法 [[卡片盒]] [Rust](rust.md) `rust` https://rust.example.com";
+        let found: Vec<&str> = parser.find_mentions(content, &names).into_iter().map(|r| &content[r]).collect();
+        assert_eq!(found, vec!["rust", "RUST LANG", "Rust", "卡片盒"]);
+    }
+
+    #[test]
+    fn test_link_mentions() {
+        let parser = LinkParser::new();
+        let content = "Rust 与 rust";
+        let mention = |range: Range<usize>, link: &str| UnlinkedMention {
+            source_note_id: "a".to_string(),
+            source_note_path: "a.md".to_string(),
+            target_note_id: "r".to_string(),
+            text: content[range.clone()].to_string(),
+            range,
+            line_number: 1,
+            context: content.to_string(),
+            link: link.to_string(),
+        };
+        let mentions = vec![mention(0..4, "[[Rust]]"), mention(9..13, "[[Rust|rust]]")];
+        assert_eq!(parser.link_mentions(content, &mentions).unwrap(), "[[Rust]] 与 [[Rust|rust]]");
+        assert!(parser.link_mentions("Rust 与 ruby", &mentions).is_none());
+    }
+}
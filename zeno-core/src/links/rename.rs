@@ -0,0 +1,243 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use super::index::LinkIndex;
+use super::model::{LinkType, WikiLink};
+use super::parser::LinkParser;
+use crate::error::{Error, Result};
+
+/// 重命名时改写的一个链接
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkEdit {
+    /// 链接所在行号（从 1 开始）
+    pub line: usize,
+    /// 改写前的链接文本
+    pub before: String,
+    /// 改写后的链接文本
+    pub after: String,
+}
+
+/// 重命名时需要改写的文件
+#[derive(Debug, Clone, Serialize)]
+pub struct FileRewrite {
+    pub note_id: String,
+    /// 文件路径（相对工作区），被重命名的笔记为新路径
+    pub path: PathBuf,
+    pub edits: Vec<LinkEdit>,
+    /// 改写后的完整内容
+    #[serde(skip)]
+    pub content: String,
+}
+
+/// 随重命名移动的一篇笔记
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteMove {
+    pub note_id: String,
+    /// 原路径（相对工作区）
+    pub from: PathBuf,
+    /// 新路径（相对工作区）
+    pub to: PathBuf,
+}
+
+/// 重命名笔记或文件夹的计划：移动的文件和需要改写链接的文件
+#[derive(Debug, Clone, Serialize)]
+pub struct RenamePlan {
+    /// 移动的笔记文件或文件夹的原路径（相对工作区）
+    pub from: PathBuf,
+    /// 新路径（相对工作区）
+    pub to: PathBuf,
+    /// 移动的笔记，重命名单篇笔记时只有一篇
+    pub moves: Vec<NoteMove>,
+    pub rewrites: Vec<FileRewrite>,
+}
+
+impl RenamePlan {
+    /// 计算把笔记移到 `to` 后需要改写的链接
+    ///
+    /// `index` 为重命名前的链接索引，`sources` 为可能受影响的笔记的 `(ID, 内容)`：
+    /// 链接到该笔记的笔记和该笔记本身。移动后仍能解析到原目标的链接保持不变，
+    /// 其余改写为能解析到目标的最短形式，保留嵌入标记、锚点和别名。
+    /// 笔记本身移到其他目录时，其中的相对 Markdown 链接也会改写。
+    pub fn new(index: &LinkIndex, note_id: &str, to: &Path, sources: &[(String, String)]) -> Result<Self> {
+        let from = index
+            .note_path(note_id)
+            .ok_or_else(|| Error::not_found(format!("笔记 {}", note_id)))?
+            .to_path_buf();
+        Self::for_moves(index, from, to.to_path_buf(), &[(note_id.to_string(), to.to_path_buf())], sources)
+    }
+
+    /// 计算把文件夹 `from` 移到 `to` 后需要改写的链接，文件夹中的笔记一起移动
+    ///
+    /// `sources` 为链接到文件夹中笔记的笔记和文件夹中的笔记本身。
+    pub fn for_folder(index: &LinkIndex, from: &Path, to: &Path, sources: &[(String, String)]) -> Result<Self> {
+        let moves: Vec<(String, PathBuf)> = index
+            .notes_under(from)
+            .into_iter()
+            .filter_map(|note_id| {
+                let relative = index.note_path(note_id)?.strip_prefix(from).ok()?;
+                Some((note_id.to_string(), to.join(relative)))
+            })
+            .collect();
+        Self::for_moves(index, from.to_path_buf(), to.to_path_buf(), &moves, sources)
+    }
+
+    fn for_moves(
+        index: &LinkIndex,
+        from: PathBuf,
+        to: PathBuf,
+        moves: &[(String, PathBuf)],
+        sources: &[(String, String)],
+    ) -> Result<Self> {
+        let mut moved = index.clone();
+        let mut note_moves = Vec::with_capacity(moves.len());
+        for (note_id, note_to) in moves {
+            let note_from = index
+                .note_path(note_id)
+                .ok_or_else(|| Error::not_found(format!("笔记 {}", note_id)))?;
+            moved.register_note(note_id.clone(), note_to.clone(), index.note_title(note_id).unwrap_or_default().to_string());
+            note_moves.push(NoteMove { note_id: note_id.clone(), from: note_from.to_path_buf(), to: note_to.clone() });
+        }
+        let is_moved = |id: &str| moves.iter().any(|(note_id, _)| note_id == id);
+
+        let parser = LinkParser::new();
+        let mut rewrites = Vec::new();
+        for (source_id, content) in sources {
+            let (Some(old_path), Some(new_path)) = (index.note_path(source_id), moved.note_path(source_id)) else {
+                continue;
+            };
+            let source_moved = is_moved(source_id);
+
+            let mut replacements = Vec::new();
+            let mut edits = Vec::new();
+            for link in parser.parse_links(content).links {
+                let Some(target_id) = index.resolve_link_at(old_path, &link) else {
+                    continue;
+                };
+                let relative_link = source_moved && link.link_type() == LinkType::Markdown;
+                if !is_moved(target_id) && !relative_link {
+                    continue;
+                }
+                if moved.resolve_link_at(new_path, &link) == Some(target_id) {
+                    continue;
+                }
+
+                let target = new_link_target(&moved, new_path, &link, target_id)
+                    .ok_or_else(|| Error::validation(format!("无法为 {} 生成新的链接", link.raw)))?;
+                let after = parser.replace_link(&link.raw, &WikiLink { range: 0..link.raw.len(), ..link.clone() }, &target);
+                edits.push(LinkEdit { line: link.line_number, before: link.raw.clone(), after });
+                replacements.push((link, target));
+            }
+
+            if !edits.is_empty() {
+                rewrites.push(FileRewrite {
+                    note_id: source_id.clone(),
+                    path: new_path.to_path_buf(),
+                    edits,
+                    content: parser.replace_multiple_links(content, &replacements),
+                });
+            }
+        }
+        rewrites.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Self { from, to, moves: note_moves, rewrites })
+    }
+
+    /// 需要改写的链接总数
+    pub fn link_count(&self) -> usize {
+        self.rewrites.iter().map(|rewrite| rewrite.edits.len()).sum()
+    }
+}
+
+/// 链接的新目标：Wiki 链接用能唯一解析到目标的最短路径后缀（不含扩展名），
+/// Markdown 链接用相对源笔记目录的路径（以 `/` 开头的保持为工作区路径），保留原有的锚点
+fn new_link_target(index: &LinkIndex, source: &Path, link: &WikiLink, target_id: &str) -> Option<String> {
+    let target = index.note_path(target_id)?;
+
+    if link.link_type() != LinkType::Markdown {
+        let stem = target.with_extension("");
+        let parts: Vec<String> = stem.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+        return (1..=parts.len())
+            .map(|n| parts[parts.len() - n..].join("/"))
+            .find(|candidate| index.resolve_target(candidate) == Some(target_id));
+    }
+
+    let suffix = link.target.find(['#', '?']).map(|i| &link.target[i..]).unwrap_or_default();
+    let path = if link.target.starts_with('/') {
+        format!("/{}", to_url_path(target))
+    } else {
+        to_url_path(&relative_path(source.parent().unwrap_or_else(|| Path::new("")), target))
+    };
+    Some(format!("{}{}", path.replace(' ', "%20"), suffix))
+}
+
+/// `target` 相对于目录 `base` 的路径，两者都是相对工作区的路径
+fn relative_path(base: &Path, target: &Path) -> PathBuf {
+    let base: Vec<Component> = base.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = base.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..base.len() {
+        path.push("..");
+    }
+    for component in &target[common..] {
+        path.push(component);
+    }
+    path
+}
+
+fn to_url_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, path: &str, title: &str, content: &str) -> (String, PathBuf, String, String) {
+        (id.to_string(), PathBuf::from(path), title.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_rename_plan_rewrites_inbound_links() {
+        let notes = vec![
+            note("a", "notes/a.md", "A", "[[old note#章节|别名]] ![[Old Note]]\n[x](sub/old%20note.md#top) [[Other]]"),
+            note("o", "notes/sub/old note.md", "Old Note", "[[old note#自身]] [a](../a.md) [[A]]"),
+            note("t", "notes/other.md", "Other", "无关"),
+        ];
+        let index = LinkIndex::build(notes.clone());
+        let sources: Vec<(String, String)> = notes.iter().map(|(id, _, _, content)| (id.clone(), content.clone())).collect();
+
+        // 标题没有变，按标题和文件名写的 Wiki 链接仍然有效，按路径写的链接需要改写
+        let plan = RenamePlan::new(&index, "o", Path::new("notes/new.md"), &sources).unwrap();
+        assert_eq!(plan.from, PathBuf::from("notes/sub/old note.md"));
+        let a = &plan.rewrites[0];
+        assert_eq!(a.path, PathBuf::from("notes/a.md"));
+        assert_eq!(a.edits.len(), 1);
+        assert_eq!((a.edits[0].line, a.edits[0].after.as_str()), (2, "[x](new.md#top)"));
+        assert_eq!(a.content, "[[old note#章节|别名]] ![[Old Note]]\n[x](new.md#top) [[Other]]");
+        // 笔记本身换了目录，其中的相对链接随之改写
+        let moved = &plan.rewrites[1];
+        assert_eq!(moved.path, PathBuf::from("notes/new.md"));
+        assert_eq!(moved.content, "[[old note#自身]] [a](a.md) [[A]]");
+        assert_eq!(plan.link_count(), 2);
+
+        // 标题与文件名不同时，按旧文件名写的链接改为新文件名
+        let mut renamed = index.clone();
+        renamed.register_note("o".to_string(), PathBuf::from("notes/sub/old note.md"), "Renamed".to_string());
+        let plan = RenamePlan::new(&renamed, "o", Path::new("notes/new.md"), &sources).unwrap();
+        assert_eq!(
+            plan.rewrites[0].content,
+            "[[new#章节|别名]] ![[new]]\n[x](new.md#top) [[Other]]"
+        );
+        assert_eq!(plan.rewrites[1].content, "[[new#自身]] [a](a.md) [[A]]");
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path(Path::new("notes"), Path::new("notes/sub/a.md")), PathBuf::from("sub/a.md"));
+        assert_eq!(relative_path(Path::new("notes/x/y"), Path::new("notes/a.md")), PathBuf::from("../../a.md"));
+        assert_eq!(relative_path(Path::new(""), Path::new("a.md")), PathBuf::from("a.md"));
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use sqlx::SqliteConnection;
+
+use super::model::{LinkType, WikiLink};
+use crate::error::Result;
+use crate::indexer::{normalize_key, record_aliases};
+
+/// 链接目标解析：按路径、文件名或标题、前言中的别名、路径后缀匹配笔记
+///
+/// 索引器写入链接和 [`LinkIndex`](super::LinkIndex) 检查断链共用同一套规则。
+/// 同名时路径靠前的优先；笔记可以逐个加入或移除，批量索引时不必每篇重新加载。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LinkResolver {
+    by_path: HashMap<String, String>,
+    /// 小写的文件名或标题 -> `(规范化路径, 笔记ID)`，按路径排序
+    by_name: HashMap<String, Vec<(String, String)>>,
+    /// 小写的别名 -> `(规范化路径, 笔记ID)`，按路径排序
+    by_alias: HashMap<String, Vec<(String, String)>>,
+    /// 笔记ID -> 登记的键，更新或移除笔记时用于清理
+    keys: HashMap<String, ResolverKeys>,
+}
+
+#[derive(Debug, Clone)]
+struct ResolverKeys {
+    path: String,
+    names: Vec<String>,
+    aliases: Vec<String>,
+}
+
+impl LinkResolver {
+    /// 加载数据库中未删除的笔记
+    pub(crate) async fn load(conn: &mut SqliteConnection) -> Result<Self> {
+        let notes: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT id, title, file_path, frontmatter FROM notes WHERE status != 'deleted'"
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut resolver = Self::default();
+        for (id, title, file_path, frontmatter) in notes {
+            resolver.insert(&id, &title, &file_path, &record_aliases(&frontmatter));
+        }
+        Ok(resolver)
+    }
+
+    /// 加载回收站中的笔记，按删除前的路径登记
+    pub(crate) async fn load_trashed(conn: &mut SqliteConnection) -> Result<Self> {
+        let notes: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT t.note_id, n.title, t.original_path, n.frontmatter FROM trash t JOIN notes n ON n.id = t.note_id"
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut resolver = Self::default();
+        for (id, title, original_path, frontmatter) in notes {
+            resolver.insert(&id, &title, &original_path, &record_aliases(&frontmatter));
+        }
+        Ok(resolver)
+    }
+
+    /// 加入或更新笔记
+    pub(crate) fn insert(&mut self, id: &str, title: &str, file_path: &str, aliases: &[String]) {
+        self.remove(id);
+
+        let path = normalize_key(file_path);
+        let mut names = vec![path.rsplit('/').next().unwrap_or_default().to_string(), title.to_lowercase()];
+        names.dedup();
+        let mut aliases: Vec<String> = aliases
+            .iter()
+            .map(|alias| alias.trim().to_lowercase())
+            .filter(|alias| !alias.is_empty())
+            .collect();
+        aliases.sort();
+        aliases.dedup();
+
+        for name in &names {
+            claim(&mut self.by_name, name, &path, id);
+        }
+        for alias in &aliases {
+            claim(&mut self.by_alias, alias, &path, id);
+        }
+        self.by_path.insert(path.clone(), id.to_string());
+        self.keys.insert(id.to_string(), ResolverKeys { path, names, aliases });
+    }
+
+    /// 移除笔记
+    pub(crate) fn remove(&mut self, id: &str) {
+        let Some(keys) = self.keys.remove(id) else {
+            return;
+        };
+        if self.by_path.get(&keys.path).is_some_and(|owner| owner == id) {
+            self.by_path.remove(&keys.path);
+        }
+        for name in &keys.names {
+            release(&mut self.by_name, name, id);
+        }
+        for alias in &keys.aliases {
+            release(&mut self.by_alias, alias, id);
+        }
+    }
+
+    /// 解析 `[[target]]`：路径、文件名或标题、别名，最后是路径后缀（如 `[[sub/note]]`）
+    pub(crate) fn resolve_wiki(&self, target: &str) -> Option<&str> {
+        let key = normalize_key(target);
+        if key.is_empty() {
+            return None;
+        }
+        if let Some(id) = self
+            .by_path
+            .get(&key)
+            .map(String::as_str)
+            .or_else(|| first_claimant(&self.by_name, &key))
+            .or_else(|| first_claimant(&self.by_alias, &key))
+        {
+            return Some(id);
+        }
+
+        let suffix = format!("/{}", key);
+        self.by_path
+            .iter()
+            .filter(|(path, _)| path.ends_with(&suffix))
+            .min_by(|a, b| a.0.cmp(b.0))
+            .map(|(_, id)| id.as_str())
+    }
+
+    /// 解析 `base` 目录下的笔记中的 Markdown 链接，`/` 开头的相对于工作区
+    pub(crate) fn resolve_path(&self, base: &Path, url: &str) -> Option<&str> {
+        let url = url.split(['#', '?']).next().unwrap_or_default().replace("%20", " ");
+        if url.is_empty() {
+            return None;
+        }
+
+        let target = match url.strip_prefix('/') {
+            Some(absolute) => PathBuf::from(absolute),
+            None => base.join(&url),
+        };
+
+        let mut normalized = PathBuf::new();
+        for component in target.components() {
+            match component {
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                Component::Normal(part) => normalized.push(part),
+                _ => {}
+            }
+        }
+
+        self.by_path
+            .get(&normalize_key(&normalized.to_string_lossy()))
+            .map(String::as_str)
+    }
+
+    /// 解析 `base` 目录下的笔记中的链接：`[text](url)` 按路径，其余按 `[[target]]` 的规则
+    pub(crate) fn resolve_link(&self, base: &Path, link: &WikiLink) -> Option<&str> {
+        match link.link_type() {
+            LinkType::Markdown => self.resolve_path(base, &link.target),
+            _ => self.resolve_wiki(&link.target),
+        }
+    }
+
+    /// 以 `[[target]]` 为文件名、标题或别名的笔记（按路径排序）；能按路径解析时为空
+    pub(crate) fn claimants(&self, target: &str) -> Vec<&str> {
+        let key = normalize_key(target);
+        if self.by_path.contains_key(&key) {
+            return Vec::new();
+        }
+
+        let mut owners: Vec<&(String, String)> = self
+            .by_name
+            .get(&key)
+            .into_iter()
+            .chain(self.by_alias.get(&key))
+            .flatten()
+            .collect();
+        owners.sort();
+        owners.dedup_by(|a, b| a.1 == b.1);
+        owners.into_iter().map(|(_, id)| id.as_str()).collect()
+    }
+}
+
+/// 按路径顺序登记名称的持有者
+fn claim(map: &mut HashMap<String, Vec<(String, String)>>, key: &str, path: &str, id: &str) {
+    let owners = map.entry(key.to_string()).or_default();
+    let entry = (path.to_string(), id.to_string());
+    let position = owners.binary_search(&entry).unwrap_or_else(|position| position);
+    owners.insert(position, entry);
+}
+
+fn release(map: &mut HashMap<String, Vec<(String, String)>>, key: &str, id: &str) {
+    if let Some(owners) = map.get_mut(key) {
+        owners.retain(|(_, owner)| owner != id);
+        if owners.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+fn first_claimant<'a>(map: &'a HashMap<String, Vec<(String, String)>>, key: &str) -> Option<&'a str> {
+    map.get(key).and_then(|owners| owners.first()).map(|(_, id)| id.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_order_and_suffix() {
+        let mut resolver = LinkResolver::default();
+        resolver.insert("a", "Alpha", "notes/sub/a.md", &[]);
+        resolver.insert("b", "Beta", "notes/b.md", &["Alpha".to_string()]);
+        resolver.insert("c", "Alpha", "other/c.md", &[]);
+
+        assert_eq!(resolver.resolve_wiki("notes/b"), Some("b"));
+        assert_eq!(resolver.resolve_wiki("beta"), Some("b"));
+        // 标题优先于别名，同名时路径靠前的优先
+        assert_eq!(resolver.resolve_wiki("alpha"), Some("a"));
+        assert_eq!(resolver.claimants("alpha"), vec!["b", "a", "c"]);
+        assert!(resolver.claimants("notes/b").is_empty());
+        // 路径后缀
+        assert_eq!(resolver.resolve_wiki("sub/a"), Some("a"));
+        assert_eq!(resolver.resolve_wiki("missing"), None);
+
+        assert_eq!(resolver.resolve_path(Path::new("notes/sub"), "../b.md#part"), Some("b"));
+        assert_eq!(resolver.resolve_path(Path::new("notes/sub"), "/other/c.md"), Some("c"));
+
+        resolver.remove("a");
+        assert_eq!(resolver.resolve_wiki("alpha"), Some("c"));
+        assert_eq!(resolver.resolve_wiki("sub/a"), None);
+    }
+}
@@ -0,0 +1,622 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use sqlx::{FromRow, SqlitePool};
+
+use super::index::{rank_similar_notes, suggest_anchor_fixes, suggest_link_fixes, LinkIndexStats};
+use super::model::{
+    AmbiguousLink, BacklinkInfo, BrokenLink, BrokenLinkKind, LinkType, SimilarNote, UnlinkedMention, WikiLink,
+};
+use super::parser::LinkParser;
+use super::resolver::LinkResolver;
+use crate::db::models::{Link, LinkType as StoredLinkType, NoteRecord};
+use crate::error::{Error, Result};
+use crate::indexer::{record_aliases, SqliteIndexer};
+use crate::storage::{NoteRepository, SqliteNoteRepository};
+
+/// 查询源笔记未删除的链接原文，`{}` 处为附加的筛选条件
+const LINK_REF_SELECT: &str = r#"
+SELECT r.source_id, s.file_path AS source_path, r.target_id, t.status AS target_status,
+       r.raw, r.target, r.alias, r.anchor, r.is_embed, r.range_start, r.range_end, r.line_number, r.anchor_kind
+FROM link_refs r
+JOIN notes s ON s.id = r.source_id
+LEFT JOIN notes t ON t.id = r.target_id
+WHERE s.status != 'deleted' AND (?1 IS NULL OR r.source_id = ?1) AND ({})
+ORDER BY s.file_path, r.range_start
+"#;
+
+/// 找不到目标笔记，或目标笔记中没有链接的标题、块ID
+const BROKEN_FILTER: &str = r#"
+r.target_id IS NULL OR (
+    t.status != 'deleted' AND r.anchor_kind IS NOT NULL AND NOT EXISTS (
+        SELECT 1 FROM note_anchors a
+        WHERE a.note_id = r.target_id AND a.kind = r.anchor_kind AND r.anchor_key != ''
+          AND (a.anchor_key = r.anchor_key OR a.anchor_slug = r.anchor_slug)
+    )
+)
+"#;
+
+/// 按名称解析到未删除笔记的 `[[...]]` 和 `![[...]]` 链接
+const NAMED_FILTER: &str = "t.status != 'deleted' AND (r.is_embed OR substr(r.raw, 1, 2) = '[[')";
+
+/// 找不到目标笔记，或目标笔记已移入回收站
+const UNRESOLVED_FILTER: &str = "r.target_id IS NULL OR t.status = 'deleted'";
+
+/// 保存在工作区数据库 `links` 表中的链接图谱
+///
+/// 链接由 `SqliteIndexer` 在索引笔记时逐篇写入，查询直接读取数据库，
+/// 打开工作区后无需重新解析全部笔记。`links` 表中只有能解析到笔记的链接，
+/// 断链和歧义链接由 `link_refs` 表中保存的链接原文和 `note_anchors` 表中的标题、块ID检查；
+/// 行号相对于完整文件（含 frontmatter）。
+pub struct LinkStore {
+    repository: SqliteNoteRepository,
+}
+
+impl LinkStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { repository: SqliteNoteRepository::new(pool) }
+    }
+
+    /// 获取笔记的反向链接，按源笔记路径排序
+    pub async fn get_backlinks(&self, note_id: &str) -> Result<Vec<BacklinkInfo>> {
+        let mut by_source: BTreeMap<String, Vec<Link>> = BTreeMap::new();
+        for link in self.repository.get_inbound_links(note_id).await? {
+            // 链接到自身不计入链接关系
+            if link.source_id != note_id {
+                by_source.entry(link.source_id.clone()).or_default().push(link);
+            }
+        }
+
+        let mut backlinks = Vec::new();
+        for (source_id, mut links) in by_source {
+            let Some(source) = self.active_note(&source_id).await? else {
+                continue;
+            };
+            links.sort_by_key(|link| (link.source_line, link.source_column));
+            let first = &links[0];
+            let line = source
+                .content
+                .lines()
+                .nth((first.source_line as usize).saturating_sub(1))
+                .unwrap_or_default();
+
+            backlinks.push(BacklinkInfo {
+                source_note_id: source_id,
+                source_note_title: source.title,
+                source_note_path: source.file_path,
+                context: line.trim().to_string(),
+                line_number: first.source_line as usize,
+                link_type: link_type(first, line),
+                occurrence_count: links.len(),
+            });
+        }
+
+        backlinks.sort_by(|a, b| a.source_note_path.cmp(&b.source_note_path));
+        Ok(backlinks)
+    }
+
+    /// 获取笔记的正向链接，按目标笔记路径排序
+    pub async fn get_outgoing_links(&self, note_id: &str) -> Result<Vec<String>> {
+        let mut targets = Vec::new();
+        let mut seen = HashSet::new();
+        for link in self.repository.get_outbound_links(note_id).await? {
+            if link.target_id == note_id || !seen.insert(link.target_id.clone()) {
+                continue;
+            }
+            if let Some(target) = self.active_note(&link.target_id).await? {
+                targets.push((target.file_path, link.target_id));
+            }
+        }
+        targets.sort();
+        Ok(targets.into_iter().map(|(_, id)| id).collect())
+    }
+
+    /// 查找相似笔记
+    pub async fn find_similar_notes(&self, note_id: &str, limit: usize) -> Result<Vec<SimilarNote>> {
+        let edges: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT DISTINCT l.source_id, l.target_id FROM links l
+            JOIN notes s ON s.id = l.source_id
+            JOIN notes t ON t.id = l.target_id
+            WHERE l.source_id != l.target_id AND s.status != 'deleted' AND t.status != 'deleted'
+            "#
+        )
+        .fetch_all(self.repository.pool())
+        .await?;
+
+        let mut outgoing: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut incoming: HashMap<String, HashSet<String>> = HashMap::new();
+        for (source_id, target_id) in edges {
+            outgoing.entry(source_id.clone()).or_default().insert(target_id.clone());
+            incoming.entry(target_id).or_default().insert(source_id);
+        }
+
+        let mut similar = rank_similar_notes(note_id, &outgoing, &incoming, limit, |_| Default::default());
+        for note in &mut similar {
+            if let Some(record) = self.repository.get_note_by_id(&note.note_id).await? {
+                note.title = record.title;
+                note.path = record.file_path;
+            }
+        }
+        // 路径在补全后才知道，相似度相同的笔记重新按路径排序
+        similar.sort_by(|a, b| {
+            b.similarity_score
+                .total_cmp(&a.similarity_score)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        Ok(similar)
+    }
+
+    /// 获取孤立笔记（没有任何出链和入链），按路径排序
+    pub async fn get_orphaned_notes(&self) -> Result<Vec<String>> {
+        let orphans = sqlx::query_scalar(
+            r#"
+            SELECT n.id FROM notes n
+            WHERE n.status != 'deleted'
+              AND NOT EXISTS (
+                  SELECT 1 FROM links l
+                  JOIN notes o ON o.id = CASE WHEN l.source_id = n.id THEN l.target_id ELSE l.source_id END
+                  WHERE (l.source_id = n.id OR l.target_id = n.id)
+                    AND l.source_id != l.target_id AND o.status != 'deleted'
+              )
+            ORDER BY n.file_path
+            "#
+        )
+        .fetch_all(self.repository.pool())
+        .await?;
+        Ok(orphans)
+    }
+
+    /// 查找提到笔记标题或别名但没有链接到它的地方，按源笔记路径和位置排序
+    ///
+    /// 内容读取 `root` 下的文件，范围和行号与磁盘上的文件一致，无法读取的文件跳过；
+    /// 标题或别名与之相同的笔记中出现的这个名字不算提及。能按原文解析到该笔记时
+    /// 链接写作 `[[原文]]`，否则写作 `[[路径|原文]]`。
+    pub async fn get_unlinked_mentions(&self, root: &Path, note_id: &str) -> Result<Vec<UnlinkedMention>> {
+        let target = self
+            .active_note(note_id)
+            .await?
+            .ok_or_else(|| Error::not_found(format!("笔记 {}", note_id)))?;
+        let mut names = vec![target.title.clone()];
+        for alias in record_aliases(&target.frontmatter) {
+            if !names.iter().any(|name| name.to_lowercase() == alias.to_lowercase()) {
+                names.push(alias);
+            }
+        }
+
+        let mut conn = self.repository.pool().acquire().await?;
+        let resolver = LinkResolver::load(&mut conn).await?;
+        let notes: Vec<(String, String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT id, file_path, title, frontmatter FROM notes
+            WHERE status != 'deleted' AND id != ? ORDER BY file_path
+            "#
+        )
+        .bind(note_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let parser = LinkParser::new();
+        let path_target = Path::new(&target.file_path).with_extension("").to_string_lossy().replace('\\', "/");
+        let mut mentions = Vec::new();
+        for (source_id, file_path, title, frontmatter) in notes {
+            // 源笔记自己也叫这个名字时，出现的是它自己的名字
+            let mut own_names = record_aliases(&frontmatter);
+            own_names.push(title);
+            let own_names: HashSet<String> = own_names.iter().map(|name| name.trim().to_lowercase()).collect();
+
+            // 提及的范围用于改写文件，不能退回数据库中的内容
+            let content = match tokio::fs::read_to_string(root.join(&file_path)).await {
+                Ok(content) => content,
+                Err(e) => {
+                    log::warn!("无法读取 {}: {}", file_path, e);
+                    continue;
+                }
+            };
+            for range in parser.find_mentions(&content, &names) {
+                let text = content[range.clone()].to_string();
+                if own_names.contains(&text.to_lowercase()) {
+                    continue;
+                }
+                let link = if resolver.resolve_wiki(&text) == Some(note_id) {
+                    format!("[[{}]]", text)
+                } else {
+                    format!("[[{}|{}]]", path_target, text)
+                };
+                let line_start = content[..range.start].rfind('\n').map_or(0, |i| i + 1);
+                let line_end = content[range.end..].find('\n').map_or(content.len(), |i| range.end + i);
+                mentions.push(UnlinkedMention {
+                    source_note_id: source_id.clone(),
+                    source_note_path: file_path.clone(),
+                    target_note_id: note_id.to_string(),
+                    line_number: content[..range.start].matches('\n').count() + 1,
+                    context: content[line_start..line_end].trim().to_string(),
+                    text,
+                    range,
+                    link,
+                });
+            }
+        }
+        Ok(mentions)
+    }
+
+    /// 获取断链信息，未指定笔记时检查整个工作区，按源笔记路径和行号排序
+    pub async fn get_broken_links(&self, note_id: Option<&str>) -> Result<Vec<BrokenLink>> {
+        self.ensure_link_refs().await?;
+        let refs = self.link_refs(note_id, BROKEN_FILTER).await?;
+        if refs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.repository.pool().acquire().await?;
+        let trash = LinkResolver::load_trashed(&mut conn).await?;
+        let mut names: Option<Vec<String>> = None;
+        let mut broken_links = Vec::new();
+        for row in refs {
+            let link = row.link();
+            let (kind, suggestions) = match &row.target_id {
+                None => {
+                    // 指向回收站中笔记的链接不算断链
+                    if trash.resolve_link(source_dir(&row.source_path), &link).is_some() {
+                        continue;
+                    }
+                    if names.is_none() {
+                        names = Some(self.note_names().await?);
+                    }
+                    let candidates = names.iter().flatten().cloned();
+                    (BrokenLinkKind::MissingNote, suggest_link_fixes(candidates, &link.target))
+                }
+                Some(target_id) => {
+                    let kind = match row.anchor_kind.as_deref() {
+                        Some("block") => BrokenLinkKind::MissingBlock,
+                        _ => BrokenLinkKind::MissingHeading,
+                    };
+                    let anchors: Vec<String> = sqlx::query_scalar(
+                        "SELECT name FROM note_anchors WHERE note_id = ? AND kind = ? ORDER BY line"
+                    )
+                    .bind(target_id)
+                    .bind(&row.anchor_kind)
+                    .fetch_all(&mut *conn)
+                    .await?;
+                    (kind, suggest_anchor_fixes(anchors, kind, &link.target_anchor().unwrap_or_default()))
+                }
+            };
+            broken_links.push(BrokenLink {
+                source_note_id: row.source_id,
+                source_note_path: row.source_path,
+                link,
+                kind,
+                target_note_id: row.target_id,
+                suggestions,
+            });
+        }
+        Ok(broken_links)
+    }
+
+    /// 获取目标有歧义的链接，未指定笔记时检查整个工作区，按源笔记路径和行号排序
+    pub async fn get_ambiguous_links(&self, note_id: Option<&str>) -> Result<Vec<AmbiguousLink>> {
+        self.ensure_link_refs().await?;
+        let refs = self.link_refs(note_id, NAMED_FILTER).await?;
+        if refs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.repository.pool().acquire().await?;
+        let resolver = LinkResolver::load(&mut conn).await?;
+        let mut ambiguous_links = Vec::new();
+        for row in refs {
+            let candidates = resolver.claimants(&row.target);
+            if candidates.len() < 2 {
+                continue;
+            }
+            let mut candidate_paths = Vec::with_capacity(candidates.len());
+            for id in &candidates {
+                let path: String = sqlx::query_scalar("SELECT file_path FROM notes WHERE id = ?")
+                    .bind(id)
+                    .fetch_one(&mut *conn)
+                    .await?;
+                candidate_paths.push(path);
+            }
+            ambiguous_links.push(AmbiguousLink {
+                link: row.link(),
+                source_note_id: row.source_id,
+                source_note_path: row.source_path,
+                target_note_id: row.target_id.unwrap_or_default(),
+                candidates: candidates.into_iter().map(str::to_string).collect(),
+                candidate_paths,
+            });
+        }
+        Ok(ambiguous_links)
+    }
+
+    /// 获取链接统计信息
+    pub async fn get_statistics(&self) -> Result<LinkIndexStats> {
+        self.ensure_link_refs().await?;
+        let total_notes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM notes WHERE status != 'deleted'")
+            .fetch_one(self.repository.pool())
+            .await?;
+        let total_links: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM (
+                SELECT DISTINCT l.source_id, l.target_id FROM links l
+                JOIN notes s ON s.id = l.source_id
+                JOIN notes t ON t.id = l.target_id
+                WHERE l.source_id != l.target_id AND s.status != 'deleted' AND t.status != 'deleted'
+            )
+            "#
+        )
+        .fetch_one(self.repository.pool())
+        .await?;
+
+        let total_broken_links = self.get_broken_links(None).await?.len();
+        let unresolved = self.link_refs(None, UNRESOLVED_FILTER).await?;
+        let mut conn = self.repository.pool().acquire().await?;
+        let trash = LinkResolver::load_trashed(&mut conn).await?;
+        let total_trashed_links = unresolved
+            .iter()
+            .filter(|row| {
+                row.target_status.as_deref() == Some("deleted")
+                    || trash.resolve_link(source_dir(&row.source_path), &row.link()).is_some()
+            })
+            .count();
+
+        Ok(LinkIndexStats {
+            total_notes: total_notes as usize,
+            total_links: total_links as usize,
+            total_broken_links,
+            total_trashed_links,
+            total_ambiguous_links: self.get_ambiguous_links(None).await?.len(),
+            orphaned_notes: self.get_orphaned_notes().await?.len(),
+        })
+    }
+
+    /// 按 `[[target]]` 的规则解析链接目标，返回对应的笔记ID
+    pub async fn resolve_targets(&self, targets: &[String]) -> Result<Vec<Option<String>>> {
+        let mut conn = self.repository.pool().acquire().await?;
+        let resolver = LinkResolver::load(&mut conn).await?;
+        Ok(targets
+            .iter()
+            .map(|target| resolver.resolve_wiki(target).map(str::to_string))
+            .collect())
+    }
+
+    /// 源笔记未删除、满足 `filter` 的链接原文，按源笔记路径和位置排序
+    async fn link_refs(&self, note_id: Option<&str>, filter: &str) -> Result<Vec<LinkRefRow>> {
+        let sql = LINK_REF_SELECT.replace("{}", filter);
+        let refs = sqlx::query_as(&sql).bind(note_id).fetch_all(self.repository.pool()).await?;
+        Ok(refs)
+    }
+
+    /// 升级前索引的工作区没有链接原文和锚点，首次查询时重新解析一次全部笔记
+    async fn ensure_link_refs(&self) -> Result<()> {
+        let indexed: Option<String> = sqlx::query_scalar("SELECT value FROM system_config WHERE key = 'link_refs_indexed'")
+            .fetch_optional(self.repository.pool())
+            .await?;
+        if indexed.is_none() {
+            SqliteIndexer::new(self.repository.pool().clone()).refresh_links().await?;
+        }
+        Ok(())
+    }
+
+    /// 未删除笔记的标题和文件名，用于建议断链的修复方案
+    async fn note_names(&self) -> Result<Vec<String>> {
+        let notes: Vec<(String, String)> = sqlx::query_as("SELECT title, file_path FROM notes WHERE status != 'deleted'")
+            .fetch_all(self.repository.pool())
+            .await?;
+        let mut names = Vec::with_capacity(notes.len() * 2);
+        for (title, file_path) in notes {
+            names.push(title);
+            names.extend(Path::new(&file_path).file_stem().map(|stem| stem.to_string_lossy().to_string()));
+        }
+        Ok(names)
+    }
+
+    /// 未删除的笔记
+    async fn active_note(&self, note_id: &str) -> Result<Option<NoteRecord>> {
+        Ok(self.repository.get_note_by_id(note_id).await?.filter(|note| note.status != "deleted"))
+    }
+}
+
+/// `link_refs` 表中的一条链接及其源笔记和目标笔记
+#[derive(FromRow)]
+struct LinkRefRow {
+    source_id: String,
+    source_path: String,
+    target_id: Option<String>,
+    target_status: Option<String>,
+    raw: String,
+    target: String,
+    alias: Option<String>,
+    anchor: Option<String>,
+    is_embed: bool,
+    range_start: i64,
+    range_end: i64,
+    line_number: i64,
+    anchor_kind: Option<String>,
+}
+
+impl LinkRefRow {
+    fn link(&self) -> WikiLink {
+        WikiLink {
+            raw: self.raw.clone(),
+            target: self.target.clone(),
+            alias: self.alias.clone(),
+            anchor: self.anchor.clone(),
+            is_embed: self.is_embed,
+            range: self.range_start as usize..self.range_end as usize,
+            line_number: self.line_number as usize,
+        }
+    }
+}
+
+/// 源笔记所在目录，Markdown 链接相对于它解析
+fn source_dir(source_path: &str) -> &Path {
+    Path::new(source_path).parent().unwrap_or_else(|| Path::new(""))
+}
+
+/// 由链接记录和所在行推断链接类型，`[[...]]` 与 `[...](...)` 在表中都记为引用
+fn link_type(link: &Link, line: &str) -> LinkType {
+    if link.link_type == StoredLinkType::Embed.to_string() {
+        return LinkType::Embed;
+    }
+    let rest: String = line.chars().skip((link.source_column as usize).saturating_sub(1)).take(2).collect();
+    if rest == "[[" {
+        LinkType::Wiki
+    } else {
+        LinkType::Markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::indexer::SqliteIndexer;
+    use std::path::Path;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let full = root.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_link_store_reads_persisted_links() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.md", "# Alpha\n\nsee [[Beta]] and [[Gamma]]\n\n![[Beta]]");
+        write(dir.path(), "b.md", "# Beta\n\n[back](a.md)");
+        write(dir.path(), "c.md", "# Gamma\n\n[[Beta]]");
+        write(dir.path(), "lonely.md", "# Lonely");
+
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(db_dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let indexer = SqliteIndexer::new(db.pool().clone());
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let mut ids = Vec::new();
+        for path in ["a.md", "b.md", "c.md", "lonely.md"] {
+            ids.push(indexer.repository().get_note_by_path(path).await.unwrap().unwrap().id);
+        }
+        let [a, b, c, lonely] = <[String; 4]>::try_from(ids).unwrap();
+
+        // 新的 LinkStore 只读取数据库，相当于重新启动应用
+        let store = LinkStore::new(db.pool().clone());
+        let backlinks = store.get_backlinks(&b).await.unwrap();
+        assert_eq!(backlinks.len(), 2);
+        assert_eq!((backlinks[0].source_note_id.as_str(), backlinks[0].occurrence_count), (a.as_str(), 2));
+        assert_eq!((backlinks[0].line_number, backlinks[0].link_type.clone()), (3, LinkType::Wiki));
+        assert_eq!(backlinks[0].context, "see [[Beta]] and [[Gamma]]");
+        assert_eq!(backlinks[1].source_note_title, "Gamma");
+        assert_eq!(store.get_backlinks(&a).await.unwrap()[0].link_type, LinkType::Markdown);
+
+        assert_eq!(store.get_outgoing_links(&a).await.unwrap(), vec![b.clone(), c.clone()]);
+        assert_eq!(store.get_orphaned_notes().await.unwrap(), vec![lonely]);
+        let similar = store.find_similar_notes(&c, 10).await.unwrap();
+        assert_eq!(similar[0].note_id, a);
+        assert_eq!(similar[0].path, "a.md");
+        let resolved = store.resolve_targets(&["gamma".to_string(), "Missing".to_string()]).await.unwrap();
+        assert_eq!(resolved, vec![Some(c), None]);
+    }
+
+    #[tokio::test]
+    async fn test_link_store_reports_broken_and_ambiguous_links() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.md", "---\ntitle: A\n---\n\n[[Missing]] [[sub/b]] [[Dup]]");
+        write(dir.path(), "sub/b.md", "# B");
+        write(dir.path(), "x/dup.md", "# Dup");
+        write(dir.path(), "y/dup.md", "# Dup");
+
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(db_dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let indexer = SqliteIndexer::new(db.pool().clone());
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let a = indexer.repository().get_note_by_path("a.md").await.unwrap().unwrap().id;
+        // 数据库中的原文足以检查断链，不读取文件
+        std::fs::remove_file(dir.path().join("a.md")).unwrap();
+
+        let store = LinkStore::new(db.pool().clone());
+        let broken = store.get_broken_links(Some(&a)).await.unwrap();
+        assert_eq!(broken.len(), 1);
+        assert_eq!((broken[0].link.target.as_str(), broken[0].link.line_number), ("Missing", 5));
+        let ambiguous = store.get_ambiguous_links(None).await.unwrap();
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].candidate_paths, vec!["x/dup.md", "y/dup.md"]);
+
+        let stats = store.get_statistics().await.unwrap();
+        assert_eq!((stats.total_notes, stats.total_broken_links, stats.total_ambiguous_links), (4, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_link_store_checks_anchors_and_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.md", "# A\n\n[[B#快速开始]] [[B#快速开使]] [[B#^Intro]] [[B#^intr]]\n[[Old]] [b](b.md#任意)");
+        write(dir.path(), "b.md", "# 快速开始\n\n第一段 ^intro");
+        write(dir.path(), "old.md", "# Old");
+
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(db_dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let indexer = SqliteIndexer::new(db.pool().clone());
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let old = indexer.repository().get_note_by_path("old.md").await.unwrap().unwrap().id;
+        indexer.repository().delete_note(&old).await.unwrap();
+        // 重新解析后 [[Old]] 不再有目标，按原路径在回收站中找到
+        indexer.refresh_links().await.unwrap();
+
+        let store = LinkStore::new(db.pool().clone());
+        let broken = store.get_broken_links(None).await.unwrap();
+        let found: Vec<(&str, BrokenLinkKind, &[String])> =
+            broken.iter().map(|b| (b.link.raw.as_str(), b.kind, b.suggestions.as_slice())).collect();
+        assert_eq!(found, vec![
+            ("[[B#快速开使]]", BrokenLinkKind::MissingHeading, &["快速开始".to_string()][..]),
+            ("[[B#^intr]]", BrokenLinkKind::MissingBlock, &["^intro".to_string()][..]),
+        ]);
+
+        // 目标笔记改动后锚点随之更新
+        write(dir.path(), "b.md", "# 快速开使\n\n第一段");
+        indexer.index_path(dir.path(), Path::new("b.md")).await.unwrap();
+        let broken: Vec<String> = store.get_broken_links(None).await.unwrap().into_iter().map(|b| b.link.raw).collect();
+        assert_eq!(broken, vec!["[[B#快速开始]]", "[[B#^Intro]]", "[[B#^intr]]"]);
+
+        let stats = store.get_statistics().await.unwrap();
+        assert_eq!((stats.total_broken_links, stats.total_trashed_links), (3, 1));
+    }
+
+    #[tokio::test]
+    async fn test_unlinked_mentions() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "zk.md", "---\naliases: [ZK]\n---\n# 卡片盒笔记法\n\n提到卡片盒笔记法自己");
+        write(dir.path(), "a.md", "# A\n\n学习卡片盒笔记法，简称 zk。\n已经链接 [[ZK]]，zkx 不算");
+        write(dir.path(), "other/卡片盒笔记法.md", "# 卡片盒笔记法");
+
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(db_dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let indexer = SqliteIndexer::new(db.pool().clone());
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        let zk = indexer.repository().get_note_by_path("zk.md").await.unwrap().unwrap().id;
+
+        let store = LinkStore::new(db.pool().clone());
+        let mentions = store.get_unlinked_mentions(dir.path(), &zk).await.unwrap();
+        let found: Vec<(&str, usize, &str)> =
+            mentions.iter().map(|m| (m.text.as_str(), m.line_number, m.link.as_str())).collect();
+        // 同名的另一篇笔记优先按文件名解析，只能按路径链接；同名笔记中的名字不算提及
+        assert_eq!(found, vec![("卡片盒笔记法", 3, "[[zk|卡片盒笔记法]]"), ("zk", 3, "[[zk]]")]);
+        assert_eq!(mentions[0].context, "学习卡片盒笔记法，简称 zk。");
+
+        indexer.link_mentions(dir.path(), &mentions[..2]).await.unwrap();
+        let a = std::fs::read_to_string(dir.path().join("a.md")).unwrap();
+        assert_eq!(a, "# A\n\n学习[[zk|卡片盒笔记法]]，简称 [[zk]]。\n已经链接 [[ZK]]，zkx 不算");
+        assert_eq!(store.get_backlinks(&zk).await.unwrap()[0].occurrence_count, 3);
+        assert!(store.get_unlinked_mentions(dir.path(), &zk).await.unwrap().is_empty());
+        // 文件已改动，旧的提及不能再写入
+        assert!(indexer.link_mentions(dir.path(), &mentions[..1]).await.is_err());
+
+        // 无法读取的文件跳过，不退回数据库中的内容
+        write(dir.path(), "b.md", "# B\n\n卡片盒笔记法");
+        indexer.reindex_all(dir.path(), |_| {}).await.unwrap();
+        std::fs::remove_file(dir.path().join("b.md")).unwrap();
+        assert!(store.get_unlinked_mentions(dir.path(), &zk).await.unwrap().is_empty());
+    }
+}
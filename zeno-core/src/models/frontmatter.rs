@@ -1,60 +1,83 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::note::NoteStatus;
 
 /// 前言配置
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct Frontmatter {
     /// 标题
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// 日期
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub date: Option<NaiveDate>,
     /// 标签
-    #[serde(default)]
+    #[serde(deserialize_with = "string_or_seq", skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     /// 分类
-    #[serde(default)]
+    #[serde(deserialize_with = "string_or_seq", skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<String>,
+    /// 别名，`[[别名]]` 也能链接到这篇笔记；兼容 Obsidian 的单数形式 `alias`
+    #[serde(alias = "alias", deserialize_with = "string_or_seq", skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
     /// 状态
-    #[serde(default)]
-    pub status: NoteStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<NoteStatus>,
+    /// 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 作者
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     /// 发布配置
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub publish: Option<PublishConfig>,
-    /// 自定义字段
-    #[serde(default)]
+    /// 未识别的自定义字段
+    #[serde(flatten)]
     pub custom: HashMap<String, serde_json::Value>,
 }
 
 /// 发布配置
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct PublishConfig {
     /// 是否启用发布
     pub enabled: bool,
     /// 发布平台
     pub platforms: Vec<String>,
+    /// 定时发布时间
+    pub scheduled_at: Option<DateTime<Utc>>,
     /// 发布路径
     pub path: Option<String>,
     /// 发布模板
     pub template: Option<String>,
 }
 
-impl Default for Frontmatter {
-    fn default() -> Self {
-        Self {
-            title: None,
-            date: None,
-            tags: Vec::new(),
-            categories: Vec::new(),
-            status: NoteStatus::default(),
-            publish: None,
-            custom: HashMap::new(),
-        }
+/// 同时接受列表和逗号分隔的字符串，如 `tags: rust, zeno`
+fn string_or_seq<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrSeq {
+        String(String),
+        Seq(Vec<String>),
+        Null(()),
     }
+
+    Ok(match StringOrSeq::deserialize(deserializer)? {
+        StringOrSeq::String(value) => value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        StringOrSeq::Seq(values) => values,
+        StringOrSeq::Null(()) => Vec::new(),
+    })
 }
 
 impl Frontmatter {
@@ -68,6 +91,11 @@ impl Frontmatter {
         serde_yaml::to_string(self)
     }
 
+    /// 是否没有任何字段
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
     /// 添加标签
     pub fn add_tag(&mut self, tag: String) {
         if !self.tags.contains(&tag) {
@@ -103,17 +131,6 @@ impl Frontmatter {
     }
 }
 
-impl Default for PublishConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            platforms: Vec::new(),
-            path: None,
-            template: None,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,7 +138,8 @@ mod tests {
     #[test]
     fn test_frontmatter_default() {
         let frontmatter = Frontmatter::default();
-        assert_eq!(frontmatter.status, NoteStatus::Draft);
+        assert!(frontmatter.status.is_none());
+        assert!(frontmatter.is_empty());
         assert!(frontmatter.tags.is_empty());
         assert!(frontmatter.categories.is_empty());
     }
@@ -158,4 +176,28 @@ mod tests {
         
         assert_eq!(frontmatter.get_custom_field("nonexistent"), None);
     }
+
+    #[test]
+    fn test_aliases() {
+        let frontmatter = Frontmatter::from_yaml("aliases: [卡片盒, Zettelkasten]\n").unwrap();
+        assert_eq!(frontmatter.aliases, vec!["卡片盒", "Zettelkasten"]);
+        assert!(frontmatter.custom.is_empty());
+        assert_eq!(Frontmatter::from_yaml("alias: 卡片盒\n").unwrap().aliases, vec!["卡片盒"]);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let yaml = "title: 标题\ntags: rust, zeno\nstatus: published\npriority: high\n";
+        let frontmatter = Frontmatter::from_yaml(yaml).unwrap();
+
+        assert_eq!(frontmatter.tags, vec!["rust", "zeno"]);
+        assert_eq!(frontmatter.status, Some(NoteStatus::Published));
+        assert_eq!(
+            frontmatter.get_custom_field("priority"),
+            Some(&serde_json::Value::String("high".to_string()))
+        );
+
+        let reparsed = Frontmatter::from_yaml(&frontmatter.to_yaml().unwrap()).unwrap();
+        assert_eq!(reparsed, frontmatter);
+    }
 }
\ No newline at end of file
@@ -4,4 +4,4 @@ pub mod tag;
 
 pub use note::*;
 pub use frontmatter::*;
-pub use tag::*;
\ No newline at end of file
+pub use tag::*;
@@ -5,7 +5,13 @@ use uuid::Uuid;
 
 use super::frontmatter::Frontmatter;
 
+/// 没有标题时使用的默认标题
+pub const UNTITLED: &str = "未命名";
+
 /// 笔记核心数据结构
+///
+/// 这是 Zeno 唯一的笔记领域模型，数据库行类型 `db::models::NoteRecord`
+/// 和应用层都通过它进行转换。
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Note {
     /// 笔记唯一标识符
@@ -14,27 +20,41 @@ pub struct Note {
     pub path: PathBuf,
     /// 笔记标题
     pub title: String,
-    /// 原始内容
+    /// Markdown 正文（不包含前言）
     pub content: String,
+    /// 渲染后的 HTML
+    #[serde(default)]
+    pub html_content: Option<String>,
     /// 前言数据
+    #[serde(default)]
     pub frontmatter: Frontmatter,
+    /// 笔记状态
+    #[serde(default)]
+    pub status: NoteStatus,
     /// 创建时间
     pub created_at: DateTime<Utc>,
     /// 修改时间
     pub modified_at: DateTime<Utc>,
-    /// 文件内容校验和
+    /// 源文件内容校验和
     pub checksum: String,
+    /// 文件大小（字节）
+    #[serde(default)]
+    pub file_size: u64,
     /// 字数统计
     pub word_count: usize,
     /// 预计阅读时间（分钟）
     pub reading_time: usize,
+    /// 源文件原文（含前言），由解析器填写；行号按原文计算
+    #[serde(skip)]
+    pub source: Option<String>,
 }
 
 /// 笔记状态
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum NoteStatus {
     /// 草稿
+    #[default]
     Draft,
     /// 已发布
     Published,
@@ -44,87 +64,146 @@ pub enum NoteStatus {
     Deleted,
 }
 
-impl Default for NoteStatus {
-    fn default() -> Self {
-        NoteStatus::Draft
+impl std::fmt::Display for NoteStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoteStatus::Draft => write!(f, "draft"),
+            NoteStatus::Published => write!(f, "published"),
+            NoteStatus::Archived => write!(f, "archived"),
+            NoteStatus::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+impl std::str::FromStr for NoteStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "draft" => Ok(NoteStatus::Draft),
+            "published" => Ok(NoteStatus::Published),
+            "archived" => Ok(NoteStatus::Archived),
+            "deleted" => Ok(NoteStatus::Deleted),
+            _ => Err(format!("无效的笔记状态: {}", s)),
+        }
     }
 }
 
 impl Note {
-    /// 创建新笔记
+    /// 创建新笔记，标题从内容中的一级标题提取
     pub fn new(path: PathBuf, content: String) -> Self {
-        let id = Uuid::new_v4();
+        let title = extract_title(&content).unwrap_or_else(|| UNTITLED.to_string());
+        Self::with_title(path, title, content)
+    }
+
+    /// 使用指定标题创建新笔记
+    pub fn with_title(path: PathBuf, title: String, content: String) -> Self {
         let now = Utc::now();
-        let checksum = Self::calculate_checksum(&content);
-        let frontmatter = Frontmatter::default();
-        let title = Self::extract_title(&content);
-        let word_count = Self::count_words(&content);
-        let reading_time = Self::calculate_reading_time(word_count);
-
-        Self {
-            id,
+        let word_count = count_words(&content);
+
+        let mut note = Self {
+            id: Uuid::new_v4(),
             path,
             title,
             content,
-            frontmatter,
+            html_content: None,
+            frontmatter: Frontmatter::default(),
+            status: NoteStatus::default(),
             created_at: now,
             modified_at: now,
-            checksum,
+            checksum: String::new(),
+            file_size: 0,
             word_count,
-            reading_time,
-        }
+            reading_time: calculate_reading_time(word_count),
+            source: None,
+        };
+        note.checksum = calculate_checksum(&note.to_markdown());
+        note
     }
 
-    /// 更新笔记内容
+    /// 更新笔记正文
     pub fn update_content(&mut self, content: String) {
+        if let Some(title) = extract_title(&content) {
+            self.title = title;
+        }
         self.content = content;
         self.modified_at = Utc::now();
-        self.checksum = Self::calculate_checksum(&self.content);
-        self.title = Self::extract_title(&self.content);
-        self.word_count = Self::count_words(&self.content);
-        self.reading_time = Self::calculate_reading_time(self.word_count);
+        self.word_count = count_words(&self.content);
+        self.reading_time = calculate_reading_time(self.word_count);
+        self.checksum = calculate_checksum(&self.to_markdown());
     }
 
-    /// 计算内容校验和
-    fn calculate_checksum(content: &str) -> String {
-        use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        hasher.update(content.as_bytes());
-        format!("{:x}", hasher.finalize())
+    /// 检查源文件内容是否与当前校验和不同
+    pub fn is_content_changed(&self, new_content: &str) -> bool {
+        self.checksum != calculate_checksum(new_content)
     }
 
-    /// 从内容中提取标题
-    fn extract_title(content: &str) -> String {
-        // 查找第一个 # 标题
-        for line in content.lines() {
-            let line = line.trim();
-            if line.starts_with("# ") {
-                return line[2..].trim().to_string();
+    /// 生成包含 YAML 前言的完整 Markdown 文档
+    pub fn to_markdown(&self) -> String {
+        match self.frontmatter.to_yaml() {
+            Ok(yaml) if !self.frontmatter.is_empty() => {
+                format!("---\n{}---\n\n{}", yaml, self.content)
             }
+            _ => self.content.clone(),
         }
-        
-        // 如果没有找到标题，使用"无标题"
-        "无标题".to_string()
     }
+}
 
-    /// 计算字数
-    fn count_words(content: &str) -> usize {
-        content
-            .chars()
-            .filter(|c| !c.is_whitespace() && !c.is_ascii_punctuation())
-            .count()
-    }
+/// 计算内容校验和 (SHA-256)
+pub fn calculate_checksum(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
 
-    /// 计算阅读时间（假设每分钟阅读 300 字）
-    fn calculate_reading_time(word_count: usize) -> usize {
-        ((word_count as f64) / 300.0).ceil() as usize
-    }
+/// 从 Markdown 内容中提取第一个一级标题
+pub fn extract_title(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
 
-    /// 检查内容是否已更改
-    pub fn is_content_changed(&self, new_content: &str) -> bool {
-        let new_checksum = Self::calculate_checksum(new_content);
-        self.checksum != new_checksum
+/// 统计字数：中日韩字符按字计，其他文字按单词计
+pub fn count_words(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            count += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+        }
     }
+
+    count
+}
+
+/// 是否为中日韩字符
+pub fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF      // 日文假名
+        | 0x3400..=0x4DBF    // CJK 扩展 A
+        | 0x4E00..=0x9FFF    // CJK 统一汉字
+        | 0xAC00..=0xD7AF    // 韩文音节
+        | 0xF900..=0xFAFF    // CJK 兼容汉字
+        | 0x20000..=0x2A6DF  // CJK 扩展 B
+    )
+}
+
+/// 计算阅读时间（每分钟 250 字，至少 1 分钟）
+pub fn calculate_reading_time(word_count: usize) -> usize {
+    word_count.div_ceil(250).max(1)
 }
 
 #[cfg(test)]
@@ -135,14 +214,16 @@ mod tests {
     fn test_note_creation() {
         let path = PathBuf::from("test.md");
         let content = "# 测试标题\n\n这是测试内容。".to_string();
-        
+
         let note = Note::new(path.clone(), content.clone());
-        
+
         assert_eq!(note.path, path);
         assert_eq!(note.content, content);
         assert_eq!(note.title, "测试标题");
+        assert_eq!(note.status, NoteStatus::Draft);
         assert!(note.word_count > 0);
         assert!(note.reading_time > 0);
+        assert!(!note.is_content_changed(&content));
     }
 
     #[test]
@@ -151,14 +232,14 @@ mod tests {
             PathBuf::from("test.md"),
             "# 原标题\n原内容".to_string(),
         );
-        
+
         let old_checksum = note.checksum.clone();
         let old_modified = note.modified_at;
-        
+
         std::thread::sleep(std::time::Duration::from_millis(1));
-        
+
         note.update_content("# 新标题\n新内容".to_string());
-        
+
         assert_eq!(note.title, "新标题");
         assert_ne!(note.checksum, old_checksum);
         assert!(note.modified_at > old_modified);
@@ -166,8 +247,36 @@ mod tests {
 
     #[test]
     fn test_title_extraction() {
-        assert_eq!(Note::extract_title("# 标题"), "标题");
-        assert_eq!(Note::extract_title("## 不是标题\n# 真标题"), "真标题");
-        assert_eq!(Note::extract_title("没有标题的内容"), "无标题");
+        assert_eq!(extract_title("# 标题").as_deref(), Some("标题"));
+        assert_eq!(extract_title("## 不是标题\n# 真标题").as_deref(), Some("真标题"));
+        assert_eq!(extract_title("没有标题的内容"), None);
+        assert_eq!(Note::new(PathBuf::from("a.md"), "没有标题的内容".to_string()).title, UNTITLED);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(count_words("Hello world，你好世界"), 6);
+        assert_eq!(count_words("rust-lang 1.0"), 4);
+        assert_eq!(calculate_reading_time(0), 1);
+        assert_eq!(calculate_reading_time(251), 2);
+    }
+
+    #[test]
+    fn test_to_markdown_includes_frontmatter() {
+        let mut note = Note::new(PathBuf::from("a.md"), "# 标题\n".to_string());
+        assert_eq!(note.to_markdown(), "# 标题\n");
+
+        note.frontmatter.add_tag("rust".to_string());
+        let markdown = note.to_markdown();
+        assert!(markdown.starts_with("---\ntags:\n- rust\n---\n"));
+        assert!(markdown.ends_with("# 标题\n"));
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [NoteStatus::Draft, NoteStatus::Published, NoteStatus::Archived, NoteStatus::Deleted] {
+            assert_eq!(status.to_string().parse::<NoteStatus>().unwrap(), status);
+        }
+        assert!("unknown".parse::<NoteStatus>().is_err());
+    }
+}
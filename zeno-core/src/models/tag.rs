@@ -38,8 +38,6 @@ impl Tag {
             .map(|c| {
                 if c.is_alphanumeric() {
                     c
-                } else if c.is_whitespace() || c == '-' || c == '_' {
-                    '-'
                 } else {
                     '-'
                 }
@@ -0,0 +1,308 @@
+use crate::models::Frontmatter;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// 前言格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontmatterFormat {
+    /// `---` 包裹的 YAML
+    Yaml,
+    /// `+++` 包裹的 TOML
+    Toml,
+}
+
+impl FrontmatterFormat {
+    fn delimiter(self) -> &'static str {
+        match self {
+            FrontmatterFormat::Yaml => "---",
+            FrontmatterFormat::Toml => "+++",
+        }
+    }
+}
+
+/// 从文档中切分出的前言块
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterBlock<'a> {
+    /// 前言格式
+    pub format: FrontmatterFormat,
+    /// 分隔符之间的原始文本
+    pub raw: &'a str,
+    /// 原始文本在文档中的起始行号（从 1 开始）
+    pub start_line: usize,
+    /// 正文在文档中的字节偏移
+    pub body_offset: usize,
+}
+
+/// 检测文档开头的前言块
+///
+/// 前言必须从第一行开始，并以同样的分隔符单独成行结束；
+/// 没有结束分隔符，或 YAML 块能解析但不是键值映射（如以分隔线开头的普通段落）时
+/// 不视为前言（`---` 也可能是分隔线）。
+pub fn split_frontmatter(content: &str) -> Option<FrontmatterBlock<'_>> {
+    let bom = if content.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+    let text = &content[bom..];
+
+    let format = [FrontmatterFormat::Yaml, FrontmatterFormat::Toml]
+        .into_iter()
+        .find(|format| first_line(text).trim_end() == format.delimiter())?;
+
+    let raw_start = bom + first_line(text).len();
+    let mut offset = raw_start;
+    for line in content[raw_start..].split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        let is_end = trimmed == format.delimiter()
+            || (format == FrontmatterFormat::Yaml && trimmed == "...");
+        if is_end {
+            let raw = &content[raw_start..offset];
+            if format == FrontmatterFormat::Yaml && is_plain_yaml_text(raw) {
+                return None;
+            }
+            return Some(FrontmatterBlock {
+                format,
+                raw,
+                start_line: 2,
+                body_offset: offset + line.len(),
+            });
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// 解析文档前言，返回前言和正文的字节偏移
+///
+/// 没有前言时返回默认前言和偏移 0；前言格式错误时返回带行列号的 `Error::Parse`。
+pub fn parse_frontmatter(content: &str) -> Result<(Frontmatter, usize)> {
+    let Some(block) = split_frontmatter(content) else {
+        return Ok((Frontmatter::default(), 0));
+    };
+
+    if block.raw.trim().is_empty() {
+        return Ok((Frontmatter::default(), block.body_offset));
+    }
+
+    let frontmatter = match block.format {
+        FrontmatterFormat::Yaml => parse_yaml(&block)?,
+        FrontmatterFormat::Toml => parse_toml(&block)?,
+    };
+
+    Ok((frontmatter, block.body_offset))
+}
+
+/// 能解析为 YAML 但既不是键值映射也不为空，说明是分隔线之间的普通文本
+fn is_plain_yaml_text(raw: &str) -> bool {
+    serde_yaml::from_str::<serde_yaml::Value>(raw).is_ok_and(|value| !value.is_null() && !value.is_mapping())
+}
+
+fn parse_yaml(block: &FrontmatterBlock) -> Result<Frontmatter> {
+    let value: serde_yaml::Value = serde_yaml::from_str(block.raw).map_err(|e| {
+        let offset = e.location().map(|location| location.index()).unwrap_or(0);
+        located_error(block, offset, format!("YAML 前言格式错误: {}", e))
+    })?;
+
+    if value.is_null() {
+        return Ok(Frontmatter::default());
+    }
+
+    serde_yaml::from_value(value.clone()).map_err(|e| {
+        let mapping = value.as_mapping().cloned().unwrap_or_default();
+        let offset = invalid_field_offset(block, mapping.into_iter().filter_map(|(key, value)| {
+            let key = key.as_str()?.to_string();
+            let single = serde_yaml::Mapping::from_iter([(serde_yaml::Value::String(key.clone()), value)]);
+            serde_yaml::from_value::<Frontmatter>(serde_yaml::Value::Mapping(single)).is_err().then_some(key)
+        }));
+        located_error(block, offset, format!("YAML 前言字段无效: {}", e))
+    })
+}
+
+fn parse_toml(block: &FrontmatterBlock) -> Result<Frontmatter> {
+    let table: toml::Table = toml::from_str(block.raw).map_err(|e| {
+        let offset = e.span().map(|span| span.start).unwrap_or(0);
+        located_error(block, offset, format!("TOML 前言格式错误: {}", e.message()))
+    })?;
+
+    // TOML 日期是独立类型，先转换为 JSON 再反序列化，使日期统一按字符串处理
+    let value = toml_to_json(toml::Value::Table(table));
+    serde_json::from_value(value.clone()).map_err(|e| {
+        let object = value.as_object().cloned().unwrap_or_default();
+        let offset = invalid_field_offset(block, object.into_iter().filter_map(|(key, value)| {
+            let single = serde_json::Value::Object(serde_json::Map::from_iter([(key.clone(), value)]));
+            serde_json::from_value::<Frontmatter>(single).is_err().then_some(key)
+        }));
+        located_error(block, offset, format!("TOML 前言字段无效: {}", e))
+    })
+}
+
+/// 字段类型错误时定位到出错字段的值：逐个检查顶层字段，取在前言中最靠前的一个；
+/// 找不到时定位到前言开头
+fn invalid_field_offset(block: &FrontmatterBlock, keys: impl Iterator<Item = String>) -> usize {
+    keys.filter_map(|key| field_value_offset(block, &key)).min().unwrap_or(0)
+}
+
+/// 顶层字段的值在前言块内的字节偏移；值写在下一行（如 YAML 块列表、TOML 表）时为字段名的位置
+fn field_value_offset(block: &FrontmatterBlock, key: &str) -> Option<usize> {
+    let separator = match block.format {
+        FrontmatterFormat::Yaml => ':',
+        FrontmatterFormat::Toml => '=',
+    };
+
+    let mut offset = 0;
+    for line in block.raw.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        if block.format == FrontmatterFormat::Toml {
+            let header = line.trim_end().trim_start_matches('[').trim_end_matches(']');
+            if line.starts_with('[') && header.split('.').next().map(|name| name.trim().trim_matches('"')) == Some(key) {
+                return Some(start);
+            }
+        }
+
+        let Some((name, value)) = line.split_once(separator) else { continue };
+        if name.trim().trim_matches(|c| c == '"' || c == '\'') != key {
+            continue;
+        }
+        let value_start = line.len() - value.len();
+        let padding = value.len() - value.trim_start().len();
+        let value_offset = if value.trim().is_empty() { 0 } else { value_start + padding };
+        return Some(start + value_offset);
+    }
+    None
+}
+
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => serde_json::Value::String(s),
+        toml::Value::Integer(i) => serde_json::Value::from(i),
+        toml::Value::Float(f) => serde_json::Value::from(f),
+        toml::Value::Boolean(b) => serde_json::Value::Bool(b),
+        toml::Value::Datetime(dt) => serde_json::Value::String(dt.to_string()),
+        toml::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(toml_to_json).collect())
+        }
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect(),
+        ),
+    }
+}
+
+/// 将前言块内的字节偏移换算为文档中的行列号
+fn located_error<T: std::fmt::Display>(block: &FrontmatterBlock, offset: usize, msg: T) -> Error {
+    let offset = offset.min(block.raw.len());
+    let before = &block.raw[..offset];
+    let line = block.start_line + before.matches('\n').count();
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    Error::parse_at(msg, line, column)
+}
+
+fn first_line(text: &str) -> &str {
+    text.split_inclusive('\n').next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NoteStatus;
+
+    #[test]
+    fn test_yaml_frontmatter() {
+        let content = "---\ntitle: 欢迎\ndate: 2024-06-30\ntags: [\"zeno\", \"知识管理\"]\nstatus: draft\npriority: high\n---\n\n# 正文\n";
+        let (frontmatter, offset) = parse_frontmatter(content).unwrap();
+
+        assert_eq!(frontmatter.title.as_deref(), Some("欢迎"));
+        assert_eq!(frontmatter.date.unwrap().to_string(), "2024-06-30");
+        assert_eq!(frontmatter.tags, vec!["zeno", "知识管理"]);
+        assert_eq!(frontmatter.status, Some(NoteStatus::Draft));
+        assert_eq!(
+            frontmatter.custom.get("priority"),
+            Some(&serde_json::Value::String("high".to_string()))
+        );
+        assert_eq!(&content[offset..], "\n# 正文\n");
+    }
+
+    #[test]
+    fn test_toml_frontmatter() {
+        let content = "+++\ntitle = \"TOML 笔记\"\ndate = 2025-01-02\ncategories = [\"技术\"]\n[extra]\nweight = 3\n+++\n正文";
+        let (frontmatter, offset) = parse_frontmatter(content).unwrap();
+
+        assert_eq!(frontmatter.title.as_deref(), Some("TOML 笔记"));
+        assert_eq!(frontmatter.date.unwrap().to_string(), "2025-01-02");
+        assert_eq!(frontmatter.categories, vec!["技术"]);
+        assert_eq!(frontmatter.custom["extra"]["weight"], serde_json::json!(3));
+        assert_eq!(&content[offset..], "正文");
+    }
+
+    #[test]
+    fn test_comma_separated_tags() {
+        let (frontmatter, _) = parse_frontmatter("---\ntags: rust, zeno\n---\n").unwrap();
+        assert_eq!(frontmatter.tags, vec!["rust", "zeno"]);
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        let (frontmatter, offset) = parse_frontmatter("# 标题\n\n---\n分隔线之后").unwrap();
+        assert!(frontmatter.title.is_none());
+        assert_eq!(offset, 0);
+
+        // 没有结束分隔符时不视为前言
+        assert!(split_frontmatter("---\ntitle: 未闭合\n").is_none());
+
+        // 以分隔线开头的普通段落不是前言
+        let (frontmatter, offset) = parse_frontmatter("---\nSome intro\n---\n正文").unwrap();
+        assert!(frontmatter.title.is_none());
+        assert_eq!(offset, 0);
+        assert!(split_frontmatter("---\n- 列表\n---\n").is_none());
+        assert!(split_frontmatter("---\n---\n正文").is_some());
+    }
+
+    #[test]
+    fn test_malformed_yaml_reports_position() {
+        let content = "---\ntitle: ok\ntags: [a, b\n---\n";
+        match parse_frontmatter(content) {
+            Err(Error::Parse(error)) => {
+                assert!(error.line.unwrap() >= 3);
+                assert!(error.column.is_some());
+            }
+            other => panic!("期望解析错误, 得到 {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_field_reports_position() {
+        let content = "---\ntitle: ok\nstatus: draft\ndate: not-a-date\n---\n";
+        match parse_frontmatter(content) {
+            Err(Error::Parse(error)) => {
+                assert_eq!(error.line, Some(4));
+                assert_eq!(error.column, Some(7));
+            }
+            other => panic!("期望解析错误, 得到 {:?}", other),
+        }
+
+        let content = "+++\ntitle = \"ok\"\nstatus = 3\n+++\n";
+        match parse_frontmatter(content) {
+            Err(Error::Parse(error)) => {
+                assert_eq!(error.line, Some(3));
+                assert_eq!(error.column, Some(10));
+            }
+            other => panic!("期望解析错误, 得到 {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_toml_reports_position() {
+        let content = "+++\ntitle = \"ok\"\ndate = = 1\n+++\n";
+        match parse_frontmatter(content) {
+            Err(Error::Parse(error)) => {
+                assert_eq!(error.line, Some(3));
+                assert_eq!(error.column, Some(8));
+            }
+            other => panic!("期望解析错误, 得到 {:?}", other),
+        }
+    }
+}
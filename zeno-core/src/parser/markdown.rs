@@ -1,9 +1,13 @@
 use crate::error::Result;
-use crate::db::models::{Frontmatter, Note};
-use pulldown_cmark::{html, Event, Options, Parser};
+use crate::parser::frontmatter::{parse_frontmatter, split_frontmatter};
+use crate::models::{calculate_checksum, calculate_reading_time, count_words, Frontmatter, Note, UNTITLED};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
 
 /// Markdown解析器
 #[derive(Debug, Clone)]
@@ -136,62 +140,425 @@ impl MarkdownParser {
     }
 
     /// 解析Markdown文件
-    pub fn parse_file(&self, path: &PathBuf) -> Result<Note> {
+    pub fn parse_file(&self, path: &Path) -> Result<Note> {
         let content = std::fs::read_to_string(path)?;
-        self.parse(&content, path.clone())
+        self.parse(&content, path.to_path_buf())
     }
 
     /// 解析Markdown内容并创建Note
     pub fn parse(&self, content: &str, path: PathBuf) -> Result<Note> {
-        // 简化版本，先让项目能编译
-        let id = uuid::Uuid::new_v4().to_string();
-        let file_path = path.to_string_lossy().to_string();
-        
-        // 提取标题（简单实现）
-        let title = self.extract_title_simple(content);
-        
-        let mut note = Note::new(title, file_path, content.to_string());
-        note.id = id;
-        
-        Ok(note)
-    }
+        let result = self.parse_content(content)?;
+        // 优先使用前言中的标题，其次是第一个一级标题
+        let title = result
+            .frontmatter
+            .title
+            .clone()
+            .filter(|title| !title.trim().is_empty())
+            .or_else(|| {
+                result
+                    .headings
+                    .iter()
+                    .find(|heading| heading.level == 1)
+                    .map(|heading| heading.text.clone())
+            })
+            .unwrap_or_else(|| UNTITLED.to_string());
 
-    /// 简单的标题提取
-    fn extract_title_simple(&self, content: &str) -> String {
-        for line in content.lines() {
-            let line = line.trim();
-            if line.starts_with("# ") {
-                return line[2..].trim().to_string();
-            }
+        let mut note = Note::with_title(path, title, result.content);
+        if let Some(status) = &result.frontmatter.status {
+            note.status = status.clone();
         }
-        "未命名".to_string()
+        note.frontmatter = result.frontmatter;
+        note.html_content = Some(result.html);
+        note.word_count = result.word_count;
+        note.reading_time = result.reading_time;
+        note.checksum = calculate_checksum(content);
+        note.source = Some(content.to_string());
+
+        Ok(note)
     }
 
     /// 解析内容并返回详细结果
+    ///
+    /// 文档开头的 YAML (`---`) 或 TOML (`+++`) 前言会被解析到 `frontmatter`，
+    /// 返回的 `content` 不包含前言；行号始终相对于完整文档。
     pub fn parse_content(&self, content: &str) -> Result<ParseResult> {
-        // 简化版本实现
-        let frontmatter = Frontmatter::default();
-        let parser = Parser::new_ext(content, self.options);
-        let events: Vec<Event> = parser.collect();
-        
+        let (frontmatter, body_offset) = parse_frontmatter(content)?;
+        let body = &content[body_offset..];
+        let line_index = LineIndex::new(content, body_offset);
+
+        let mut links = Vec::new();
+        let mut headings: Vec<Heading> = Vec::new();
+        let mut heading_ids: HashMap<String, usize> = HashMap::new();
+        // 代码、链接等区域内的文本不参与 Wiki 链接和标签的提取
+        let mut excluded_ranges: Vec<Range<usize>> = Vec::new();
+        let mut plain_text = String::new();
+
+        // 链接中可以嵌套图片（如徽章），未闭合的链接按栈保存
+        let mut pending_links: Vec<PendingLink> = Vec::new();
+        let mut pending_heading: Option<(u8, usize, String)> = None;
+        let mut in_code_block = false;
+
+        let mut events = Vec::new();
+        for (event, range) in Parser::new_ext(body, self.options).into_offset_iter() {
+            match &event {
+                Event::Start(Tag::CodeBlock(_)) => {
+                    in_code_block = true;
+                    excluded_ranges.push(range.clone());
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    in_code_block = false;
+                }
+                Event::Start(Tag::Heading { level, .. }) => {
+                    pending_heading = Some((*level as u8, range.start, String::new()));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, start, text)) = pending_heading.take() {
+                        let text = text.trim().to_string();
+                        let id = unique_heading_id(&text, &mut heading_ids);
+                        headings.push(Heading {
+                            level,
+                            text,
+                            id,
+                            line: line_index.line(start),
+                        });
+                    }
+                }
+                Event::Start(Tag::Link { link_type, dest_url, title, .. }) => {
+                    excluded_ranges.push(range.clone());
+                    pending_links.push(PendingLink {
+                        url: dest_url.to_string(),
+                        title: title.to_string(),
+                        link_type: classify_link(*link_type, dest_url, false),
+                        start: range.start,
+                        text: String::new(),
+                    });
+                }
+                Event::Start(Tag::Image { link_type, dest_url, title, .. }) => {
+                    excluded_ranges.push(range.clone());
+                    pending_links.push(PendingLink {
+                        url: dest_url.to_string(),
+                        title: title.to_string(),
+                        link_type: classify_link(*link_type, dest_url, true),
+                        start: range.start,
+                        text: String::new(),
+                    });
+                }
+                Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+                    if let Some(link) = pending_links.pop() {
+                        let (line, column) = line_index.position(link.start);
+                        links.push(Link {
+                            text: link.text,
+                            url: link.url,
+                            link_type: link.link_type,
+                            title: Some(link.title).filter(|t| !t.is_empty()),
+                            line,
+                            column,
+                        });
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    if matches!(event, Event::Code(_)) {
+                        excluded_ranges.push(range.clone());
+                    }
+                    if let Some((_, _, heading_text)) = pending_heading.as_mut() {
+                        heading_text.push_str(text);
+                    }
+                    for link in &mut pending_links {
+                        link.text.push_str(text);
+                    }
+                    if !in_code_block {
+                        plain_text.push_str(text);
+                        plain_text.push(' ');
+                    }
+                }
+                Event::Html(_) | Event::InlineHtml(_) => {
+                    excluded_ranges.push(range.clone());
+                }
+                _ => {}
+            }
+            events.push(event);
+        }
+
+        // 嵌套的图片先闭合，按出现位置排序
+        links.sort_by_key(|link| (link.line, link.column));
+
         // 渲染HTML
         let mut html_output = String::new();
-        html::push_html(&mut html_output, events.iter().cloned());
-        
+        html::push_html(&mut html_output, events.into_iter());
+
+        let wiki_links = extract_wiki_links(body, &line_index, &excluded_ranges);
+        excluded_ranges.extend(wiki_links.iter().map(|(_, range)| range.clone()));
+
+        // 前言中的标签在前，正文中的 #标签 在后
+        let mut tags = frontmatter.tags.clone();
+        for tag in extract_tags(body, &excluded_ranges) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        let toc = build_toc(&headings);
+        let word_count = count_words(&plain_text);
+        let reading_time = calculate_reading_time(word_count);
+
         Ok(ParseResult {
             frontmatter,
-            content: content.to_string(),
+            content: body.to_string(),
             html: html_output,
-            links: Vec::new(),
-            wiki_links: Vec::new(),
-            headings: Vec::new(),
-            toc: Vec::new(),
-            tags: Vec::new(),
-            word_count: content.len(),
-            reading_time: 1,
+            links,
+            wiki_links: wiki_links.into_iter().map(|(link, _)| link).collect(),
+            headings,
+            toc,
+            tags,
+            word_count,
+            reading_time,
             metadata: HashMap::new(),
         })
     }
+
+    /// 提取标题和代码块所在的行，跳过前言（格式错误的前言也跳过）
+    ///
+    /// 标题识别与 [`parse_content`](Self::parse_content) 一致，包括 Setext 标题；
+    /// 标题文本不做智能标点替换，以便与链接中手写的锚点比较。
+    pub(crate) fn outline(&self, content: &str) -> Outline {
+        let body_offset = split_frontmatter(content).map_or(0, |block| block.body_offset);
+        let body = &content[body_offset..];
+        let line_index = LineIndex::new(content, body_offset);
+        let span = |range: &Range<usize>| line_index.line(range.start)..=line_index.line(range.end.max(range.start + 1) - 1);
+
+        let mut outline = Outline::default();
+        let mut heading_ids: HashMap<String, usize> = HashMap::new();
+        let mut pending_heading: Option<(u8, Range<usize>, String)> = None;
+        let options = self.options.difference(Options::ENABLE_SMART_PUNCTUATION);
+        for (event, range) in Parser::new_ext(body, options).into_offset_iter() {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => outline.code_blocks.push(span(&range)),
+                Event::Start(Tag::Heading { level, .. }) => {
+                    pending_heading = Some((level as u8, range, String::new()));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, range, text)) = pending_heading.take() {
+                        let text = text.trim().to_string();
+                        let id = unique_heading_id(&text, &mut heading_ids);
+                        let lines = span(&range);
+                        outline.headings.push((Heading { level, text, id, line: *lines.start() }, lines));
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    if let Some((_, _, heading_text)) = pending_heading.as_mut() {
+                        heading_text.push_str(&text);
+                    }
+                }
+                Event::SoftBreak | Event::HardBreak => {
+                    if let Some((_, _, heading_text)) = pending_heading.as_mut() {
+                        heading_text.push(' ');
+                    }
+                }
+                _ => {}
+            }
+        }
+        outline
+    }
+}
+
+/// 文档中标题和代码块的位置，行号从 1 开始、相对于完整文档
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Outline {
+    /// 标题及其占据的行（Setext 标题包括下划线）
+    pub headings: Vec<(Heading, RangeInclusive<usize>)>,
+    /// 代码块占据的行（包括围栏）
+    pub code_blocks: Vec<RangeInclusive<usize>>,
+}
+
+/// 解析过程中尚未闭合的链接
+struct PendingLink {
+    url: String,
+    title: String,
+    link_type: LinkType,
+    start: usize,
+    text: String,
+}
+
+/// 字节偏移到行列号的映射
+///
+/// 传入的偏移相对于正文，`base` 为正文在完整文档中的起始偏移。
+struct LineIndex<'a> {
+    content: &'a str,
+    base: usize,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(content: &'a str, base: usize) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { content, base, line_starts }
+    }
+
+    /// 返回从 1 开始的行号
+    fn line(&self, offset: usize) -> usize {
+        let offset = self.base + offset;
+        match self.line_starts.binary_search(&offset) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        }
+    }
+
+    /// 返回从 1 开始的行号和列号（列号按字符计算）
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line(offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.content[line_start..self.base + offset].chars().count() + 1;
+        (line, column)
+    }
+}
+
+static WIKI_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"!?\[\[([^\[\]\n]+?)\]\]").unwrap());
+
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)(?:^|[^#\w&/])#([\p{L}\p{N}_][\p{L}\p{N}_/-]*)").unwrap()
+});
+
+fn is_excluded(position: usize, excluded_ranges: &[Range<usize>]) -> bool {
+    excluded_ranges.iter().any(|range| range.contains(&position))
+}
+
+/// 提取 `[[目标#片段|显示文本]]` 形式的双向链接
+fn extract_wiki_links(
+    content: &str,
+    line_index: &LineIndex,
+    excluded_ranges: &[Range<usize>],
+) -> Vec<(WikiLink, Range<usize>)> {
+    let mut wiki_links = Vec::new();
+
+    for cap in WIKI_LINK_REGEX.captures_iter(content) {
+        let full_match = cap.get(0).unwrap();
+        if is_excluded(full_match.start(), excluded_ranges) {
+            continue;
+        }
+
+        let inner = cap.get(1).unwrap().as_str();
+        let (target_part, display_text) = match inner.split_once('|') {
+            Some((target, display)) => (target, Some(display.trim().to_string())),
+            None => (inner, None),
+        };
+        let (target, fragment) = match target_part.split_once('#') {
+            Some((target, fragment)) => (target, Some(fragment.trim().to_string())),
+            None => (target_part, None),
+        };
+
+        let target = target.trim().to_string();
+        if target.is_empty() && fragment.is_none() {
+            continue;
+        }
+
+        let (line, column) = line_index.position(full_match.start());
+        wiki_links.push((
+            WikiLink {
+                target,
+                display_text: display_text.filter(|d| !d.is_empty()),
+                fragment: fragment.filter(|f| !f.is_empty()),
+                line,
+                column,
+            },
+            full_match.range(),
+        ));
+    }
+
+    wiki_links
+}
+
+/// 提取正文中的 `#标签`，按首次出现顺序去重
+fn extract_tags(content: &str, excluded_ranges: &[Range<usize>]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for cap in TAG_REGEX.captures_iter(content) {
+        let tag = cap.get(1).unwrap();
+        if is_excluded(tag.start(), excluded_ranges) {
+            continue;
+        }
+
+        let name = tag.as_str().trim_end_matches(['/', '-']);
+        // 纯数字（如 #1）通常是编号而不是标签
+        if name.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if !tags.iter().any(|t| t == name) {
+            tags.push(name.to_string());
+        }
+    }
+
+    tags
+}
+
+/// 链接分类
+fn classify_link(link_type: pulldown_cmark::LinkType, url: &str, is_image: bool) -> LinkType {
+    if is_image {
+        return LinkType::Image;
+    }
+    if link_type == pulldown_cmark::LinkType::Email || url.starts_with("mailto:") {
+        return LinkType::Email;
+    }
+    if url.starts_with('#') {
+        return LinkType::Anchor;
+    }
+    if url.starts_with("//") || url.contains("://") {
+        return LinkType::External;
+    }
+    LinkType::Internal
+}
+
+/// 生成标题锚点ID，重复的标题追加序号
+fn unique_heading_id(text: &str, used: &mut HashMap<String, usize>) -> String {
+    let base = slugify(text);
+    let base = if base.is_empty() { "section".to_string() } else { base };
+
+    let count = used.entry(base.clone()).or_insert(0);
+    let id = if *count == 0 {
+        base.clone()
+    } else {
+        format!("{}-{}", base, count)
+    };
+    *count += 1;
+    id
+}
+
+/// 生成 URL 友好的 slug，保留中文等非 ASCII 字母
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// 根据标题列表构建嵌套目录
+fn build_toc(headings: &[Heading]) -> Vec<TocItem> {
+    fn insert(items: &mut Vec<TocItem>, item: TocItem) {
+        match items.last_mut() {
+            Some(last) if last.level < item.level => insert(&mut last.children, item),
+            _ => items.push(item),
+        }
+    }
+
+    let mut toc = Vec::new();
+    for heading in headings {
+        insert(
+            &mut toc,
+            TocItem {
+                level: heading.level,
+                text: heading.text.clone(),
+                id: heading.id.clone(),
+                children: Vec::new(),
+            },
+        );
+    }
+    toc
 }
 
 #[cfg(test)]
@@ -217,4 +584,177 @@ mod tests {
         let result = parser.parse_content(content).unwrap();
         assert!(!result.html.is_empty());
     }
-}
\ No newline at end of file
+
+    const CORPUS: &str = r#"# 知识管理
+
+介绍 [Rust 官网](https://www.rust-lang.org "Rust") 和 [本地笔记](notes/intro.md)。
+联系 <zeno@example.com> 或跳转到 [小节](#小节)。
+
+![架构图](assets/arch.png)
+
+参见 [[Zettelkasten]] 与 [[卡片盒#方法|卡片盒方法]]，以及 ![[图表]]。
+
+## 小节
+
+标签 #rust #知识/管理 和重复的 #rust，不是标签的 #1。
+
+```rust
+// [[代码中的链接]] #代码标签
+fn main() {}
+```
+
+行内代码 `[[不是链接]] #not-tag`。
+
+### 细节
+
+## 小节
+"#;
+
+    #[test]
+    fn test_extract_links() {
+        let parser = MarkdownParser::new();
+        let result = parser.parse_content(CORPUS).unwrap();
+
+        assert_eq!(result.links.len(), 5);
+
+        let external = &result.links[0];
+        assert_eq!(external.text, "Rust 官网");
+        assert_eq!(external.url, "https://www.rust-lang.org");
+        assert!(matches!(external.link_type, LinkType::External));
+        assert_eq!(external.title.as_deref(), Some("Rust"));
+        assert_eq!((external.line, external.column), (3, 4));
+
+        let internal = &result.links[1];
+        assert!(matches!(internal.link_type, LinkType::Internal));
+        assert_eq!(internal.url, "notes/intro.md");
+        assert!(internal.title.is_none());
+
+        assert!(matches!(result.links[2].link_type, LinkType::Email));
+        assert_eq!(result.links[2].line, 4);
+        assert!(matches!(result.links[3].link_type, LinkType::Anchor));
+
+        let image = &result.links[4];
+        assert!(matches!(image.link_type, LinkType::Image));
+        assert_eq!(image.text, "架构图");
+        assert_eq!((image.line, image.column), (6, 1));
+    }
+
+    #[test]
+    fn test_image_inside_link() {
+        let parser = MarkdownParser::new();
+        let result = parser.parse_content("构建 [![badge](img.svg)](https://ci.example) 状态").unwrap();
+
+        let urls: Vec<&str> = result.links.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://ci.example", "img.svg"]);
+        assert!(matches!(result.links[0].link_type, LinkType::External));
+        assert_eq!(result.links[0].text, "badge");
+        assert!(matches!(result.links[1].link_type, LinkType::Image));
+        assert_eq!((result.links[1].line, result.links[1].column), (1, 5));
+    }
+
+    #[test]
+    fn test_extract_wiki_links() {
+        let parser = MarkdownParser::new();
+        let result = parser.parse_content(CORPUS).unwrap();
+
+        let targets: Vec<&str> = result.wiki_links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, vec!["Zettelkasten", "卡片盒", "图表"]);
+
+        let simple = &result.wiki_links[0];
+        assert_eq!((simple.line, simple.column), (8, 4));
+        assert!(simple.display_text.is_none());
+        assert!(simple.fragment.is_none());
+
+        let aliased = &result.wiki_links[1];
+        assert_eq!(aliased.fragment.as_deref(), Some("方法"));
+        assert_eq!(aliased.display_text.as_deref(), Some("卡片盒方法"));
+        assert_eq!(aliased.column, 23);
+    }
+
+    #[test]
+    fn test_extract_headings_and_toc() {
+        let parser = MarkdownParser::new();
+        let result = parser.parse_content(CORPUS).unwrap();
+
+        let headings: Vec<(u8, &str, &str, usize)> = result
+            .headings
+            .iter()
+            .map(|h| (h.level, h.text.as_str(), h.id.as_str(), h.line))
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                (1, "知识管理", "知识管理", 1),
+                (2, "小节", "小节", 10),
+                (3, "细节", "细节", 21),
+                (2, "小节", "小节-1", 23),
+            ]
+        );
+
+        assert_eq!(result.toc.len(), 1);
+        let root = &result.toc[0];
+        assert_eq!(root.text, "知识管理");
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].children.len(), 1);
+        assert_eq!(root.children[0].children[0].text, "细节");
+        assert_eq!(root.children[1].id, "小节-1");
+    }
+
+    #[test]
+    fn test_extract_tags() {
+        let parser = MarkdownParser::new();
+        let result = parser.parse_content(CORPUS).unwrap();
+
+        assert_eq!(result.tags, vec!["rust", "知识/管理"]);
+    }
+
+    #[test]
+    fn test_word_count_and_reading_time() {
+        let parser = MarkdownParser::new();
+
+        let result = parser.parse_content("# 标题\n\nHello world，你好世界").unwrap();
+        assert_eq!(result.word_count, 8);
+        assert_eq!(result.reading_time, 1);
+
+        let long = "字".repeat(600);
+        let result = parser.parse_content(&long).unwrap();
+        assert_eq!(result.word_count, 600);
+        assert_eq!(result.reading_time, 3);
+    }
+
+    #[test]
+    fn test_frontmatter_in_parse_content() {
+        let parser = MarkdownParser::new();
+        let content = "---\ntitle: 前言标题\ntags: [前言]\n---\n# 正文标题\n\n[[链接]] #正文\n";
+        let result = parser.parse_content(content).unwrap();
+
+        assert_eq!(result.frontmatter.title.as_deref(), Some("前言标题"));
+        assert!(!result.content.contains("title:"));
+        assert_eq!(result.tags, vec!["前言", "正文"]);
+        // 行号相对于完整文档
+        assert_eq!(result.headings[0].line, 5);
+        assert_eq!((result.wiki_links[0].line, result.wiki_links[0].column), (7, 1));
+
+        let note = parser.parse(content, PathBuf::from("a.md")).unwrap();
+        assert_eq!(note.title, "前言标题");
+        assert_eq!(note.frontmatter.tags, vec!["前言"]);
+        assert!(!note.is_content_changed(content));
+    }
+
+    #[test]
+    fn test_malformed_frontmatter_is_error() {
+        let parser = MarkdownParser::new();
+        let result = parser.parse("---\ntitle: [未闭合\n---\n正文", PathBuf::from("a.md"));
+        assert!(matches!(result, Err(crate::error::Error::Parse(_))));
+    }
+
+    #[test]
+    fn test_parse_fills_note_fields() {
+        let parser = MarkdownParser::new();
+        let note = parser.parse("没有标题\n\n## 二级标题", PathBuf::from("a.md")).unwrap();
+
+        assert_eq!(note.title, UNTITLED);
+        assert!(note.html_content.unwrap().contains("<h2>"));
+        assert!(note.word_count > 0);
+    }
+}
@@ -1,3 +1,5 @@
 pub mod markdown;
+pub mod frontmatter;
 
-pub use markdown::*;
\ No newline at end of file
+pub use markdown::*;
+pub use frontmatter::*;
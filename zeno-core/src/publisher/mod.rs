@@ -1,3 +1,8 @@
+pub mod zola;
+
+// Zola 发布结果与下面的 PublishResult 重名，通过 `publisher::zola::` 访问
+pub use zola::{PublishOptions, ZolaConfig, ZolaPublisher};
+
 use anyhow::Result;
 use crate::models::Note;
 
@@ -0,0 +1,679 @@
+//! Zola 静态网站发布
+
+mod model;
+
+pub use model::*;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tokio::fs;
+use tokio::time::Instant;
+use walkdir::WalkDir;
+
+use crate::config::WorkspaceConfig;
+use crate::error::{Error, Result};
+use crate::indexer::collect_markdown_files;
+use crate::models::{Note, NoteStatus};
+use crate::parser::{slugify, MarkdownParser};
+
+static WIKI_LINK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[\[([^\]]+?)\]\]").unwrap());
+static EMBED_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[\[([^\]]+?)\]\]").unwrap());
+static IMAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[([^\]]*)\]\(([^)]+)\)").unwrap());
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg"];
+
+/// Zola 静态网站发布器
+pub struct ZolaPublisher {
+    config: ZolaConfig,
+    site_path: PathBuf,
+    /// 笔记路径相对于该目录，用于查找引用的图片
+    workspace_root: Option<PathBuf>,
+    assets_dir: Option<PathBuf>,
+    excluded_dirs: Vec<PathBuf>,
+}
+
+/// 构建结果
+#[derive(Debug)]
+pub struct BuildResult {
+    pub success: bool,
+    pub output: String,
+}
+
+impl ZolaPublisher {
+    pub fn new(config: ZolaConfig, site_path: PathBuf) -> Self {
+        Self {
+            config,
+            site_path,
+            workspace_root: None,
+            assets_dir: None,
+            excluded_dirs: Vec::new(),
+        }
+    }
+
+    /// 关联工作区，用于读取笔记和附件
+    pub fn with_workspace(mut self, root: &Path, config: &WorkspaceConfig) -> Self {
+        self.workspace_root = Some(root.to_path_buf());
+        self.assets_dir = Some(config.assets_dir(root));
+        self.excluded_dirs = config.excluded_dirs();
+        self
+    }
+
+    /// 站点是否已初始化（存在 `config.toml`）
+    pub fn is_initialized(&self) -> bool {
+        self.site_path.join("config.toml").exists()
+    }
+
+    /// 初始化 Zola 站点结构
+    pub async fn initialize_site(&self) -> Result<()> {
+        let site_path = &self.site_path;
+
+        // 创建 Zola 目录结构
+        fs::create_dir_all(site_path.join("content")).await?;
+        fs::create_dir_all(site_path.join("templates")).await?;
+        fs::create_dir_all(site_path.join("static")).await?;
+        fs::create_dir_all(site_path.join("sass")).await?;
+        fs::create_dir_all(site_path.join("themes")).await?;
+
+        // 生成 config.toml
+        self.generate_config_file().await?;
+
+        // 创建基础模板
+        self.create_base_templates().await?;
+
+        // 创建基础样式
+        self.create_base_styles().await?;
+
+        Ok(())
+    }
+
+    /// 读取关联工作区中的全部笔记并发布，前言无法解析的笔记记为发布错误
+    pub async fn publish_workspace(&self, options: &PublishOptions) -> Result<PublishResult> {
+        let root = self
+            .workspace_root
+            .as_deref()
+            .ok_or_else(|| Error::config("发布工作区前需要调用 with_workspace"))?;
+
+        // 站点目录在工作区内时，不能把生成的内容当作笔记
+        let mut excluded = self.excluded_dirs.clone();
+        if let (Ok(root), Ok(site)) = (root.canonicalize(), self.site_path.canonicalize()) {
+            if let Ok(relative) = site.strip_prefix(&root) {
+                excluded.push(relative.to_path_buf());
+            }
+        }
+
+        let parser = MarkdownParser::new();
+        let mut notes = Vec::new();
+        let mut errors = Vec::new();
+        for path in collect_markdown_files(root, &excluded) {
+            match load_note(&parser, root, &path).await {
+                Ok(note) => notes.push(note),
+                Err(e) => errors.push(PublishError {
+                    file_path: path,
+                    error_type: PublishErrorType::FrontmatterParseError,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        let mut result = self.publish_notes(notes, options).await?;
+        if !errors.is_empty() {
+            errors.append(&mut result.errors);
+            result.errors = errors;
+            result.success = false;
+        }
+        Ok(result)
+    }
+
+    /// 发布笔记集合到静态网站
+    ///
+    /// 草稿只在 `options.drafts` 时发布；前言中 `publish.enabled: false`
+    /// 或 `platforms` 不包含 zola 的笔记会被跳过。
+    pub async fn publish_notes(&self, notes: Vec<Note>, options: &PublishOptions) -> Result<PublishResult> {
+        let start_time = Instant::now();
+        let mut published_files = Vec::new();
+        let mut errors = Vec::new();
+        // 输出路径 -> 源文件，检测标题相同导致的覆盖
+        let mut outputs: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+        // 清理旧内容
+        self.clean_content_directory().await?;
+
+        // 处理每个笔记
+        for note in notes.iter().filter(|note| should_publish(note, options)) {
+            let output_path = determine_output_path(note);
+            if let Some(existing) = outputs.get(&output_path) {
+                errors.push(PublishError {
+                    file_path: note.path.clone(),
+                    error_type: PublishErrorType::ContentProcessingError,
+                    message: format!("输出路径 {} 与 {} 冲突", output_path.display(), existing.display()),
+                });
+                continue;
+            }
+
+            match self.process_note(note, &output_path).await {
+                Ok(()) => {
+                    outputs.insert(output_path.clone(), note.path.clone());
+                    published_files.push(output_path);
+                    log::info!("Published note: {}", note.title);
+                }
+                Err(e) => {
+                    errors.push(PublishError {
+                        file_path: note.path.clone(),
+                        error_type: PublishErrorType::ContentProcessingError,
+                        message: e.to_string(),
+                    });
+                    log::error!("Failed to publish note {}: {}", note.title, e);
+                }
+            }
+        }
+
+        // 构建网站
+        let mut build_output = String::new();
+        if options.build {
+            match self.build_site(options.drafts).await {
+                Ok(build) => build_output = build.output,
+                Err(e) => {
+                    build_output = e.to_string();
+                    errors.push(PublishError {
+                        file_path: self.site_path.clone(),
+                        error_type: PublishErrorType::BuildError,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(PublishResult {
+            success: errors.is_empty(),
+            total_pages: published_files.len(),
+            published_files,
+            errors,
+            build_output,
+            site_url: Some(self.config.base_url.clone()),
+            build_time: start_time.elapsed().as_secs_f64(),
+            total_size: self.calculate_site_size(),
+        })
+    }
+
+    /// 处理单个笔记
+    async fn process_note(&self, note: &Note, output_path: &Path) -> Result<()> {
+        // 转换内容格式
+        let zola_content = self.convert_to_zola_format(note).await?;
+
+        // 处理图片和资源
+        let processed_content = self.process_embedded_assets(&zola_content, note).await?;
+
+        // 写入文件
+        let full_path = self.site_path.join("content").join(output_path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&full_path, processed_content).await?;
+
+        Ok(())
+    }
+
+    /// 将笔记转换为 Zola 格式
+    pub async fn convert_to_zola_format(&self, note: &Note) -> Result<String> {
+        let frontmatter = &note.frontmatter;
+        let date = frontmatter
+            .date
+            .unwrap_or_else(|| note.created_at.date_naive());
+
+        // 生成 TOML frontmatter，自定义字段放在 Zola 的 [extra] 中
+        let mut table = toml::Table::new();
+        table.insert("title".into(), note.title.clone().into());
+        table.insert("date".into(), toml_date(&date.format("%Y-%m-%d").to_string())?);
+        if let Some(description) = &frontmatter.description {
+            table.insert("description".into(), description.clone().into());
+        }
+        table.insert("slug".into(), slugify(&note.title).into());
+        table.insert("draft".into(), (note.status == NoteStatus::Draft).into());
+        table.insert(
+            "updated".into(),
+            toml_date(&note.modified_at.format("%Y-%m-%d").to_string())?,
+        );
+
+        // 处理标签和分类
+        let mut taxonomies = toml::Table::new();
+        if !frontmatter.tags.is_empty() {
+            taxonomies.insert("tags".into(), frontmatter.tags.clone().into());
+        }
+        if !frontmatter.categories.is_empty() {
+            taxonomies.insert("categories".into(), frontmatter.categories.clone().into());
+        }
+        if !taxonomies.is_empty() {
+            table.insert("taxonomies".into(), taxonomies.into());
+        }
+
+        let mut extra = toml::Table::new();
+        for (key, value) in &frontmatter.custom {
+            // TOML 没有 null
+            if let Ok(value) = toml::Value::try_from(value) {
+                extra.insert(key.clone(), value);
+            }
+        }
+        if !extra.is_empty() {
+            table.insert("extra".into(), extra.into());
+        }
+
+        let toml = toml::to_string(&table).map_err(|e| Error::publisher(format!("生成前言失败: {}", e)))?;
+        Ok(format!("+++\n{}+++\n\n{}", toml, self.process_content_for_zola(&note.content)))
+    }
+
+    /// 为 Zola 处理内容
+    fn process_content_for_zola(&self, content: &str) -> String {
+        // 先处理嵌入，避免 ![[...]] 被当作普通 Wiki 链接
+        let processed = self.process_embeds(content);
+
+        // 转换 Wiki 链接为 Zola 链接
+        let processed = self.convert_wiki_links(&processed);
+
+        // 处理数学公式
+        let processed = self.process_math_blocks(&processed);
+
+        // 处理代码块
+        self.process_code_blocks(&processed)
+    }
+
+    /// 转换 Wiki 链接为 Zola 内部链接
+    fn convert_wiki_links(&self, content: &str) -> String {
+        WIKI_LINK_REGEX
+            .replace_all(content, |caps: &regex::Captures| {
+                // 解析链接目标（可能包含别名和锚点）
+                let (target, alias) = match caps[1].split_once('|') {
+                    Some((target, alias)) => (target.trim(), Some(alias.trim())),
+                    None => (caps[1].trim(), None),
+                };
+                let (page, anchor) = match target.split_once('#') {
+                    Some((page, anchor)) => (page.trim(), Some(anchor.trim())),
+                    None => (target, None),
+                };
+
+                let url = match anchor {
+                    Some(anchor) => format!("../{}/#{}", slugify(page), slugify(anchor)),
+                    None => format!("../{}", slugify(page)),
+                };
+                format!("[{}]({})", alias.unwrap_or(target), url)
+            })
+            .to_string()
+    }
+
+    /// 处理嵌入内容
+    fn process_embeds(&self, content: &str) -> String {
+        EMBED_REGEX
+            .replace_all(content, |caps: &regex::Captures| {
+                let embed_target = caps[1].split('|').next().unwrap_or_default().trim();
+
+                // 根据文件类型处理嵌入
+                if is_image(embed_target) {
+                    // 图片嵌入，稍后由 process_embedded_assets 复制
+                    format!("![{}](<{}>)", embed_target, embed_target)
+                } else {
+                    // 文档嵌入（暂时替换为链接）
+                    format!("[{}](../{})", embed_target, slugify(embed_target))
+                }
+            })
+            .to_string()
+    }
+
+    /// 处理数学公式块
+    fn process_math_blocks(&self, content: &str) -> String {
+        // Zola 模板中的 KaTeX 直接识别 $...$ 和 $$...$$，无需转换
+        content.to_string()
+    }
+
+    /// 处理代码块
+    fn process_code_blocks(&self, content: &str) -> String {
+        // Zola 原生支持 Markdown 代码块，无需特殊处理
+        content.to_string()
+    }
+
+    /// 处理嵌入的资源文件
+    async fn process_embedded_assets(&self, content: &str, note: &Note) -> Result<String> {
+        let mut replacements = Vec::new();
+
+        // 查找内容中引用的图片
+        for captures in IMAGE_REGEX.captures_iter(content) {
+            let img_path = captures[2].trim().trim_start_matches('<').trim_end_matches('>');
+            if img_path.contains("://") || img_path.starts_with('/') {
+                continue;
+            }
+
+            // 相对于笔记所在目录，其次是附件目录
+            let note_path = match &self.workspace_root {
+                Some(root) => root.join(&note.path),
+                None => note.path.clone(),
+            };
+            let candidates = note_path
+                .parent()
+                .map(|dir| dir.join(img_path))
+                .into_iter()
+                .chain(self.assets_dir.iter().map(|dir| dir.join(img_path)));
+            let Some(source_path) = candidates.into_iter().find(|path| path.is_file()) else {
+                log::warn!("{} 引用的图片不存在: {}", note.path.display(), img_path);
+                continue;
+            };
+
+            // 复制到静态资源目录
+            let Some(filename) = source_path.file_name() else {
+                continue;
+            };
+            let dest_path = self.site_path.join("static").join("images").join(filename);
+            fs::create_dir_all(dest_path.parent().unwrap()).await?;
+            fs::copy(&source_path, &dest_path).await?;
+
+            // 更新内容中的路径
+            let new_link = format!("![{}](</images/{}>)", &captures[1], filename.to_string_lossy());
+            replacements.push((captures[0].to_string(), new_link));
+        }
+
+        let mut result = content.to_string();
+        for (old, new) in replacements {
+            result = result.replace(&old, &new);
+        }
+        Ok(result)
+    }
+
+    /// 生成 config.toml 文件
+    async fn generate_config_file(&self) -> Result<()> {
+        let string = |value: &str| toml::Value::String(value.to_string()).to_string();
+        let taxonomies: Vec<String> = self
+            .config
+            .taxonomies
+            .iter()
+            .map(|taxonomy| {
+                let paginate = taxonomy
+                    .paginate_by
+                    .map(|n| format!(", paginate_by = {}", n))
+                    .unwrap_or_default();
+                format!("    {{name = {}, feed = {}{}}},\n", string(&taxonomy.name), taxonomy.rss, paginate)
+            })
+            .collect();
+        let markdown = &self.config.markdown;
+
+        let config_content = format!(
+            r#"# 由 Zeno 生成的 Zola 站点配置
+
+# The URL the site will be built for
+base_url = {}
+
+# The site title and description
+title = {}
+description = {}
+
+# The default author
+author = {}
+
+# The default language
+default_language = {}
+
+# Whether to automatically compile all Sass files in the sass directory
+compile_sass = {}
+
+# Whether to generate a feed automatically
+generate_feeds = {}
+
+# Whether to build a search index to be used later on by a JavaScript library
+build_search_index = {}
+
+# The taxonomies to be rendered for the site
+taxonomies = [
+{}]
+
+# Markdown configuration
+[markdown]
+highlight_code = {}
+highlight_theme = {}
+render_emoji = {}
+external_links_target_blank = {}
+external_links_no_follow = {}
+external_links_no_referrer = {}
+smart_punctuation = {}
+
+[search]
+include_title = true
+include_description = false
+include_path = false
+include_content = true
+
+[extra]
+{}"#,
+            string(&self.config.base_url),
+            string(&self.config.title),
+            string(&self.config.description),
+            string(&self.config.author),
+            string(&self.config.default_language),
+            self.config.compile_sass,
+            self.config.generate_rss,
+            self.config.build_search_index,
+            taxonomies.concat(),
+            markdown.highlight_code,
+            string(&markdown.highlight_theme),
+            markdown.render_emoji,
+            markdown.external_links_target_blank,
+            markdown.external_links_no_follow,
+            markdown.external_links_no_referrer,
+            markdown.smart_punctuation,
+            self.extra_config()?,
+        );
+
+        let config_path = self.site_path.join("config.toml");
+        fs::write(config_path, config_content).await?;
+
+        Ok(())
+    }
+
+    fn extra_config(&self) -> Result<String> {
+        let extra: toml::Table = self
+            .config
+            .extra
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), toml::Value::try_from(value).ok()?)))
+            .collect();
+        toml::to_string(&extra).map_err(|e| Error::publisher(format!("生成站点配置失败: {}", e)))
+    }
+
+    /// 创建基础模板
+    async fn create_base_templates(&self) -> Result<()> {
+        let templates_dir = self.site_path.join("templates");
+
+        // base.html 基础模板
+        let base_template = include_str!("templates/base.html");
+        fs::write(templates_dir.join("base.html"), base_template).await?;
+
+        // index.html 首页模板
+        let index_template = include_str!("templates/index.html");
+        fs::write(templates_dir.join("index.html"), index_template).await?;
+
+        // page.html 页面模板
+        let page_template = include_str!("templates/page.html");
+        fs::write(templates_dir.join("page.html"), page_template).await?;
+
+        // section.html 分区模板
+        let section_template = include_str!("templates/section.html");
+        fs::write(templates_dir.join("section.html"), section_template).await?;
+
+        Ok(())
+    }
+
+    /// 创建基础样式
+    async fn create_base_styles(&self) -> Result<()> {
+        let sass_dir = self.site_path.join("sass");
+        fs::create_dir_all(&sass_dir).await?;
+
+        let main_scss = include_str!("styles/main.scss");
+        fs::write(sass_dir.join("main.scss"), main_scss).await?;
+
+        Ok(())
+    }
+
+    /// 清理内容目录
+    async fn clean_content_directory(&self) -> Result<()> {
+        let content_dir = self.site_path.join("content");
+        if content_dir.exists() {
+            fs::remove_dir_all(&content_dir).await?;
+        }
+        fs::create_dir_all(&content_dir).await?;
+        Ok(())
+    }
+
+    /// 运行 `zola build`，输出到站点的 `public` 目录
+    pub async fn build_site(&self, drafts: bool) -> Result<BuildResult> {
+        let mut command = tokio::process::Command::new("zola");
+        command
+            .current_dir(&self.site_path)
+            .args(["build", "--output-dir", "public", "--force"]);
+        if drafts {
+            command.arg("--drafts");
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| Error::publisher(format!("无法运行 zola: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(Error::publisher(format!(
+                "Zola build failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(BuildResult {
+            success: true,
+            output: String::from_utf8_lossy(&output.stdout).to_string(),
+        })
+    }
+
+    /// 计算站点大小
+    fn calculate_site_size(&self) -> u64 {
+        WalkDir::new(self.site_path.join("public"))
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+}
+
+/// 读取并解析工作区中的笔记，`path` 为相对路径
+async fn load_note(parser: &MarkdownParser, root: &Path, path: &Path) -> Result<Note> {
+    let full_path = root.join(path);
+    let content = fs::read_to_string(&full_path).await?;
+    let metadata = fs::metadata(&full_path).await?;
+
+    let mut note = parser.parse(&content, path.to_path_buf())?;
+    note.file_size = metadata.len();
+    if let Ok(modified) = metadata.modified() {
+        note.modified_at = modified.into();
+    }
+    Ok(note)
+}
+
+/// 按状态和前言中的发布配置判断是否发布
+fn should_publish(note: &Note, options: &PublishOptions) -> bool {
+    let by_status = match note.status {
+        NoteStatus::Draft => options.drafts,
+        NoteStatus::Deleted => false,
+        NoteStatus::Published | NoteStatus::Archived => true,
+    };
+    let by_frontmatter = note.frontmatter.publish.as_ref().is_none_or(|publish| {
+        publish.enabled
+            && (publish.platforms.is_empty()
+                || publish.platforms.iter().any(|p| p.eq_ignore_ascii_case("zola")))
+    });
+    by_status && by_frontmatter
+}
+
+/// 确定笔记的输出路径，按分类组织目录结构
+fn determine_output_path(note: &Note) -> PathBuf {
+    let mut path = PathBuf::new();
+
+    for category in &note.frontmatter.categories {
+        path.push(slugify(category));
+    }
+
+    path.push(format!("{}.md", slugify(&note.title)));
+    path
+}
+
+fn is_image(target: &str) -> bool {
+    Path::new(target)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+fn toml_date(date: &str) -> Result<toml::Value> {
+    date.parse()
+        .map(toml::Value::Datetime)
+        .map_err(|e| Error::publisher(format!("无效日期 {}: {}", date, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_convert_to_zola_format() {
+        let parser = MarkdownParser::new();
+        let content = "---\ntitle: \"Hello \\\"Zola\\\"\"\ndate: 2024-07-01\nstatus: published\ntags: [rust]\nseries: intro\n---\n\nSee [[Other Note#Part One|other]] and ![[pic.png]].\n";
+        let note = parser.parse(content, PathBuf::from("notes/hello.md")).unwrap();
+
+        let publisher = ZolaPublisher::new(ZolaConfig::default(), PathBuf::from("site"));
+        let converted = publisher.convert_to_zola_format(&note).await.unwrap();
+
+        let (front, body) = converted
+            .strip_prefix("+++\n")
+            .and_then(|rest| rest.split_once("+++\n"))
+            .unwrap();
+        let front: toml::Table = front.parse().unwrap();
+        assert_eq!(front["title"].as_str(), Some("Hello \"Zola\""));
+        assert!(converted.contains("\ndate = 2024-07-01\n"));
+        assert_eq!(front["draft"].as_bool(), Some(false));
+        assert_eq!(front["taxonomies"]["tags"].as_array().unwrap().len(), 1);
+        assert_eq!(front["extra"]["series"].as_str(), Some("intro"));
+        assert!(body.contains("[other](../other-note/#part-one)"));
+        assert!(body.contains("![pic.png](<pic.png>)"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_workspace() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        std::fs::create_dir_all(root.join("assets")).unwrap();
+        std::fs::write(root.join("assets/pic.png"), b"png").unwrap();
+        std::fs::write(root.join("notes/a.md"), "---\nstatus: published\n---\n# Same\n\n![[pic.png]]").unwrap();
+        std::fs::write(root.join("notes/b.md"), "---\nstatus: published\n---\n# Same\n").unwrap();
+        std::fs::write(root.join("notes/draft.md"), "# Draft\n").unwrap();
+        std::fs::write(root.join("notes/bad.md"), "---\ntitle: [oops\n---\n").unwrap();
+
+        let config = WorkspaceConfig::default();
+        let site = root.join("site");
+        let publisher = ZolaPublisher::new(ZolaConfig::from_workspace(&config), site.clone()).with_workspace(root, &config);
+        publisher.initialize_site().await.unwrap();
+        let site_config: toml::Table = std::fs::read_to_string(site.join("config.toml")).unwrap().parse().unwrap();
+        assert_eq!(site_config["title"].as_str(), Some("我的知识库"));
+
+        let result = publisher.publish_workspace(&PublishOptions::default()).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.published_files, vec![PathBuf::from("same.md")]);
+        let failed: Vec<&Path> = result.errors.iter().map(|e| e.file_path.as_path()).collect();
+        assert_eq!(failed, vec![Path::new("notes/bad.md"), Path::new("notes/b.md")]);
+        assert!(site.join("static/images/pic.png").exists());
+        let page = std::fs::read_to_string(site.join("content/same.md")).unwrap();
+        assert!(page.contains("![pic.png](</images/pic.png>)"));
+
+        // 再次发布时站点目录中的内容不会被当作笔记
+        let options = PublishOptions { drafts: true, build: false };
+        let result = publisher.publish_workspace(&options).await.unwrap();
+        assert_eq!(result.total_pages, 2);
+    }
+}
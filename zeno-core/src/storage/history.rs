@@ -0,0 +1,174 @@
+use crate::config::HistorySettings;
+use crate::db::models::NoteRecord;
+use crate::error::Result;
+use crate::models::{calculate_checksum, Note};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use sqlx::SqliteConnection;
+
+/// 差异行的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Equal,
+    Added,
+    Removed,
+}
+
+/// 逐行比较的一行结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    /// 在旧内容中的行号（从 1 开始），新增的行没有
+    pub old_line: Option<usize>,
+    /// 在新内容中的行号（从 1 开始），删除的行没有
+    pub new_line: Option<usize>,
+    /// 行内容，不含换行符
+    pub text: String,
+}
+
+/// 逐行比较两段文本
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            kind: match change.tag() {
+                ChangeTag::Equal => DiffKind::Equal,
+                ChangeTag::Insert => DiffKind::Added,
+                ChangeTag::Delete => DiffKind::Removed,
+            },
+            old_line: change.old_index().map(|i| i + 1),
+            new_line: change.new_index().map(|i| i + 1),
+            text: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect()
+}
+
+/// 笔记记录对应的完整 Markdown（含 frontmatter）
+///
+/// 优先使用索引时保存的原始文件内容，保留前言格式和字段顺序；
+/// 没有原始内容的旧记录才由前言和正文重新生成。
+pub(crate) fn record_markdown(record: &NoteRecord) -> String {
+    if let Some(source) = &record.source {
+        return source.clone();
+    }
+    Note::try_from(record.clone())
+        .map(|note| note.to_markdown())
+        .unwrap_or_else(|_| record.content.clone())
+}
+
+/// 笔记内容即将被 `new` 覆盖时保存旧内容，返回新版本号
+///
+/// 校验和相同或内容没有变化时不保存；`max_versions` 为 0 时不记录历史。
+/// 在调用方的事务中执行，写入后按保留策略清理该笔记的旧版本。
+pub(crate) async fn record_version(
+    conn: &mut SqliteConnection,
+    new: &NoteRecord,
+    settings: &HistorySettings,
+) -> Result<Option<i64>> {
+    if settings.max_versions == 0 {
+        return Ok(None);
+    }
+
+    let old = sqlx::query_as::<_, NoteRecord>("SELECT * FROM notes WHERE id = ?")
+        .bind(&new.id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    let Some(old) = old else {
+        return Ok(None);
+    };
+    if !new.file_hash.is_empty() && old.file_hash == new.file_hash {
+        return Ok(None);
+    }
+    let content = record_markdown(&old);
+    if content == record_markdown(new) {
+        return Ok(None);
+    }
+    let file_hash = calculate_checksum(&content);
+
+    let version: i64 = sqlx::query_scalar(
+        r#"
+        INSERT INTO note_versions (note_id, version, title, file_path, content, file_hash, modified_at)
+        VALUES (?, (SELECT COALESCE(MAX(version), 0) + 1 FROM note_versions WHERE note_id = ?), ?, ?, ?, ?, ?)
+        RETURNING version
+        "#
+    )
+    .bind(&old.id)
+    .bind(&old.id)
+    .bind(&old.title)
+    .bind(&old.file_path)
+    .bind(&content)
+    .bind(&file_hash)
+    .bind(old.modified_at)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    prune_versions(conn, Some(&old.id), settings).await?;
+    Ok(Some(version))
+}
+
+/// 按保留策略删除旧版本，`note_id` 为空时清理全部笔记，返回删除的版本数
+pub(crate) async fn prune_versions(
+    conn: &mut SqliteConnection,
+    note_id: Option<&str>,
+    settings: &HistorySettings,
+) -> Result<u64> {
+    let mut removed = 0;
+
+    if settings.max_versions > 0 {
+        removed += sqlx::query(
+            r#"
+            DELETE FROM note_versions
+            WHERE (? IS NULL OR note_id = ?)
+              AND version <= (
+                  SELECT MAX(v.version) FROM note_versions v WHERE v.note_id = note_versions.note_id
+              ) - ?
+            "#
+        )
+        .bind(note_id)
+        .bind(note_id)
+        .bind(settings.max_versions as i64)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+    }
+
+    if settings.max_age_days > 0 {
+        removed += sqlx::query(
+            "DELETE FROM note_versions WHERE (? IS NULL OR note_id = ?) AND created_at < datetime('now', ?)"
+        )
+        .bind(note_id)
+        .bind(note_id)
+        .bind(format!("-{} days", settings.max_age_days))
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nB\nc\nd");
+        let summary: Vec<(DiffKind, Option<usize>, Option<usize>, &str)> = diff
+            .iter()
+            .map(|line| (line.kind, line.old_line, line.new_line, line.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DiffKind::Equal, Some(1), Some(1), "a"),
+                (DiffKind::Removed, Some(2), None, "b"),
+                (DiffKind::Added, None, Some(2), "B"),
+                (DiffKind::Equal, Some(3), Some(3), "c"),
+                (DiffKind::Added, None, Some(4), "d"),
+            ]
+        );
+        assert!(diff_lines("same\n", "same\n").iter().all(|line| line.kind == DiffKind::Equal));
+    }
+}
@@ -1,31 +1,44 @@
 pub mod repository;
+pub mod query;
+pub mod search;
+pub mod history;
+pub mod trash;
 
-use crate::error::Result;
-use crate::db::models::{Note, Tag, TreeNode, Statistics, SearchQuery, SearchResult};
-use std::path::PathBuf;
+use crate::config::{DirectoryConfig, TrashSettings, WorkspaceConfig};
+use crate::error::{Error, Result};
+use crate::models::Note;
+use crate::db::models::{NoteRecord, Tag, TreeNode, Statistics, SearchQuery, SearchResult, TrashEntry};
+use std::path::{Path, PathBuf};
 
 pub use repository::*;
+pub use query::*;
+pub use search::*;
+pub use history::*;
+pub use trash::*;
 
 /// 文件存储接口
 #[async_trait::async_trait]
 pub trait FileStorage: Send + Sync {
     /// 读取文件内容
-    async fn read_file(&self, path: &PathBuf) -> Result<String>;
+    async fn read_file(&self, path: &Path) -> Result<String>;
     
     /// 写入文件内容
-    async fn write_file(&self, path: &PathBuf, content: &str) -> Result<()>;
+    async fn write_file(&self, path: &Path, content: &str) -> Result<()>;
     
     /// 删除文件
-    async fn delete_file(&self, path: &PathBuf) -> Result<()>;
+    async fn delete_file(&self, path: &Path) -> Result<()>;
+
+    /// 移动文件，目标目录不存在时自动创建
+    async fn rename_file(&self, from: &Path, to: &Path) -> Result<()>;
     
     /// 检查文件是否存在
-    async fn file_exists(&self, path: &PathBuf) -> Result<bool>;
+    async fn file_exists(&self, path: &Path) -> Result<bool>;
     
     /// 列出目录下的所有Markdown文件
-    async fn list_markdown_files(&self, dir: &PathBuf) -> Result<Vec<PathBuf>>;
+    async fn list_markdown_files(&self, dir: &Path) -> Result<Vec<PathBuf>>;
     
     /// 获取文件元数据
-    async fn get_file_metadata(&self, path: &PathBuf) -> Result<FileMetadata>;
+    async fn get_file_metadata(&self, path: &Path) -> Result<FileMetadata>;
 }
 
 /// 文件元数据
@@ -44,18 +57,42 @@ pub struct FileMetadata {
 /// 本地文件系统存储实现
 pub struct LocalFileStorage {
     base_path: PathBuf,
+    directories: DirectoryConfig,
 }
 
 impl LocalFileStorage {
-    /// 创建新的本地存储
+    /// 创建新的本地存储，使用默认目录配置
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self::with_config(base_path, &WorkspaceConfig::default())
+    }
+
+    /// 按工作区配置创建本地存储
+    pub fn with_config(base_path: PathBuf, config: &WorkspaceConfig) -> Self {
+        Self {
+            base_path,
+            directories: config.directories.clone(),
+        }
+    }
+
+    /// 笔记目录（相对工作区）
+    pub fn notes_dir(&self) -> &Path {
+        &self.directories.notes
+    }
+
+    /// 附件目录（相对工作区）
+    pub fn assets_dir(&self) -> &Path {
+        &self.directories.assets
+    }
+
+    /// 模板目录（相对工作区）
+    pub fn templates_dir(&self) -> &Path {
+        &self.directories.templates
     }
     
     /// 获取完整路径
-    fn full_path(&self, path: &PathBuf) -> PathBuf {
+    fn full_path(&self, path: &Path) -> PathBuf {
         if path.is_absolute() {
-            path.clone()
+            path.to_path_buf()
         } else {
             self.base_path.join(path)
         }
@@ -64,13 +101,13 @@ impl LocalFileStorage {
 
 #[async_trait::async_trait]
 impl FileStorage for LocalFileStorage {
-    async fn read_file(&self, path: &PathBuf) -> Result<String> {
+    async fn read_file(&self, path: &Path) -> Result<String> {
         let full_path = self.full_path(path);
         let content = tokio::fs::read_to_string(full_path).await?;
         Ok(content)
     }
     
-    async fn write_file(&self, path: &PathBuf, content: &str) -> Result<()> {
+    async fn write_file(&self, path: &Path, content: &str) -> Result<()> {
         let full_path = self.full_path(path);
         
         // 确保父目录存在
@@ -82,25 +119,34 @@ impl FileStorage for LocalFileStorage {
         Ok(())
     }
     
-    async fn delete_file(&self, path: &PathBuf) -> Result<()> {
+    async fn delete_file(&self, path: &Path) -> Result<()> {
         let full_path = self.full_path(path);
         tokio::fs::remove_file(full_path).await?;
         Ok(())
     }
+
+    async fn rename_file(&self, from: &Path, to: &Path) -> Result<()> {
+        let to = self.full_path(to);
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::rename(self.full_path(from), to).await?;
+        Ok(())
+    }
     
-    async fn file_exists(&self, path: &PathBuf) -> Result<bool> {
+    async fn file_exists(&self, path: &Path) -> Result<bool> {
         let full_path = self.full_path(path);
         let exists = tokio::fs::try_exists(full_path).await?;
         Ok(exists)
     }
     
-    async fn list_markdown_files(&self, dir: &PathBuf) -> Result<Vec<PathBuf>> {
+    async fn list_markdown_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
         let full_dir = self.full_path(dir);
         let mut files = Vec::new();
         
         fn collect_markdown_files(
-            dir: &PathBuf, 
-            base: &PathBuf, 
+            dir: &Path, 
+            base: &Path, 
             files: &mut Vec<PathBuf>
         ) -> Result<()> {
             for entry in std::fs::read_dir(dir)? {
@@ -122,7 +168,7 @@ impl FileStorage for LocalFileStorage {
         Ok(files)
     }
     
-    async fn get_file_metadata(&self, path: &PathBuf) -> Result<FileMetadata> {
+    async fn get_file_metadata(&self, path: &Path) -> Result<FileMetadata> {
         let full_path = self.full_path(path);
         let metadata = tokio::fs::metadata(full_path).await?;
         
@@ -153,44 +199,93 @@ impl KnowledgeBase {
     }
 
     /// 从文件系统加载笔记到数据库
-    pub async fn import_note_from_file(&self, file_path: &PathBuf) -> Result<Note> {
+    pub async fn import_note_from_file(&self, file_path: &Path) -> Result<NoteRecord> {
         // 读取文件内容
         let content = self.file_storage.read_file(file_path).await?;
         
         // 解析 Markdown
         let parser = crate::parser::MarkdownParser::new();
-        let mut note = parser.parse(&content, file_path.clone())?;
+        let mut note = parser.parse(&content, file_path.to_path_buf())?;
         
         // 获取文件元数据
         let metadata = self.file_storage.get_file_metadata(file_path).await?;
-        note.file_size = metadata.size as i64;
+        note.file_size = metadata.size;
+        note.modified_at = metadata.modified.into();
+
+        // 已存在的笔记保留原有 ID 和创建时间
+        if let Some(existing) = self.note_repository.get_note_by_path(&file_path.to_string_lossy()).await? {
+            note.id = uuid::Uuid::parse_str(&existing.id)?;
+            note.created_at = existing.created_at;
+        }
         
         // 保存到数据库
-        self.note_repository.save_note(&note).await?;
+        let record = NoteRecord::from(&note);
+        self.note_repository.save_note(&record).await?;
         
-        Ok(note)
+        Ok(record)
     }
 
     /// 将笔记导出到文件系统
-    pub async fn export_note_to_file(&self, note: &Note, file_path: &PathBuf) -> Result<()> {
+    pub async fn export_note_to_file(&self, note: &NoteRecord, file_path: &Path) -> Result<()> {
         // 构建完整的 Markdown 内容（包含 frontmatter）
-        let frontmatter = note.get_frontmatter()?;
-        let yaml_content = serde_yaml::to_string(&frontmatter)?;
-        
-        let full_content = if !yaml_content.trim().is_empty() && yaml_content.trim() != "{}" {
-            format!("---\n{}\n---\n\n{}", yaml_content.trim(), note.content)
-        } else {
-            note.content.clone()
-        };
+        let note = Note::try_from(note.clone())?;
         
         // 写入文件
-        self.file_storage.write_file(file_path, &full_content).await?;
+        self.file_storage.write_file(file_path, &note.to_markdown()).await?;
         
         Ok(())
     }
 
+    /// 文件被删除后移除对应笔记，返回是否存在该笔记
+    pub async fn remove_note_by_path(&self, file_path: &Path) -> Result<bool> {
+        match self.note_repository.get_note_by_path(&file_path.to_string_lossy()).await? {
+            Some(note) => {
+                self.note_repository.purge_note(&note.id).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 文件被重命名后更新笔记路径，保留笔记 ID 和反向链接
+    ///
+    /// 旧路径没有对应笔记时按新文件导入。
+    pub async fn rename_note_path(&self, old_path: &Path, new_path: &Path) -> Result<NoteRecord> {
+        let old_key = old_path.to_string_lossy();
+        let new_key = new_path.to_string_lossy();
+
+        if let Some(mut note) = self.note_repository.get_note_by_path(&old_key).await? {
+            // 新路径上的旧记录会与唯一约束冲突，先移除
+            if let Some(existing) = self.note_repository.get_note_by_path(&new_key).await? {
+                if existing.id != note.id {
+                    self.note_repository.purge_note(&existing.id).await?;
+                }
+            }
+            note.file_path = new_key.to_string();
+            self.note_repository.save_note(&note).await?;
+        }
+
+        self.import_note_from_file(new_path).await
+    }
+
+    /// 检查文件是否存在
+    pub async fn file_exists(&self, file_path: &Path) -> Result<bool> {
+        self.file_storage.file_exists(file_path).await
+    }
+
+    /// 读取文件并计算校验和，用于识别内容相同的文件
+    pub async fn file_checksum(&self, file_path: &Path) -> Result<String> {
+        let content = self.file_storage.read_file(file_path).await?;
+        Ok(crate::models::calculate_checksum(&content))
+    }
+
+    /// 按路径获取笔记
+    pub async fn get_note_by_path(&self, file_path: &Path) -> Result<Option<NoteRecord>> {
+        self.note_repository.get_note_by_path(&file_path.to_string_lossy()).await
+    }
+
     /// 同步文件系统到数据库
-    pub async fn sync_filesystem_to_database(&self, directory: &PathBuf) -> Result<Vec<Note>> {
+    pub async fn sync_filesystem_to_database(&self, directory: &Path) -> Result<Vec<NoteRecord>> {
         let mut synchronized_notes = Vec::new();
         
         // 获取所有 Markdown 文件
@@ -230,22 +325,111 @@ impl KnowledgeBase {
     }
 
     /// 获取笔记
-    pub async fn get_note(&self, note_id: &str) -> Result<Option<Note>> {
+    pub async fn get_note(&self, note_id: &str) -> Result<Option<NoteRecord>> {
         self.note_repository.get_note_by_id(note_id).await
     }
 
     /// 保存笔记
-    pub async fn save_note(&self, note: &Note) -> Result<()> {
+    pub async fn save_note(&self, note: &NoteRecord) -> Result<()> {
         self.note_repository.save_note(note).await
     }
 
-    /// 删除笔记
-    pub async fn delete_note(&self, note_id: &str) -> Result<()> {
-        self.note_repository.delete_note(note_id).await
+    /// 将笔记移入回收站：文件移到 `.zeno/trash`，标签和链接保留，可以恢复
+    pub async fn delete_note(&self, note_id: &str) -> Result<TrashEntry> {
+        let note = self.note_repository.get_note_by_id(note_id).await?
+            .ok_or_else(|| Error::not_found(format!("笔记 {}", note_id)))?;
+        let original_path = PathBuf::from(&note.file_path);
+        let trash_path = trash_file_path(note_id, &original_path);
+
+        // 文件已不存在时只更新数据库
+        let moved = self.file_storage.file_exists(&original_path).await?;
+        if moved {
+            self.file_storage.rename_file(&original_path, &trash_path).await?;
+        }
+
+        match self.note_repository.delete_note(note_id).await {
+            Ok(entry) => Ok(entry),
+            Err(e) => {
+                if moved {
+                    self.undo_rename(&trash_path, &original_path).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// 列出回收站中的笔记，最近删除的在前
+    pub async fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        self.note_repository.list_trash().await
+    }
+
+    /// 从回收站恢复笔记到原路径
+    ///
+    /// 原路径已有文件时返回错误；回收站中的文件丢失时按数据库中的内容重新写出。
+    pub async fn restore_note(&self, note_id: &str) -> Result<TrashEntry> {
+        let entry = self.list_trash().await?
+            .into_iter()
+            .find(|entry| entry.note_id == note_id)
+            .ok_or_else(|| Error::not_found(format!("回收站中的笔记 {}", note_id)))?;
+        let original_path = PathBuf::from(&entry.original_path);
+        let trash_path = PathBuf::from(&entry.trash_path);
+
+        if self.file_storage.file_exists(&original_path).await? {
+            return Err(Error::validation(format!("{} 已被其他文件占用", entry.original_path)));
+        }
+
+        let moved = self.file_storage.file_exists(&trash_path).await?;
+        if moved {
+            self.file_storage.rename_file(&trash_path, &original_path).await?;
+        }
+
+        let entry = match self.note_repository.restore_note(note_id).await {
+            Ok(entry) => entry,
+            Err(e) => {
+                if moved {
+                    self.undo_rename(&original_path, &trash_path).await;
+                }
+                return Err(e);
+            }
+        };
+
+        if !moved {
+            if let Some(note) = self.note_repository.get_note_by_id(note_id).await? {
+                self.export_note_to_file(&note, &original_path).await?;
+            }
+        }
+        Ok(entry)
+    }
+
+    /// 彻底删除回收站中超过 `older_than_days` 天的笔记，未指定时清空回收站
+    pub async fn purge_trash(&self, older_than_days: Option<u32>) -> Result<Vec<TrashEntry>> {
+        let entries = self.note_repository.purge_trash(older_than_days).await?;
+        for entry in &entries {
+            let trash_path = Path::new(&entry.trash_path);
+            if self.file_storage.file_exists(trash_path).await? {
+                self.file_storage.delete_file(trash_path).await?;
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 按回收站设置彻底删除到期的笔记
+    pub async fn purge_expired_trash(&self, settings: &TrashSettings) -> Result<Vec<TrashEntry>> {
+        if settings.retention_days == 0 {
+            return Ok(Vec::new());
+        }
+        self.purge_trash(Some(settings.retention_days)).await
+    }
+
+    /// 数据库更新失败后把文件移回原处
+    async fn undo_rename(&self, from: &Path, to: &Path) {
+        if let Err(e) = self.file_storage.rename_file(from, to).await {
+            log::warn!("无法将 {} 移回 {}: {}", from.display(), to.display(), e);
+        }
     }
 
     /// 列出所有笔记
-    pub async fn list_notes(&self, filter: Option<NoteFilter>) -> Result<Vec<Note>> {
+    pub async fn list_notes(&self, filter: Option<NoteFilter>) -> Result<Vec<NoteRecord>> {
         self.note_repository.list_notes(filter).await
     }
 
@@ -313,4 +497,48 @@ mod tests {
         assert!(files.iter().any(|p| p.to_string_lossy().contains("test2.md")));
         assert!(files.iter().any(|p| p.to_string_lossy().contains("test3.md")));
     }
+
+    #[tokio::test]
+    async fn test_trash_restore_and_purge() {
+        let dir = tempdir().unwrap();
+        let db = crate::db::Database::new(dir.path().join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let kb = KnowledgeBase::new(
+            Box::new(LocalFileStorage::new(dir.path().to_path_buf())),
+            Box::new(SqliteNoteRepository::new(db.pool().clone())),
+        );
+
+        fs::create_dir_all(dir.path().join("notes")).unwrap();
+        fs::write(dir.path().join("notes/a.md"), "# A\n\n#tag [[B]]").unwrap();
+        fs::write(dir.path().join("notes/b.md"), "# B").unwrap();
+        let a = kb.import_note_from_file(Path::new("notes/a.md")).await.unwrap();
+        let b = kb.import_note_from_file(Path::new("notes/b.md")).await.unwrap();
+
+        // 删除：文件移入回收站，标签保留，原路径可以被新笔记使用
+        let entry = kb.delete_note(&a.id).await.unwrap();
+        assert_eq!(entry.original_path, "notes/a.md");
+        assert_eq!(PathBuf::from(&entry.trash_path), trash_file_path(&a.id, Path::new("notes/a.md")));
+        assert!(!dir.path().join("notes/a.md").exists());
+        assert!(dir.path().join(&entry.trash_path).exists());
+        assert!(kb.get_note(&a.id).await.unwrap().is_none());
+        assert_eq!(kb.get_tags().await.unwrap()[0].usage_count, 1);
+        assert!(matches!(kb.delete_note(&a.id).await, Err(Error::NotFound(_))));
+
+        // 恢复：原路径被占用时失败
+        fs::write(dir.path().join("notes/a.md"), "# Other").unwrap();
+        assert!(matches!(kb.restore_note(&a.id).await, Err(Error::Validation(_))));
+        fs::remove_file(dir.path().join("notes/a.md")).unwrap();
+        kb.restore_note(&a.id).await.unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("notes/a.md")).unwrap(), "# A\n\n#tag [[B]]");
+        assert_eq!(kb.get_note(&a.id).await.unwrap().unwrap().file_path, "notes/a.md");
+        assert!(kb.list_trash().await.unwrap().is_empty());
+
+        // 清理：未到期的保留，清空时删除文件和数据库记录
+        let entry = kb.delete_note(&b.id).await.unwrap();
+        assert!(kb.purge_expired_trash(&TrashSettings { retention_days: 30 }).await.unwrap().is_empty());
+        assert_eq!(kb.purge_trash(None).await.unwrap().len(), 1);
+        assert!(!dir.path().join(&entry.trash_path).exists());
+        assert!(kb.list_trash().await.unwrap().is_empty());
+        assert!(matches!(kb.restore_note(&b.id).await, Err(Error::NotFound(_))));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,495 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::db::models::{DateRange, SearchFilters};
+use crate::models::NoteStatus;
+
+use super::search::contains_cjk;
+
+/// 查询语法错误
+///
+/// `start` 和 `end` 是出错片段在查询中的字符偏移（从 0 开始，不含 `end`），
+/// 界面可以据此给出错的部分加下划线。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuerySyntaxError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl QuerySyntaxError {
+    fn new(message: impl Into<String>, start: usize, end: usize) -> Self {
+        Self { message: message.into(), start, end }
+    }
+}
+
+impl std::fmt::Display for QuerySyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (第 {} 列)", self.message, self.start + 1)
+    }
+}
+
+impl std::error::Error for QuerySyntaxError {}
+
+type ParseResult<T> = std::result::Result<T, QuerySyntaxError>;
+
+/// 文本条件，多个词之间是 OR 关系
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextClause {
+    pub terms: Vec<String>,
+    pub negated: bool,
+}
+
+/// 解析后的搜索查询
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedQuery {
+    /// 文本条件之间是 AND 关系
+    pub clauses: Vec<TextClause>,
+    pub filters: SearchFilters,
+}
+
+impl ParsedQuery {
+    /// 没有任何文本条件和过滤条件
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty() && self.filters.is_empty()
+    }
+
+    /// 未取反的全部搜索词，用于高亮
+    pub fn positive_terms(&self) -> Vec<String> {
+        self.clauses
+            .iter()
+            .filter(|clause| !clause.negated)
+            .flat_map(|clause| clause.terms.iter().cloned())
+            .collect()
+    }
+
+    /// 文本部分对应的 `notes_fts` 匹配表达式
+    ///
+    /// 包含中日韩字符的词无法由 unicode61 分词匹配，只有取反的条件时
+    /// FTS5 也无法表达，这两种情况返回 None。
+    pub fn fts_expression(&self) -> Option<String> {
+        if self.clauses.iter().flat_map(|c| &c.terms).any(|t| contains_cjk(t)) {
+            return None;
+        }
+
+        let group = |clause: &TextClause| {
+            let terms: Vec<String> = clause.terms.iter().map(|t| quote_fts(t)).collect();
+            format!("({})", terms.join(" OR "))
+        };
+        let positive: Vec<String> = self.clauses.iter().filter(|c| !c.negated).map(group).collect();
+        if positive.is_empty() {
+            return None;
+        }
+
+        let mut expression = format!("({})", positive.join(" AND "));
+        for clause in self.clauses.iter().filter(|c| c.negated) {
+            expression.push_str(" NOT ");
+            expression.push_str(&group(clause));
+        }
+        Some(expression)
+    }
+}
+
+/// 把词转换为 FTS5 短语
+pub fn quote_fts(term: &str) -> String {
+    format!("\"{}\"", term.replace('"', "\"\""))
+}
+
+/// 解析查询语法
+///
+/// 支持的写法：
+///
+/// - `rust 笔记`：同时包含两个词
+/// - `"exact phrase"`：短语
+/// - `rust OR go`：任一词
+/// - `-draft` / `NOT draft`：排除
+/// - `tag:project`、`category:技术`、`path:notes/`，均可用 `-` 取反
+/// - `status:draft`
+/// - `modified:>2025-01-01`、`created:<=2025-06-30`、`modified:2025-01-01..2025-02-01`
+///
+/// 其他带冒号的词（如 `TODO:`、网址）按文本搜索。
+pub fn parse_query(input: &str) -> ParseResult<ParsedQuery> {
+    let mut lexer = Lexer::new(input);
+    let mut query = ParsedQuery::default();
+    // 等待右侧词的 OR，以及尚未作用的 NOT
+    let mut pending_or: Option<Token> = None;
+    let mut pending_not: Option<Token> = None;
+
+    while let Some(token) = lexer.next_token()? {
+        match &token.kind {
+            TokenKind::Or => {
+                let joinable = query.clauses.last().is_some_and(|c| !c.negated);
+                if !joinable || pending_or.is_some() || pending_not.is_some() {
+                    return Err(token.error("OR 前缺少搜索词"));
+                }
+                pending_or = Some(token);
+            }
+            TokenKind::And => {
+                if pending_or.is_some() || pending_not.is_some() {
+                    return Err(token.error("AND 不能出现在这里"));
+                }
+            }
+            TokenKind::Not => {
+                if token.negated || pending_not.is_some() {
+                    return Err(token.error("重复的取反"));
+                }
+                pending_not = Some(token);
+            }
+            TokenKind::Text(text) => {
+                let negated = token.negated || pending_not.take().is_some();
+                if pending_or.take().is_some() {
+                    if negated {
+                        return Err(token.error("OR 连接的词不能取反"));
+                    }
+                    if let Some(clause) = query.clauses.last_mut() {
+                        clause.terms.push(text.clone());
+                    }
+                } else {
+                    query.clauses.push(TextClause { terms: vec![text.clone()], negated });
+                }
+            }
+            TokenKind::Field { name, value, value_start } => {
+                if pending_or.is_some() {
+                    return Err(token.error("OR 只能连接搜索词"));
+                }
+                let negated = token.negated || pending_not.take().is_some();
+                apply_field(&mut query.filters, &token, name, value, *value_start, negated)?;
+            }
+        }
+    }
+
+    if let Some(token) = pending_or {
+        return Err(token.error("OR 后缺少搜索词"));
+    }
+    if let Some(token) = pending_not {
+        return Err(token.error("NOT 后缺少内容"));
+    }
+
+    Ok(query)
+}
+
+fn apply_field(
+    filters: &mut SearchFilters,
+    token: &Token,
+    name: &str,
+    value: &str,
+    value_start: usize,
+    negated: bool,
+) -> ParseResult<()> {
+    let value_error = |message: String| {
+        QuerySyntaxError::new(message, value_start, value_start + value.chars().count())
+    };
+    let not_negatable = || token.error(format!("{} 不支持取反", name));
+
+    match name {
+        "tag" | "tags" => {
+            let tag = value.trim_start_matches('#').to_string();
+            if negated { filters.exclude_tags.push(tag) } else { filters.tags.push(tag) }
+        }
+        "category" | "categories" => {
+            let category = value.to_string();
+            if negated { filters.exclude_categories.push(category) } else { filters.categories.push(category) }
+        }
+        "path" => {
+            let path = value.replace('\\', "/");
+            let path = path.trim_start_matches("./").to_string();
+            if negated { filters.exclude_paths.push(path) } else { filters.paths.push(path) }
+        }
+        "status" => {
+            if negated {
+                return Err(not_negatable());
+            }
+            if filters.status.is_some() {
+                return Err(token.error("只能指定一个 status"));
+            }
+            filters.status = Some(value.parse::<NoteStatus>().map_err(value_error)?);
+        }
+        "modified" | "created" => {
+            if negated {
+                return Err(not_negatable());
+            }
+            let range = parse_date_range(value).map_err(value_error)?;
+            let slot = if name == "modified" { &mut filters.modified } else { &mut filters.created };
+            *slot = DateRange::intersect(slot.take(), Some(range));
+        }
+        _ => unreachable!("词法分析只产生已知字段: {}", name),
+    }
+    Ok(())
+}
+
+/// 支持的搜索字段
+const FIELDS: &[&str] = &["tag", "tags", "category", "categories", "path", "status", "modified", "created"];
+
+/// 解析日期条件：`>d`、`>=d`、`<d`、`<=d`、`a..b` 或单独的日期
+fn parse_date_range(value: &str) -> std::result::Result<DateRange, String> {
+    let range = if let Some((from, to)) = value.split_once("..") {
+        DateRange {
+            start: optional_date(from)?.map(|(start, _)| start),
+            end: optional_date(to)?.map(|(_, end)| end),
+        }
+    } else if let Some(rest) = value.strip_prefix(">=") {
+        DateRange { start: Some(parse_date(rest)?.0), end: None }
+    } else if let Some(rest) = value.strip_prefix('>') {
+        DateRange { start: Some(parse_date(rest)?.1), end: None }
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        DateRange { start: None, end: Some(parse_date(rest)?.1) }
+    } else if let Some(rest) = value.strip_prefix('<') {
+        DateRange { start: None, end: Some(parse_date(rest)?.0) }
+    } else {
+        let (start, end) = parse_date(value)?;
+        DateRange { start: Some(start), end: Some(end) }
+    };
+
+    if let (Some(start), Some(end)) = (range.start, range.end) {
+        if start >= end {
+            return Err("日期范围的开始不早于结束".to_string());
+        }
+    }
+    Ok(range)
+}
+
+/// 当天开始和次日开始
+type Day = (DateTime<Utc>, DateTime<Utc>);
+
+fn optional_date(value: &str) -> std::result::Result<Option<Day>, String> {
+    if value.is_empty() { Ok(None) } else { parse_date(value).map(Some) }
+}
+
+/// 解析 `YYYY-MM-DD`（UTC）
+fn parse_date(value: &str) -> std::result::Result<Day, String> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("无效的日期 `{}`，应为 YYYY-MM-DD", value))?;
+    let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    Ok((start, start + Duration::days(1)))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Text(String),
+    Field { name: String, value: String, value_start: usize },
+    Or,
+    And,
+    Not,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// 带 `-` 前缀
+    negated: bool,
+    start: usize,
+    end: usize,
+}
+
+impl Token {
+    fn error(&self, message: impl Into<String>) -> QuerySyntaxError {
+        QuerySyntaxError::new(message, self.start, self.end)
+    }
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Lexer {
+    fn new(input: &str) -> Self {
+        Self { chars: input.chars().collect(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn at_boundary(&self) -> bool {
+        self.peek().is_none_or(char::is_whitespace)
+    }
+
+    fn next_token(&mut self) -> ParseResult<Option<Token>> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        let start = self.pos;
+        let Some(first) = self.peek() else {
+            return Ok(None);
+        };
+
+        let negated = first == '-';
+        if negated {
+            self.pos += 1;
+            if self.at_boundary() {
+                return Err(QuerySyntaxError::new("取反符号后缺少内容", start, self.pos));
+            }
+        }
+
+        if self.peek() == Some('"') {
+            let text = self.read_quoted()?;
+            return Ok(Some(self.token(TokenKind::Text(text), negated, start)));
+        }
+
+        // 字段名只由 ASCII 字母组成，不是已知字段时整个词按文本处理
+        let name_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        let name = self.chars[name_start..self.pos].iter().collect::<String>().to_lowercase();
+        if self.peek() == Some(':') && FIELDS.contains(&name.as_str()) {
+            self.pos += 1;
+            let value_start = self.pos;
+            let value = if self.peek() == Some('"') { self.read_quoted()? } else { self.read_word() };
+            if value.is_empty() {
+                return Err(QuerySyntaxError::new(format!("{} 缺少值", name), start, self.pos));
+            }
+            let kind = TokenKind::Field { name, value, value_start };
+            return Ok(Some(self.token(kind, negated, start)));
+        }
+
+        self.pos = name_start;
+        let word = self.read_word();
+        let kind = match word.as_str() {
+            "OR" if !negated => TokenKind::Or,
+            "AND" if !negated => TokenKind::And,
+            "NOT" => TokenKind::Not,
+            _ => TokenKind::Text(word),
+        };
+        Ok(Some(self.token(kind, negated, start)))
+    }
+
+    fn token(&self, kind: TokenKind, negated: bool, start: usize) -> Token {
+        Token { kind, negated, start, end: self.pos }
+    }
+
+    /// 读取到空白为止
+    fn read_word(&mut self) -> String {
+        let start = self.pos;
+        while !self.at_boundary() {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// 读取引号内的短语，当前位置在开头的引号上
+    fn read_quoted(&mut self) -> ParseResult<String> {
+        let quote = self.pos;
+        self.pos += 1;
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != '"') {
+            self.pos += 1;
+        }
+        if self.peek().is_none() {
+            return Err(QuerySyntaxError::new("引号未闭合", quote, self.pos));
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        self.pos += 1;
+        Ok(text.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clause(terms: &[&str], negated: bool) -> TextClause {
+        TextClause { terms: terms.iter().map(|t| t.to_string()).collect(), negated }
+    }
+
+    fn date(value: &str) -> DateTime<Utc> {
+        parse_date(value).unwrap().0
+    }
+
+    #[test]
+    fn test_parse_full_query() {
+        let query = parse_query(
+            r#"rust tag:project status:draft modified:>2025-01-01 -tag:archive "exact phrase" path:notes/ -path:notes/tmp"#,
+        )
+        .unwrap();
+
+        assert_eq!(query.clauses, vec![clause(&["rust"], false), clause(&["exact phrase"], false)]);
+        assert_eq!(query.filters.tags, vec!["project"]);
+        assert_eq!(query.filters.exclude_tags, vec!["archive"]);
+        assert_eq!(query.filters.status, Some(NoteStatus::Draft));
+        assert_eq!(query.filters.paths, vec!["notes/"]);
+        assert_eq!(query.filters.exclude_paths, vec!["notes/tmp"]);
+        assert_eq!(query.filters.modified, Some(DateRange { start: Some(date("2025-01-02")), end: None }));
+        assert_eq!(
+            query.fts_expression().as_deref(),
+            Some(r#"(("rust") AND ("exact phrase"))"#)
+        );
+    }
+
+    #[test]
+    fn test_parse_boolean_operators() {
+        let query = parse_query("rust OR go -draft NOT wip AND test").unwrap();
+        assert_eq!(query.clauses, vec![
+            clause(&["rust", "go"], false),
+            clause(&["draft"], true),
+            clause(&["wip"], true),
+            clause(&["test"], false),
+        ]);
+        assert_eq!(
+            query.fts_expression().as_deref(),
+            Some(r#"(("rust" OR "go") AND ("test")) NOT ("draft") NOT ("wip")"#)
+        );
+        assert_eq!(query.positive_terms(), vec!["rust", "go", "test"]);
+
+        // 只有取反或包含中文时无法用单个 FTS 表达式
+        assert_eq!(parse_query("-draft").unwrap().fts_expression(), None);
+        assert_eq!(parse_query("rust 笔记").unwrap().fts_expression(), None);
+        // 加引号后的关键字按文本处理
+        assert_eq!(parse_query("\"OR\"").unwrap().clauses, vec![clause(&["OR"], false)]);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_text() {
+        let query = parse_query("TODO: fix https://example.com/a stauts:draft").unwrap();
+        assert_eq!(query.clauses, vec![
+            clause(&["TODO:"], false),
+            clause(&["fix"], false),
+            clause(&["https://example.com/a"], false),
+            clause(&["stauts:draft"], false),
+        ]);
+        assert_eq!(query.filters.status, None);
+        assert_eq!(parse_query("Tag:rust").unwrap().filters.tags, vec!["rust"]);
+    }
+
+    #[test]
+    fn test_parse_date_ranges() {
+        let range = |q: &str| parse_query(q).unwrap().filters;
+        assert_eq!(
+            range("modified:2025-03-01").modified,
+            Some(DateRange { start: Some(date("2025-03-01")), end: Some(date("2025-03-02")) })
+        );
+        assert_eq!(
+            range("created:<=2025-03-01").created,
+            Some(DateRange { start: None, end: Some(date("2025-03-02")) })
+        );
+        assert_eq!(
+            range("modified:2025-01-01..2025-01-31 modified:>=2025-01-10").modified,
+            Some(DateRange { start: Some(date("2025-01-10")), end: Some(date("2025-02-01")) })
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_positions() {
+        let error = |q: &str| parse_query(q).unwrap_err();
+
+        let e = error("status:done");
+        assert_eq!((e.start, e.end), (7, 11));
+
+        let e = error("笔记 modified:>2025-13-01");
+        assert_eq!((e.start, e.end), (12, 23));
+
+        let e = error("rust \"unclosed");
+        assert_eq!((e.start, e.end), (5, 14));
+
+        let e = error("rust OR");
+        assert_eq!((e.start, e.end), (5, 7));
+        assert_eq!(e.to_string(), "OR 后缺少搜索词 (第 6 列)");
+
+        assert_eq!(error("OR rust").start, 0);
+        assert_eq!(error("rust - go").start, 5);
+        assert_eq!(error("-status:draft").start, 0);
+        assert_eq!(error("tag:").start, 0);
+        assert_eq!(error("modified:2025-02-01..2025-01-01").start, 9);
+    }
+}
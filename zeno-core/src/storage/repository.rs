@@ -1,24 +1,46 @@
+use crate::config::HistorySettings;
 use crate::error::{Error, Result};
 use crate::db::{Database, models::*};
+use crate::links::{checked_anchor, NoteAnchors, WikiLink};
 use crate::parser::MarkdownParser;
-use sqlx::{SqlitePool, Row};
-use std::path::{Path, PathBuf};
+use super::history::{diff_lines, prune_versions, record_markdown, record_version, DiffLine};
+use super::query::parse_query;
+use super::search::{highlight_snippet, matches_any, SearchStatement};
+use super::trash::trash_file_path;
+use sqlx::{FromRow, Row, SqliteConnection, SqlitePool};
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
 use async_trait::async_trait;
 
+/// 搜索历史保留的最大条数
+const SEARCH_HISTORY_LIMIT: i64 = 1000;
+
+/// 查询回收站条目
+const TRASH_ENTRY_SELECT: &str = r#"
+    SELECT t.note_id, n.title, t.original_path, n.file_path AS trash_path, t.deleted_at
+    FROM trash t JOIN notes n ON n.id = t.note_id
+"#;
+
 /// 笔记仓库接口
 #[async_trait]
 pub trait NoteRepository: Send + Sync {
     // 笔记 CRUD 操作
-    async fn get_note_by_id(&self, id: &str) -> Result<Option<Note>>;
-    async fn get_note_by_path(&self, path: &str) -> Result<Option<Note>>;
-    async fn save_note(&self, note: &Note) -> Result<()>;
-    async fn delete_note(&self, id: &str) -> Result<()>;
-    async fn list_notes(&self, filter: Option<NoteFilter>) -> Result<Vec<Note>>;
+    async fn get_note_by_id(&self, id: &str) -> Result<Option<NoteRecord>>;
+    async fn get_note_by_path(&self, path: &str) -> Result<Option<NoteRecord>>;
+    async fn save_note(&self, note: &NoteRecord) -> Result<()>;
+    async fn delete_note(&self, id: &str) -> Result<TrashEntry>;
+    async fn purge_note(&self, id: &str) -> Result<()>;
+    async fn list_notes(&self, filter: Option<NoteFilter>) -> Result<Vec<NoteRecord>>;
+
+    // 回收站
+    async fn list_trash(&self) -> Result<Vec<TrashEntry>>;
+    async fn restore_note(&self, id: &str) -> Result<TrashEntry>;
+    async fn purge_trash(&self, older_than_days: Option<u32>) -> Result<Vec<TrashEntry>>;
     
     // 标签操作
     async fn get_all_tags(&self) -> Result<Vec<Tag>>;
-    async fn get_notes_by_tag(&self, tag_name: &str) -> Result<Vec<Note>>;
+    async fn get_notes_by_tag(&self, tag_name: &str) -> Result<Vec<NoteRecord>>;
     async fn create_tag(&self, tag: &Tag) -> Result<i64>;
     async fn update_tag(&self, tag: &Tag) -> Result<()>;
     async fn delete_tag(&self, tag_id: i64) -> Result<()>;
@@ -26,7 +48,7 @@ pub trait NoteRepository: Send + Sync {
     // 分类操作
     async fn get_all_categories(&self) -> Result<Vec<Category>>;
     async fn get_category_tree(&self) -> Result<Vec<TreeNode>>;
-    async fn get_notes_by_category(&self, category_id: i64) -> Result<Vec<Note>>;
+    async fn get_notes_by_category(&self, category_id: i64) -> Result<Vec<NoteRecord>>;
     async fn create_category(&self, category: &Category) -> Result<i64>;
     async fn update_category(&self, category: &Category) -> Result<()>;
     async fn delete_category(&self, category_id: i64) -> Result<()>;
@@ -39,7 +61,21 @@ pub trait NoteRepository: Send + Sync {
     
     // 搜索操作
     async fn search_notes(&self, query: &SearchQuery) -> Result<Vec<SearchResult>>;
-    async fn get_related_notes(&self, note_id: &str, limit: Option<usize>) -> Result<Vec<Note>>;
+    async fn get_related_notes(&self, note_id: &str, limit: Option<usize>) -> Result<Vec<NoteRecord>>;
+
+    // 搜索历史与保存的搜索
+    async fn get_search_history(&self, limit: usize) -> Result<Vec<SearchHistoryEntry>>;
+    async fn clear_search_history(&self) -> Result<u64>;
+    async fn get_saved_searches(&self) -> Result<Vec<SavedSearch>>;
+    async fn save_search(&self, name: &str, query: &str) -> Result<SavedSearch>;
+    async fn delete_saved_search(&self, name: &str) -> Result<bool>;
+    async fn run_saved_search(&self, name: &str, limit: Option<usize>) -> Result<Vec<SearchResult>>;
+
+    // 版本历史
+    async fn get_note_versions(&self, note_id: &str) -> Result<Vec<NoteVersion>>;
+    async fn get_note_version(&self, note_id: &str, version: i64) -> Result<Option<NoteVersion>>;
+    async fn diff_note_versions(&self, note_id: &str, from: i64, to: Option<i64>) -> Result<Vec<DiffLine>>;
+    async fn prune_note_versions(&self) -> Result<u64>;
     
     // 统计操作
     async fn get_statistics(&self) -> Result<Statistics>;
@@ -59,25 +95,133 @@ pub struct NoteFilter {
 /// SQLite 笔记仓库实现
 pub struct SqliteNoteRepository {
     pool: SqlitePool,
-    parser: MarkdownParser,
+    history: HistorySettings,
 }
 
 impl SqliteNoteRepository {
     pub fn new(pool: SqlitePool) -> Self {
-        Self {
-            pool,
-            parser: MarkdownParser::new(),
+        Self { pool, history: HistorySettings::default() }
+    }
+
+    /// 设置版本历史的保留策略
+    pub fn with_history(mut self, history: HistorySettings) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// 获取连接池
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// 版本历史的保留策略
+    pub fn history(&self) -> &HistorySettings {
+        &self.history
+    }
+
+    /// 按路径查找有历史版本的笔记 ID，笔记已删除时从版本历史中查找
+    pub async fn find_versioned_note(&self, path: &str) -> Result<Option<String>> {
+        let id = sqlx::query_scalar(
+            r#"
+            SELECT id FROM (
+                SELECT id, 0 AS priority, 0 AS version FROM notes WHERE file_path = ?
+                UNION ALL
+                SELECT note_id, 1, version FROM note_versions WHERE file_path = ?
+            )
+            ORDER BY priority, version DESC
+            LIMIT 1
+            "#
+        )
+        .bind(path)
+        .bind(path)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// 获取笔记详情（包含标签、分类和双向链接）
+    pub async fn get_note_detail(&self, id: &str) -> Result<Option<NoteDetail>> {
+        match self.get_note_by_id(id).await? {
+            Some(note) => Ok(Some(self.build_note_with_relations(note).await?)),
+            None => Ok(None),
         }
     }
 
     /// 从数据库结果构建笔记详情
-    async fn build_note_with_relations(&self, note: Note) -> Result<(Note, Vec<Tag>, Vec<Category>, Vec<Link>, Vec<Link>)> {
+    async fn build_note_with_relations(&self, note: NoteRecord) -> Result<NoteDetail> {
         let tags = self.get_tags_for_note(&note.id).await?;
         let categories = self.get_categories_for_note(&note.id).await?;
         let outbound_links = self.get_outbound_links(&note.id).await?;
         let inbound_links = self.get_inbound_links(&note.id).await?;
 
-        Ok((note, tags, categories, outbound_links, inbound_links))
+        Ok(NoteDetail { note, tags, categories, outbound_links, inbound_links })
+    }
+
+    /// 执行搜索，不记录历史
+    async fn execute_search(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
+        let mut parsed = parse_query(&query.query)?;
+        parsed.filters.merge(query.filters.clone());
+        if parsed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let statement = SearchStatement::build(&parsed, query.limit.unwrap_or(50), query.offset.unwrap_or(0));
+        let mut sql = sqlx::query(&statement.sql);
+        for value in &statement.binds {
+            sql = sql.bind(value);
+        }
+        let rows = sql.fetch_all(&self.pool).await?;
+
+        let terms = parsed.positive_terms();
+        let mut results = Vec::new();
+        for row in rows {
+            let note = NoteRecord::from_row(&row)?;
+            let score: f64 = row.get("score");
+
+            let mut matched_fields = Vec::new();
+            for field in ["title", "content", "tags", "categories"] {
+                let value: String = row.get(field);
+                if matches_any(&value, &terms) {
+                    matched_fields.push(field.to_string());
+                }
+            }
+
+            // FTS 摘要只覆盖正文中能被 unicode61 匹配的词
+            let snippet: Option<String> = row.get("highlight");
+            let highlights = snippet
+                .filter(|snippet| snippet.contains("<mark>"))
+                .or_else(|| highlight_snippet(&note.content, &terms, 64))
+                .or_else(|| highlight_snippet(&note.title, &terms, 64))
+                .into_iter()
+                .collect();
+
+            results.push(SearchResult {
+                note,
+                score: score as f32,
+                highlights,
+                matched_fields,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// 记录一次搜索，只保留最近的 `SEARCH_HISTORY_LIMIT` 条
+    async fn record_search(&self, query: &str, result_count: usize, search_time: f64) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("INSERT INTO search_history (query, result_count, search_time) VALUES (?, ?, ?)")
+            .bind(query.trim())
+            .bind(result_count as i64)
+            .bind(search_time)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM search_history WHERE id <= (SELECT MAX(id) FROM search_history) - ?")
+            .bind(SEARCH_HISTORY_LIMIT)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
     }
 
     /// 获取笔记的标签
@@ -114,15 +258,12 @@ impl SqliteNoteRepository {
         Ok(categories)
     }
 
-    /// 更新笔记标签关联
-    async fn update_note_tags(&self, note_id: &str, tag_names: &[String]) -> Result<()> {
-        // 开始事务
-        let mut tx = self.pool.begin().await?;
-
+    /// 更新笔记标签关联（在调用方的事务中执行）
+    pub(crate) async fn update_note_tags(conn: &mut SqliteConnection, note_id: &str, tag_names: &[String]) -> Result<()> {
         // 删除现有关联
         sqlx::query("DELETE FROM note_tags WHERE note_id = ?")
             .bind(note_id)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
 
         // 创建或获取标签并建立关联
@@ -132,7 +273,7 @@ impl SqliteNoteRepository {
                 "SELECT id FROM tags WHERE name = ?"
             )
             .bind(tag_name)
-            .fetch_optional(&mut *tx)
+            .fetch_optional(&mut *conn)
             .await?;
 
             let tag_id = if let Some(id) = tag_id {
@@ -143,64 +284,66 @@ impl SqliteNoteRepository {
                     "INSERT INTO tags (name) VALUES (?) RETURNING id"
                 )
                 .bind(tag_name)
-                .fetch_one(&mut *tx)
+                .fetch_one(&mut *conn)
                 .await?
             };
 
             // 建立关联
             sqlx::query(
-                "INSERT INTO note_tags (note_id, tag_id) VALUES (?, ?)"
+                "INSERT OR IGNORE INTO note_tags (note_id, tag_id) VALUES (?, ?)"
             )
             .bind(note_id)
             .bind(tag_id)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         }
 
-        tx.commit().await?;
         Ok(())
     }
 
-    /// 更新笔记分类关联
-    async fn update_note_categories(&self, note_id: &str, category_ids: &[i64]) -> Result<()> {
-        let mut tx = self.pool.begin().await?;
-
+    /// 按名称更新笔记分类关联，不存在的分类会被创建（在调用方的事务中执行）
+    pub(crate) async fn update_note_categories(conn: &mut SqliteConnection, note_id: &str, category_names: &[String]) -> Result<()> {
         // 删除现有关联
         sqlx::query("DELETE FROM note_categories WHERE note_id = ?")
             .bind(note_id)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
 
         // 建立新关联
-        for category_id in category_ids {
+        for category_name in category_names {
+            sqlx::query("INSERT OR IGNORE INTO categories (name) VALUES (?)")
+                .bind(category_name)
+                .execute(&mut *conn)
+                .await?;
+
             sqlx::query(
-                "INSERT INTO note_categories (note_id, category_id) VALUES (?, ?)"
+                r#"
+                INSERT OR IGNORE INTO note_categories (note_id, category_id)
+                SELECT ?, id FROM categories WHERE name = ?
+                "#
             )
             .bind(note_id)
-            .bind(category_id)
-            .execute(&mut *tx)
+            .bind(category_name)
+            .execute(&mut *conn)
             .await?;
         }
 
-        tx.commit().await?;
         Ok(())
     }
 
-    /// 更新笔记链接
-    async fn update_note_links(&self, note_id: &str, links: &[Link]) -> Result<()> {
-        let mut tx = self.pool.begin().await?;
-
+    /// 更新笔记的出链（在调用方的事务中执行）
+    pub(crate) async fn update_note_links(conn: &mut SqliteConnection, note_id: &str, links: &[Link]) -> Result<()> {
         // 删除现有链接
         sqlx::query("DELETE FROM links WHERE source_id = ?")
             .bind(note_id)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
 
         // 插入新链接
         for link in links {
             sqlx::query(
                 r#"
-                INSERT INTO links (source_id, target_id, link_type, anchor_text, source_line, source_column)
+                INSERT OR IGNORE INTO links (source_id, target_id, link_type, anchor_text, source_line, source_column)
                 VALUES (?, ?, ?, ?, ?, ?)
                 "#
             )
@@ -210,19 +353,84 @@ impl SqliteNoteRepository {
             .bind(&link.anchor_text)
             .bind(link.source_line)
             .bind(link.source_column)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// 更新笔记中指向笔记的链接及其解析到的笔记（在调用方的事务中执行），解析不到的链接也保存
+    pub(crate) async fn update_link_refs(
+        conn: &mut SqliteConnection,
+        note_id: &str,
+        refs: &[(WikiLink, Option<String>)],
+    ) -> Result<()> {
+        sqlx::query("DELETE FROM link_refs WHERE source_id = ?")
+            .bind(note_id)
+            .execute(&mut *conn)
+            .await?;
+
+        for (link, target_id) in refs {
+            let anchor = checked_anchor(link);
+            sqlx::query(
+                r#"
+                INSERT INTO link_refs (
+                    source_id, target_id, raw, target, alias, anchor, is_embed,
+                    range_start, range_end, line_number, anchor_kind, anchor_key, anchor_slug
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#
+            )
+            .bind(note_id)
+            .bind(target_id)
+            .bind(&link.raw)
+            .bind(&link.target)
+            .bind(&link.alias)
+            .bind(&link.anchor)
+            .bind(link.is_embed)
+            .bind(link.range.start as i64)
+            .bind(link.range.end as i64)
+            .bind(link.line_number as i64)
+            .bind(anchor.as_ref().map(|(kind, _, _)| kind.as_str()))
+            .bind(anchor.as_ref().map(|(_, key, _)| key.as_str()).unwrap_or_default())
+            .bind(anchor.as_ref().map(|(_, _, slug)| slug.as_str()).unwrap_or_default())
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// 更新笔记中的标题和块ID（在调用方的事务中执行）
+    pub(crate) async fn update_note_anchors(conn: &mut SqliteConnection, note_id: &str, anchors: &NoteAnchors) -> Result<()> {
+        sqlx::query("DELETE FROM note_anchors WHERE note_id = ?")
+            .bind(note_id)
+            .execute(&mut *conn)
+            .await?;
+
+        for anchor in anchors.iter() {
+            let (key, slug) = anchor.keys();
+            sqlx::query(
+                "INSERT INTO note_anchors (note_id, kind, name, anchor_key, anchor_slug, line) VALUES (?, ?, ?, ?, ?, ?)"
+            )
+            .bind(note_id)
+            .bind(anchor.kind.as_str())
+            .bind(&anchor.name)
+            .bind(key)
+            .bind(slug)
+            .bind(anchor.line as i64)
+            .execute(&mut *conn)
             .await?;
         }
 
-        tx.commit().await?;
         Ok(())
     }
 }
 
 #[async_trait]
 impl NoteRepository for SqliteNoteRepository {
-    async fn get_note_by_id(&self, id: &str) -> Result<Option<Note>> {
-        let note = sqlx::query_as::<_, Note>(
+    async fn get_note_by_id(&self, id: &str) -> Result<Option<NoteRecord>> {
+        let note = sqlx::query_as::<_, NoteRecord>(
             "SELECT * FROM notes WHERE id = ? AND status != 'deleted'"
         )
         .bind(id)
@@ -232,8 +440,8 @@ impl NoteRepository for SqliteNoteRepository {
         Ok(note)
     }
 
-    async fn get_note_by_path(&self, path: &str) -> Result<Option<Note>> {
-        let note = sqlx::query_as::<_, Note>(
+    async fn get_note_by_path(&self, path: &str) -> Result<Option<NoteRecord>> {
+        let note = sqlx::query_as::<_, NoteRecord>(
             "SELECT * FROM notes WHERE file_path = ? AND status != 'deleted'"
         )
         .bind(path)
@@ -243,88 +451,184 @@ impl NoteRepository for SqliteNoteRepository {
         Ok(note)
     }
 
-    async fn save_note(&self, note: &Note) -> Result<()> {
-        // 检查笔记是否已存在
-        let existing = self.get_note_by_id(&note.id).await?;
-        
-        if existing.is_some() {
-            // 更新现有笔记
-            sqlx::query(
-                r#"
-                UPDATE notes SET
-                    title = ?, file_path = ?, content = ?, html_content = ?,
-                    word_count = ?, reading_time = ?, modified_at = CURRENT_TIMESTAMP,
-                    status = ?, frontmatter = ?, file_size = ?, file_hash = ?
-                WHERE id = ?
-                "#
-            )
-            .bind(&note.title)
-            .bind(&note.file_path)
-            .bind(&note.content)
-            .bind(&note.html_content)
-            .bind(note.word_count)
-            .bind(note.reading_time)
-            .bind(&note.status)
-            .bind(&note.frontmatter)
-            .bind(note.file_size)
-            .bind(&note.file_hash)
-            .bind(&note.id)
-            .execute(&self.pool)
+    async fn save_note(&self, note: &NoteRecord) -> Result<()> {
+        // 笔记、标签和分类在同一事务中写入，全文索引由触发器同步
+        let mut tx = self.pool.begin().await?;
+
+        // 覆盖前保存旧内容
+        record_version(&mut tx, note, &self.history).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO notes (
+                id, title, file_path, content, html_content, word_count, reading_time,
+                created_at, modified_at, status, frontmatter, file_size, file_hash, source
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                file_path = excluded.file_path,
+                content = excluded.content,
+                html_content = excluded.html_content,
+                word_count = excluded.word_count,
+                reading_time = excluded.reading_time,
+                modified_at = CURRENT_TIMESTAMP,
+                status = excluded.status,
+                frontmatter = excluded.frontmatter,
+                file_size = excluded.file_size,
+                file_hash = excluded.file_hash,
+                source = excluded.source
+            "#
+        )
+        .bind(&note.id)
+        .bind(&note.title)
+        .bind(&note.file_path)
+        .bind(&note.content)
+        .bind(&note.html_content)
+        .bind(note.word_count)
+        .bind(note.reading_time)
+        .bind(note.created_at)
+        .bind(note.modified_at)
+        .bind(&note.status)
+        .bind(&note.frontmatter)
+        .bind(note.file_size)
+        .bind(&note.file_hash)
+        .bind(&note.source)
+        .execute(&mut *tx)
+        .await?;
+
+        // 解析 frontmatter 并更新关联数据
+        let frontmatter = note.get_frontmatter().unwrap_or_default();
+        let tags = collect_tags(&frontmatter, &note.content);
+        Self::update_note_tags(&mut tx, &note.id, &tags).await?;
+        Self::update_note_categories(&mut tx, &note.id, &frontmatter.categories).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_note(&self, id: &str) -> Result<TrashEntry> {
+        // 移入回收站：状态设置为 deleted，路径指向回收站中的文件，标签和链接保留
+        let mut tx = self.pool.begin().await?;
+
+        let note: Option<(String, String)> = sqlx::query_as(
+            "SELECT file_path, status FROM notes WHERE id = ? AND id NOT IN (SELECT note_id FROM trash)"
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (original_path, status) = note.ok_or_else(|| Error::not_found(format!("笔记 {}", id)))?;
+
+        let trash_path = trash_file_path(id, Path::new(&original_path));
+        sqlx::query(
+            "UPDATE notes SET status = 'deleted', file_path = ?, modified_at = CURRENT_TIMESTAMP WHERE id = ?"
+        )
+        .bind(trash_path.to_string_lossy().to_string())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("INSERT INTO trash (note_id, original_path, previous_status) VALUES (?, ?, ?)")
+            .bind(id)
+            .bind(&original_path)
+            .bind(&status)
+            .execute(&mut *tx)
             .await?;
-        } else {
-            // 插入新笔记
-            sqlx::query(
-                r#"
-                INSERT INTO notes (
-                    id, title, file_path, content, html_content, word_count, reading_time,
-                    created_at, modified_at, status, frontmatter, file_size, file_hash
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-                "#
-            )
-            .bind(&note.id)
-            .bind(&note.title)
-            .bind(&note.file_path)
-            .bind(&note.content)
-            .bind(&note.html_content)
-            .bind(note.word_count)
-            .bind(note.reading_time)
-            .bind(note.created_at)
-            .bind(note.modified_at)
-            .bind(&note.status)
-            .bind(&note.frontmatter)
-            .bind(note.file_size)
-            .bind(&note.file_hash)
-            .execute(&self.pool)
+
+        let entry = sqlx::query_as::<_, TrashEntry>(&format!("{} WHERE t.note_id = ?", TRASH_ENTRY_SELECT))
+            .bind(id)
+            .fetch_one(&mut *tx)
             .await?;
-        }
 
-        // 解析 frontmatter 并更新关联数据
-        if let Ok(frontmatter) = note.get_frontmatter() {
-            // 更新标签
-            if !frontmatter.tags.is_empty() {
-                self.update_note_tags(&note.id, &frontmatter.tags).await?;
-            }
+        tx.commit().await?;
+        Ok(entry)
+    }
 
-            // 更新分类（这里简化处理，实际需要根据分类名称查找ID）
-            // TODO: 实现分类名称到ID的映射
-        }
+    async fn purge_note(&self, id: &str) -> Result<()> {
+        // 物理删除：标签、分类和链接随外键级联删除
+        sqlx::query("DELETE FROM notes WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
 
         Ok(())
     }
 
-    async fn delete_note(&self, id: &str) -> Result<()> {
-        // 软删除：将状态设置为 deleted
+    async fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        let entries = sqlx::query_as::<_, TrashEntry>(&format!("{} ORDER BY t.deleted_at DESC, n.title", TRASH_ENTRY_SELECT))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(entries)
+    }
+
+    async fn restore_note(&self, id: &str) -> Result<TrashEntry> {
+        let mut tx = self.pool.begin().await?;
+
+        let entry = sqlx::query_as::<_, TrashEntry>(&format!("{} WHERE t.note_id = ?", TRASH_ENTRY_SELECT))
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| Error::not_found(format!("回收站中的笔记 {}", id)))?;
+
+        let occupied: bool = sqlx::query_scalar("SELECT COUNT(*) > 0 FROM notes WHERE file_path = ?")
+            .bind(&entry.original_path)
+            .fetch_one(&mut *tx)
+            .await?;
+        if occupied {
+            return Err(Error::validation(format!("{} 已被其他笔记占用", entry.original_path)));
+        }
+
         sqlx::query(
-            "UPDATE notes SET status = 'deleted', modified_at = CURRENT_TIMESTAMP WHERE id = ?"
+            r#"
+            UPDATE notes SET
+                status = (SELECT previous_status FROM trash WHERE note_id = ?),
+                file_path = ?,
+                modified_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#
         )
         .bind(id)
-        .execute(&self.pool)
+        .bind(&entry.original_path)
+        .bind(id)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(())
+        sqlx::query("DELETE FROM trash WHERE note_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(entry)
     }
 
-    async fn list_notes(&self, filter: Option<NoteFilter>) -> Result<Vec<Note>> {
+    async fn purge_trash(&self, older_than_days: Option<u32>) -> Result<Vec<TrashEntry>> {
+        let mut tx = self.pool.begin().await?;
+
+        // 未指定天数时清空回收站
+        let cutoff = older_than_days.map(|days| format!("-{} days", days));
+        let entries = sqlx::query_as::<_, TrashEntry>(&format!(
+            "{} WHERE ? IS NULL OR t.deleted_at < datetime('now', ?) ORDER BY t.deleted_at",
+            TRASH_ENTRY_SELECT
+        ))
+        .bind(&cutoff)
+        .bind(&cutoff)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // 标签、链接和回收站记录随外键级联删除
+        for entry in &entries {
+            sqlx::query("DELETE FROM notes WHERE id = ?")
+                .bind(&entry.note_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(entries)
+    }
+
+    async fn list_notes(&self, filter: Option<NoteFilter>) -> Result<Vec<NoteRecord>> {
         let filter = filter.unwrap_or_default();
         
         let mut query = "SELECT * FROM notes WHERE status != 'deleted'".to_string();
@@ -350,7 +654,7 @@ impl NoteRepository for SqliteNoteRepository {
             }
         }
 
-        let mut sql_query = sqlx::query_as::<_, Note>(&query);
+        let mut sql_query = sqlx::query_as::<_, NoteRecord>(&query);
         for binding in bindings {
             sql_query = sql_query.bind(binding);
         }
@@ -369,8 +673,8 @@ impl NoteRepository for SqliteNoteRepository {
         Ok(tags)
     }
 
-    async fn get_notes_by_tag(&self, tag_name: &str) -> Result<Vec<Note>> {
-        let notes = sqlx::query_as::<_, Note>(
+    async fn get_notes_by_tag(&self, tag_name: &str) -> Result<Vec<NoteRecord>> {
+        let notes = sqlx::query_as::<_, NoteRecord>(
             r#"
             SELECT n.* FROM notes n
             JOIN note_tags nt ON n.id = nt.note_id
@@ -448,7 +752,6 @@ impl NoteRepository for SqliteNoteRepository {
 
     async fn get_category_tree(&self) -> Result<Vec<TreeNode>> {
         let categories = self.get_all_categories().await?;
-        let mut tree = Vec::new();
         let mut category_map: HashMap<i64, Vec<Category>> = HashMap::new();
 
         // 按父ID分组
@@ -477,12 +780,11 @@ impl NoteRepository for SqliteNoteRepository {
                 .unwrap_or_default()
         }
 
-        tree = build_tree_nodes(0, &category_map);
-        Ok(tree)
+        Ok(build_tree_nodes(0, &category_map))
     }
 
-    async fn get_notes_by_category(&self, category_id: i64) -> Result<Vec<Note>> {
-        let notes = sqlx::query_as::<_, Note>(
+    async fn get_notes_by_category(&self, category_id: i64) -> Result<Vec<NoteRecord>> {
+        let notes = sqlx::query_as::<_, NoteRecord>(
             r#"
             SELECT n.* FROM notes n
             JOIN note_categories nc ON n.id = nc.note_id
@@ -616,64 +918,22 @@ impl NoteRepository for SqliteNoteRepository {
     }
 
     async fn search_notes(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
-        // 使用 FTS5 进行全文搜索
-        let search_sql = r#"
-            SELECT n.*, 
-                   bm25(notes_fts) as score,
-                   snippet(notes_fts, 1, '<mark>', '</mark>', '...', 32) as highlight
-            FROM notes_fts
-            JOIN notes n ON notes_fts.rowid = n.rowid
-            WHERE notes_fts MATCH ? AND n.status != 'deleted'
-            ORDER BY bm25(notes_fts)
-            LIMIT ?
-        "#;
-
-        let limit = query.limit.unwrap_or(50);
-        let rows = sqlx::query(search_sql)
-            .bind(&query.query)
-            .bind(limit as i64)
-            .fetch_all(&self.pool)
-            .await?;
-
-        let mut results = Vec::new();
-        for row in rows {
-            let note = Note {
-                id: row.get("id"),
-                title: row.get("title"),
-                file_path: row.get("file_path"),
-                content: row.get("content"),
-                html_content: row.get("html_content"),
-                word_count: row.get("word_count"),
-                reading_time: row.get("reading_time"),
-                created_at: row.get("created_at"),
-                modified_at: row.get("modified_at"),
-                indexed_at: row.get("indexed_at"),
-                status: row.get("status"),
-                frontmatter: row.get("frontmatter"),
-                file_size: row.get("file_size"),
-                file_hash: row.get("file_hash"),
-                search_vector: row.get("search_vector"),
-            };
-
-            let score: f64 = row.get("score");
-            let highlight: String = row.get("highlight");
-
-            results.push(SearchResult {
-                note,
-                score: score as f32,
-                highlights: vec![highlight],
-                matched_fields: vec!["content".to_string()],
-            });
+        let started = Instant::now();
+        let results = self.execute_search(query).await?;
+        // 搜索历史只是附带记录，写入失败不影响搜索结果
+        if !query.query.trim().is_empty() {
+            if let Err(e) = self.record_search(&query.query, results.len(), started.elapsed().as_secs_f64()).await {
+                log::warn!("无法记录搜索历史: {}", e);
+            }
         }
-
         Ok(results)
     }
 
-    async fn get_related_notes(&self, note_id: &str, limit: Option<usize>) -> Result<Vec<Note>> {
+    async fn get_related_notes(&self, note_id: &str, limit: Option<usize>) -> Result<Vec<NoteRecord>> {
         let limit = limit.unwrap_or(10);
         
         // 通过标签和链接查找相关笔记
-        let notes = sqlx::query_as::<_, Note>(
+        let notes = sqlx::query_as::<_, NoteRecord>(
             r#"
             SELECT DISTINCT n.*, 
                    (CASE WHEN l.target_id IS NOT NULL THEN 3 ELSE 0 END +
@@ -707,6 +967,136 @@ impl NoteRepository for SqliteNoteRepository {
         Ok(notes)
     }
 
+    async fn get_search_history(&self, limit: usize) -> Result<Vec<SearchHistoryEntry>> {
+        // 同一查询只保留最近一次
+        let entries = sqlx::query_as::<_, SearchHistoryEntry>(
+            r#"
+            SELECT * FROM search_history
+            WHERE id IN (SELECT MAX(id) FROM search_history GROUP BY query)
+            ORDER BY id DESC
+            LIMIT ?
+            "#
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn clear_search_history(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM search_history")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_saved_searches(&self) -> Result<Vec<SavedSearch>> {
+        let searches = sqlx::query_as::<_, SavedSearch>("SELECT * FROM saved_searches ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(searches)
+    }
+
+    async fn save_search(&self, name: &str, query: &str) -> Result<SavedSearch> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::validation("搜索名称不能为空"));
+        }
+        // 保存前检查语法，避免运行时才报错
+        parse_query(query)?;
+
+        let search = sqlx::query_as::<_, SavedSearch>(
+            r#"
+            INSERT INTO saved_searches (name, query) VALUES (?, ?)
+            ON CONFLICT(name) DO UPDATE SET
+                query = excluded.query,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#
+        )
+        .bind(name)
+        .bind(query.trim())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(search)
+    }
+
+    async fn delete_saved_search(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE name = ?")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn run_saved_search(&self, name: &str, limit: Option<usize>) -> Result<Vec<SearchResult>> {
+        let query: Option<String> = sqlx::query_scalar("SELECT query FROM saved_searches WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        let query = query.ok_or_else(|| Error::not_found(format!("保存的搜索: {}", name)))?;
+
+        self.execute_search(&SearchQuery {
+            query,
+            limit,
+            offset: None,
+            filters: SearchFilters::default(),
+        })
+        .await
+    }
+
+    async fn get_note_versions(&self, note_id: &str) -> Result<Vec<NoteVersion>> {
+        let versions = sqlx::query_as::<_, NoteVersion>(
+            "SELECT * FROM note_versions WHERE note_id = ? ORDER BY version DESC"
+        )
+        .bind(note_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(versions)
+    }
+
+    async fn get_note_version(&self, note_id: &str, version: i64) -> Result<Option<NoteVersion>> {
+        let version = sqlx::query_as::<_, NoteVersion>(
+            "SELECT * FROM note_versions WHERE note_id = ? AND version = ?"
+        )
+        .bind(note_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(version)
+    }
+
+    async fn diff_note_versions(&self, note_id: &str, from: i64, to: Option<i64>) -> Result<Vec<DiffLine>> {
+        let load = |version: i64| async move {
+            self.get_note_version(note_id, version)
+                .await?
+                .map(|v| v.content)
+                .ok_or_else(|| Error::not_found(format!("笔记 {} 的版本 {}", note_id, version)))
+        };
+
+        let old = load(from).await?;
+        // 未指定目标版本时与当前内容比较
+        let new = match to {
+            Some(to) => load(to).await?,
+            None => {
+                let note = self.get_note_by_id(note_id).await?
+                    .ok_or_else(|| Error::not_found(format!("笔记: {}", note_id)))?;
+                record_markdown(&note)
+            }
+        };
+
+        Ok(diff_lines(&old, &new))
+    }
+
+    async fn prune_note_versions(&self) -> Result<u64> {
+        let mut conn = self.pool.acquire().await?;
+        prune_versions(&mut conn, None, &self.history).await
+    }
+
     async fn get_statistics(&self) -> Result<Statistics> {
         // 这里重用 Database 的 get_statistics 方法
         let db = Database::from_pool(self.pool.clone());
@@ -714,13 +1104,27 @@ impl NoteRepository for SqliteNoteRepository {
     }
 }
 
+/// 笔记的全部标签：前言中的标签在前，正文中的 `#标签` 在后
+pub(crate) fn collect_tags(frontmatter: &Frontmatter, content: &str) -> Vec<String> {
+    let mut tags = frontmatter.tags.clone();
+    if let Ok(parsed) = MarkdownParser::new().parse_content(content) {
+        for tag in parsed.tags {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    tags
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use crate::db::create_connection_pool;
 
-    async fn setup_test_db() -> SqlitePool {
+    // 返回 TempDir 以保证测试期间数据库文件不被删除
+    async fn setup_test_db() -> (TempDir, SqlitePool) {
         let dir = tempdir().unwrap();
         let db_path = dir.path().join("test.db");
         let db_url = format!("sqlite:{}", db_path.display());
@@ -729,15 +1133,15 @@ mod tests {
         let db = Database::new(&db_path).await.unwrap();
         db.initialize().await.unwrap();
         
-        pool
+        (dir, pool)
     }
 
     #[tokio::test]
     async fn test_note_crud() {
-        let pool = setup_test_db().await;
+        let (_dir, pool) = setup_test_db().await;
         let repo = SqliteNoteRepository::new(pool);
 
-        let mut note = Note::new("测试笔记".to_string(), "test.md".to_string(), "# 测试\n\n内容".to_string());
+        let mut note = NoteRecord::new("测试笔记".to_string(), "test.md".to_string(), "# 测试\n\n内容".to_string());
         
         // 创建笔记
         repo.save_note(&note).await.unwrap();
@@ -761,7 +1165,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_tag_operations() {
-        let pool = setup_test_db().await;
+        let (_dir, pool) = setup_test_db().await;
         let repo = SqliteNoteRepository::new(pool);
 
         let tag = Tag::new("测试标签".to_string());
@@ -777,11 +1181,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_search() {
-        let pool = setup_test_db().await;
+        let (_dir, pool) = setup_test_db().await;
         let repo = SqliteNoteRepository::new(pool);
 
         // 创建测试笔记
-        let note = Note::new("搜索测试".to_string(), "search_test.md".to_string(), "这是一个用于搜索的测试笔记".to_string());
+        let note = NoteRecord::new("搜索测试".to_string(), "search_test.md".to_string(), "这是一个用于搜索的测试笔记".to_string());
         repo.save_note(&note).await.unwrap();
 
         // 搜索
@@ -796,4 +1200,263 @@ mod tests {
         assert!(!results.is_empty());
         assert!(results[0].note.title.contains("搜索"));
     }
-}
\ No newline at end of file
+
+    fn query(text: &str) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
+            limit: Some(10),
+            offset: None,
+            filters: SearchFilters::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_sees_edit_after_save() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+
+        let mut note = NoteRecord::new("Editing".to_string(), "edit.md".to_string(), "original wording".to_string());
+        repo.save_note(&note).await.unwrap();
+        assert_eq!(repo.search_notes(&query("original")).await.unwrap().len(), 1);
+
+        note.content = "rewritten paragraph".to_string();
+        repo.save_note(&note).await.unwrap();
+        assert!(repo.search_notes(&query("original")).await.unwrap().is_empty());
+        let results = repo.search_notes(&query("rewritten")).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].note.id, note.id);
+
+        // 物理删除后索引同步移除
+        repo.purge_note(&note.id).await.unwrap();
+        assert!(repo.search_notes(&query("rewritten")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_tags_and_categories() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+
+        let mut note = NoteRecord::new("Tagged".to_string(), "tagged.md".to_string(), "body with #inline".to_string());
+        let mut frontmatter = Frontmatter {
+            tags: vec!["rust".to_string()],
+            categories: vec!["programming".to_string()],
+            ..Default::default()
+        };
+        note.set_frontmatter(&frontmatter).unwrap();
+        repo.save_note(&note).await.unwrap();
+
+        assert_eq!(repo.search_notes(&query("rust")).await.unwrap().len(), 1);
+        assert_eq!(repo.search_notes(&query("inline")).await.unwrap().len(), 1);
+        assert_eq!(repo.search_notes(&query("programming")).await.unwrap().len(), 1);
+
+        // 修改标签后立即可见
+        frontmatter.tags = vec!["golang".to_string()];
+        note.set_frontmatter(&frontmatter).unwrap();
+        repo.save_note(&note).await.unwrap();
+        assert!(repo.search_notes(&query("rust")).await.unwrap().is_empty());
+        assert_eq!(repo.search_notes(&query("golang")).await.unwrap().len(), 1);
+
+        // 标签改名同步到索引
+        let mut tag = repo.get_all_tags().await.unwrap().into_iter().find(|t| t.name == "golang").unwrap();
+        tag.name = "go".to_string();
+        repo.update_tag(&tag).await.unwrap();
+        assert_eq!(repo.search_notes(&query("go")).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_cjk() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+
+        for (title, content) in [
+            ("Rust 搜索", "用 Rust 实现全文搜索引擎，支持中文分词。"),
+            ("Python 搜索", "Python 的搜索工具。"),
+            ("Rust 内存", "Rust 的所有权与内存安全。"),
+        ] {
+            let note = NoteRecord::new(title.to_string(), format!("{}.md", title), content.to_string());
+            repo.save_note(&note).await.unwrap();
+        }
+
+        let titles = |results: Vec<SearchResult>| {
+            let mut titles: Vec<String> = results.into_iter().map(|r| r.note.title).collect();
+            titles.sort();
+            titles
+        };
+
+        // 双字词走 LIKE，较长的词走 trigram
+        assert_eq!(titles(repo.search_notes(&query("搜索")).await.unwrap()), vec!["Python 搜索", "Rust 搜索"]);
+        assert_eq!(titles(repo.search_notes(&query("全文搜索")).await.unwrap()), vec!["Rust 搜索"]);
+        assert_eq!(titles(repo.search_notes(&query("分词")).await.unwrap()), vec!["Rust 搜索"]);
+        // 中英文混合查询的各个词都必须命中
+        assert_eq!(titles(repo.search_notes(&query("rust 搜索")).await.unwrap()), vec!["Rust 搜索"]);
+        assert_eq!(titles(repo.search_notes(&query("Rust 内存安全")).await.unwrap()), vec!["Rust 内存"]);
+        assert!(repo.search_notes(&query("Go 搜索")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_cjk_highlight() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+
+        let content = format!("{}知识库支持全文检索。{}", "前言。".repeat(30), "结尾。".repeat(30));
+        let note = NoteRecord::new("高亮".to_string(), "highlight.md".to_string(), content);
+        repo.save_note(&note).await.unwrap();
+
+        let results = repo.search_notes(&query("全文检索")).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_fields, vec!["content"]);
+        let highlight = &results[0].highlights[0];
+        assert!(highlight.contains("<mark>全文检索</mark>"), "{}", highlight);
+        assert!(highlight.starts_with("...") && highlight.ends_with("..."), "{}", highlight);
+        assert!(highlight.chars().count() < 100);
+
+        let results = repo.search_notes(&query("知识 KB")).await.unwrap();
+        assert!(results.is_empty());
+        let results = repo.search_notes(&query("知识 检索")).await.unwrap();
+        assert!(results[0].highlights[0].contains("<mark>知识</mark>库支持全文<mark>检索</mark>"));
+    }
+
+    #[tokio::test]
+    async fn test_search_query_syntax() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+
+        let notes = [
+            ("Rust 项目", "projects/zeno.md", "rust notes", &["project/zeno"][..], "draft", "2025-02-10T08:00:00Z"),
+            ("Rust 归档", "archive/old.md", "rust notes", &["project", "archive"][..], "draft", "2024-06-01T08:00:00Z"),
+            ("Go 项目", "projects/go.md", "go notes", &["project"][..], "published", "2025-03-01T08:00:00Z"),
+        ];
+        for (title, path, content, tags, status, modified) in notes {
+            let mut note = NoteRecord::new(title.to_string(), path.to_string(), content.to_string());
+            note.set_frontmatter(&Frontmatter {
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            }).unwrap();
+            note.status = status.to_string();
+            note.modified_at = modified.parse().unwrap();
+            repo.save_note(&note).await.unwrap();
+        }
+
+        let titles = |results: Vec<SearchResult>| {
+            let mut titles: Vec<String> = results.into_iter().map(|r| r.note.title).collect();
+            titles.sort();
+            titles
+        };
+        let search = |text: &str| {
+            let repo = &repo;
+            let q = query(text);
+            async move { titles(repo.search_notes(&q).await.unwrap()) }
+        };
+
+        assert_eq!(search("rust tag:project").await, vec!["Rust 归档", "Rust 项目"]);
+        assert_eq!(search("rust tag:project -tag:archive").await, vec!["Rust 项目"]);
+        assert_eq!(search("tag:project status:published").await, vec!["Go 项目"]);
+        assert_eq!(search("notes path:projects/ -go").await, vec!["Rust 项目"]);
+        assert_eq!(search("notes modified:>=2025-01-01 modified:<2025-03-01").await, vec!["Rust 项目"]);
+        assert_eq!(search("rust OR go -path:archive/").await, vec!["Go 项目", "Rust 项目"]);
+        assert_eq!(search("\"rust notes\" 项目").await, vec!["Rust 项目"]);
+
+        // 查询语法与调用方传入的过滤器合并
+        let mut q = query("notes");
+        q.filters.exclude_tags.push("project/zeno".to_string());
+        q.filters.status = Some(NoteStatus::Draft);
+        assert_eq!(titles(repo.search_notes(&q).await.unwrap()), vec!["Rust 归档"]);
+
+        match repo.search_notes(&query("rust status:done")).await {
+            Err(Error::Query(e)) => assert_eq!((e.start, e.end), (12, 16)),
+            other => panic!("期望查询语法错误: {:?}", other.map(|r| r.len())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_history() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+        let note = NoteRecord::new("History".to_string(), "history.md".to_string(), "rust notes".to_string());
+        repo.save_note(&note).await.unwrap();
+
+        repo.search_notes(&query("rust")).await.unwrap();
+        repo.search_notes(&query("golang")).await.unwrap();
+        repo.search_notes(&query("rust")).await.unwrap();
+        // 空查询和语法错误不记录
+        repo.search_notes(&query("  ")).await.unwrap();
+        assert!(repo.search_notes(&query("rust OR")).await.is_err());
+
+        let history = repo.get_search_history(10).await.unwrap();
+        let queries: Vec<&str> = history.iter().map(|h| h.query.as_str()).collect();
+        assert_eq!(queries, vec!["rust", "golang"]);
+        assert_eq!(history[0].result_count, 1);
+        assert_eq!(history[1].result_count, 0);
+        assert!(history[0].search_time >= 0.0);
+
+        assert_eq!(repo.clear_search_history().await.unwrap(), 3);
+        assert!(repo.get_search_history(10).await.unwrap().is_empty());
+
+        // 无法记录历史时仍返回搜索结果
+        sqlx::query("DROP TABLE search_history").execute(repo.pool()).await.unwrap();
+        assert_eq!(repo.search_notes(&query("rust")).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_saved_searches() {
+        let (_dir, pool) = setup_test_db().await;
+        let repo = SqliteNoteRepository::new(pool);
+
+        let saved = repo.save_search("Rust 草稿", "rust status:draft").await.unwrap();
+        assert_eq!(saved.query, "rust status:draft");
+        assert!(repo.run_saved_search("Rust 草稿", None).await.unwrap().is_empty());
+
+        // 每次运行都反映最新的笔记
+        let note = NoteRecord::new("Saved".to_string(), "saved.md".to_string(), "rust notes".to_string());
+        repo.save_note(&note).await.unwrap();
+        assert_eq!(repo.run_saved_search("Rust 草稿", None).await.unwrap().len(), 1);
+
+        // 同名保存会更新查询
+        let updated = repo.save_search("Rust 草稿", "rust status:published").await.unwrap();
+        assert_eq!(updated.id, saved.id);
+        assert!(repo.run_saved_search("Rust 草稿", None).await.unwrap().is_empty());
+
+        assert!(matches!(repo.save_search("坏查询", "tag:").await, Err(Error::Query(_))));
+        assert!(matches!(repo.save_search(" ", "rust").await, Err(Error::Validation(_))));
+        assert!(matches!(repo.run_saved_search("不存在", None).await, Err(Error::NotFound(_))));
+
+        assert_eq!(repo.get_saved_searches().await.unwrap().len(), 1);
+        assert!(repo.delete_saved_search("Rust 草稿").await.unwrap());
+        assert!(!repo.delete_saved_search("Rust 草稿").await.unwrap());
+        // 运行保存的搜索不计入历史
+        assert!(repo.get_search_history(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_note_versions_retention() {
+        let (_dir, pool) = setup_test_db().await;
+        let history = HistorySettings { max_versions: 2, max_age_days: 30 };
+        let repo = SqliteNoteRepository::new(pool).with_history(history);
+
+        let mut note = NoteRecord::new("Versions".to_string(), "versions.md".to_string(), "v0".to_string());
+        repo.save_note(&note).await.unwrap();
+        // 内容不变时不产生版本
+        repo.save_note(&note).await.unwrap();
+        assert!(repo.get_note_versions(&note.id).await.unwrap().is_empty());
+
+        for i in 1..=4 {
+            note.content = format!("v{}", i);
+            repo.save_note(&note).await.unwrap();
+        }
+        let versions = repo.get_note_versions(&note.id).await.unwrap();
+        let kept: Vec<(i64, &str)> = versions.iter().map(|v| (v.version, v.content.as_str())).collect();
+        assert_eq!(kept, vec![(4, "v3"), (3, "v2")]);
+        assert!(repo.get_note_version(&note.id, 1).await.unwrap().is_none());
+
+        let diff = repo.diff_note_versions(&note.id, 3, Some(4)).await.unwrap();
+        assert_eq!(diff.iter().map(|line| line.text.as_str()).collect::<Vec<_>>(), vec!["v2", "v3"]);
+        assert!(matches!(repo.diff_note_versions(&note.id, 1, None).await, Err(Error::NotFound(_))));
+
+        sqlx::query("UPDATE note_versions SET created_at = datetime('now', '-31 days') WHERE version = 3")
+            .execute(repo.pool())
+            .await
+            .unwrap();
+        assert_eq!(repo.prune_note_versions().await.unwrap(), 1);
+        assert_eq!(repo.get_note_versions(&note.id).await.unwrap().len(), 1);
+    }
+}
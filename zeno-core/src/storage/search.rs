@@ -0,0 +1,332 @@
+use crate::db::models::SearchFilters;
+use crate::models::is_cjk;
+
+use super::query::{quote_fts, ParsedQuery};
+
+/// trigram 索引能够匹配的最短词长（按字符计）
+const TRIGRAM_MIN_CHARS: usize = 3;
+
+/// 查询中是否包含中日韩字符
+pub fn contains_cjk(text: &str) -> bool {
+    text.chars().any(is_cjk)
+}
+
+/// 单个搜索词使用的索引
+///
+/// unicode61 分词会把连续的汉字切成一个词，包含中日韩字符的词改用
+/// `notes_trigram` 做子串匹配；trigram 至少需要三个字符，更短的词
+/// （例如常见的双字词）退化为 LIKE。
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TermIndex {
+    Fts,
+    Trigram,
+    Like,
+}
+
+impl TermIndex {
+    fn for_term(term: &str) -> Self {
+        if !contains_cjk(term) {
+            TermIndex::Fts
+        } else if term.chars().count() >= TRIGRAM_MIN_CHARS {
+            TermIndex::Trigram
+        } else {
+            TermIndex::Like
+        }
+    }
+}
+
+/// 由解析后的查询生成的 SQL 语句，参数全部按顺序绑定为文本
+#[derive(Debug)]
+pub(crate) struct SearchStatement {
+    pub sql: String,
+    pub binds: Vec<String>,
+}
+
+impl SearchStatement {
+    /// 结果包含 `notes` 的全部列以及 `score`（越小越相关）和 `highlight`
+    pub fn build(query: &ParsedQuery, limit: usize, offset: usize) -> Self {
+        let mut conditions = Vec::new();
+        let mut where_binds = Vec::new();
+
+        let (rank_fts, rank_trigram) = match query.fts_expression() {
+            Some(expression) => {
+                conditions.push(fts_condition("notes_fts"));
+                where_binds.push(expression.clone());
+                (Some(expression), None)
+            }
+            None => {
+                for clause in &query.clauses {
+                    let terms: Vec<String> = clause
+                        .terms
+                        .iter()
+                        .map(|term| term_condition(term, &mut where_binds))
+                        .collect();
+                    let condition = format!("({})", terms.join(" OR "));
+                    conditions.push(if clause.negated { format!("NOT {}", condition) } else { condition });
+                }
+                let positive = query.positive_terms();
+                (rank_expression(&positive, TermIndex::Fts), rank_expression(&positive, TermIndex::Trigram))
+            }
+        };
+        filter_conditions(&query.filters, &mut conditions, &mut where_binds);
+
+        let mut binds = Vec::new();
+        let mut score = Vec::new();
+        for (table, expression) in [("notes_fts", &rank_fts), ("notes_trigram", &rank_trigram)] {
+            if let Some(expression) = expression {
+                score.push(format!(
+                    "COALESCE((SELECT bm25({table}) FROM {table} WHERE {table} MATCH ? AND rowid = n.rowid), 0)"
+                ));
+                binds.push(expression.clone());
+            }
+        }
+        let score = if score.is_empty() { "0.0".to_string() } else { score.join(" + ") };
+        let highlight = match &rank_fts {
+            Some(expression) => {
+                binds.push(expression.clone());
+                "(SELECT snippet(notes_fts, 1, '<mark>', '</mark>', '...', 32) \
+                 FROM notes_fts WHERE notes_fts MATCH ? AND rowid = n.rowid)"
+            }
+            None => "NULL",
+        };
+        binds.extend(where_binds);
+
+        let sql = format!(
+            "SELECT n.*, {score} AS score, {highlight} AS highlight FROM notes n \
+             WHERE {} ORDER BY score, n.modified_at DESC LIMIT {limit} OFFSET {offset}",
+            conditions.join(" AND "),
+        );
+
+        Self { sql, binds }
+    }
+}
+
+fn fts_condition(table: &str) -> String {
+    format!("n.rowid IN (SELECT rowid FROM {table} WHERE {table} MATCH ?)")
+}
+
+fn term_condition(term: &str, binds: &mut Vec<String>) -> String {
+    match TermIndex::for_term(term) {
+        TermIndex::Fts => {
+            binds.push(quote_fts(term));
+            fts_condition("notes_fts")
+        }
+        TermIndex::Trigram => {
+            binds.push(quote_fts(term));
+            fts_condition("notes_trigram")
+        }
+        TermIndex::Like => {
+            let pattern = format!("%{}%", escape_like(term));
+            let columns = ["n.title", "n.content", "n.tags", "n.categories"];
+            binds.extend(std::iter::repeat_n(pattern, columns.len()));
+            let likes: Vec<String> = columns.iter().map(|c| format!("{c} LIKE ? ESCAPE '\\'")).collect();
+            format!("({})", likes.join(" OR "))
+        }
+    }
+}
+
+/// 排序用的表达式：某个索引能匹配的全部正向词之间取 OR
+fn rank_expression(terms: &[String], index: TermIndex) -> Option<String> {
+    let terms: Vec<String> = terms
+        .iter()
+        .filter(|term| TermIndex::for_term(term) == index)
+        .map(|term| quote_fts(term))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" OR "))
+}
+
+fn filter_conditions(filters: &SearchFilters, conditions: &mut Vec<String>, binds: &mut Vec<String>) {
+    match &filters.status {
+        Some(status) => {
+            conditions.push("n.status = ?".to_string());
+            binds.push(status.to_string());
+        }
+        None => conditions.push("n.status != 'deleted'".to_string()),
+    }
+
+    // 标签同时匹配子标签
+    let tag = "EXISTS (SELECT 1 FROM note_tags nt JOIN tags t ON t.id = nt.tag_id \
+               WHERE nt.note_id = n.id AND (t.name = ? COLLATE NOCASE OR t.name LIKE ? ESCAPE '\\'))";
+    for (tags, negated) in [(&filters.tags, false), (&filters.exclude_tags, true)] {
+        for name in tags {
+            conditions.push(if negated { format!("NOT {}", tag) } else { tag.to_string() });
+            binds.push(name.clone());
+            binds.push(format!("{}/%", escape_like(name)));
+        }
+    }
+
+    let category = "EXISTS (SELECT 1 FROM note_categories nc JOIN categories c ON c.id = nc.category_id \
+                    WHERE nc.note_id = n.id AND c.name = ? COLLATE NOCASE)";
+    for (categories, negated) in [(&filters.categories, false), (&filters.exclude_categories, true)] {
+        for name in categories {
+            conditions.push(if negated { format!("NOT {}", category) } else { category.to_string() });
+            binds.push(name.clone());
+        }
+    }
+
+    if !filters.paths.is_empty() {
+        let paths: Vec<&str> = filters.paths.iter().map(|_| "n.file_path LIKE ? ESCAPE '\\'").collect();
+        conditions.push(format!("({})", paths.join(" OR ")));
+        binds.extend(filters.paths.iter().map(|p| format!("{}%", escape_like(p))));
+    }
+    for path in &filters.exclude_paths {
+        conditions.push("n.file_path NOT LIKE ? ESCAPE '\\'".to_string());
+        binds.push(format!("{}%", escape_like(path)));
+    }
+
+    // 存储的时间格式不统一，统一转换为儒略日比较
+    for (column, range) in [("n.modified_at", &filters.modified), ("n.created_at", &filters.created)] {
+        let Some(range) = range else { continue };
+        if let Some(start) = range.start {
+            conditions.push(format!("julianday({column}) >= julianday(?)"));
+            binds.push(start.format("%Y-%m-%d %H:%M:%S").to_string());
+        }
+        if let Some(end) = range.end {
+            conditions.push(format!("julianday({column}) < julianday(?)"));
+            binds.push(end.format("%Y-%m-%d %H:%M:%S").to_string());
+        }
+    }
+}
+
+fn escape_like(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 文本是否包含任一查询词（ASCII 不区分大小写，与 LIKE 和 trigram 一致）
+pub fn matches_any(text: &str, terms: &[String]) -> bool {
+    let text = text.to_ascii_lowercase();
+    terms.iter().any(|term| !term.is_empty() && text.contains(&term.to_ascii_lowercase()))
+}
+
+/// 生成高亮片段
+///
+/// 以第一个命中位置为中心截取约 `width` 个字符，命中的词用 `<mark>` 包裹，
+/// 截断处用 `...` 表示。没有命中时返回 None。
+pub fn highlight_snippet(text: &str, terms: &[String], width: usize) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_ascii_lowercase()).collect();
+    let terms: Vec<Vec<char>> = terms
+        .iter()
+        .filter(|term| !term.is_empty())
+        .map(|term| term.chars().map(|c| c.to_ascii_lowercase()).collect())
+        .collect();
+
+    // 不重叠的命中区间，同一位置取最长的词
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < lower.len() {
+        let len = terms
+            .iter()
+            .filter(|term| lower[i..].starts_with(term))
+            .map(|term| term.len())
+            .max();
+        match len {
+            Some(len) => {
+                ranges.push((i, i + len));
+                i += len;
+            }
+            None => i += 1,
+        }
+    }
+
+    let &(first_start, first_end) = ranges.first()?;
+    let start = first_start.saturating_sub(width / 2);
+    let end = (start + width).max(first_end).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("...");
+    }
+    let mut ranges = ranges.into_iter().peekable();
+    let mut pos = start;
+    while pos < end {
+        match ranges.peek() {
+            Some(&(s, e)) if s < end => {
+                ranges.next();
+                if e <= pos {
+                    continue;
+                }
+                push_plain(&mut snippet, &chars[pos..s.max(pos)]);
+                snippet.push_str("<mark>");
+                push_plain(&mut snippet, &chars[s.max(pos)..e.min(end)]);
+                snippet.push_str("</mark>");
+                pos = e.min(end);
+            }
+            _ => {
+                push_plain(&mut snippet, &chars[pos..end]);
+                pos = end;
+            }
+        }
+    }
+    if end < chars.len() {
+        snippet.push_str("...");
+    }
+
+    Some(snippet)
+}
+
+/// 片段中的换行替换为空格
+fn push_plain(out: &mut String, chars: &[char]) {
+    out.extend(chars.iter().map(|&c| if c == '\n' || c == '\r' { ' ' } else { c }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_query;
+
+    fn terms(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_term_index() {
+        assert_eq!(TermIndex::for_term("Rust"), TermIndex::Fts);
+        assert_eq!(TermIndex::for_term("全文检索"), TermIndex::Trigram);
+        assert_eq!(TermIndex::for_term("笔记"), TermIndex::Like);
+        assert!(contains_cjk("Rust 笔记"));
+        assert!(!contains_cjk("Rust notes"));
+        assert_eq!(escape_like("5%_a"), "5\\%\\_a");
+    }
+
+    #[test]
+    fn test_build_statement() {
+        // 纯英文查询合并为一个 FTS 表达式
+        let statement = SearchStatement::build(&parse_query("rust -draft tag:dev").unwrap(), 10, 0);
+        assert_eq!(statement.sql.matches("MATCH ?").count(), 3);
+        assert_eq!(statement.binds[..3], [
+            r#"(("rust")) NOT ("draft")"#.to_string(),
+            r#"(("rust")) NOT ("draft")"#.to_string(),
+            r#"(("rust")) NOT ("draft")"#.to_string(),
+        ]);
+        assert_eq!(statement.binds[3..], ["dev".to_string(), "dev/%".to_string()]);
+
+        // 中英文混合时逐词选择索引
+        let statement = SearchStatement::build(&parse_query("rust 全文检索 -笔记").unwrap(), 10, 0);
+        assert!(statement.sql.contains("NOT ((n.title LIKE ?"));
+        assert_eq!(statement.binds[..3], terms(&["\"rust\"", "\"全文检索\"", "\"rust\""]));
+        assert_eq!(statement.sql.matches('?').count(), statement.binds.len());
+    }
+
+    #[test]
+    fn test_highlight_snippet() {
+        let text = "Zeno 是一个本地优先的笔记应用，支持全文搜索。\n搜索结果会高亮显示 rust 关键词。";
+        let snippet = highlight_snippet(text, &terms(&["搜索", "Rust"]), 200).unwrap();
+        assert_eq!(
+            snippet,
+            "Zeno 是一个本地优先的笔记应用，支持全文<mark>搜索</mark>。 <mark>搜索</mark>结果会高亮显示 <mark>rust</mark> 关键词。"
+        );
+
+        let snippet = highlight_snippet(text, &terms(&["高亮"]), 10).unwrap();
+        assert_eq!(snippet, "...搜索结果会<mark>高亮</mark>显示 ...");
+
+        assert_eq!(highlight_snippet(text, &terms(&["不存在"]), 10), None);
+    }
+}
@@ -0,0 +1,12 @@
+use std::path::{Path, PathBuf};
+
+/// 回收站目录（相对工作区），删除的笔记文件移到这里
+pub const TRASH_DIR: &str = ".zeno/trash";
+
+/// 笔记文件在回收站中的路径：`.zeno/trash/<笔记ID>-<文件名>`
+///
+/// 文件名前加上笔记 ID，不同目录下的同名笔记不会冲突。
+pub fn trash_file_path(note_id: &str, original_path: &Path) -> PathBuf {
+    let file_name = original_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    Path::new(TRASH_DIR).join(format!("{}-{}", note_id, file_name))
+}
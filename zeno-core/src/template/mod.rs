@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use tokio::io::AsyncWriteExt;
+
+use crate::error::{Error, Result};
+use crate::parser::{parse_frontmatter, slugify, split_frontmatter, FrontmatterFormat};
+
+/// 默认模板名称
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// `templates/` 中没有默认模板时使用的内置模板
+pub const BUILTIN_TEMPLATE: &str = "# {{ title }}\n\n";
+
+/// 模板变量
+///
+/// 内置 `title`、`slug`、`date`、`time`、`datetime` 和 `uuid`，
+/// 可以用 [`TemplateVars::set`] 追加或覆盖。
+#[derive(Debug, Clone, Default)]
+pub struct TemplateVars {
+    values: BTreeMap<String, String>,
+}
+
+impl TemplateVars {
+    /// 为新笔记生成内置变量
+    pub fn for_note(title: &str) -> Self {
+        Self::for_note_at(title, Local::now())
+    }
+
+    fn for_note_at(title: &str, now: DateTime<Local>) -> Self {
+        let mut vars = Self::default();
+        vars.set("title", title);
+        vars.set("slug", slugify(title));
+        vars.set("date", now.format("%Y-%m-%d").to_string());
+        vars.set("time", now.format("%H:%M").to_string());
+        vars.set("datetime", now.to_rfc3339());
+        vars.set("uuid", uuid::Uuid::new_v4().to_string());
+        vars
+    }
+
+    /// 设置变量
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    /// 获取变量
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+}
+
+/// 渲染模板，替换 `{{ name }}` 占位符
+///
+/// 未定义的变量和未闭合的占位符都会报错，避免生成半成品笔记。
+pub fn render_template(template: &str, vars: &TemplateVars) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| Error::validation("模板占位符缺少 }}"))?;
+        let name = after[..end].trim();
+        let value = vars
+            .get(name)
+            .ok_or_else(|| Error::validation(format!("模板变量未定义: {}", name)))?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// 工作区模板目录
+#[derive(Debug, Clone)]
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 读取模板，名称可以省略 `.md` 扩展名
+    ///
+    /// 默认模板不存在时回退到内置模板。
+    pub async fn load(&self, name: &str) -> Result<String> {
+        validate_template_name(name)?;
+        let file_name = if name.ends_with(".md") { name.to_string() } else { format!("{}.md", name) };
+        match tokio::fs::read_to_string(self.dir.join(&file_name)).await {
+            Ok(template) => Ok(template),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if name == DEFAULT_TEMPLATE {
+                    Ok(BUILTIN_TEMPLATE.to_string())
+                } else {
+                    Err(Error::not_found(format!("模板 {}", name)))
+                }
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 列出可用模板名称
+    pub async fn names(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(names),
+            Err(e) => return Err(e.into()),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+                if let Some(stem) = path.file_stem() {
+                    names.push(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// 渲染新笔记内容
+    ///
+    /// 模板前言中没有 `title` 或 `date` 时自动补上。
+    pub async fn render_note(&self, name: &str, vars: &TemplateVars) -> Result<String> {
+        let template = self.load(name).await?;
+        let rendered = render_template(&template, vars)?;
+        let content = with_generated_frontmatter(&rendered, vars)?;
+        // 校验生成的前言
+        parse_frontmatter(&content)?;
+        Ok(content)
+    }
+}
+
+/// 补全前言中的标题和日期，保留模板原有字段的顺序
+fn with_generated_frontmatter(rendered: &str, vars: &TemplateVars) -> Result<String> {
+    let title = vars.get("title").unwrap_or_default();
+    let date = vars.get("date").unwrap_or_default();
+
+    let Some(block) = split_frontmatter(rendered) else {
+        let yaml = format!("title: {}\ndate: {}\n", yaml_string(title)?, date);
+        return Ok(format!("---\n{}---\n\n{}", yaml, rendered.trim_start()));
+    };
+
+    let (frontmatter, _) = parse_frontmatter(rendered)?;
+    let mut generated = String::new();
+    match block.format {
+        FrontmatterFormat::Yaml => {
+            if frontmatter.title.is_none() {
+                generated.push_str(&format!("title: {}\n", yaml_string(title)?));
+            }
+            if frontmatter.date.is_none() {
+                generated.push_str(&format!("date: {}\n", date));
+            }
+        }
+        FrontmatterFormat::Toml => {
+            if frontmatter.title.is_none() {
+                generated.push_str(&format!("title = {}\n", toml::Value::String(title.to_string())));
+            }
+            if frontmatter.date.is_none() {
+                generated.push_str(&format!("date = {}\n", date));
+            }
+        }
+    }
+
+    // 插入到前言开头，`raw` 是 `rendered` 的子切片
+    let raw_start = block.raw.as_ptr() as usize - rendered.as_ptr() as usize;
+    let mut content = String::with_capacity(rendered.len() + generated.len());
+    content.push_str(&rendered[..raw_start]);
+    content.push_str(&generated);
+    content.push_str(&rendered[raw_start..]);
+    Ok(content)
+}
+
+fn yaml_string(value: &str) -> Result<String> {
+    Ok(serde_yaml::to_string(value)?.trim_end().to_string())
+}
+
+/// 校验模板名称：只能是模板目录下的文件名，不能包含路径分隔符或 `..`
+pub fn validate_template_name(name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(Error::validation(format!("模板名称无效: {}", name)));
+    }
+    Ok(())
+}
+
+/// 校验新笔记的目标目录：必须是相对笔记目录的路径，不能包含 `..` 或绝对路径
+pub fn validate_note_dir(dir: &Path) -> Result<()> {
+    if !dir.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+        return Err(Error::validation(format!("目录必须在工作区内: {}", dir.display())));
+    }
+    Ok(())
+}
+
+/// 新笔记的文件路径：`<dir>/<slug>.md`
+pub fn new_note_path(dir: &Path, title: &str) -> Result<PathBuf> {
+    let slug = slugify(title);
+    if slug.is_empty() {
+        return Err(Error::validation("笔记标题不能为空"));
+    }
+    Ok(dir.join(format!("{}.md", slug)))
+}
+
+/// 写入新笔记，文件已存在时报错而不覆盖
+pub async fn write_new_note(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => {
+                Error::validation(format!("文件已存在: {}", path.display()))
+            }
+            _ => e.into(),
+        })?;
+    file.write_all(content.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn vars(title: &str) -> TemplateVars {
+        let now = Local.with_ymd_and_hms(2024, 7, 1, 9, 30, 0).unwrap();
+        TemplateVars::for_note_at(title, now)
+    }
+
+    #[test]
+    fn test_render_template() {
+        let mut vars = vars("Hello World");
+        vars.set("project", "zeno");
+        let rendered = render_template("# {{title}} ({{ slug }}) {{ date }} {{ project }}", &vars).unwrap();
+        assert_eq!(rendered, "# Hello World (hello-world) 2024-07-01 zeno");
+
+        assert!(render_template("{{ missing }}", &vars).is_err());
+        assert!(render_template("{{ title", &vars).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_note_frontmatter() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("daily.md"),
+            "---\nstatus: draft\ntags: [daily]\n---\n\n# {{ title }}\n",
+        )
+        .unwrap();
+        let store = TemplateStore::new(dir.path());
+        assert_eq!(store.names().await.unwrap(), vec!["daily"]);
+
+        let content = store.render_note("daily", &vars("Note: one")).await.unwrap();
+        assert_eq!(
+            content,
+            "---\ntitle: 'Note: one'\ndate: 2024-07-01\nstatus: draft\ntags: [daily]\n---\n\n# Note: one\n"
+        );
+
+        // 内置默认模板
+        let content = store.render_note(DEFAULT_TEMPLATE, &vars("Plain")).await.unwrap();
+        assert_eq!(content, "---\ntitle: Plain\ndate: 2024-07-01\n---\n\n# Plain\n\n");
+        let (frontmatter, _) = parse_frontmatter(&content).unwrap();
+        assert_eq!(frontmatter.title.as_deref(), Some("Plain"));
+
+        assert!(matches!(store.render_note("missing", &vars("x")).await, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_paths_outside_workspace() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("secret.md"), "secret").unwrap();
+        let store = TemplateStore::new(dir.path().join("templates"));
+        for name in ["../secret", "../secret.md", "sub/daily", "..", ""] {
+            assert!(matches!(store.load(name).await, Err(Error::Validation(_))), "{}", name);
+        }
+
+        assert!(validate_note_dir(Path::new("projects/rust")).is_ok());
+        assert!(validate_note_dir(Path::new("../outside")).is_err());
+        assert!(validate_note_dir(Path::new("projects/../../outside")).is_err());
+        assert!(validate_note_dir(&dir.path().join("notes")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_new_note_refuses_overwrite() {
+        let dir = tempdir().unwrap();
+        let path = new_note_path(&dir.path().join("notes/sub"), "Hello World").unwrap();
+        assert!(path.ends_with("notes/sub/hello-world.md"));
+
+        write_new_note(&path, "first").await.unwrap();
+        assert!(matches!(write_new_note(&path, "second").await, Err(Error::Validation(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first");
+        assert!(new_note_path(dir.path(), "???").is_err());
+    }
+}
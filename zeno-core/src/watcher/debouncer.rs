@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::db::models::FileEventType;
+
+/// 合并后的文件事件，路径相对于工作区
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingEvent {
+    pub event_type: FileEventType,
+    pub path: PathBuf,
+    /// 重命名前的路径
+    pub old_path: Option<PathBuf>,
+}
+
+impl PendingEvent {
+    fn new(event_type: FileEventType, path: PathBuf) -> Self {
+        Self { event_type, path, old_path: None }
+    }
+}
+
+/// 文件事件去抖动器
+///
+/// 在一个静默窗口内按路径合并事件：创建后修改仍是创建，创建后删除
+/// 相互抵消，删除后创建视为修改，重命名链会折叠成一次重命名。
+#[derive(Debug, Default)]
+pub struct EventDebouncer {
+    pending: HashMap<PathBuf, (u64, PendingEvent)>,
+    next_seq: u64,
+}
+
+impl EventDebouncer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 是否有待输出的事件
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// 记录一个创建、修改或删除事件
+    pub fn push(&mut self, event_type: FileEventType, path: PathBuf) {
+        let next = PendingEvent::new(event_type, path.clone());
+        let merged = match self.pending.remove(&path) {
+            Some((_, prev)) => merge(prev, next),
+            None => Some(next),
+        };
+        if let Some(event) = merged {
+            self.insert(path, event);
+        }
+    }
+
+    /// 记录一次重命名
+    pub fn push_rename(&mut self, from: PathBuf, to: PathBuf) {
+        let event = match self.pending.remove(&from) {
+            // 窗口内新建的文件被改名，仍然只是新建
+            Some((_, prev)) if prev.event_type == FileEventType::Created => {
+                PendingEvent::new(FileEventType::Created, to.clone())
+            }
+            // 连续重命名折叠为从最初路径到最终路径
+            Some((_, prev)) if prev.event_type == FileEventType::Renamed => {
+                let origin = prev.old_path.unwrap_or(from);
+                if origin == to {
+                    PendingEvent::new(FileEventType::Modified, to.clone())
+                } else {
+                    PendingEvent { event_type: FileEventType::Renamed, path: to.clone(), old_path: Some(origin) }
+                }
+            }
+            _ => PendingEvent { event_type: FileEventType::Renamed, path: to.clone(), old_path: Some(from) },
+        };
+        self.pending.remove(&to);
+        self.insert(to, event);
+    }
+
+    /// 按发生顺序取出全部事件
+    pub fn drain(&mut self) -> Vec<PendingEvent> {
+        let mut events: Vec<_> = self.pending.drain().map(|(_, entry)| entry).collect();
+        events.sort_by_key(|(seq, _)| *seq);
+        events.into_iter().map(|(_, event)| event).collect()
+    }
+
+    fn insert(&mut self, key: PathBuf, event: PendingEvent) {
+        self.next_seq += 1;
+        self.pending.insert(key, (self.next_seq, event));
+    }
+}
+
+/// 合并同一路径上的两个事件，返回 None 表示相互抵消
+fn merge(prev: PendingEvent, next: PendingEvent) -> Option<PendingEvent> {
+    use FileEventType::*;
+
+    match (&prev.event_type, &next.event_type) {
+        (Created, Deleted) => None,
+        (Created, _) => Some(prev),
+        (Deleted, Created) | (Deleted, Modified) => Some(PendingEvent::new(Modified, next.path)),
+        (Renamed, Modified) | (Renamed, Created) => Some(prev),
+        // 重命名后的文件被删除，等同于删除原笔记
+        (Renamed, Deleted) => Some(PendingEvent::new(Deleted, prev.old_path.unwrap_or(prev.path))),
+        _ => Some(next),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FileEventType::*;
+
+    fn p(s: &str) -> PathBuf {
+        PathBuf::from(s)
+    }
+
+    #[test]
+    fn test_merge_rules() {
+        let mut debouncer = EventDebouncer::new();
+        debouncer.push(Created, p("a.md"));
+        debouncer.push(Modified, p("a.md"));
+        debouncer.push(Modified, p("b.md"));
+        debouncer.push(Deleted, p("b.md"));
+        debouncer.push(Created, p("c.md"));
+        debouncer.push(Deleted, p("c.md"));
+        debouncer.push(Deleted, p("d.md"));
+        debouncer.push(Created, p("d.md"));
+
+        let events = debouncer.drain();
+        assert_eq!(events, vec![
+            PendingEvent::new(Created, p("a.md")),
+            PendingEvent::new(Deleted, p("b.md")),
+            PendingEvent::new(Modified, p("d.md")),
+        ]);
+        assert!(debouncer.is_empty());
+    }
+
+    #[test]
+    fn test_rename_chain() {
+        let mut debouncer = EventDebouncer::new();
+        debouncer.push_rename(p("a.md"), p("b.md"));
+        debouncer.push(Modified, p("b.md"));
+        debouncer.push_rename(p("b.md"), p("c.md"));
+
+        let events = debouncer.drain();
+        assert_eq!(events, vec![PendingEvent {
+            event_type: Renamed,
+            path: p("c.md"),
+            old_path: Some(p("a.md")),
+        }]);
+
+        // 改回原名只是一次修改
+        debouncer.push_rename(p("a.md"), p("b.md"));
+        debouncer.push_rename(p("b.md"), p("a.md"));
+        assert_eq!(debouncer.drain(), vec![PendingEvent::new(Modified, p("a.md"))]);
+
+        // 新建后改名仍是新建
+        debouncer.push(Created, p("tmp.md"));
+        debouncer.push_rename(p("tmp.md"), p("final.md"));
+        assert_eq!(debouncer.drain(), vec![PendingEvent::new(Created, p("final.md"))]);
+    }
+}
@@ -0,0 +1,274 @@
+pub mod debouncer;
+pub mod processor;
+
+pub use debouncer::*;
+pub use processor::*;
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use sqlx::SqlitePool;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+
+use crate::db::models::{FileEvent, FileEventType};
+use crate::error::Result;
+
+/// 文件监控配置
+#[derive(Debug, Clone)]
+pub struct WatcherConfig {
+    /// 静默多久后写入事件
+    pub debounce: Duration,
+    /// 忽略的目录（相对工作区），如模板目录
+    pub excluded: Vec<PathBuf>,
+}
+
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(500),
+            excluded: Vec::new(),
+        }
+    }
+}
+
+/// 工作区文件监控服务
+///
+/// 监听工作区内 Markdown 文件的变化，去抖动后写入 `file_events` 表，
+/// 由 [`FileEventProcessor`] 负责把事件应用到数据库。
+pub struct FileWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+    flushed: watch::Receiver<u64>,
+}
+
+impl FileWatcher {
+    /// 开始监控工作区
+    pub fn start(root: PathBuf, pool: SqlitePool, config: WatcherConfig) -> Result<Self> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            match res {
+                Ok(event) => {
+                    let _ = tx.send(event);
+                }
+                Err(e) => log::warn!("文件监控错误: {}", e),
+            }
+        })?;
+        watcher.watch(&root, RecursiveMode::Recursive)?;
+
+        let (flush_tx, flushed) = watch::channel(0);
+        let task = tokio::spawn(debounce_loop(root, pool, config, rx, flush_tx));
+
+        Ok(Self { _watcher: watcher, task, flushed })
+    }
+
+    /// 等待下一批事件写入数据库，监控任务结束时返回 false
+    pub async fn flushed(&mut self) -> bool {
+        self.flushed.changed().await.is_ok()
+    }
+
+    /// 停止监控
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+/// 收集原始事件，静默 `debounce` 后批量写入
+async fn debounce_loop(
+    root: PathBuf,
+    pool: SqlitePool,
+    config: WatcherConfig,
+    mut rx: mpsc::UnboundedReceiver<Event>,
+    flushed: watch::Sender<u64>,
+) {
+    let mut debouncer = EventDebouncer::new();
+    // inotify 的 From/To 两半通过 tracker 配对
+    let mut rename_from: HashMap<Option<usize>, PathBuf> = HashMap::new();
+
+    loop {
+        let event = if debouncer.is_empty() && rename_from.is_empty() {
+            rx.recv().await
+        } else {
+            match tokio::time::timeout(config.debounce, rx.recv()).await {
+                Ok(event) => event,
+                Err(_) => {
+                    // 未配对的重命名：移出工作区视为删除
+                    for (_, from) in rename_from.drain() {
+                        debouncer.push(FileEventType::Deleted, from);
+                    }
+                    let events = debouncer.drain();
+                    if let Err(e) = record_events(&pool, &events).await {
+                        log::error!("写入文件事件失败: {}", e);
+                    }
+                    flushed.send_modify(|batches| *batches += 1);
+                    continue;
+                }
+            }
+        };
+
+        match event {
+            Some(event) => apply_event(&root, &config.excluded, event, &mut debouncer, &mut rename_from),
+            None => break,
+        }
+    }
+}
+
+/// 把 notify 事件转换为去抖动器中的事件
+fn apply_event(
+    root: &Path,
+    excluded: &[PathBuf],
+    event: Event,
+    debouncer: &mut EventDebouncer,
+    rename_from: &mut HashMap<Option<usize>, PathBuf>,
+) {
+    let tracker = event.tracker();
+    let paths: Vec<Option<PathBuf>> = event.paths.iter().map(|p| relative_markdown_path(root, excluded, p)).collect();
+
+    match event.kind {
+        EventKind::Create(_) => {
+            for path in paths.into_iter().flatten() {
+                debouncer.push(FileEventType::Created, path);
+            }
+        }
+        EventKind::Remove(_) => {
+            for path in paths.into_iter().flatten() {
+                debouncer.push(FileEventType::Deleted, path);
+            }
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if paths.len() == 2 => {
+            push_rename(debouncer, paths[0].clone(), paths[1].clone());
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            if let Some(Some(path)) = paths.into_iter().next() {
+                rename_from.insert(tracker, path);
+            }
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            let from = rename_from.remove(&tracker);
+            let to = paths.into_iter().next().flatten();
+            push_rename(debouncer, from, to);
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => {
+            // 平台没有给出方向时按文件是否存在判断
+            for (raw, path) in event.paths.iter().zip(paths) {
+                if let Some(path) = path {
+                    let kind = if raw.exists() { FileEventType::Created } else { FileEventType::Deleted };
+                    debouncer.push(kind, path);
+                }
+            }
+        }
+        EventKind::Modify(ModifyKind::Metadata(_)) => {}
+        EventKind::Modify(_) | EventKind::Any => {
+            for path in paths.into_iter().flatten() {
+                debouncer.push(FileEventType::Modified, path);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 只有一侧是 Markdown 文件的重命名（例如编辑器的临时文件）退化为创建或删除
+fn push_rename(debouncer: &mut EventDebouncer, from: Option<PathBuf>, to: Option<PathBuf>) {
+    match (from, to) {
+        (Some(from), Some(to)) => debouncer.push_rename(from, to),
+        (None, Some(to)) => debouncer.push(FileEventType::Modified, to),
+        (Some(from), None) => debouncer.push(FileEventType::Deleted, from),
+        (None, None) => {}
+    }
+}
+
+/// 转换为相对工作区的路径，忽略隐藏目录、排除的目录和非 Markdown 文件
+fn relative_markdown_path(root: &Path, excluded: &[PathBuf], path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(root).ok()?;
+    if excluded.iter().any(|dir| relative.starts_with(dir)) {
+        return None;
+    }
+    let hidden = relative.components().any(|c| match c {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    });
+    let markdown = matches!(
+        relative.extension().and_then(|ext| ext.to_str()),
+        Some("md") | Some("markdown")
+    );
+    (!hidden && markdown).then(|| relative.to_path_buf())
+}
+
+/// 把一批事件写入 `file_events`
+pub async fn record_events(pool: &SqlitePool, events: &[PendingEvent]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    for event in events {
+        sqlx::query("INSERT INTO file_events (file_path, event_type, old_path) VALUES (?, ?, ?)")
+            .bind(event.path.to_string_lossy().to_string())
+            .bind(event.event_type.to_string())
+            .bind(event.old_path.as_ref().map(|p| p.to_string_lossy().to_string()))
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// 获取未处理的文件事件
+pub async fn pending_events(pool: &SqlitePool) -> Result<Vec<FileEvent>> {
+    let events = sqlx::query_as::<_, FileEvent>(
+        "SELECT * FROM file_events WHERE processed = FALSE ORDER BY id ASC"
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_relative_markdown_path() {
+        let root = Path::new("/ws");
+        let excluded = [PathBuf::from("templates")];
+        assert_eq!(relative_markdown_path(root, &excluded, Path::new("/ws/notes/a.md")), Some(PathBuf::from("notes/a.md")));
+        assert_eq!(relative_markdown_path(root, &excluded, Path::new("/ws/.zeno/trash/a.md")), None);
+        assert_eq!(relative_markdown_path(root, &excluded, Path::new("/ws/notes/a.md.swp")), None);
+        assert_eq!(relative_markdown_path(root, &excluded, Path::new("/other/a.md")), None);
+        assert_eq!(relative_markdown_path(root, &excluded, Path::new("/ws/templates/daily.md")), None);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_records_debounced_events() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        let db = Database::new(root.join(".zeno.db")).await.unwrap();
+        db.initialize().await.unwrap();
+
+        let config = WatcherConfig { debounce: Duration::from_millis(200), ..Default::default() };
+        let mut watcher = FileWatcher::start(root.clone(), db.pool().clone(), config).unwrap();
+
+        std::fs::write(root.join("notes/a.md"), "# A").unwrap();
+        std::fs::write(root.join("notes/a.md"), "# A\n\nmore").unwrap();
+        std::fs::write(root.join("notes/ignored.txt"), "x").unwrap();
+        assert!(tokio::time::timeout(Duration::from_secs(5), watcher.flushed()).await.unwrap());
+
+        let events = pending_events(db.pool()).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].file_path, "notes/a.md");
+        assert_eq!(events[0].event_type, "created");
+
+        std::fs::rename(root.join("notes/a.md"), root.join("notes/b.md")).unwrap();
+        assert!(tokio::time::timeout(Duration::from_secs(5), watcher.flushed()).await.unwrap());
+
+        let events = pending_events(db.pool()).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].event_type, "renamed");
+        assert_eq!(events[1].file_path, "notes/b.md");
+        assert_eq!(events[1].old_path.as_deref(), Some("notes/a.md"));
+
+        watcher.stop();
+    }
+}
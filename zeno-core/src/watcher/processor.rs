@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::db::models::{FileEvent, FileEventType};
+use crate::error::Result;
+use crate::indexer::{record_aliases, IndexFailure};
+use crate::storage::KnowledgeBase;
+
+use super::pending_events;
+
+/// 事件处理报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessReport {
+    /// 新增或更新的笔记
+    pub imported: usize,
+    /// 重命名的笔记
+    pub renamed: usize,
+    /// 删除的笔记
+    pub removed: usize,
+    pub failed: Vec<IndexFailure>,
+    /// 新增、更新或重命名后的笔记路径
+    pub changed_paths: Vec<PathBuf>,
+    /// 更新、重命名或删除前笔记的标题、文件名和别名，指向这些名称的链接需要重新解析
+    pub previous_names: Vec<String>,
+}
+
+/// 文件事件处理器：把 `file_events` 中未处理的事件应用到知识库
+pub struct FileEventProcessor {
+    pool: SqlitePool,
+    knowledge_base: Arc<KnowledgeBase>,
+}
+
+enum Action {
+    Import(PathBuf),
+    Rename { from: PathBuf, to: PathBuf },
+    Remove(PathBuf),
+    Skip,
+}
+
+impl FileEventProcessor {
+    pub fn new(pool: SqlitePool, knowledge_base: Arc<KnowledgeBase>) -> Self {
+        Self { pool, knowledge_base }
+    }
+
+    /// 处理全部未处理事件
+    ///
+    /// 同一批中被删除的笔记如果与新建文件内容完全相同，视为重命名，
+    /// 以保留笔记 ID 和反向链接。失败的事件同样标记为已处理并记入报告。
+    pub async fn process_pending(&self) -> Result<ProcessReport> {
+        let events = pending_events(&self.pool).await?;
+        let mut report = ProcessReport::default();
+
+        for (event, action) in events.iter().zip(self.plan(&events).await) {
+            let path = PathBuf::from(&event.file_path);
+            let (target, previous_names) = match &action {
+                Action::Import(path) => (Some(path.clone()), self.note_names(path).await),
+                Action::Rename { from, to } => {
+                    let mut names = self.note_names(from).await;
+                    names.extend(self.note_names(to).await);
+                    (Some(to.clone()), names)
+                }
+                Action::Remove(path) => (None, self.note_names(path).await),
+                Action::Skip => (None, Vec::new()),
+            };
+            let applied = self.apply(action).await;
+            if let Ok(Some(event_type)) = &applied {
+                report.previous_names.extend(previous_names);
+                if *event_type != FileEventType::Deleted {
+                    report.changed_paths.extend(target);
+                }
+            }
+            match applied {
+                Ok(Some(FileEventType::Renamed)) => report.renamed += 1,
+                Ok(Some(FileEventType::Deleted)) => report.removed += 1,
+                Ok(Some(_)) => report.imported += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("处理文件事件失败 {}: {}", event.file_path, e);
+                    report.failed.push(IndexFailure { path, error: e.to_string() });
+                }
+            }
+
+            sqlx::query("UPDATE file_events SET processed = TRUE WHERE id = ?")
+                .bind(event.id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(report)
+    }
+
+    /// 为每个事件确定要执行的操作
+    async fn plan(&self, events: &[FileEvent]) -> Vec<Action> {
+        // 被删除笔记的内容哈希 -> 事件下标
+        let mut deleted: HashMap<String, usize> = HashMap::new();
+        for (i, event) in events.iter().enumerate() {
+            if event.event_type == FileEventType::Deleted.to_string() {
+                if let Ok(Some(note)) = self.knowledge_base.get_note_by_path(Path::new(&event.file_path)).await {
+                    deleted.entry(note.file_hash).or_insert(i);
+                }
+            }
+        }
+
+        let mut actions: Vec<Action> = events.iter().map(to_action).collect();
+        if deleted.is_empty() {
+            return actions;
+        }
+
+        for i in 0..events.len() {
+            if events[i].event_type != FileEventType::Created.to_string() {
+                continue;
+            }
+            let path = Path::new(&events[i].file_path);
+            let Ok(checksum) = self.knowledge_base.file_checksum(path).await else {
+                continue;
+            };
+            if let Some(j) = deleted.remove(&checksum) {
+                actions[i] = Action::Rename {
+                    from: PathBuf::from(&events[j].file_path),
+                    to: path.to_path_buf(),
+                };
+                actions[j] = Action::Skip;
+            }
+        }
+
+        actions
+    }
+
+    /// 执行操作，返回实际生效的事件类型
+    async fn apply(&self, action: Action) -> Result<Option<FileEventType>> {
+        match action {
+            Action::Import(path) => {
+                // 文件在事件写入后又被删除
+                if !self.knowledge_base.file_exists(&path).await? {
+                    return self.remove(&path).await;
+                }
+                self.knowledge_base.import_note_from_file(&path).await?;
+                Ok(Some(FileEventType::Modified))
+            }
+            Action::Rename { from, to } => {
+                if !self.knowledge_base.file_exists(&to).await? {
+                    return self.remove(&from).await;
+                }
+                self.knowledge_base.rename_note_path(&from, &to).await?;
+                Ok(Some(FileEventType::Renamed))
+            }
+            Action::Remove(path) => self.remove(&path).await,
+            Action::Skip => Ok(None),
+        }
+    }
+
+    /// 路径上现有笔记的标题、文件名和别名
+    async fn note_names(&self, path: &Path) -> Vec<String> {
+        let Ok(Some(note)) = self.knowledge_base.get_note_by_path(path).await else {
+            return Vec::new();
+        };
+        let mut names = vec![note.title];
+        names.extend(path.file_stem().map(|stem| stem.to_string_lossy().to_string()));
+        names.extend(record_aliases(&note.frontmatter));
+        names
+    }
+
+    async fn remove(&self, path: &Path) -> Result<Option<FileEventType>> {
+        let removed = self.knowledge_base.remove_note_by_path(path).await?;
+        Ok(removed.then_some(FileEventType::Deleted))
+    }
+}
+
+fn to_action(event: &FileEvent) -> Action {
+    let path = PathBuf::from(&event.file_path);
+    match event.event_type.parse::<FileEventType>() {
+        Ok(FileEventType::Created) | Ok(FileEventType::Modified) => Action::Import(path),
+        Ok(FileEventType::Deleted) => Action::Remove(path),
+        Ok(FileEventType::Renamed) => match &event.old_path {
+            Some(old_path) => Action::Rename { from: PathBuf::from(old_path), to: path },
+            None => Action::Import(path),
+        },
+        Err(_) => Action::Skip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::indexer::SqliteIndexer;
+    use crate::storage::{LocalFileStorage, NoteRepository, SqliteNoteRepository};
+    use crate::watcher::{record_events, PendingEvent};
+    use tempfile::tempdir;
+
+    fn event(event_type: FileEventType, path: &str, old_path: Option<&str>) -> PendingEvent {
+        PendingEvent {
+            event_type,
+            path: PathBuf::from(path),
+            old_path: old_path.map(PathBuf::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_events_keeps_ids_on_rename() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        let db = Database::new(root.join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let pool = db.pool().clone();
+
+        let kb = Arc::new(KnowledgeBase::new(
+            Box::new(LocalFileStorage::new(root.to_path_buf())),
+            Box::new(SqliteNoteRepository::new(pool.clone())),
+        ));
+        let processor = FileEventProcessor::new(pool.clone(), kb.clone());
+        let repo = SqliteNoteRepository::new(pool.clone());
+
+        std::fs::write(root.join("notes/a.md"), "# A\n\nbody").unwrap();
+        std::fs::write(root.join("notes/b.md"), "# B\n\nbody").unwrap();
+        record_events(&pool, &[
+            event(FileEventType::Created, "notes/a.md", None),
+            event(FileEventType::Created, "notes/b.md", None),
+        ]).await.unwrap();
+        let report = processor.process_pending().await.unwrap();
+        assert_eq!(report.imported, 2);
+        let a_id = repo.get_note_by_path("notes/a.md").await.unwrap().unwrap().id;
+        let b_id = repo.get_note_by_path("notes/b.md").await.unwrap().unwrap().id;
+
+        // 平台报告的重命名
+        std::fs::rename(root.join("notes/a.md"), root.join("notes/a2.md")).unwrap();
+        record_events(&pool, &[event(FileEventType::Renamed, "notes/a2.md", Some("notes/a.md"))]).await.unwrap();
+        // 删除 + 新建且内容相同，也识别为重命名
+        std::fs::rename(root.join("notes/b.md"), root.join("notes/b2.md")).unwrap();
+        record_events(&pool, &[
+            event(FileEventType::Deleted, "notes/b.md", None),
+            event(FileEventType::Created, "notes/b2.md", None),
+        ]).await.unwrap();
+
+        let report = processor.process_pending().await.unwrap();
+        assert_eq!(report.renamed, 2);
+        assert_eq!(report.removed, 0);
+        assert_eq!(repo.get_note_by_path("notes/a2.md").await.unwrap().unwrap().id, a_id);
+        assert_eq!(repo.get_note_by_path("notes/b2.md").await.unwrap().unwrap().id, b_id);
+        assert!(repo.get_note_by_path("notes/a.md").await.unwrap().is_none());
+
+        std::fs::remove_file(root.join("notes/a2.md")).unwrap();
+        record_events(&pool, &[event(FileEventType::Deleted, "notes/a2.md", None)]).await.unwrap();
+        let report = processor.process_pending().await.unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(repo.get_note_by_id(&a_id).await.unwrap().is_none());
+        assert!(pending_events(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_report_changes_for_link_refresh() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let db = Database::new(root.join("test.db")).await.unwrap();
+        db.initialize().await.unwrap();
+        let pool = db.pool().clone();
+
+        let kb = Arc::new(KnowledgeBase::new(
+            Box::new(LocalFileStorage::new(root.to_path_buf())),
+            Box::new(SqliteNoteRepository::new(pool.clone())),
+        ));
+        let processor = FileEventProcessor::new(pool.clone(), kb);
+        let indexer = SqliteIndexer::new(pool.clone());
+        let repo = indexer.repository();
+
+        std::fs::write(root.join("a.md"), "# A\n\nsee [[Beta]]").unwrap();
+        std::fs::write(root.join("b.md"), "# Beta").unwrap();
+        record_events(&pool, &[
+            event(FileEventType::Created, "a.md", None),
+            event(FileEventType::Created, "b.md", None),
+        ]).await.unwrap();
+        let report = processor.process_pending().await.unwrap();
+        assert_eq!(report.changed_paths, vec![PathBuf::from("a.md"), PathBuf::from("b.md")]);
+        indexer.refresh_changed(&report.changed_paths, &report.previous_names).await.unwrap();
+        let a = repo.get_note_by_path("a.md").await.unwrap().unwrap().id;
+        let b = repo.get_note_by_path("b.md").await.unwrap().unwrap().id;
+        assert_eq!(repo.get_outbound_links(&a).await.unwrap()[0].target_id, b);
+
+        // 删除的笔记只留下名称，提到它的笔记重新解析到同名的新笔记
+        std::fs::remove_file(root.join("b.md")).unwrap();
+        std::fs::write(root.join("beta.md"), "# Other").unwrap();
+        record_events(&pool, &[
+            event(FileEventType::Deleted, "b.md", None),
+            event(FileEventType::Created, "beta.md", None),
+        ]).await.unwrap();
+        let report = processor.process_pending().await.unwrap();
+        assert_eq!(report.changed_paths, vec![PathBuf::from("beta.md")]);
+        assert_eq!(report.previous_names, vec!["Beta", "b"]);
+        indexer.refresh_changed(&report.changed_paths, &report.previous_names).await.unwrap();
+        let beta = repo.get_note_by_path("beta.md").await.unwrap().unwrap().id;
+        assert_eq!(repo.get_outbound_links(&a).await.unwrap()[0].target_id, beta);
+    }
+}